@@ -6,11 +6,15 @@
 // Run with: cargo run --example sign_sol_frost
 
 use anyhow::Result;
+use base64::prelude::*;
 use ed25519_dalek::{Signature as Ed25519Signature, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use solana_sdk::{
-    hash::Hash, instruction::Instruction, message::Message, pubkey::Pubkey, signature::Signature,
-    system_instruction, transaction::Transaction,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey::Pubkey,
+    transaction::Transaction,
 };
 use std::str::FromStr;
 
@@ -42,16 +46,15 @@ async fn main() -> Result<()> {
 
     let to_pubkey = Pubkey::from_str("7EqQdEULxWcraVx3mXKFjc84LhCkMGZCkRuDpvcMwJeK")?;
 
-    // Create transfer instruction (1 SOL = 1,000,000,000 lamports)
-    let transfer_ix: Instruction =
-        system_instruction::transfer(&from_pubkey, &to_pubkey, 1_000_000_000);
+    // Transfer 1 SOL = 1,000,000,000 lamports
+    let transfer_ix = system_transfer(&from_pubkey, &to_pubkey, 1_000_000_000);
 
     // Recent blockhash (in production, fetch from RPC)
     // For demo, use a dummy hash
     let recent_blockhash = Hash::new_from_array([1u8; 32]);
 
-    // Build message using solana-sdk
-    let message = Message::new(&[transfer_ix], Some(&from_pubkey));
+    let message =
+        Message::new_with_blockhash(&[transfer_ix], Some(&from_pubkey), &recent_blockhash);
 
     println!("  From:        {}", from_pubkey);
     println!("  To:          {}", to_pubkey);
@@ -63,35 +66,48 @@ async fn main() -> Result<()> {
         &recent_blockhash.to_string()[..16]
     );
 
-    // Step 3: Serialize message for signing
-    println!("Step 3: Serialize message for signing\n");
+    // Step 3: Sign with FROST via /api/sign/solana
+    println!("Step 3: Sign with FROST Ed25519 threshold signatures\n");
 
     let message_bytes = message.serialize();
     println!("  Message bytes: {} bytes", message_bytes.len());
-    println!(
-        "  Message (hex): 0x{}...\n",
-        hex::encode(&message_bytes[..16.min(message_bytes.len())])
-    );
-
-    // Step 4: Sign with FROST via signing aggregator
-    println!("Step 4: Sign with FROST Ed25519 threshold signatures\n");
 
-    let signature_hex = sign_message_via_aggregator(
+    let signed = sign_transaction_via_aggregator(
         signing_aggregator,
-        &hex::encode(&message_bytes),
+        &BASE64_STANDARD.encode(&message_bytes),
         &passphrase,
     )
     .await?;
 
-    println!("  ✅ Ed25519 signature: {}...", &signature_hex[..16]);
-    println!("  ✅ Signature length: {} bytes\n", signature_hex.len() / 2);
+    println!("  ✅ Signers:       {:?}", signed.signers);
+    println!(
+        "  ✅ Signature:     {}",
+        signed
+            .signature
+            .as_deref()
+            .unwrap_or("(fee payer not signed)")
+    );
+    println!("  ✅ Fully signed:  {}\n", signed.fully_signed);
 
-    // Step 5: Verify signature using ed25519-dalek
-    println!("Step 5: Verify Ed25519 signature with ed25519-dalek\n");
+    if !signed.fully_signed {
+        anyhow::bail!("Missing signers: {:?}", signed.missing_signers);
+    }
+
+    // Step 4: Decode the wire transaction and verify it with ed25519-dalek
+    println!("Step 4: Verify wire transaction with ed25519-dalek\n");
 
-    let sig_bytes = hex::decode(&signature_hex)?;
+    let wire = BASE64_STANDARD.decode(&signed.transaction)?;
+    let transaction: Transaction = bincode::deserialize(&wire)?;
+
+    if transaction.message.serialize() != message_bytes {
+        anyhow::bail!("Aggregator returned a different message!");
+    }
 
-    let verified = verify_ed25519_signature(&sig_bytes, &message_bytes, &pubkey_bytes)?;
+    let verified = verify_ed25519_signature(
+        transaction.signatures[0].as_ref(),
+        &message_bytes,
+        &pubkey_bytes,
+    )?;
 
     if verified {
         println!("  🔒 ed25519-dalek verification: PASSED");
@@ -100,50 +116,32 @@ async fn main() -> Result<()> {
         anyhow::bail!("Signature verification failed!");
     }
 
-    // Step 6: Build Solana transaction with signature
-    println!("Step 6: Build signed Solana transaction\n");
-
-    let mut sig_array = [0u8; 64];
-    sig_array.copy_from_slice(&sig_bytes);
-    let solana_signature = Signature::from(sig_array);
-
-    // Create complete transaction
-    let transaction = Transaction {
-        signatures: vec![solana_signature],
-        message,
-    };
-
-    // Serialize transaction for broadcast
-    let serialized = bincode::serialize(&transaction)?;
-    let encoded_tx = bs58::encode(&serialized).into_string();
-
-    println!("  Transaction size: {} bytes", serialized.len());
-    println!("  Encoded (Base58): {}...", &encoded_tx[..32]);
-    println!("  Ready to broadcast to Solana network\n");
-
-    // Additional verification: Check transaction is valid
-    println!("Step 7: Verify transaction structure\n");
-
-    println!(
-        "  ✅ Transaction has {} signature",
-        transaction.signatures.len()
-    );
-    println!(
-        "  ✅ Message has {} instruction",
-        transaction.message.instructions.len()
-    );
-    println!("  ✅ Valid Solana Transaction structure\n");
+    println!("  Transaction size: {} bytes", wire.len());
+    println!("  Encoded (Base64): {}...", &signed.transaction[..32]);
+    println!("  Ready for sendTransaction (encoding: base64)\n");
 
     println!("════════════════════════════════════════");
     println!("✅ Complete FROST Solana transaction signing");
     println!("✅ Ed25519 threshold signatures working!");
     println!("✅ Signature verified with ed25519-dalek!");
-    println!("✅ Transaction built with solana-sdk!");
+    println!("✅ Wire transaction assembled by the aggregator!");
     println!("════════════════════════════════════════");
 
     Ok(())
 }
 
+/// System program transfer instruction (index 2, u64 lamports)
+fn system_transfer(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    Instruction {
+        program_id: Pubkey::default(), // 11111111111111111111111111111111
+        accounts: vec![AccountMeta::new(*from, true), AccountMeta::new(*to, false)],
+        data,
+    }
+}
+
 /// Generate Solana address and get Ed25519 public key
 async fn generate_address_with_pubkey(
     aggregator: &str,
@@ -181,33 +179,32 @@ async fn generate_address_with_pubkey(
     Ok((resp_data.address, resp_data.public_key))
 }
 
-/// Sign message via signing aggregator (Ed25519)
-async fn sign_message_via_aggregator(
+#[derive(Deserialize)]
+struct SignedTransaction {
+    transaction: String,
+    signature: Option<String>,
+    signers: Vec<String>,
+    missing_signers: Vec<String>,
+    fully_signed: bool,
+}
+
+/// Sign a Solana message via the signing aggregator
+async fn sign_transaction_via_aggregator(
     aggregator_url: &str,
-    message_hex: &str,
+    message_base64: &str,
     passphrase: &str,
-) -> Result<String> {
+) -> Result<SignedTransaction> {
     #[derive(Serialize)]
     struct Req {
-        passphrase: String,
         message: String,
-        curve: String,
+        passphrases: Vec<String>,
     }
 
-    #[derive(Deserialize)]
-    struct Resp {
-        signature: String,
-        verified: bool,
-    }
-
-    let client = reqwest::Client::new();
-
-    let resp = client
-        .post(format!("{}/api/sign/message", aggregator_url))
+    let resp = reqwest::Client::new()
+        .post(format!("{}/api/sign/solana", aggregator_url))
         .json(&Req {
-            passphrase: passphrase.to_string(),
-            message: message_hex.to_string(),
-            curve: "ed25519".to_string(), // Solana uses Ed25519
+            message: message_base64.to_string(),
+            passphrases: vec![passphrase.to_string()],
         })
         .send()
         .await?;
@@ -216,13 +213,7 @@ async fn sign_message_via_aggregator(
         anyhow::bail!("Signing failed: {}", resp.text().await?);
     }
 
-    let result = resp.json::<Resp>().await?;
-
-    if !result.verified {
-        anyhow::bail!("Signature verification failed by aggregator");
-    }
-
-    Ok(result.signature)
+    Ok(resp.json::<SignedTransaction>().await?)
 }
 
 /// Verify Ed25519 signature using ed25519-dalek (cryptographic proof!)
@@ -1,6 +1,8 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::curves::CurveType;
+
 #[derive(Serialize)]
 struct DkgRound1Request {
     passphrase: String,
@@ -50,55 +52,19 @@ struct DkgFinalizeResponse {
     pubkey_hex: String, // Raw public key from signer (not address)
 }
 
-/// Orchestrate secp256k1-tr (Taproot/Schnorr) DKG for Bitcoin
-pub async fn orchestrate_dkg_taproot(signer_urls: &[String], passphrase: &str) -> Result<String> {
-    orchestrate_dkg_for_curve(signer_urls, passphrase, "secp256k1-tr").await
-}
-
-/// Orchestrate secp256k1 (ECDSA) DKG for Ethereum/EVM
-pub async fn orchestrate_dkg_ecdsa(signer_urls: &[String], passphrase: &str) -> Result<String> {
-    orchestrate_dkg_for_curve(signer_urls, passphrase, "secp256k1").await
-}
-
-/// Orchestrate Ed25519 DKG for Solana
-pub async fn orchestrate_dkg_ed25519(signer_urls: &[String], passphrase: &str) -> Result<String> {
-    orchestrate_dkg_for_curve(signer_urls, passphrase, "ed25519").await
-}
-
-/// Generic DKG orchestrator supporting both secp256k1 and Ed25519
-async fn orchestrate_dkg_for_curve(
+/// Orchestrate DKG for a curve across all signer nodes, returning the group public key (hex)
+pub async fn orchestrate_dkg(
     signer_urls: &[String],
     passphrase: &str,
-    curve_suffix: &str,
+    curve: CurveType,
 ) -> Result<String> {
     let client = reqwest::Client::new();
 
-    // Build endpoint URLs based on curve
-    // Format: /api/dkg/{curve}/round1 or /api/dkg/round1 for default
-    let (round1_endpoint, round2_endpoint, finalize_endpoint) = match curve_suffix {
-        "" | "secp256k1-tr" => {
-            // Taproot is the default (backward compatible)
-            (
-                "secp256k1-tr/round1".to_string(),
-                "secp256k1-tr/round2".to_string(),
-                "secp256k1-tr/finalize".to_string(),
-            )
-        }
-        other => {
-            // Other curves: secp256k1, ed25519, etc.
-            (
-                format!("{}/round1", other),
-                format!("{}/round2", other),
-                format!("{}/finalize", other),
-            )
-        }
-    };
-
-    let curve_name = if curve_suffix.is_empty() {
-        "secp256k1-tr"
-    } else {
-        curve_suffix
-    };
+    // Format: /api/dkg/{curve}/round1
+    let curve_name = curve.as_str();
+    let round1_endpoint = format!("{}/round1", curve_name);
+    let round2_endpoint = format!("{}/round2", curve_name);
+    let finalize_endpoint = format!("{}/finalize", curve_name);
 
     tracing::info!(
         "Starting {} DKG for passphrase across {} nodes",
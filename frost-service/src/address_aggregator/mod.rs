@@ -1,4 +1,3 @@
-pub mod chain_derivation;
 pub mod dkg_orchestrator;
 pub mod multi_chain_api;
 
@@ -12,6 +11,7 @@ pub async fn run(
     server_config: crate::config::ServerConfig,
     aggregator_config: crate::config::AggregatorConfig,
     network_config: Option<crate::config::NetworkConfig>,
+    audit_log: Arc<crate::audit::AuditLog>,
 ) -> Result<()> {
     tracing::info!("Signer nodes: {:?}", aggregator_config.signer_nodes);
     tracing::info!(
@@ -24,10 +24,17 @@ pub async fn run(
         tracing::info!("Network: {}", net.network_type);
     }
 
+    let chains = crate::chains::ChainRegistry::from_config(
+        network_config.as_ref(),
+        aggregator_config.chains.as_deref(),
+    )?;
+    tracing::info!("Chains: {}", chains.names().join(", "));
+
     // Create multi-chain aggregator API
     let api = multi_chain_api::MultiChainAggregatorApi {
         config: Arc::new(aggregator_config),
-        network: Arc::new(network_config),
+        audit: audit_log,
+        chains: Arc::new(chains),
     };
 
     // Create API service
@@ -50,6 +57,7 @@ pub async fn run(
         "   🌍 POST /api/address/generate {{\"chain\": \"bitcoin\", \"passphrase\": \"uuid\"}}"
     );
     tracing::info!("   🌍 GET /api/address?chain=ethereum&passphrase=uuid");
+    tracing::info!("   🌍 GET /api/chains");
     tracing::info!(
         "   📖 Documentation: http://{}:{}/docs",
         server_config.host,
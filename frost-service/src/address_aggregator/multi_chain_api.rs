@@ -13,19 +13,19 @@ use poem_openapi::payload::Json;
 use poem_openapi::{ApiResponse, Object, OpenApi};
 use std::sync::Arc;
 
-use super::chain_derivation::{
-    derive_bitcoin_address, derive_ethereum_address, derive_solana_address, Chain,
-};
+use crate::audit::{AuditEvent, AuditKind, AuditLog};
+use crate::chains::ChainRegistry;
 use crate::config::AggregatorConfig;
 
 pub struct MultiChainAggregatorApi {
     pub config: Arc<AggregatorConfig>,
-    pub network: Arc<Option<crate::config::NetworkConfig>>,
+    pub audit: Arc<AuditLog>,
+    pub chains: Arc<ChainRegistry>,
 }
 
 #[derive(Debug, Object)]
 pub struct AddressRequest {
-    /// Chain identifier (bitcoin, ethereum, solana)
+    /// Chain name or alias (see GET /api/chains)
     pub chain: String,
     /// Unique passphrase for address generation
     pub passphrase: String,
@@ -38,6 +38,18 @@ pub struct AddressResponse {
     pub address: String,
     pub public_key: String, // hex-encoded public key
     pub curve: String,
+    pub network: String,
+    /// Second form of the address where the chain has one (Nostr: hex pubkey next to npub)
+    #[oai(skip_serializing_if_is_none)]
+    pub alternate_address: Option<String>,
+}
+
+#[derive(Debug, Object)]
+pub struct ChainInfo {
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub curve: String,
+    pub network: String,
 }
 
 #[derive(Debug, Object)]
@@ -73,23 +85,16 @@ impl MultiChainAggregatorApi {
     /// 4. Applies chain-specific address derivation locally
     #[oai(path = "/api/address/generate", method = "post")]
     async fn generate_address(&self, Json(req): Json<AddressRequest>) -> AddressResult {
-        // Parse chain
-        let chain = match Chain::from_str(&req.chain) {
-            Ok(c) => c,
+        let adapter = match self.chains.get(&req.chain) {
+            Ok(adapter) => adapter,
             Err(e) => {
                 return AddressResult::BadRequest(Json(ErrorResponse {
                     error: format!("Invalid chain: {}", e),
                 }))
             }
         };
-
-        // Determine curve and DKG endpoint
-        // Bitcoin uses Taproot (Schnorr), Ethereum uses ECDSA, Solana uses Ed25519
-        let (curve_name, curve_endpoint) = match chain {
-            Chain::Bitcoin => ("secp256k1-tr", "secp256k1-tr"), // Taproot/Schnorr
-            Chain::Ethereum => ("secp256k1", "secp256k1"),      // ECDSA
-            Chain::Solana => ("ed25519", "ed25519"),
-        };
+        let curve = adapter.curve();
+        let curve_name = curve.as_str();
 
         // Step 1: Check if DKG has been run (try to fetch pubkey from first node)
         let client = reqwest::Client::new();
@@ -98,7 +103,7 @@ impl MultiChainAggregatorApi {
         let pubkey_response = client
             .get(format!(
                 "{}/api/curve/{}/pubkey",
-                first_node_url, curve_endpoint
+                first_node_url, curve_name
             ))
             .query(&[("passphrase", &req.passphrase)])
             .send()
@@ -123,33 +128,28 @@ impl MultiChainAggregatorApi {
                     curve_name
                 );
 
-                // Choose the right orchestrator based on chain/curve
-                let dkg_result = match chain {
-                    Chain::Bitcoin => {
-                        // secp256k1-tr (Taproot/Schnorr) DKG
-                        super::dkg_orchestrator::orchestrate_dkg_taproot(
-                            self.config.signer_urls(),
-                            &req.passphrase,
-                        )
-                        .await
-                    }
-                    Chain::Ethereum => {
-                        // secp256k1 (ECDSA) DKG
-                        super::dkg_orchestrator::orchestrate_dkg_ecdsa(
-                            self.config.signer_urls(),
-                            &req.passphrase,
-                        )
-                        .await
-                    }
-                    Chain::Solana => {
-                        // Ed25519 DKG
-                        super::dkg_orchestrator::orchestrate_dkg_ed25519(
-                            self.config.signer_urls(),
-                            &req.passphrase,
-                        )
-                        .await
-                    }
-                };
+                let dkg_result = super::dkg_orchestrator::orchestrate_dkg(
+                    self.config.signer_urls(),
+                    &req.passphrase,
+                    curve,
+                )
+                .await;
+
+                let ceremony = AuditEvent::new(
+                    AuditKind::DkgCeremony,
+                    if dkg_result.is_ok() {
+                        "success"
+                    } else {
+                        "failure"
+                    },
+                )
+                .curve(curve_name)
+                .passphrase(&req.passphrase)
+                .participants(self.config.signer_urls());
+                self.audit.record(match &dkg_result {
+                    Ok(pubkey) => ceremony.detail(format!("pubkey={}", pubkey)),
+                    Err(e) => ceremony.detail(e.to_string()),
+                });
 
                 match dkg_result {
                     Ok(pubkey) => {
@@ -176,46 +176,30 @@ impl MultiChainAggregatorApi {
         }
 
         // Step 2: Derive chain-specific address from raw pubkey
-        let bitcoin_network = self
-            .network
-            .as_ref()
-            .as_ref()
-            .map(|n| n.bitcoin_network())
-            .unwrap_or(bitcoin::Network::Bitcoin);
-
-        let address = match chain {
-            Chain::Bitcoin => match derive_bitcoin_address(&pubkey_hex, bitcoin_network) {
-                Ok(addr) => addr,
-                Err(e) => {
-                    return AddressResult::InternalError(Json(ErrorResponse {
-                        error: format!("Bitcoin address derivation failed: {}", e),
-                    }))
-                }
-            },
-            Chain::Ethereum => match derive_ethereum_address(&pubkey_hex) {
-                Ok(addr) => addr,
-                Err(e) => {
-                    return AddressResult::InternalError(Json(ErrorResponse {
-                        error: format!("Ethereum address derivation failed: {}", e),
-                    }))
-                }
-            },
-            Chain::Solana => match derive_solana_address(&pubkey_hex) {
-                Ok(addr) => addr,
-                Err(e) => {
-                    return AddressResult::InternalError(Json(ErrorResponse {
-                        error: format!("Solana address derivation failed: {}", e),
-                    }))
-                }
-            },
+        let (address, alternate_address) = match hex::decode(&pubkey_hex)
+            .map_err(anyhow::Error::from)
+            .and_then(|pubkey| {
+                Ok((
+                    adapter.derive_address(&pubkey)?,
+                    adapter.alternate_address(&pubkey),
+                ))
+            }) {
+            Ok(addresses) => addresses,
+            Err(e) => {
+                return AddressResult::InternalError(Json(ErrorResponse {
+                    error: format!("{} address derivation failed: {}", adapter.name(), e),
+                }))
+            }
         };
 
         AddressResult::Ok(Json(AddressResponse {
-            chain: chain.as_str().to_string(),
+            chain: adapter.name().to_string(),
             passphrase: req.passphrase,
             address,
             public_key: pubkey_hex, // hex-encoded public key for client-side verification
             curve: curve_name.to_string(),
+            network: adapter.network(),
+            alternate_address,
         }))
     }
 
@@ -232,16 +216,28 @@ impl MultiChainAggregatorApi {
             .await
     }
 
+    /// Registered chains with their curve and network
+    #[oai(path = "/api/chains", method = "get")]
+    async fn list_chains(&self) -> Json<Vec<ChainInfo>> {
+        Json(
+            self.chains
+                .adapters()
+                .map(|adapter| ChainInfo {
+                    name: adapter.name().to_string(),
+                    aliases: adapter.aliases().iter().map(|a| a.to_string()).collect(),
+                    curve: adapter.curve().as_str().to_string(),
+                    network: adapter.network(),
+                })
+                .collect(),
+        )
+    }
+
     /// Health check
     #[oai(path = "/health", method = "get")]
     async fn health(&self) -> Json<HealthResponse> {
         Json(HealthResponse {
             status: "ok".to_string(),
-            supported_chains: vec![
-                "bitcoin".to_string(),
-                "ethereum".to_string(),
-                "solana".to_string(),
-            ],
+            supported_chains: self.chains.names(),
             signer_nodes: self.config.signer_urls().len(),
         })
     }
@@ -272,5 +268,12 @@ impl MultiChainAggregatorApi {
 //   "passphrase": "550e8400-e29b-41d4-a716-446655440000"
 // }
 //
+// Generate Nostr identity (the Bitcoin Taproot key; npub + hex pubkey):
+// POST /api/address/generate
+// {
+//   "chain": "nostr",
+//   "passphrase": "550e8400-e29b-41d4-a716-446655440000"
+// }
+//
 // Or use query params:
 // GET /api/address?chain=bitcoin&passphrase=550e8400-e29b-41d4-a716-446655440000
@@ -0,0 +1,478 @@
+//! Tamper-evident audit log
+//!
+//! Append-only JSON-lines file where every entry commits to its predecessor:
+//!
+//! ```text
+//! hash_n = sha256(hash_{n-1} || canonical_json(entry_n without hash))
+//! ```
+//!
+//! Editing or deleting any entry breaks the chain. Truncating the tail is caught
+//! by the `<log>.head` file (last seq + hash, rewritten after every append) and,
+//! for an attacker who rewrites both, by comparing against an externally recorded
+//! head (`frost-audit verify --expect-head`).
+//!
+//! This module is shared with the `frost-audit` binary, so it only depends on
+//! external crates.
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// prev_hash of the first entry
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditKind {
+    DkgCeremony,
+    SigningRound,
+    HsmUnlock,
+    HsmLock,
+    PolicyDecision,
+    BackupExport,
+    BackupRestore,
+    KeyLifecycle,
+    StorageScrub,
+}
+
+/// What happened (everything except the chain bookkeeping)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub kind: AuditKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub participants: Vec<String>,
+    pub outcome: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl AuditEvent {
+    pub fn new(kind: AuditKind, outcome: impl Into<String>) -> Self {
+        Self {
+            kind,
+            curve: None,
+            passphrase_hash: None,
+            message_hash: None,
+            participants: Vec::new(),
+            outcome: outcome.into(),
+            detail: None,
+        }
+    }
+
+    pub fn curve(mut self, curve: impl Into<String>) -> Self {
+        self.curve = Some(curve.into());
+        self
+    }
+
+    /// Records sha256(passphrase), never the passphrase itself
+    pub fn passphrase(mut self, passphrase: &str) -> Self {
+        self.passphrase_hash = Some(sha256_hex(passphrase.as_bytes()));
+        self
+    }
+
+    /// Records sha256(message)
+    pub fn message(mut self, message: &[u8]) -> Self {
+        self.message_hash = Some(sha256_hex(message));
+        self
+    }
+
+    pub fn participants<I, S>(mut self, participants: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.participants = participants.into_iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub timestamp: u64,
+    pub source: String,
+    #[serde(flatten)]
+    pub event: AuditEvent,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+/// The hashed part of an entry (everything but `hash`)
+#[derive(Serialize)]
+struct UnsealedEntry<'a> {
+    seq: u64,
+    timestamp: u64,
+    source: &'a str,
+    #[serde(flatten)]
+    event: &'a AuditEvent,
+    prev_hash: &'a str,
+}
+
+impl AuditEntry {
+    fn compute_hash(&self) -> Result<String> {
+        let unsealed = UnsealedEntry {
+            seq: self.seq,
+            timestamp: self.timestamp,
+            source: &self.source,
+            event: &self.event,
+            prev_hash: &self.prev_hash,
+        };
+        let json = serde_json::to_vec(&unsealed).context("Failed to serialize audit entry")?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.prev_hash.as_bytes());
+        hasher.update(&json);
+        Ok(hex::encode(hasher.finalize()))
+    }
+}
+
+/// Contents of the `.head` file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditHead {
+    pub seq: u64,
+    pub hash: String,
+}
+
+struct ChainState {
+    file: File,
+    next_seq: u64,
+    last_hash: String,
+}
+
+/// Append-only, hash-chained audit log
+pub struct AuditLog {
+    path: PathBuf,
+    source: String,
+    state: Mutex<ChainState>,
+}
+
+impl AuditLog {
+    /// Open (or create) the log, verifying the existing chain first
+    ///
+    /// Refuses to open a log that fails verification - a broken chain needs
+    /// an operator to look at it, not to be silently extended.
+    pub fn open<P: AsRef<Path>>(path: P, source: impl Into<String>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent).context(format!(
+                    "Failed to create audit log directory {}",
+                    parent.display()
+                ))?;
+            }
+        }
+
+        let (next_seq, last_hash) = if path.exists() {
+            let report = verify_log(&path, None)?;
+            if !report.is_valid() {
+                anyhow::bail!(
+                    "Audit log {} failed verification: {}",
+                    path.display(),
+                    report.errors.join("; ")
+                );
+            }
+            (report.entries, report.last_hash)
+        } else {
+            (0, GENESIS_HASH.to_string())
+        };
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .context(format!("Failed to open audit log {}", path.display()))?;
+
+        Ok(Self {
+            path,
+            source: source.into(),
+            state: Mutex::new(ChainState {
+                file,
+                next_seq,
+                last_hash,
+            }),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an event, fsync it and advance the head
+    pub fn append(&self, event: AuditEvent) -> Result<AuditEntry> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Audit log lock poisoned: {:?}", e))?;
+
+        let mut entry = AuditEntry {
+            seq: state.next_seq,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            source: self.source.clone(),
+            event,
+            prev_hash: state.last_hash.clone(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash()?;
+
+        let mut line = serde_json::to_vec(&entry).context("Failed to serialize audit entry")?;
+        line.push(b'\n');
+        state
+            .file
+            .write_all(&line)
+            .context("Failed to append audit entry")?;
+        state.file.sync_data().context("Failed to sync audit log")?;
+
+        write_head(
+            &self.path,
+            &AuditHead {
+                seq: entry.seq,
+                hash: entry.hash.clone(),
+            },
+        )?;
+
+        state.next_seq += 1;
+        state.last_hash = entry.hash.clone();
+
+        Ok(entry)
+    }
+
+    /// Append, logging instead of failing (for events that must not block the operation)
+    pub fn record(&self, event: AuditEvent) {
+        if let Err(e) = self.append(event) {
+            tracing::error!("Failed to write audit log {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+pub fn head_path(log_path: &Path) -> PathBuf {
+    let mut name = log_path.as_os_str().to_owned();
+    name.push(".head");
+    PathBuf::from(name)
+}
+
+fn write_head(log_path: &Path, head: &AuditHead) -> Result<()> {
+    let head_path = head_path(log_path);
+    let mut tmp = head_path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    std::fs::write(&tmp, serde_json::to_vec(head)?).context("Failed to write audit head")?;
+    std::fs::rename(&tmp, &head_path).context("Failed to update audit head")?;
+    Ok(())
+}
+
+pub fn read_head(log_path: &Path) -> Result<Option<AuditHead>> {
+    let head_path = head_path(log_path);
+    if !head_path.exists() {
+        return Ok(None);
+    }
+    let bytes = std::fs::read(&head_path).context("Failed to read audit head")?;
+    Ok(Some(
+        serde_json::from_slice(&bytes).context("Failed to parse audit head")?,
+    ))
+}
+
+/// Read every entry without verifying (use `verify_log` for that)
+#[allow(dead_code)] // used by frost-audit
+pub fn read_entries(path: &Path) -> Result<Vec<AuditEntry>> {
+    let file = File::open(path).context(format!("Failed to open {}", path.display()))?;
+    let mut entries = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(
+            serde_json::from_str(&line)
+                .context(format!("Malformed audit entry on line {}", i + 1))?,
+        );
+    }
+    Ok(entries)
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifyReport {
+    pub entries: u64,
+    pub last_hash: String,
+    pub errors: Vec<String>,
+}
+
+impl VerifyReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Walk the chain and check hashes, sequence numbers and the head file
+///
+/// `expected_head` is an externally anchored hash (e.g. copied into a ticket or
+/// another system) - if given, the log must end exactly there.
+pub fn verify_log(path: &Path, expected_head: Option<&str>) -> Result<VerifyReport> {
+    let file = File::open(path).context(format!("Failed to open {}", path.display()))?;
+
+    let mut errors = Vec::new();
+    let mut prev_hash = GENESIS_HASH.to_string();
+    let mut count = 0u64;
+
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let entry: AuditEntry = match serde_json::from_str(&line) {
+            Ok(e) => e,
+            Err(e) => {
+                errors.push(format!("line {}: malformed entry: {}", i + 1, e));
+                break;
+            }
+        };
+
+        if entry.seq != count {
+            errors.push(format!(
+                "line {}: expected seq {}, found {}",
+                i + 1,
+                count,
+                entry.seq
+            ));
+        }
+        if entry.prev_hash != prev_hash {
+            errors.push(format!(
+                "seq {}: prev_hash does not match previous entry",
+                entry.seq
+            ));
+        }
+        if entry.compute_hash()? != entry.hash {
+            errors.push(format!("seq {}: hash mismatch (entry edited)", entry.seq));
+        }
+
+        prev_hash = entry.hash;
+        count += 1;
+    }
+
+    match read_head(path)? {
+        Some(head) if count == 0 || head.seq != count - 1 || head.hash != prev_hash => {
+            errors.push(format!(
+                "head file points to seq {} ({}), log ends at {} entries - log truncated or head replaced",
+                head.seq,
+                &head.hash[..16.min(head.hash.len())],
+                count
+            ));
+        }
+        Some(_) => {}
+        None if count > 0 => errors.push("head file missing".to_string()),
+        None => {}
+    }
+
+    if let Some(expected) = expected_head {
+        if expected != prev_hash {
+            errors.push(format!(
+                "log ends at {} but expected head {}",
+                prev_hash, expected
+            ));
+        }
+    }
+
+    Ok(VerifyReport {
+        entries: count,
+        last_hash: prev_hash,
+        errors,
+    })
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("frost-audit-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join("audit.log")
+    }
+
+    fn write_sample(path: &Path) {
+        let log = AuditLog::open(path, "node-0").unwrap();
+        log.append(
+            AuditEvent::new(AuditKind::DkgCeremony, "success")
+                .curve("ed25519")
+                .passphrase("p1")
+                .participants([0, 1, 2]),
+        )
+        .unwrap();
+        log.append(
+            AuditEvent::new(AuditKind::SigningRound, "success")
+                .passphrase("p1")
+                .message(b"hello"),
+        )
+        .unwrap();
+        log.append(AuditEvent::new(AuditKind::HsmLock, "success"))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_chain_verifies_and_resumes() {
+        let path = temp_log("resume");
+        write_sample(&path);
+
+        let report = verify_log(&path, None).unwrap();
+        assert!(report.is_valid(), "{:?}", report.errors);
+        assert_eq!(report.entries, 3);
+
+        // Reopening continues the chain
+        let log = AuditLog::open(&path, "node-0").unwrap();
+        let entry = log
+            .append(AuditEvent::new(AuditKind::HsmUnlock, "success"))
+            .unwrap();
+        assert_eq!(entry.seq, 3);
+        assert_eq!(entry.prev_hash, report.last_hash);
+        assert!(verify_log(&path, Some(&entry.hash)).unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_detects_edit() {
+        let path = temp_log("edit");
+        write_sample(&path);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, content.replace("\"success\"", "\"failure\"")).unwrap();
+
+        assert!(!verify_log(&path, None).unwrap().is_valid());
+        assert!(AuditLog::open(&path, "node-0").is_err());
+    }
+
+    #[test]
+    fn test_detects_truncation() {
+        let path = temp_log("truncate");
+        write_sample(&path);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let truncated: Vec<&str> = content.lines().take(2).collect();
+        std::fs::write(&path, truncated.join("\n") + "\n").unwrap();
+
+        let report = verify_log(&path, None).unwrap();
+        assert!(!report.is_valid());
+        assert_eq!(report.entries, 2);
+    }
+}
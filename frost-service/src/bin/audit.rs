@@ -0,0 +1,96 @@
+use anyhow::Result;
+use std::env;
+use std::path::Path;
+
+#[path = "../audit.rs"]
+#[allow(dead_code)] // the writer side is only used by frost-service
+mod audit;
+
+const USAGE: &str = "Usage:
+  frost-audit verify <audit.log> [--expect-head <hash>]
+  frost-audit export <audit.log> [--format json|csv]";
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("verify") if args.len() >= 2 => {
+            let expected_head = flag_value(&args[2..], "--expect-head");
+            verify(Path::new(&args[1]), expected_head)
+        }
+        Some("export") if args.len() >= 2 => {
+            let format = flag_value(&args[2..], "--format").unwrap_or("json");
+            export(Path::new(&args[1]), format)
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+fn verify(path: &Path, expected_head: Option<&str>) -> Result<()> {
+    let report = audit::verify_log(path, expected_head)?;
+
+    println!("Entries:   {}", report.entries);
+    println!("Last hash: {}", report.last_hash);
+
+    if report.is_valid() {
+        println!("✅ Audit log chain is intact");
+        Ok(())
+    } else {
+        println!("❌ Audit log verification FAILED:");
+        for error in &report.errors {
+            println!("   - {}", error);
+        }
+        std::process::exit(1);
+    }
+}
+
+fn export(path: &Path, format: &str) -> Result<()> {
+    let entries = audit::read_entries(path)?;
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&entries)?),
+        "csv" => {
+            println!("seq,timestamp,source,kind,curve,passphrase_hash,message_hash,participants,outcome,detail,prev_hash,hash");
+            for entry in &entries {
+                let kind = serde_json::to_value(entry.event.kind)?;
+                let fields = [
+                    entry.seq.to_string(),
+                    entry.timestamp.to_string(),
+                    entry.source.clone(),
+                    kind.as_str().unwrap_or_default().to_string(),
+                    entry.event.curve.clone().unwrap_or_default(),
+                    entry.event.passphrase_hash.clone().unwrap_or_default(),
+                    entry.event.message_hash.clone().unwrap_or_default(),
+                    entry.event.participants.join(";"),
+                    entry.event.outcome.clone(),
+                    entry.event.detail.clone().unwrap_or_default(),
+                    entry.prev_hash.clone(),
+                    entry.hash.clone(),
+                ];
+                let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                println!("{}", row.join(","));
+            }
+        }
+        other => anyhow::bail!("Unknown export format: {} (expected json or csv)", other),
+    }
+
+    Ok(())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,65 @@
+use anyhow::{Context, Result};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::SecretKey;
+use sha2::Digest;
+use std::env;
+use std::path::Path;
+
+const USAGE: &str = "Usage:
+  frost-backup keygen
+  frost-backup inspect <archive.json>";
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("keygen") => keygen(),
+        Some("inspect") if args.len() >= 2 => inspect(Path::new(&args[1])),
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Recovery keypair for `[node.backup] recovery_keys` (run on an offline machine)
+fn keygen() -> Result<()> {
+    let secret = SecretKey::random(&mut rand::thread_rng());
+    let public = secret.public_key().to_encoded_point(true);
+
+    println!("Recovery public key (add to [node.backup] recovery_keys):");
+    println!("  {}", hex::encode(public.as_bytes()));
+    println!();
+    println!("Recovery secret key (keep offline, needed for /api/backup/restore):");
+    println!("  {}", hex::encode(secret.to_bytes()));
+    Ok(())
+}
+
+/// Print an archive's header without decrypting it
+fn inspect(path: &Path) -> Result<()> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let archive: serde_json::Value =
+        serde_json::from_str(contents.trim()).context("Archive is not JSON")?;
+    let field = |name: &str| archive.get(name).cloned().unwrap_or_default();
+
+    println!("Version:    {}", field("version"));
+    println!("Node index: {}", field("node_index"));
+    println!("Created at: {} (unix)", field("created_at"));
+    println!(
+        "SHA-256:    {}",
+        hex::encode(sha2::Sha256::digest(contents.trim()))
+    );
+    println!("Recovery keys ({} needed):", field("threshold"));
+    for recipient in field("recipients").as_array().into_iter().flatten() {
+        println!(
+            "  #{} {}",
+            recipient.get("index").cloned().unwrap_or_default(),
+            recipient
+                .get("public_key")
+                .and_then(|key| key.as_str())
+                .unwrap_or_default()
+        );
+    }
+    Ok(())
+}
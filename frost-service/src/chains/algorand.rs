@@ -0,0 +1,176 @@
+//! Algorand adapter
+//!
+//! Addresses are base32(pubkey || sha512/256(pubkey)[28..]) of the Ed25519
+//! group key. The transaction is base64 canonical msgpack of the unsigned
+//! `txn`; FROST signs "TX" || txn and the result is a msgpack `SignedTxn`
+//! ready for `/v2/transactions`.
+
+use anyhow::{Context, Result};
+use base64::prelude::*;
+use data_encoding::BASE32_NOPAD;
+use sha2::{Digest, Sha512_256};
+
+use super::solana::verify_signature;
+use super::{ChainAdapter, SignedTransaction, SigningInput};
+use crate::curves::CurveType;
+use crate::node::tx_policy::TransactionContext;
+
+pub struct AlgorandAdapter {
+    network: String,
+}
+
+impl AlgorandAdapter {
+    pub fn new(network: &str) -> Self {
+        Self {
+            network: network.to_string(),
+        }
+    }
+}
+
+impl ChainAdapter for AlgorandAdapter {
+    fn name(&self) -> &'static str {
+        "algorand"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["algo"]
+    }
+
+    fn curve(&self) -> CurveType {
+        CurveType::Ed25519
+    }
+
+    fn network(&self) -> String {
+        self.network.clone()
+    }
+
+    fn derive_address(&self, public_key: &[u8]) -> Result<String> {
+        if public_key.len() != 32 {
+            anyhow::bail!("Invalid Ed25519 pubkey length: {}", public_key.len());
+        }
+        let checksum = Sha512_256::digest(public_key);
+        let mut payload = public_key.to_vec();
+        payload.extend_from_slice(&checksum[28..]);
+        Ok(BASE32_NOPAD.encode(&payload))
+    }
+
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>> {
+        if public_keys.len() != 1 {
+            anyhow::bail!("Algorand transactions take exactly one passphrase (the sender)");
+        }
+        let txn = decode_txn(transaction)?;
+
+        Ok(vec![SigningInput {
+            message: signing_message(&txn),
+            signer: 0,
+            // Nodes recompute the "TX"-prefixed message before releasing shares
+            context: Some(TransactionContext {
+                kind: "algorand".to_string(),
+                payload: transaction.to_string(),
+                input_index: None,
+            }),
+            optional: false,
+        }])
+    }
+
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction> {
+        let txn = decode_txn(transaction)?;
+        let public_key = &public_keys[0];
+        let signature = signatures
+            .first()
+            .and_then(Option::as_deref)
+            .context("Missing signature")?;
+
+        let verified = verify_signature(public_key, &inputs[0].message, signature);
+        if !verified {
+            tracing::error!("❌ Algorand signature does not verify");
+        }
+
+        // SignedTxn {"sig": bin, "txn": map}, keys in canonical (sorted) order
+        let mut signed = vec![0x82, 0xa3, b's', b'i', b'g', 0xc4, signature.len() as u8];
+        signed.extend_from_slice(signature);
+        signed.extend_from_slice(&[0xa3, b't', b'x', b'n']);
+        signed.extend_from_slice(&txn);
+
+        Ok(SignedTransaction {
+            transaction: BASE64_STANDARD.encode(signed),
+            tx_id: Some(BASE32_NOPAD.encode(&Sha512_256::digest(&inputs[0].message))),
+            signers: vec![self.derive_address(public_key)?],
+            missing_signers: Vec::new(),
+            verified,
+            signature: Some(BASE64_STANDARD.encode(signature)),
+        })
+    }
+}
+
+/// "TX" || msgpack(txn), the bytes an Algorand key signs
+pub fn signing_message(txn: &[u8]) -> Vec<u8> {
+    let mut message = b"TX".to_vec();
+    message.extend_from_slice(txn);
+    message
+}
+
+/// base64 msgpack of an unsigned transaction (not a SignedTxn)
+pub fn decode_txn(transaction: &str) -> Result<Vec<u8>> {
+    let txn = BASE64_STANDARD
+        .decode(transaction)
+        .context("Invalid base64 transaction")?;
+    match txn.first() {
+        Some(0x80..=0x8f) | Some(0xde) => {}
+        _ => anyhow::bail!("Transaction must be a msgpack map"),
+    }
+    if txn.get(1..5) == Some(b"\xa3sig") || txn.get(1..5) == Some(b"\xa3txn") {
+        anyhow::bail!("Expected the unsigned txn, got a SignedTxn");
+    }
+    Ok(txn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_and_signed_txn() {
+        let adapter = AlgorandAdapter::new("mainnet");
+        assert_eq!(
+            adapter.derive_address(&[0u8; 32]).unwrap(),
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAY5HFKQ"
+        );
+
+        let mut rng = rand::thread_rng();
+        let signing_key = frost_ed25519::SigningKey::new(&mut rng);
+        let public_key = frost_ed25519::VerifyingKey::from(&signing_key)
+            .serialize()
+            .unwrap();
+
+        // {"fee": 1000}
+        let txn = [0x81, 0xa3, b'f', b'e', b'e', 0xcd, 0x03, 0xe8];
+        let tx = BASE64_STANDARD.encode(txn);
+        let inputs = adapter
+            .prepare(&tx, std::slice::from_ref(&public_key))
+            .unwrap();
+        assert_eq!(&inputs[0].message[..2], b"TX");
+
+        let signature = signing_key.sign(&mut rng, &inputs[0].message);
+        let signed = adapter
+            .assemble(
+                &tx,
+                std::slice::from_ref(&public_key),
+                &inputs,
+                &[Some(signature.serialize().unwrap())],
+            )
+            .unwrap();
+        assert!(signed.verified);
+        assert_eq!(signed.tx_id.unwrap().len(), 52);
+
+        let signed = BASE64_STANDARD.decode(signed.transaction).unwrap();
+        assert!(decode_txn(&BASE64_STANDARD.encode(&signed)).is_err());
+        assert!(signed.ends_with(&txn));
+    }
+}
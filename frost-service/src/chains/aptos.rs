@@ -0,0 +1,214 @@
+//! Aptos adapter
+//!
+//! The account address is the authentication key sha3-256(pubkey || scheme)
+//! of the Ed25519 group key. The transaction is 0x-hex BCS `RawTransaction`;
+//! FROST signs sha3-256("APTOS::RawTransaction") || raw and the result is a
+//! BCS `SignedTransaction` ready for the submit endpoint.
+
+use anyhow::{Context, Result};
+use sha3::{Digest, Sha3_256};
+
+use super::solana::verify_signature;
+use super::{ChainAdapter, SignedTransaction, SigningInput};
+use crate::curves::CurveType;
+use crate::node::tx_policy::TransactionContext;
+
+/// Authentication key scheme for single Ed25519 keys
+const ED25519_SCHEME: u8 = 0x00;
+
+pub struct AptosAdapter {
+    network: String,
+}
+
+impl AptosAdapter {
+    pub fn new(network: &str) -> Self {
+        Self {
+            network: network.to_string(),
+        }
+    }
+
+    /// Chain id raw transactions must carry (devnet resets, so it isn't pinned)
+    fn chain_id(&self) -> Option<u8> {
+        match self.network.as_str() {
+            "mainnet" => Some(1),
+            "testnet" => Some(2),
+            _ => None,
+        }
+    }
+}
+
+impl ChainAdapter for AptosAdapter {
+    fn name(&self) -> &'static str {
+        "aptos"
+    }
+
+    fn curve(&self) -> CurveType {
+        CurveType::Ed25519
+    }
+
+    fn network(&self) -> String {
+        self.network.clone()
+    }
+
+    fn derive_address(&self, public_key: &[u8]) -> Result<String> {
+        Ok(format!(
+            "0x{}",
+            hex::encode(authentication_key(ed25519_key(public_key)?))
+        ))
+    }
+
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>> {
+        if public_keys.len() != 1 {
+            anyhow::bail!("Aptos transactions take exactly one passphrase (the sender)");
+        }
+        let raw = decode_raw(transaction)?;
+
+        // RawTransaction starts with the sender and ends with the chain id
+        if raw.len() < 33 {
+            anyhow::bail!("Raw transaction too short");
+        }
+        if raw[..32] != authentication_key(ed25519_key(&public_keys[0])?) {
+            anyhow::bail!(
+                "Transaction sender 0x{} is not this passphrase's account",
+                hex::encode(&raw[..32])
+            );
+        }
+        let chain_id = raw[raw.len() - 1];
+        if let Some(expected) = self.chain_id() {
+            if chain_id != expected {
+                anyhow::bail!(
+                    "Chain id mismatch: transaction has {}, aggregator is configured for {}",
+                    chain_id,
+                    expected
+                );
+            }
+        }
+
+        Ok(vec![SigningInput {
+            message: signing_message(&raw),
+            signer: 0,
+            // Nodes recompute the signing message before releasing shares
+            context: Some(TransactionContext {
+                kind: "aptos".to_string(),
+                payload: transaction.to_string(),
+                input_index: None,
+            }),
+            optional: false,
+        }])
+    }
+
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction> {
+        let raw = decode_raw(transaction)?;
+        let public_key = ed25519_key(&public_keys[0])?;
+        let signature = signatures
+            .first()
+            .and_then(Option::as_deref)
+            .context("Missing signature")?;
+
+        let verified = verify_signature(public_key, &inputs[0].message, signature);
+        if !verified {
+            tracing::error!("❌ Aptos signature does not verify");
+        }
+
+        // SignedTransaction { raw_txn, authenticator: Ed25519 { public_key, signature } }
+        let mut signed = raw;
+        signed.push(0x00);
+        signed.push(public_key.len() as u8);
+        signed.extend_from_slice(public_key);
+        signed.push(signature.len() as u8);
+        signed.extend_from_slice(signature);
+
+        // Transaction::UserTransaction(signed), hashed with its domain prefix
+        let mut hasher = Sha3_256::new();
+        hasher.update(Sha3_256::digest(b"APTOS::Transaction"));
+        hasher.update([0x00]);
+        hasher.update(&signed);
+
+        Ok(SignedTransaction {
+            transaction: format!("0x{}", hex::encode(&signed)),
+            tx_id: Some(format!("0x{}", hex::encode(hasher.finalize()))),
+            signers: vec![self.derive_address(public_key)?],
+            missing_signers: Vec::new(),
+            verified,
+            signature: Some(format!("0x{}", hex::encode(signature))),
+        })
+    }
+}
+
+/// sha3-256("APTOS::RawTransaction") || raw, the bytes an Aptos key signs
+pub fn signing_message(raw: &[u8]) -> Vec<u8> {
+    let mut message = Sha3_256::digest(b"APTOS::RawTransaction").to_vec();
+    message.extend_from_slice(raw);
+    message
+}
+
+/// 0x-hex BCS RawTransaction
+pub fn decode_raw(transaction: &str) -> Result<Vec<u8>> {
+    hex::decode(transaction.trim_start_matches("0x")).context("Invalid raw transaction hex")
+}
+
+fn authentication_key(public_key: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(public_key);
+    hasher.update([ED25519_SCHEME]);
+    hasher.finalize().into()
+}
+
+fn ed25519_key(public_key: &[u8]) -> Result<&[u8]> {
+    if public_key.len() != 32 {
+        anyhow::bail!("Invalid Ed25519 pubkey length: {}", public_key.len());
+    }
+    Ok(public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aptos_sign_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let signing_key = frost_ed25519::SigningKey::new(&mut rng);
+        let public_key = frost_ed25519::VerifyingKey::from(&signing_key)
+            .serialize()
+            .unwrap();
+
+        let adapter = AptosAdapter::new("testnet");
+        let address = adapter.derive_address(&public_key).unwrap();
+
+        // sender || (body) || chain id
+        let mut raw = hex::decode(address.trim_start_matches("0x")).unwrap();
+        raw.extend_from_slice(&[0xaa; 40]);
+        raw.push(2);
+        let tx = format!("0x{}", hex::encode(&raw));
+
+        let inputs = adapter
+            .prepare(&tx, std::slice::from_ref(&public_key))
+            .unwrap();
+        let signature = signing_key.sign(&mut rng, &inputs[0].message);
+        let signed = adapter
+            .assemble(
+                &tx,
+                std::slice::from_ref(&public_key),
+                &inputs,
+                &[Some(signature.serialize().unwrap())],
+            )
+            .unwrap();
+        assert!(signed.verified);
+        assert_eq!(signed.transaction.len(), 2 + 2 * (raw.len() + 1 + 33 + 65));
+
+        // Wrong chain id and foreign sender are rejected
+        let mainnet = AptosAdapter::new("mainnet");
+        assert!(mainnet
+            .prepare(&tx, std::slice::from_ref(&public_key))
+            .is_err());
+        raw[0] ^= 1;
+        assert!(adapter.prepare(&hex::encode(&raw), &[public_key]).is_err());
+    }
+}
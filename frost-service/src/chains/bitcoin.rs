@@ -0,0 +1,277 @@
+//! Bitcoin Taproot adapter
+//!
+//! Addresses are untweaked P2TR outputs of the secp256k1-tr group key, and
+//! transactions are PSBTs signed with key-path BIP340 signatures.
+
+use anyhow::{anyhow, Context, Result};
+use bitcoin::hashes::Hash;
+use bitcoin::psbt::Psbt;
+use bitcoin::sighash::{Prevouts, SighashCache};
+use bitcoin::{Network, TapSighashType};
+
+use super::{ChainAdapter, SignedTransaction, SigningInput};
+use crate::curves::CurveType;
+use crate::node::tx_policy::TransactionContext;
+
+pub struct BitcoinAdapter {
+    network: Network,
+}
+
+impl BitcoinAdapter {
+    pub fn new(network: Network) -> Self {
+        Self { network }
+    }
+}
+
+impl ChainAdapter for BitcoinAdapter {
+    fn name(&self) -> &'static str {
+        "bitcoin"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["btc"]
+    }
+
+    fn curve(&self) -> CurveType {
+        CurveType::Secp256k1Taproot
+    }
+
+    fn network(&self) -> String {
+        self.network.to_string()
+    }
+
+    fn derive_address(&self, public_key: &[u8]) -> Result<String> {
+        taproot_address(public_key, self.network)
+    }
+
+    /// One key-path sighash per PSBT input, signed by the passphrase at the same index
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>> {
+        prepare_psbt(transaction, public_keys, "psbt")
+    }
+
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction> {
+        assemble_psbt(transaction, public_keys, inputs, signatures, &|key| {
+            self.derive_address(key)
+        })
+    }
+}
+
+/// One key-path sighash per PSBT input, signed by the passphrase at the same index
+///
+/// Shared by every chain that uses Bitcoin's transaction format and BIP341
+/// sighashes; `kind` tells signer nodes which policy applies.
+pub(super) fn prepare_psbt(
+    transaction: &str,
+    public_keys: &[Vec<u8>],
+    kind: &str,
+) -> Result<Vec<SigningInput>> {
+    let psbt: Psbt = transaction
+        .parse()
+        .map_err(|e| anyhow!("Invalid PSBT: {}", e))?;
+
+    if psbt.inputs.len() != public_keys.len() {
+        anyhow::bail!(
+            "Passphrase count mismatch: {} inputs, {} passphrases",
+            psbt.inputs.len(),
+            public_keys.len()
+        );
+    }
+
+    let prevouts: Vec<bitcoin::TxOut> = psbt
+        .inputs
+        .iter()
+        .filter_map(|input| input.witness_utxo.clone())
+        .collect();
+
+    if prevouts.len() != psbt.inputs.len() {
+        anyhow::bail!("Missing witness_utxo in PSBT inputs");
+    }
+
+    let prevouts = Prevouts::All(&prevouts);
+    let mut cache = SighashCache::new(&psbt.unsigned_tx);
+
+    (0..psbt.inputs.len())
+        .map(|input_idx| {
+            let sighash = cache
+                .taproot_key_spend_signature_hash(input_idx, &prevouts, TapSighashType::Default)
+                .context("Sighash calculation failed")?;
+
+            Ok(SigningInput {
+                message: sighash.as_byte_array().to_vec(),
+                signer: input_idx,
+                // Nodes recompute the sighash from the PSBT before releasing shares
+                context: Some(TransactionContext {
+                    kind: kind.to_string(),
+                    payload: transaction.to_string(),
+                    input_index: Some(input_idx as u32),
+                }),
+                // A failed input leaves the PSBT partially signed, not rejected
+                optional: true,
+            })
+        })
+        .collect()
+}
+
+/// Apply key-path signatures to the PSBT and finalize the signed inputs
+pub(super) fn assemble_psbt(
+    transaction: &str,
+    public_keys: &[Vec<u8>],
+    inputs: &[SigningInput],
+    signatures: &[Option<Vec<u8>>],
+    address_of: &dyn Fn(&[u8]) -> Result<String>,
+) -> Result<SignedTransaction> {
+    let mut psbt: Psbt = transaction
+        .parse()
+        .map_err(|e| anyhow!("Invalid PSBT: {}", e))?;
+
+    let mut signers = Vec::new();
+    let mut missing_signers = Vec::new();
+    let mut verified = true;
+
+    for (input, signature) in inputs.iter().zip(signatures) {
+        let input_idx = input.signer;
+        let address = address_of(&public_keys[input_idx])?;
+
+        let schnorr_sig = match signature
+            .as_deref()
+            .map(bitcoin::secp256k1::schnorr::Signature::from_slice)
+        {
+            Some(Ok(sig)) => sig,
+            Some(Err(e)) => {
+                tracing::error!("Invalid Schnorr signature: {}", e);
+                missing_signers.push(address);
+                continue;
+            }
+            None => {
+                missing_signers.push(address);
+                continue;
+            }
+        };
+
+        // Verify signature locally before adding to PSBT
+        if let Some(witness_utxo) = &psbt.inputs[input_idx].witness_utxo {
+            if witness_utxo.script_pubkey.is_p2tr() {
+                let script_bytes = witness_utxo.script_pubkey.as_bytes();
+                if script_bytes.len() == 34 {
+                    let xonly_bytes = &script_bytes[2..34];
+                    if let Ok(xonly_pubkey) = bitcoin::key::XOnlyPublicKey::from_slice(xonly_bytes)
+                    {
+                        let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+                        let digest: [u8; 32] = input.message[..].try_into()?;
+                        let msg = bitcoin::secp256k1::Message::from_digest(digest);
+
+                        match secp.verify_schnorr(&schnorr_sig, &msg, &xonly_pubkey) {
+                            Ok(_) => {
+                                tracing::info!("✅ Input {} signature VERIFIED locally", input_idx)
+                            }
+                            Err(e) => {
+                                verified = false;
+                                tracing::error!(
+                                    "❌ Input {} signature VERIFICATION FAILED: {:?}",
+                                    input_idx,
+                                    e
+                                )
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        psbt.inputs[input_idx].tap_key_sig = Some(bitcoin::taproot::Signature {
+            signature: schnorr_sig,
+            sighash_type: TapSighashType::Default,
+        });
+        signers.push(address);
+
+        tracing::info!("✅ Input {} signed and added to PSBT", input_idx);
+    }
+
+    tracing::info!(
+        "PSBT signing complete: {}/{} inputs signed",
+        signers.len(),
+        psbt.inputs.len()
+    );
+
+    // Finalize PSBT - convert tap_key_sig to final_script_witness
+    // Note: We don't use miniscript::finalize_mut() because it requires valid
+    // public keys in witness_utxo, which clients may not have during PSBT building
+    for input in &mut psbt.inputs {
+        if let Some(tap_sig) = input.tap_key_sig {
+            // Build witness for Taproot key-path spend
+            // For default sighash (0x00), witness is just the 64-byte signature
+            let mut witness = bitcoin::Witness::new();
+            witness.push(tap_sig.signature.serialize());
+
+            input.final_script_witness = Some(witness);
+
+            // Clear signing fields after finalization
+            input.tap_key_sig = None;
+        }
+    }
+
+    Ok(SignedTransaction {
+        tx_id: Some(psbt.unsigned_tx.compute_txid().to_string()),
+        transaction: psbt.to_string(),
+        verified: verified && missing_signers.is_empty(),
+        signers,
+        missing_signers,
+        signature: None,
+    })
+}
+
+/// Bitcoin Taproot (P2TR) address of a compressed secp256k1 public key
+pub fn taproot_address(public_key: &[u8], network: Network) -> Result<String> {
+    if public_key.len() != 33 {
+        return Err(anyhow!(
+            "Invalid secp256k1 pubkey length: {}",
+            public_key.len()
+        ));
+    }
+
+    let secp_pubkey = bitcoin::secp256k1::PublicKey::from_slice(public_key)
+        .map_err(|e| anyhow!("Failed to parse secp256k1 pubkey: {}", e))?;
+
+    // Extract x-coordinate for Taproot
+    let pubkey_full = secp_pubkey.serialize();
+    let x_only = bitcoin::key::XOnlyPublicKey::from_slice(&pubkey_full[1..33])
+        .map_err(|e| anyhow!("Failed to create x-only pubkey: {}", e))?;
+
+    // IMPORTANT: FROST signs with the UNTWEAKED key
+    // For FROST compatibility, we use UNTWEAKED Taproot addresses
+    // This is valid but non-standard (BIP 341 allows it for testing)
+    //
+    // Standard Taproot: Q = P + tagged_hash("TapTweak", P) * G
+    // FROST Taproot:    Q = P (no tweak, signs with original key)
+    //
+    // Trade-off: Simpler FROST implementation, but addresses are identifiable as "raw key"
+
+    let tweaked_key = bitcoin::key::TweakedPublicKey::dangerous_assume_tweaked(x_only);
+    let address = bitcoin::Address::p2tr_tweaked(tweaked_key, network);
+
+    Ok(address.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitcoin_address_derivation() {
+        // Test with a known secp256k1 public key
+        let pubkey =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        let address = BitcoinAdapter::new(Network::Bitcoin)
+            .derive_address(&pubkey)
+            .unwrap();
+        // Should produce a valid bc1p... address
+        assert!(address.starts_with("bc1p"));
+    }
+}
@@ -0,0 +1,371 @@
+//! Bitcoin Cash adapter
+//!
+//! Addresses are CashAddr P2PKH of the full compressed secp256k1-bch group key.
+//! The transaction is JSON holding the unsigned legacy-format tx plus the
+//! prevouts it spends (BCH sighashes commit to input amounts). Every input is
+//! signed with SIGHASH_ALL | SIGHASH_FORKID; a 65-byte signature in scriptSig
+//! marks it as Schnorr.
+
+use anyhow::{Context, Result};
+use bitcoin::consensus::{deserialize, serialize};
+use bitcoin::hashes::{hash160, sha256d, Hash};
+use bitcoin::script::{Builder, PushBytesBuf};
+use bitcoin::{ScriptBuf, Transaction};
+use serde::{Deserialize, Serialize};
+
+use super::{ChainAdapter, SignedTransaction, SigningInput};
+use crate::curves::bch::verify_signature;
+use crate::curves::CurveType;
+use crate::node::tx_policy::TransactionContext;
+
+/// SIGHASH_ALL | SIGHASH_FORKID (fork id 0)
+const SIGHASH_ALL_FORKID: u32 = 0x41;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Transaction payload (and kind "bch" context payload forwarded to signer nodes)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsignedTransaction {
+    pub tx: String, // hex, empty scriptSigs
+    pub prevouts: Vec<Prevout>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Prevout {
+    pub value: u64,            // satoshis
+    pub script_pubkey: String, // hex
+}
+
+impl UnsignedTransaction {
+    pub fn parse(transaction: &str) -> Result<(Self, Transaction)> {
+        let unsigned: Self =
+            serde_json::from_str(transaction).context("Invalid Bitcoin Cash transaction JSON")?;
+        let tx: Transaction =
+            deserialize(&hex::decode(&unsigned.tx).context("Invalid transaction hex")?)
+                .context("Invalid Bitcoin Cash transaction")?;
+
+        if tx.input.len() != unsigned.prevouts.len() {
+            anyhow::bail!(
+                "Prevout count mismatch: {} inputs, {} prevouts",
+                tx.input.len(),
+                unsigned.prevouts.len()
+            );
+        }
+        if tx.input.iter().any(|input| !input.script_sig.is_empty()) {
+            anyhow::bail!("Transaction must be unsigned");
+        }
+        Ok((unsigned, tx))
+    }
+
+    /// BIP143-style digest with SIGHASH_ALL | SIGHASH_FORKID for one input
+    pub fn sighash(&self, tx: &Transaction, input_index: usize) -> Result<[u8; 32]> {
+        let input = tx.input.get(input_index).context(format!(
+            "Input index {} out of range ({} inputs)",
+            input_index,
+            tx.input.len()
+        ))?;
+        let prevout = &self.prevouts[input_index];
+        let script_code = ScriptBuf::from_bytes(
+            hex::decode(&prevout.script_pubkey).context("Invalid prevout script hex")?,
+        );
+
+        let mut prevouts = Vec::new();
+        let mut sequences = Vec::new();
+        for input in &tx.input {
+            prevouts.extend(serialize(&input.previous_output));
+            sequences.extend(input.sequence.0.to_le_bytes());
+        }
+        let outputs: Vec<u8> = tx.output.iter().flat_map(serialize).collect();
+
+        let mut preimage = tx.version.0.to_le_bytes().to_vec();
+        preimage.extend(sha256d::Hash::hash(&prevouts).as_byte_array());
+        preimage.extend(sha256d::Hash::hash(&sequences).as_byte_array());
+        preimage.extend(serialize(&input.previous_output));
+        preimage.extend(serialize(&script_code));
+        preimage.extend(prevout.value.to_le_bytes());
+        preimage.extend(input.sequence.0.to_le_bytes());
+        preimage.extend(sha256d::Hash::hash(&outputs).as_byte_array());
+        preimage.extend(tx.lock_time.to_consensus_u32().to_le_bytes());
+        preimage.extend(SIGHASH_ALL_FORKID.to_le_bytes());
+
+        Ok(sha256d::Hash::hash(&preimage).to_byte_array())
+    }
+}
+
+pub struct BitcoinCashAdapter {
+    network: String,
+}
+
+impl BitcoinCashAdapter {
+    pub fn new(network: &str) -> Self {
+        Self {
+            network: network.to_string(),
+        }
+    }
+
+    fn prefix(&self) -> &'static str {
+        match self.network.as_str() {
+            "mainnet" => "bitcoincash",
+            "regtest" => "bchreg",
+            _ => "bchtest",
+        }
+    }
+}
+
+impl ChainAdapter for BitcoinCashAdapter {
+    fn name(&self) -> &'static str {
+        "bitcoincash"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["bch"]
+    }
+
+    fn curve(&self) -> CurveType {
+        CurveType::Secp256k1Bch
+    }
+
+    fn network(&self) -> String {
+        self.network.clone()
+    }
+
+    fn derive_address(&self, public_key: &[u8]) -> Result<String> {
+        Ok(cashaddr_p2pkh(self.prefix(), &key_hash(public_key)?))
+    }
+
+    /// One passphrase per input, each spending a P2PKH output of its key
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>> {
+        let (unsigned, tx) = UnsignedTransaction::parse(transaction)?;
+        if tx.input.len() != public_keys.len() {
+            anyhow::bail!(
+                "Passphrase count mismatch: {} inputs, {} passphrases",
+                tx.input.len(),
+                public_keys.len()
+            );
+        }
+
+        (0..tx.input.len())
+            .map(|index| {
+                let expected = p2pkh_script(&key_hash(&public_keys[index])?);
+                if hex::decode(&unsigned.prevouts[index].script_pubkey)? != expected.as_bytes() {
+                    anyhow::bail!(
+                        "Input {} does not spend a P2PKH output of its passphrase",
+                        index
+                    );
+                }
+
+                Ok(SigningInput {
+                    message: unsigned.sighash(&tx, index)?.to_vec(),
+                    signer: index,
+                    // Nodes recompute the FORKID sighash before releasing shares
+                    context: Some(TransactionContext {
+                        kind: "bch".to_string(),
+                        payload: transaction.to_string(),
+                        input_index: Some(index as u32),
+                    }),
+                    optional: false,
+                })
+            })
+            .collect()
+    }
+
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction> {
+        let (_, mut tx) = UnsignedTransaction::parse(transaction)?;
+
+        let mut signers = Vec::new();
+        let mut verified = true;
+        for (input, signature) in inputs.iter().zip(signatures) {
+            let public_key = &public_keys[input.signer];
+            let signature = signature.as_deref().context("Missing signature")?;
+            if !verify_signature(public_key, &input.message, signature) {
+                tracing::error!(
+                    "❌ BCH signature for input {} does not verify",
+                    input.signer
+                );
+                verified = false;
+            }
+
+            let mut sig_with_type = signature.to_vec();
+            sig_with_type.push(SIGHASH_ALL_FORKID as u8);
+            tx.input[input.signer].script_sig = Builder::new()
+                .push_slice(PushBytesBuf::try_from(sig_with_type)?)
+                .push_slice(PushBytesBuf::try_from(public_key.clone())?)
+                .into_script();
+            signers.push(self.derive_address(public_key)?);
+        }
+
+        Ok(SignedTransaction {
+            transaction: hex::encode(serialize(&tx)),
+            tx_id: Some(tx.compute_txid().to_string()),
+            signers,
+            missing_signers: Vec::new(),
+            verified,
+            signature: None,
+        })
+    }
+}
+
+fn key_hash(public_key: &[u8]) -> Result<[u8; 20]> {
+    if public_key.len() != 33 {
+        anyhow::bail!("Invalid secp256k1 pubkey length: {}", public_key.len());
+    }
+    Ok(hash160::Hash::hash(public_key).to_byte_array())
+}
+
+fn p2pkh_script(key_hash: &[u8; 20]) -> ScriptBuf {
+    ScriptBuf::new_p2pkh(&bitcoin::PubkeyHash::from_byte_array(*key_hash))
+}
+
+/// P2PKH scriptPubKey of a compressed public key (the key's own address)
+pub fn own_script(public_key: &[u8]) -> Result<ScriptBuf> {
+    Ok(p2pkh_script(&key_hash(public_key)?))
+}
+
+/// scriptPubKey of a CashAddr address (P2PKH or P2SH, prefix required)
+pub fn script_pubkey(address: &str) -> Result<ScriptBuf> {
+    let address = address.to_lowercase();
+    let (prefix, data) = address
+        .split_once(':')
+        .context("CashAddr address needs its prefix (bitcoincash:, bchtest:, bchreg:)")?;
+    let values = data
+        .bytes()
+        .map(|c| CHARSET.iter().position(|x| *x == c).map(|v| v as u8))
+        .collect::<Option<Vec<u8>>>()
+        .context("Invalid CashAddr character")?;
+    if values.len() < 8 {
+        anyhow::bail!("CashAddr address too short");
+    }
+
+    let mut checksum_input: Vec<u8> = prefix.bytes().map(|b| b & 0x1f).collect();
+    checksum_input.push(0);
+    checksum_input.extend_from_slice(&values);
+    if polymod(&checksum_input) != 0 {
+        anyhow::bail!("Invalid CashAddr checksum");
+    }
+
+    let payload = from_5bit(&values[..values.len() - 8])?;
+    let hash: [u8; 20] = payload[1..]
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Unsupported CashAddr hash length"))?;
+    match payload[0] {
+        0 => Ok(p2pkh_script(&hash)),
+        8 => Ok(ScriptBuf::new_p2sh(&bitcoin::ScriptHash::from_byte_array(
+            hash,
+        ))),
+        version => anyhow::bail!("Unsupported CashAddr version byte {}", version),
+    }
+}
+
+/// CashAddr with version byte 0 (P2PKH, 160-bit hash)
+fn cashaddr_p2pkh(prefix: &str, key_hash: &[u8; 20]) -> String {
+    let mut payload = vec![0u8];
+    payload.extend_from_slice(key_hash);
+    let data = to_5bit(&payload);
+
+    let mut checksum_input: Vec<u8> = prefix.bytes().map(|b| b & 0x1f).collect();
+    checksum_input.push(0);
+    checksum_input.extend_from_slice(&data);
+    checksum_input.extend_from_slice(&[0; 8]);
+    let checksum = polymod(&checksum_input);
+
+    let mut address = format!("{}:", prefix);
+    for value in data
+        .iter()
+        .copied()
+        .chain((0..8).map(|i| ((checksum >> (5 * (7 - i))) & 0x1f) as u8))
+    {
+        address.push(CHARSET[value as usize] as char);
+    }
+    address
+}
+
+fn to_5bit(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let (mut acc, mut bits) = (0u32, 0);
+    for byte in bytes {
+        acc = (acc << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(((acc >> bits) & 0x1f) as u8);
+        }
+    }
+    if bits > 0 {
+        out.push(((acc << (5 - bits)) & 0x1f) as u8);
+    }
+    out
+}
+
+/// Inverse of `to_5bit`; the padding bits must be zero
+fn from_5bit(values: &[u8]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let (mut acc, mut bits) = (0u32, 0);
+    for value in values {
+        acc = (acc << 5) | *value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    if bits >= 5 || acc != 0 || out.is_empty() {
+        anyhow::bail!("Invalid CashAddr padding");
+    }
+    Ok(out)
+}
+
+fn polymod(values: &[u8]) -> u64 {
+    const GENERATORS: [u64; 5] = [
+        0x98f2bc8e61,
+        0x79b76d99e2,
+        0xf33e5fb3c4,
+        0xae2eabe2a8,
+        0x1e4f43e470,
+    ];
+    let mut c = 1u64;
+    for value in values {
+        let c0 = c >> 35;
+        c = ((c & 0x07_ffff_ffff) << 5) ^ *value as u64;
+        for (i, generator) in GENERATORS.iter().enumerate() {
+            if c0 & (1 << i) != 0 {
+                c ^= generator;
+            }
+        }
+    }
+    c ^ 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cashaddr() {
+        // CashAddr spec example (1BpEi6DfDAUFd7GtittLSdBeYJvcoaVggu)
+        let hash: [u8; 20] = hex::decode("76a04053bda0a88bda5177b86a15c3b29f559873")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            cashaddr_p2pkh("bitcoincash", &hash),
+            "bitcoincash:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6a"
+        );
+        assert_eq!(
+            cashaddr_p2pkh("bchtest", &hash),
+            "bchtest:qpm2qsznhks23z7629mms6s4cwef74vcwvqcw003ap"
+        );
+
+        assert_eq!(
+            script_pubkey("bitcoincash:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6a").unwrap(),
+            p2pkh_script(&hash)
+        );
+        assert!(script_pubkey("bitcoincash:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6b").is_err());
+    }
+}
@@ -0,0 +1,270 @@
+//! Cardano adapter
+//!
+//! Addresses carry the blake2b-224 hash of the Ed25519 group key: enterprise
+//! addresses (payment key only) by default, base addresses staking with the
+//! same key as the alternate form. The transaction is a hex CBOR transaction
+//! body; every passphrase signs its blake2b-256 hash (the tx id) and the
+//! signatures come back as a vkey witness set plus the full transaction.
+
+use anyhow::{Context, Result};
+use bitcoin::bech32::{self, Bech32, Hrp};
+use blake2::digest::consts::{U28, U32};
+use blake2::{Blake2b, Digest};
+
+use super::solana::verify_signature;
+use super::{ChainAdapter, SignedTransaction, SigningInput};
+use crate::curves::CurveType;
+use crate::node::tx_policy::TransactionContext;
+
+/// CIP-19 header types (upper nibble)
+const BASE_KEY_KEY: u8 = 0b0000;
+const ENTERPRISE_KEY: u8 = 0b0110;
+
+pub struct CardanoAdapter {
+    network: String,
+}
+
+impl CardanoAdapter {
+    pub fn new(network: &str) -> Self {
+        Self {
+            network: network.to_string(),
+        }
+    }
+
+    fn mainnet(&self) -> bool {
+        self.network == "mainnet"
+    }
+
+    fn address(&self, header_type: u8, credentials: &[[u8; 28]]) -> Result<String> {
+        let (network_id, hrp) = if self.mainnet() {
+            (1, "addr")
+        } else {
+            (0, "addr_test")
+        };
+
+        let mut payload = vec![(header_type << 4) | network_id];
+        for credential in credentials {
+            payload.extend_from_slice(credential);
+        }
+        Ok(bech32::encode::<Bech32>(Hrp::parse(hrp)?, &payload)?)
+    }
+}
+
+impl ChainAdapter for CardanoAdapter {
+    fn name(&self) -> &'static str {
+        "cardano"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["ada"]
+    }
+
+    fn curve(&self) -> CurveType {
+        CurveType::Ed25519
+    }
+
+    fn network(&self) -> String {
+        self.network.clone()
+    }
+
+    /// Enterprise address (no staking rights)
+    fn derive_address(&self, public_key: &[u8]) -> Result<String> {
+        self.address(ENTERPRISE_KEY, &[key_hash(public_key)?])
+    }
+
+    /// Base address delegating with the same key
+    fn alternate_address(&self, public_key: &[u8]) -> Option<String> {
+        let hash = key_hash(public_key).ok()?;
+        self.address(BASE_KEY_KEY, &[hash, hash]).ok()
+    }
+
+    /// One witness per passphrase, all over the body hash
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>> {
+        let body = decode_body(transaction)?;
+        if public_keys.is_empty() {
+            anyhow::bail!("At least one passphrase is required");
+        }
+        for (i, key) in public_keys.iter().enumerate() {
+            if public_keys[..i].contains(key) {
+                anyhow::bail!("Passphrase {} duplicates an earlier key", i);
+            }
+        }
+
+        let hash = body_hash(&body);
+        Ok((0..public_keys.len())
+            .map(|signer| SigningInput {
+                message: hash.to_vec(),
+                signer,
+                // Nodes recompute the body hash before releasing shares
+                context: Some(TransactionContext {
+                    kind: "cardano".to_string(),
+                    payload: transaction.to_string(),
+                    input_index: None,
+                }),
+                optional: false,
+            })
+            .collect())
+    }
+
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction> {
+        let body = decode_body(transaction)?;
+
+        let mut witnesses = Vec::new();
+        let mut signers = Vec::new();
+        let mut verified = true;
+        for (input, signature) in inputs.iter().zip(signatures) {
+            let public_key = &public_keys[input.signer];
+            let signature = signature.as_deref().context("Missing signature")?;
+            if !verify_signature(public_key, &input.message, signature) {
+                tracing::error!("❌ Cardano witness {} does not verify", input.signer);
+                verified = false;
+            }
+            witnesses.push((public_key.as_slice(), signature));
+            signers.push(self.derive_address(public_key)?);
+        }
+
+        // transaction_witness_set = { 0: [[vkey, signature], ...] }
+        let mut witness_set = vec![0xa1, 0x00];
+        cbor_head(4, witnesses.len() as u64, &mut witness_set);
+        for (vkey, signature) in witnesses {
+            witness_set.push(0x82);
+            cbor_bytes(vkey, &mut witness_set);
+            cbor_bytes(signature, &mut witness_set);
+        }
+
+        // [body, witness_set, is_valid, auxiliary_data]
+        let mut tx = vec![0x84];
+        tx.extend_from_slice(&body);
+        tx.extend_from_slice(&witness_set);
+        tx.extend_from_slice(&[0xf5, 0xf6]);
+
+        Ok(SignedTransaction {
+            transaction: hex::encode(tx),
+            tx_id: Some(hex::encode(body_hash(&body))),
+            signers,
+            missing_signers: Vec::new(),
+            verified,
+            signature: Some(hex::encode(witness_set)),
+        })
+    }
+}
+
+/// blake2b-256 of the CBOR body, which is the transaction id
+pub fn body_hash(body: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(body).into()
+}
+
+/// Hex CBOR transaction body (a map, not the full transaction array)
+pub fn decode_body(transaction: &str) -> Result<Vec<u8>> {
+    let body = hex::decode(transaction.trim_start_matches("0x"))
+        .context("Invalid transaction body hex")?;
+    match body.first() {
+        Some(b) if b >> 5 == 5 => Ok(body),
+        Some(b) if b >> 5 == 4 => {
+            anyhow::bail!("Expected the transaction body, got a full transaction (CBOR array)")
+        }
+        _ => anyhow::bail!("Transaction body must be a CBOR map"),
+    }
+}
+
+fn key_hash(public_key: &[u8]) -> Result<[u8; 28]> {
+    if public_key.len() != 32 {
+        anyhow::bail!("Invalid Ed25519 pubkey length: {}", public_key.len());
+    }
+    Ok(Blake2b::<U28>::digest(public_key).into())
+}
+
+fn cbor_head(major: u8, value: u64, out: &mut Vec<u8>) {
+    let major = major << 5;
+    match value {
+        0..=23 => out.push(major | value as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, value as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 26);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+    }
+}
+
+fn cbor_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+    cbor_head(2, bytes.len() as u64, out);
+    out.extend_from_slice(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cip19_addresses() {
+        // CIP-19 test vector payment key
+        let (_, public_key) =
+            bech32::decode("addr_vk1w0l2sr2zgfm26ztc6nl9xy8ghsk5sh6ldwemlpmp9xylzy4dtf7st80zhd")
+                .unwrap();
+
+        assert_eq!(
+            CardanoAdapter::new("mainnet")
+                .derive_address(&public_key)
+                .unwrap(),
+            "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8"
+        );
+        assert_eq!(
+            CardanoAdapter::new("preprod")
+                .derive_address(&public_key)
+                .unwrap(),
+            "addr_test1vz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzerspjrlsz"
+        );
+        assert!(CardanoAdapter::new("mainnet")
+            .alternate_address(&public_key)
+            .unwrap()
+            .starts_with("addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer"));
+    }
+
+    #[test]
+    fn test_witness_set() {
+        let mut rng = rand::thread_rng();
+        let signing_key = frost_ed25519::SigningKey::new(&mut rng);
+        let public_key = frost_ed25519::VerifyingKey::from(&signing_key)
+            .serialize()
+            .unwrap();
+
+        let adapter = CardanoAdapter::new("preprod");
+        let body = "a300800180020a";
+        assert!(adapter
+            .prepare("84a0a0f5f6", std::slice::from_ref(&public_key))
+            .is_err());
+
+        let inputs = adapter
+            .prepare(body, std::slice::from_ref(&public_key))
+            .unwrap();
+        let signature = signing_key.sign(&mut rng, &inputs[0].message);
+        let signature = signature.serialize().unwrap();
+        let signed = adapter
+            .assemble(
+                body,
+                std::slice::from_ref(&public_key),
+                &inputs,
+                &[Some(signature.clone())],
+            )
+            .unwrap();
+        assert!(signed.verified);
+
+        let expected = format!(
+            "a10081825820{}5840{}",
+            hex::encode(&public_key),
+            hex::encode(&signature)
+        );
+        assert_eq!(signed.signature.unwrap(), expected);
+        assert_eq!(signed.transaction, format!("84{}{}f5f6", body, expected));
+    }
+}
@@ -0,0 +1,654 @@
+//! Ethereum adapter and transaction assembly
+//!
+//! Builds the EIP-155 / EIP-2930 / EIP-1559 signing payloads with `rlp`, and
+//! the broadcast-ready signed transaction once FROST returns (r, s).
+//! Quantities are strings (decimal or 0x-hex) so wei values can exceed u64.
+
+use anyhow::{Context, Result};
+use ethereum_types::{H160, H256, U256};
+use poem_openapi::Object;
+use rlp::{Rlp, RlpStream};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+use super::{ChainAdapter, SignedTransaction, SigningInput};
+use crate::curves::CurveType;
+use crate::node::tx_policy::TransactionContext;
+
+/// EVM chain bound to one chain id; transactions are 0x-hex unsigned RLP
+pub struct EthereumAdapter {
+    chain_id: u64,
+}
+
+impl EthereumAdapter {
+    pub fn new(chain_id: u64) -> Self {
+        Self { chain_id }
+    }
+}
+
+impl ChainAdapter for EthereumAdapter {
+    fn name(&self) -> &'static str {
+        "ethereum"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["eth"]
+    }
+
+    fn curve(&self) -> CurveType {
+        CurveType::Secp256k1Ecdsa
+    }
+
+    fn network(&self) -> String {
+        format!("eip155:{}", self.chain_id)
+    }
+
+    fn derive_address(&self, public_key: &[u8]) -> Result<String> {
+        address_of_public_key(public_key)
+    }
+
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>> {
+        if public_keys.len() != 1 {
+            anyhow::bail!("Ethereum transactions take exactly one passphrase");
+        }
+        let tx = self.decode(transaction)?;
+
+        // Nodes recompute keccak256 of the payload before releasing shares
+        Ok(vec![SigningInput {
+            message: tx.signing_hash().to_vec(),
+            signer: 0,
+            context: Some(TransactionContext {
+                kind: "evm".to_string(),
+                payload: hex::encode(tx.unsigned_payload()),
+                input_index: None,
+            }),
+            optional: false,
+        }])
+    }
+
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        _inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction> {
+        let tx = self.decode(transaction)?;
+        let from = self.derive_address(&public_keys[0])?;
+
+        // Node returns [r (32), s (32), recovery_id (1)]
+        let signature = signatures
+            .first()
+            .and_then(Option::as_deref)
+            .context("Missing signature")?;
+        if signature.len() < 64 {
+            anyhow::bail!("Unexpected signature format: {}", hex::encode(signature));
+        }
+        let r: [u8; 32] = signature[..32].try_into()?;
+        let s: [u8; 32] = signature[32..64].try_into()?;
+
+        // Without a matching recovery id there is no valid v to broadcast with
+        let (recovery_id, recovered_address) = find_recovery_id(&tx.signing_hash(), &r, &s, &from);
+        let Some(recovery_id) = recovery_id else {
+            anyhow::bail!(
+                "Signature does not recover to {} (got {:?})",
+                from,
+                recovered_address
+            );
+        };
+
+        let raw = tx.signed_payload(&r, &s, recovery_id);
+        Ok(SignedTransaction {
+            transaction: format!("0x{}", hex::encode(&raw)),
+            tx_id: Some(format!("0x{}", hex::encode(Keccak256::digest(&raw)))),
+            signers: vec![from],
+            missing_signers: Vec::new(),
+            verified: true,
+            signature: None,
+        })
+    }
+}
+
+impl EthereumAdapter {
+    /// Decode 0x-hex unsigned RLP and check it is bound to our chain id
+    fn decode(&self, transaction: &str) -> Result<PreparedTransaction> {
+        let raw =
+            hex::decode(transaction.trim_start_matches("0x")).context("Invalid transaction hex")?;
+        let tx = PreparedTransaction::decode(&raw)?;
+        if tx.chain_id != self.chain_id {
+            anyhow::bail!(
+                "chain_id {} does not match configured network (chain id {})",
+                tx.chain_id,
+                self.chain_id
+            );
+        }
+        Ok(tx)
+    }
+}
+
+/// Unsigned Ethereum transaction fields
+#[derive(Debug, Clone, Object, Serialize, Deserialize)]
+pub struct EthereumTransaction {
+    /// "legacy", "eip2930" or "eip1559" (default: eip1559 if max_fee_per_gas is set, else legacy)
+    pub tx_type: Option<String>,
+    /// Must match the configured network if set
+    pub chain_id: Option<u64>,
+    pub nonce: u64,
+    /// Recipient (0x-hex), omit for contract creation
+    pub to: Option<String>,
+    /// Value in wei (decimal or 0x-hex), defaults to 0
+    pub value: Option<String>,
+    /// Calldata (hex)
+    pub data: Option<String>,
+    pub gas_limit: u64,
+    /// legacy / eip2930
+    pub gas_price: Option<String>,
+    /// eip1559
+    pub max_fee_per_gas: Option<String>,
+    /// eip1559
+    pub max_priority_fee_per_gas: Option<String>,
+    /// eip2930 / eip1559
+    pub access_list: Option<Vec<AccessListEntry>>,
+}
+
+#[derive(Debug, Clone, Object, Serialize, Deserialize)]
+pub struct AccessListEntry {
+    pub address: String,
+    pub storage_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxType {
+    Legacy,
+    Eip2930,
+    Eip1559,
+}
+
+/// Validated transaction, ready for RLP encoding
+#[derive(Debug, Clone)]
+pub struct PreparedTransaction {
+    pub tx_type: TxType,
+    pub chain_id: u64,
+    nonce: u64,
+    to: Option<H160>,
+    value: U256,
+    data: Vec<u8>,
+    gas_limit: u64,
+    gas_price: U256,
+    max_fee_per_gas: U256,
+    max_priority_fee_per_gas: U256,
+    access_list: Vec<(H160, Vec<H256>)>,
+}
+
+impl EthereumTransaction {
+    /// Check the fields and bind the transaction to `chain_id`
+    pub fn prepare(&self, chain_id: u64) -> Result<PreparedTransaction> {
+        if let Some(requested) = self.chain_id {
+            if requested != chain_id {
+                anyhow::bail!(
+                    "chain_id {} does not match configured network (chain id {})",
+                    requested,
+                    chain_id
+                );
+            }
+        }
+
+        let tx_type = match self.tx_type.as_deref() {
+            Some("legacy") => TxType::Legacy,
+            Some("eip2930") => TxType::Eip2930,
+            Some("eip1559") => TxType::Eip1559,
+            Some(other) => anyhow::bail!(
+                "Unknown tx_type '{}', expected legacy, eip2930 or eip1559",
+                other
+            ),
+            None if self.max_fee_per_gas.is_some() => TxType::Eip1559,
+            None => TxType::Legacy,
+        };
+
+        let gas_price = match tx_type {
+            TxType::Legacy | TxType::Eip2930 => {
+                parse_quantity(self.gas_price.as_deref().context("gas_price is required")?)?
+            }
+            TxType::Eip1559 => U256::zero(),
+        };
+        let (max_fee_per_gas, max_priority_fee_per_gas) = match tx_type {
+            TxType::Eip1559 => (
+                parse_quantity(
+                    self.max_fee_per_gas
+                        .as_deref()
+                        .context("max_fee_per_gas is required")?,
+                )?,
+                parse_quantity(
+                    self.max_priority_fee_per_gas
+                        .as_deref()
+                        .context("max_priority_fee_per_gas is required")?,
+                )?,
+            ),
+            _ => (U256::zero(), U256::zero()),
+        };
+        if max_priority_fee_per_gas > max_fee_per_gas {
+            anyhow::bail!("max_priority_fee_per_gas exceeds max_fee_per_gas");
+        }
+
+        if tx_type == TxType::Legacy && self.access_list.is_some() {
+            anyhow::bail!("access_list requires an eip2930 or eip1559 transaction");
+        }
+        let access_list = self
+            .access_list
+            .iter()
+            .flatten()
+            .map(|entry| {
+                let keys = entry
+                    .storage_keys
+                    .iter()
+                    .map(|k| parse_fixed::<32>(k).map(H256::from))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((H160::from(parse_fixed::<20>(&entry.address)?), keys))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(PreparedTransaction {
+            tx_type,
+            chain_id,
+            nonce: self.nonce,
+            to: self
+                .to
+                .as_deref()
+                .map(|to| parse_fixed::<20>(to).map(H160::from))
+                .transpose()
+                .context("Invalid to address")?,
+            value: self
+                .value
+                .as_deref()
+                .map(parse_quantity)
+                .transpose()?
+                .unwrap_or_default(),
+            data: self
+                .data
+                .as_deref()
+                .map(|d| hex::decode(d.trim_start_matches("0x")))
+                .transpose()
+                .context("Invalid data hex")?
+                .unwrap_or_default(),
+            gas_limit: self.gas_limit,
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            access_list,
+        })
+    }
+}
+
+impl PreparedTransaction {
+    /// Decode an unsigned signing payload (EIP-155 legacy, type 1 or type 2)
+    pub fn decode(raw: &[u8]) -> Result<Self> {
+        let first = *raw.first().context("Empty transaction")?;
+        let (tx_type, list, expected) = match first {
+            0x01 => (TxType::Eip2930, Rlp::new(&raw[1..]), 8),
+            0x02 => (TxType::Eip1559, Rlp::new(&raw[1..]), 9),
+            b if b >= 0xc0 => (TxType::Legacy, Rlp::new(raw), 9),
+            b => anyhow::bail!("Unsupported transaction type 0x{:02x}", b),
+        };
+
+        let fields = list.item_count().context("Invalid RLP")?;
+        if !list.is_list() || fields != expected {
+            anyhow::bail!(
+                "Unsigned {:?} transaction must be an RLP list of {} fields",
+                tx_type,
+                expected
+            );
+        }
+
+        let field = |i: usize| list.at(i).context(format!("Invalid RLP field {}", i));
+        let quantity = |i: usize| -> Result<U256> {
+            list.val_at(i).context(format!("Invalid RLP field {}", i))
+        };
+        let to = |i: usize| -> Result<Option<H160>> {
+            let data = field(i)?.data().context("Invalid to")?;
+            match data.len() {
+                0 => Ok(None),
+                20 => Ok(Some(H160::from_slice(data))),
+                n => anyhow::bail!("Invalid to address length {}", n),
+            }
+        };
+        let access_list = |i: usize| -> Result<Vec<(H160, Vec<H256>)>> {
+            field(i)?
+                .iter()
+                .map(|entry| {
+                    Ok((
+                        entry.val_at(0).context("Invalid access list address")?,
+                        entry.list_at(1).context("Invalid access list keys")?,
+                    ))
+                })
+                .collect()
+        };
+
+        let tx = match tx_type {
+            TxType::Legacy => {
+                // EIP-155: [nonce, gasPrice, gas, to, value, data, chain_id, 0, 0]
+                if !quantity(7)?.is_zero() || !quantity(8)?.is_zero() {
+                    anyhow::bail!("Legacy transaction is not an EIP-155 signing payload");
+                }
+                PreparedTransaction {
+                    tx_type,
+                    chain_id: list.val_at(6).context("Invalid chain id")?,
+                    nonce: list.val_at(0).context("Invalid nonce")?,
+                    gas_price: quantity(1)?,
+                    gas_limit: list.val_at(2).context("Invalid gas limit")?,
+                    to: to(3)?,
+                    value: quantity(4)?,
+                    data: list.val_at(5).context("Invalid data")?,
+                    max_fee_per_gas: U256::zero(),
+                    max_priority_fee_per_gas: U256::zero(),
+                    access_list: Vec::new(),
+                }
+            }
+            TxType::Eip2930 => PreparedTransaction {
+                tx_type,
+                chain_id: list.val_at(0).context("Invalid chain id")?,
+                nonce: list.val_at(1).context("Invalid nonce")?,
+                gas_price: quantity(2)?,
+                gas_limit: list.val_at(3).context("Invalid gas limit")?,
+                to: to(4)?,
+                value: quantity(5)?,
+                data: list.val_at(6).context("Invalid data")?,
+                max_fee_per_gas: U256::zero(),
+                max_priority_fee_per_gas: U256::zero(),
+                access_list: access_list(7)?,
+            },
+            TxType::Eip1559 => PreparedTransaction {
+                tx_type,
+                chain_id: list.val_at(0).context("Invalid chain id")?,
+                nonce: list.val_at(1).context("Invalid nonce")?,
+                max_priority_fee_per_gas: quantity(2)?,
+                max_fee_per_gas: quantity(3)?,
+                gas_limit: list.val_at(4).context("Invalid gas limit")?,
+                to: to(5)?,
+                value: quantity(6)?,
+                data: list.val_at(7).context("Invalid data")?,
+                gas_price: U256::zero(),
+                access_list: access_list(8)?,
+            },
+        };
+
+        // Trailing bytes or non-canonical integers would sign something else
+        if tx.unsigned_payload() != raw {
+            anyhow::bail!("Transaction is not canonically encoded");
+        }
+        Ok(tx)
+    }
+
+    /// Bytes whose keccak256 is signed
+    pub fn unsigned_payload(&self) -> Vec<u8> {
+        self.encode(None)
+    }
+
+    pub fn signing_hash(&self) -> [u8; 32] {
+        Keccak256::digest(self.unsigned_payload()).into()
+    }
+
+    /// Broadcast-ready raw transaction
+    pub fn signed_payload(&self, r: &[u8; 32], s: &[u8; 32], recovery_id: u8) -> Vec<u8> {
+        self.encode(Some((r, s, recovery_id)))
+    }
+
+    fn encode(&self, signature: Option<(&[u8; 32], &[u8; 32], u8)>) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+
+        match self.tx_type {
+            TxType::Legacy => {
+                // EIP-155: [nonce, gasPrice, gas, to, value, data, v, r, s]
+                stream.begin_list(9);
+                stream.append(&self.nonce);
+                stream.append(&self.gas_price);
+                stream.append(&self.gas_limit);
+                self.append_to(&mut stream);
+                stream.append(&self.value);
+                stream.append(&self.data);
+                match signature {
+                    None => {
+                        stream.append(&self.chain_id);
+                        stream.append(&0u8);
+                        stream.append(&0u8);
+                    }
+                    Some((r, s, recovery_id)) => {
+                        stream.append(&(self.chain_id * 2 + 35 + recovery_id as u64));
+                        stream.append(&U256::from_big_endian(r));
+                        stream.append(&U256::from_big_endian(s));
+                    }
+                }
+                stream.out().to_vec()
+            }
+            TxType::Eip2930 | TxType::Eip1559 => {
+                let (type_byte, fields) = match self.tx_type {
+                    TxType::Eip2930 => (0x01u8, 8),
+                    _ => (0x02u8, 9),
+                };
+                stream.begin_list(fields + if signature.is_some() { 3 } else { 0 });
+                stream.append(&self.chain_id);
+                stream.append(&self.nonce);
+                if self.tx_type == TxType::Eip2930 {
+                    stream.append(&self.gas_price);
+                } else {
+                    stream.append(&self.max_priority_fee_per_gas);
+                    stream.append(&self.max_fee_per_gas);
+                }
+                stream.append(&self.gas_limit);
+                self.append_to(&mut stream);
+                stream.append(&self.value);
+                stream.append(&self.data);
+                stream.begin_list(self.access_list.len());
+                for (address, keys) in &self.access_list {
+                    stream.begin_list(2);
+                    stream.append(address);
+                    stream.append_list(keys);
+                }
+                if let Some((r, s, recovery_id)) = signature {
+                    stream.append(&recovery_id);
+                    stream.append(&U256::from_big_endian(r));
+                    stream.append(&U256::from_big_endian(s));
+                }
+
+                let mut out = vec![type_byte];
+                out.extend_from_slice(&stream.out());
+                out
+            }
+        }
+    }
+
+    fn append_to(&self, stream: &mut RlpStream) {
+        match &self.to {
+            Some(to) => stream.append(to),
+            None => stream.append_empty_data(),
+        };
+    }
+}
+
+/// Parse a wei quantity: decimal or 0x-prefixed hex
+pub fn parse_quantity(value: &str) -> Result<U256> {
+    match value.strip_prefix("0x") {
+        Some(hex_str) => U256::from_str_radix(hex_str, 16)
+            .map_err(|e| anyhow::anyhow!("Invalid hex quantity '{}': {:?}", value, e)),
+        None => U256::from_dec_str(value)
+            .map_err(|e| anyhow::anyhow!("Invalid quantity '{}': {:?}", value, e)),
+    }
+}
+
+fn parse_fixed<const N: usize>(value: &str) -> Result<[u8; N]> {
+    let bytes =
+        hex::decode(value.trim_start_matches("0x")).context(format!("Invalid hex '{}'", value))?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| anyhow::anyhow!("Expected {} bytes, got {}", N, b.len()))
+}
+
+/// Ethereum address (0x-hex, lowercase) of a secp256k1 public key
+pub fn address_of(key: &k256::ecdsa::VerifyingKey) -> String {
+    let uncompressed = key.to_encoded_point(false);
+    let hash = Keccak256::digest(&uncompressed.as_bytes()[1..]);
+    format!("0x{}", hex::encode(&hash[12..]))
+}
+
+/// Ethereum address of a compressed secp256k1 public key (as the nodes return it)
+pub fn address_of_public_key(public_key: &[u8]) -> Result<String> {
+    if public_key.len() != 33 {
+        anyhow::bail!("Invalid secp256k1 pubkey length: {}", public_key.len());
+    }
+    let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+        .map_err(|e| anyhow::anyhow!("Failed to parse secp256k1 pubkey: {}", e))?;
+    Ok(address_of(&key))
+}
+
+/// Recover the signer address for (r, s) with the given recovery id, if valid
+pub fn recover_address(
+    hash: &[u8; 32],
+    r: &[u8; 32],
+    s: &[u8; 32],
+    recovery_id: u8,
+) -> Option<String> {
+    let mut rs = [0u8; 64];
+    rs[..32].copy_from_slice(r);
+    rs[32..].copy_from_slice(s);
+
+    let signature = k256::ecdsa::Signature::from_slice(&rs).ok()?;
+    let recovery_id = k256::ecdsa::RecoveryId::from_byte(recovery_id)?;
+    let key =
+        k256::ecdsa::VerifyingKey::recover_from_prehash(hash, &signature, recovery_id).ok()?;
+    Some(address_of(&key))
+}
+
+/// Find the recovery id under which (r, s) recovers to `expected_address`
+///
+/// Returns the first recovered address as well so callers can report a mismatch.
+pub fn find_recovery_id(
+    hash: &[u8; 32],
+    r: &[u8; 32],
+    s: &[u8; 32],
+    expected_address: &str,
+) -> (Option<u8>, Option<String>) {
+    let mut first = None;
+    for recovery_id in 0..2u8 {
+        if let Some(address) = recover_address(hash, r, s, recovery_id) {
+            if address.eq_ignore_ascii_case(expected_address) {
+                return (Some(recovery_id), Some(address));
+            }
+            first.get_or_insert(address);
+        }
+    }
+    (None, first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(tx_type: &str) -> EthereumTransaction {
+        EthereumTransaction {
+            tx_type: Some(tx_type.to_string()),
+            chain_id: None,
+            nonce: 9,
+            to: Some("0x3535353535353535353535353535353535353535".to_string()),
+            value: Some("1000000000000000000".to_string()),
+            data: None,
+            gas_limit: 21_000,
+            gas_price: Some("20000000000".to_string()),
+            max_fee_per_gas: Some("0x4a817c800".to_string()),
+            max_priority_fee_per_gas: Some("1000000000".to_string()),
+            access_list: None,
+        }
+    }
+
+    #[test]
+    fn test_eip155_example_hash() {
+        // Test vector from the EIP-155 specification
+        let tx = transfer("legacy").prepare(1).unwrap();
+        assert_eq!(
+            hex::encode(tx.unsigned_payload()),
+            "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080"
+        );
+        assert_eq!(
+            hex::encode(tx.signing_hash()),
+            "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+    }
+
+    #[test]
+    fn test_sign_and_recover_all_types() {
+        let secret = k256::ecdsa::SigningKey::from_slice(&[0x46u8; 32]).unwrap();
+        let address = address_of(secret.verifying_key());
+
+        for tx_type in ["legacy", "eip2930", "eip1559"] {
+            let tx = transfer(tx_type).prepare(1).unwrap();
+            let hash = tx.signing_hash();
+            let (sig, recid) = secret.sign_prehash_recoverable(&hash).unwrap();
+            let bytes = sig.to_bytes();
+            let r: [u8; 32] = bytes[..32].try_into().unwrap();
+            let s: [u8; 32] = bytes[32..].try_into().unwrap();
+
+            let (found, recovered) = find_recovery_id(&hash, &r, &s, &address);
+            assert_eq!(found, Some(recid.to_byte()));
+            assert_eq!(recovered.as_deref(), Some(address.as_str()));
+
+            let raw = tx.signed_payload(&r, &s, recid.to_byte());
+            let decoded = PreparedTransaction::decode(&tx.unsigned_payload()).unwrap();
+            assert_eq!(decoded.signing_hash(), hash);
+            assert!(raw.len() > tx.unsigned_payload().len());
+        }
+    }
+
+    #[test]
+    fn test_ethereum_address_derivation() {
+        // Public key of private key 1
+        let pubkey =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        assert_eq!(
+            EthereumAdapter::new(1).derive_address(&pubkey).unwrap(),
+            "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        );
+    }
+
+    #[test]
+    fn test_chain_id_mismatch() {
+        let mut tx = transfer("eip1559");
+        tx.chain_id = Some(5);
+        assert!(tx.prepare(1).is_err());
+
+        let payload = hex::encode(transfer("eip1559").prepare(5).unwrap().unsigned_payload());
+        let adapter = EthereumAdapter::new(1);
+        assert!(adapter.prepare(&payload, &[vec![0x02; 33]]).is_err());
+        assert!(EthereumAdapter::new(5)
+            .prepare(&payload, &[vec![0x02; 33]])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_assemble_refuses_foreign_signature() {
+        let secret = k256::ecdsa::SigningKey::from_slice(&[0x46u8; 32]).unwrap();
+        let other = k256::ecdsa::SigningKey::from_slice(&[0x47u8; 32]).unwrap();
+        let public_key =
+            |key: &k256::ecdsa::SigningKey| key.verifying_key().to_sec1_bytes().to_vec();
+
+        let adapter = EthereumAdapter::new(1);
+        let tx = transfer("eip1559").prepare(1).unwrap();
+        let payload = hex::encode(tx.unsigned_payload());
+        let (sig, recid) = secret.sign_prehash_recoverable(&tx.signing_hash()).unwrap();
+        let mut signature = sig.to_bytes().to_vec();
+        signature.push(recid.to_byte());
+
+        let signed = adapter
+            .assemble(
+                &payload,
+                &[public_key(&secret)],
+                &[],
+                &[Some(signature.clone())],
+            )
+            .unwrap();
+        assert!(signed.verified);
+
+        // No v recovers the signing key, so there is nothing valid to broadcast
+        assert!(adapter
+            .assemble(&payload, &[public_key(&other)], &[], &[Some(signature)])
+            .is_err());
+    }
+}
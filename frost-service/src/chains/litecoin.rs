@@ -0,0 +1,126 @@
+//! Litecoin Taproot adapter
+//!
+//! Litecoin shares Bitcoin's transaction format, PSBTs and BIP341 key-path
+//! sighashes, so the secp256k1-tr group key signs LTC inputs as-is. Only the
+//! address encoding differs (`ltc1p` / `tltc1p`, untweaked like Bitcoin).
+
+use anyhow::{Context, Result};
+use bitcoin::bech32::{segwit, Hrp};
+use bitcoin::{ScriptBuf, WitnessProgram, WitnessVersion};
+
+use super::bitcoin::{assemble_psbt, prepare_psbt};
+use super::{ChainAdapter, SignedTransaction, SigningInput};
+use crate::curves::CurveType;
+
+pub struct LitecoinAdapter {
+    network: String,
+}
+
+impl LitecoinAdapter {
+    pub fn new(network: &str) -> Self {
+        Self {
+            network: network.to_string(),
+        }
+    }
+
+    fn hrp(&self) -> &'static str {
+        if self.network == "mainnet" {
+            "ltc"
+        } else {
+            "tltc"
+        }
+    }
+}
+
+impl ChainAdapter for LitecoinAdapter {
+    fn name(&self) -> &'static str {
+        "litecoin"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["ltc"]
+    }
+
+    fn curve(&self) -> CurveType {
+        CurveType::Secp256k1Taproot
+    }
+
+    fn network(&self) -> String {
+        self.network.clone()
+    }
+
+    fn derive_address(&self, public_key: &[u8]) -> Result<String> {
+        if public_key.len() != 33 {
+            anyhow::bail!("Invalid secp256k1 pubkey length: {}", public_key.len());
+        }
+        // Same untweaked output key as the Bitcoin adapter
+        let x_only = bitcoin::key::XOnlyPublicKey::from_slice(&public_key[1..])?;
+        Ok(segwit::encode(
+            Hrp::parse(self.hrp())?,
+            segwit::VERSION_1,
+            &x_only.serialize(),
+        )?)
+    }
+
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>> {
+        prepare_psbt(transaction, public_keys, "litecoin_psbt")
+    }
+
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction> {
+        assemble_psbt(transaction, public_keys, inputs, signatures, &|key| {
+            self.derive_address(key)
+        })
+    }
+}
+
+/// scriptPubKey of a bech32 / bech32m Litecoin address (policy allowlists)
+pub fn script_pubkey(address: &str) -> Result<ScriptBuf> {
+    let (hrp, version, program) =
+        segwit::decode(address).context(format!("Invalid Litecoin address: {}", address))?;
+    if hrp != Hrp::parse("ltc")? && hrp != Hrp::parse("tltc")? {
+        anyhow::bail!("Not a Litecoin address: {}", address);
+    }
+    let program = WitnessProgram::new(WitnessVersion::try_from(version)?, &program)?;
+    Ok(ScriptBuf::new_witness_program(&program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_litecoin_address() {
+        let pubkey =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+
+        let mainnet = LitecoinAdapter::new("mainnet")
+            .derive_address(&pubkey)
+            .unwrap();
+        let testnet = LitecoinAdapter::new("testnet")
+            .derive_address(&pubkey)
+            .unwrap();
+        assert!(mainnet.starts_with("ltc1p"));
+        assert!(testnet.starts_with("tltc1p"));
+
+        // Same witness program as the Bitcoin address of the key
+        let bitcoin_script =
+            super::super::bitcoin::taproot_address(&pubkey, bitcoin::Network::Bitcoin)
+                .unwrap()
+                .parse::<bitcoin::Address<_>>()
+                .unwrap()
+                .assume_checked()
+                .script_pubkey();
+        assert_eq!(script_pubkey(&mainnet).unwrap(), bitcoin_script);
+        assert_eq!(
+            bitcoin::bech32::decode(&mainnet).unwrap().1,
+            bitcoin::bech32::decode(&testnet).unwrap().1
+        );
+    }
+}
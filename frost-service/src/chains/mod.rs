@@ -0,0 +1,204 @@
+//! Chain adapters
+//!
+//! Everything chain-specific lives behind `ChainAdapter`: which curve the keys
+//! are on, how a public key becomes an address, which bytes FROST signs for a
+//! transaction and how signatures are put back into it. The aggregators only
+//! talk to the `ChainRegistry`, so adding a chain means adding an adapter here.
+
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+
+use crate::config::NetworkConfig;
+use crate::curves::CurveType;
+use crate::node::tx_policy::TransactionContext;
+
+pub mod algorand;
+pub mod aptos;
+pub mod bitcoin;
+pub mod bitcoin_cash;
+pub mod cardano;
+pub mod ethereum;
+pub mod litecoin;
+pub mod nostr;
+pub mod solana;
+pub mod stellar;
+pub mod sui;
+
+/// One FROST signing round needed to complete a transaction
+#[derive(Debug, Clone)]
+pub struct SigningInput {
+    /// Bytes handed to FROST (a sighash or the full message, depending on the chain)
+    pub message: Vec<u8>,
+    /// Index into the request's passphrases
+    pub signer: usize,
+    /// Forwarded to signer nodes so they can check it against local policy
+    pub context: Option<TransactionContext>,
+    /// Leave the input unsigned instead of failing the request if its round fails
+    pub optional: bool,
+}
+
+/// Transaction with the FROST signatures applied
+#[derive(Debug, Clone)]
+pub struct SignedTransaction {
+    /// Chain-native encoding (base64 PSBT, 0x-hex RLP, base64 wire transaction, ...)
+    pub transaction: String,
+    pub tx_id: Option<String>,
+    /// Address per signed input / signer slot
+    pub signers: Vec<String>,
+    /// Inputs / signer slots left for someone else
+    pub missing_signers: Vec<String>,
+    /// Every applied signature checks out under the chain's own rules
+    pub verified: bool,
+    /// Signature in the chain's own encoding, for chains that submit it
+    /// alongside the transaction rather than inside it
+    pub signature: Option<String>,
+}
+
+pub trait ChainAdapter: Send + Sync {
+    /// Canonical name used in API requests and responses
+    fn name(&self) -> &'static str;
+
+    /// Alternative names accepted in requests
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn curve(&self) -> CurveType;
+
+    /// Network variant this adapter was built for
+    fn network(&self) -> String;
+
+    /// Address of a raw group public key as returned by the signer nodes
+    fn derive_address(&self, public_key: &[u8]) -> Result<String>;
+
+    /// Second rendering of the key, for chains that have one (e.g. hex next to bech32)
+    fn alternate_address(&self, _public_key: &[u8]) -> Option<String> {
+        None
+    }
+
+    /// Decode an unsigned transaction and list the signing rounds it needs
+    ///
+    /// `public_keys` holds the group key of each request passphrase, in order.
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>>;
+
+    /// Apply the signatures (one per input from `prepare`, `None` if skipped)
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction>;
+}
+
+/// Enabled chain adapters, built once from config
+pub struct ChainRegistry {
+    adapters: Vec<Arc<dyn ChainAdapter>>,
+}
+
+impl ChainRegistry {
+    /// Register the built-in adapters for `network`, optionally limited to `enabled`
+    pub fn from_config(
+        network: Option<&NetworkConfig>,
+        enabled: Option<&[String]>,
+    ) -> Result<Self> {
+        let builtin: Vec<Arc<dyn ChainAdapter>> = vec![
+            Arc::new(bitcoin::BitcoinAdapter::new(
+                network
+                    .map(|n| n.bitcoin_network())
+                    .unwrap_or(::bitcoin::Network::Bitcoin),
+            )),
+            Arc::new(litecoin::LitecoinAdapter::new(
+                network.map(|n| n.litecoin_network()).unwrap_or("mainnet"),
+            )),
+            Arc::new(bitcoin_cash::BitcoinCashAdapter::new(
+                network
+                    .map(|n| n.bitcoin_cash_network())
+                    .unwrap_or("mainnet"),
+            )),
+            Arc::new(ethereum::EthereumAdapter::new(
+                network.map(|n| n.ethereum_chain_id()).unwrap_or(1),
+            )),
+            Arc::new(solana::SolanaAdapter::new(
+                network
+                    .map(|n| n.solana_cluster())
+                    .unwrap_or("mainnet-beta"),
+            )),
+            Arc::new(nostr::NostrAdapter),
+            Arc::new(sui::SuiAdapter::new(
+                network.map(|n| n.sui_network()).unwrap_or("mainnet"),
+            )),
+            Arc::new(aptos::AptosAdapter::new(
+                network.map(|n| n.aptos_network()).unwrap_or("mainnet"),
+            )),
+            Arc::new(cardano::CardanoAdapter::new(
+                network.map(|n| n.cardano_network()).unwrap_or("mainnet"),
+            )),
+            Arc::new(stellar::StellarAdapter::new(
+                network.map(|n| n.stellar_network()).unwrap_or("mainnet"),
+            )),
+            Arc::new(algorand::AlgorandAdapter::new(
+                network.map(|n| n.algorand_network()).unwrap_or("mainnet"),
+            )),
+        ];
+
+        let adapters = match enabled {
+            None => builtin,
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    builtin
+                        .iter()
+                        .find(|adapter| matches(adapter.as_ref(), name))
+                        .cloned()
+                        .ok_or_else(|| anyhow!("Unknown chain '{}' in config", name))
+                })
+                .collect::<Result<_>>()?,
+        };
+
+        Ok(Self { adapters })
+    }
+
+    /// Look up an adapter by name or alias (case-insensitive)
+    pub fn get(&self, name: &str) -> Result<Arc<dyn ChainAdapter>> {
+        self.adapters
+            .iter()
+            .find(|adapter| matches(adapter.as_ref(), name))
+            .cloned()
+            .ok_or_else(|| anyhow!("Unsupported chain: {}", name))
+    }
+
+    pub fn adapters(&self) -> impl Iterator<Item = &Arc<dyn ChainAdapter>> {
+        self.adapters.iter()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.adapters.iter().map(|a| a.name().to_string()).collect()
+    }
+}
+
+fn matches(adapter: &dyn ChainAdapter, name: &str) -> bool {
+    let name = name.to_lowercase();
+    adapter.name() == name || adapter.aliases().contains(&name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_lookup() {
+        let registry = ChainRegistry::from_config(None, None).unwrap();
+        assert_eq!(registry.get("ETH").unwrap().name(), "ethereum");
+        assert_eq!(registry.get("sol").unwrap().curve(), CurveType::Ed25519);
+        assert!(registry.get("dogecoin").is_err());
+
+        let enabled = vec!["bitcoin".to_string()];
+        let registry = ChainRegistry::from_config(None, Some(&enabled)).unwrap();
+        assert_eq!(registry.names(), vec!["bitcoin"]);
+        assert!(registry.get("ethereum").is_err());
+
+        let unknown = vec!["dogecoin".to_string()];
+        assert!(ChainRegistry::from_config(None, Some(&unknown)).is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! Nostr adapter (NIP-01 events)
+//!
+//! Nostr keys are BIP340 x-only secp256k1 keys, so the Taproot FROST key
+//! doubles as a Nostr identity. The "transaction" is an unsigned event; FROST
+//! signs its id, sha256 of `[0, pubkey, created_at, kind, tags, content]`.
+
+use anyhow::{Context, Result};
+use bitcoin::bech32::{self, Bech32, Hrp};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{ChainAdapter, SignedTransaction, SigningInput};
+use crate::curves::CurveType;
+use crate::node::tx_policy::TransactionContext;
+
+/// NIP-01 event before signing
+#[derive(Debug, Clone, Object, Serialize, Deserialize)]
+pub struct UnsignedEvent {
+    /// x-only hex pubkey, filled in from the passphrase's key if omitted
+    #[oai(skip_serializing_if_is_none)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkey: Option<String>,
+    /// Unix seconds, defaults to now
+    #[oai(skip_serializing_if_is_none)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<u64>,
+    pub kind: u16,
+    #[oai(default)]
+    #[serde(default)]
+    pub tags: Vec<Vec<String>>,
+    pub content: String,
+}
+
+impl UnsignedEvent {
+    /// Fill in `created_at` with the current time if the client left it out
+    pub fn stamped(mut self) -> Self {
+        if self.created_at.is_none() {
+            self.created_at = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs());
+        }
+        self
+    }
+
+    /// Event id: sha256 of the NIP-01 serialization
+    pub fn id(&self) -> Result<[u8; 32]> {
+        let pubkey = self.pubkey.as_deref().context("Event pubkey is required")?;
+        let created_at = self.created_at.context("Event created_at is required")?;
+        if pubkey.len() != 64 || hex::decode(pubkey).is_err() || pubkey != pubkey.to_lowercase() {
+            anyhow::bail!("Event pubkey must be 32-byte lowercase hex");
+        }
+
+        let mut serialized = format!("[0,\"{}\",{},{},[", pubkey, created_at, self.kind);
+        for (i, tag) in self.tags.iter().enumerate() {
+            if i > 0 {
+                serialized.push(',');
+            }
+            serialized.push('[');
+            for (j, value) in tag.iter().enumerate() {
+                if j > 0 {
+                    serialized.push(',');
+                }
+                push_json_string(&mut serialized, value);
+            }
+            serialized.push(']');
+        }
+        serialized.push_str("],");
+        push_json_string(&mut serialized, &self.content);
+        serialized.push(']');
+
+        Ok(Sha256::digest(serialized.as_bytes()).into())
+    }
+}
+
+/// NIP-01 string escaping: only `"`, `\` and \n \r \t \b \f, everything else verbatim
+fn push_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Signed event as relays expect it
+#[derive(Debug, Clone, Object, Serialize, Deserialize)]
+pub struct SignedEvent {
+    pub id: String,
+    pub pubkey: String,
+    pub created_at: u64,
+    pub kind: u16,
+    pub tags: Vec<Vec<String>>,
+    pub content: String,
+    pub sig: String,
+}
+
+pub struct NostrAdapter;
+
+impl ChainAdapter for NostrAdapter {
+    fn name(&self) -> &'static str {
+        "nostr"
+    }
+
+    fn curve(&self) -> CurveType {
+        CurveType::Secp256k1Taproot
+    }
+
+    fn network(&self) -> String {
+        "nostr".to_string()
+    }
+
+    /// npub (NIP-19)
+    fn derive_address(&self, public_key: &[u8]) -> Result<String> {
+        let x_only = x_only_key(public_key)?;
+        Ok(bech32::encode::<Bech32>(Hrp::parse("npub")?, &x_only)?)
+    }
+
+    /// Hex x-only pubkey, the form used inside events
+    fn alternate_address(&self, public_key: &[u8]) -> Option<String> {
+        x_only_key(public_key).ok().map(hex::encode)
+    }
+
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>> {
+        let event = self.event_for(transaction, public_keys)?;
+
+        Ok(vec![SigningInput {
+            message: event.id()?.to_vec(),
+            signer: 0,
+            // Nodes recompute the event id before releasing shares
+            context: Some(TransactionContext {
+                kind: "nostr".to_string(),
+                payload: serde_json::to_string(&event)?,
+                input_index: None,
+            }),
+            optional: false,
+        }])
+    }
+
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        _inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction> {
+        let event = self.event_for(transaction, public_keys)?;
+        let id = event.id()?;
+        let signature = signatures
+            .first()
+            .and_then(Option::as_deref)
+            .context("Missing signature")?;
+
+        let x_only = bitcoin::key::XOnlyPublicKey::from_slice(&x_only_key(&public_keys[0])?)?;
+        let sig = bitcoin::secp256k1::schnorr::Signature::from_slice(signature)
+            .context("Invalid Schnorr signature")?;
+        let verified = bitcoin::secp256k1::Secp256k1::verification_only()
+            .verify_schnorr(&sig, &bitcoin::secp256k1::Message::from_digest(id), &x_only)
+            .is_ok();
+        if !verified {
+            tracing::error!("❌ Nostr event signature does not verify");
+        }
+
+        let signed = SignedEvent {
+            id: hex::encode(id),
+            pubkey: event.pubkey.clone().unwrap_or_default(),
+            created_at: event.created_at.unwrap_or_default(),
+            kind: event.kind,
+            tags: event.tags,
+            content: event.content,
+            sig: hex::encode(signature),
+        };
+
+        Ok(SignedTransaction {
+            transaction: serde_json::to_string(&signed)?,
+            tx_id: Some(signed.id),
+            signers: vec![self.derive_address(&public_keys[0])?],
+            missing_signers: Vec::new(),
+            verified,
+            signature: None,
+        })
+    }
+}
+
+impl NostrAdapter {
+    /// Parse the event and bind it to the signing key
+    fn event_for(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<UnsignedEvent> {
+        if public_keys.len() != 1 {
+            anyhow::bail!("Nostr events take exactly one passphrase");
+        }
+        let mut event: UnsignedEvent =
+            serde_json::from_str(transaction).context("Invalid Nostr event")?;
+
+        let pubkey = hex::encode(x_only_key(&public_keys[0])?);
+        match &event.pubkey {
+            Some(given) if *given != pubkey => {
+                anyhow::bail!("Event pubkey {} does not belong to this passphrase", given)
+            }
+            Some(_) => {}
+            None => event.pubkey = Some(pubkey),
+        }
+        Ok(event)
+    }
+}
+
+/// x-only part of a compressed secp256k1 key
+fn x_only_key(public_key: &[u8]) -> Result<[u8; 32]> {
+    if public_key.len() != 33 {
+        anyhow::bail!("Invalid secp256k1 pubkey length: {}", public_key.len());
+    }
+    Ok(public_key[1..].try_into()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_id_and_npub() {
+        // NIP-19 example key
+        let key = hex::decode("033bf0c63fcb93463407af97a5e5ee64fa883d107ef9e558472c4eb9aaaefa459d")
+            .unwrap();
+        assert_eq!(
+            NostrAdapter.derive_address(&key).unwrap(),
+            "npub180cvv07tjdrrgpa0j7j7tmnyl2yr6yr7l8j4s3evf6u64th6gkwsyjh6w6"
+        );
+
+        let event = UnsignedEvent {
+            pubkey: Some(hex::encode(&key[1..])),
+            created_at: Some(1_700_000_000),
+            kind: 1,
+            tags: vec![vec!["t".to_string(), "frost".to_string()]],
+            content: "line \"one\"\nline two".to_string(),
+        };
+        let expected = format!(
+            "[0,\"{}\",1700000000,1,[[\"t\",\"frost\"]],\"line \\\"one\\\"\\nline two\"]",
+            hex::encode(&key[1..])
+        );
+        assert_eq!(
+            event.id().unwrap(),
+            <[u8; 32]>::from(Sha256::digest(expected.as_bytes()))
+        );
+    }
+
+    #[test]
+    fn test_assemble_signed_event() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let keypair = bitcoin::secp256k1::Keypair::from_seckey_slice(&secp, &[0x42; 32]).unwrap();
+        let public_key = keypair.public_key().serialize().to_vec();
+
+        let unsigned = r#"{"kind":1,"created_at":1700000000,"content":"gm"}"#;
+        let inputs = NostrAdapter
+            .prepare(unsigned, std::slice::from_ref(&public_key))
+            .unwrap();
+        let digest: [u8; 32] = inputs[0].message[..].try_into().unwrap();
+        let sig = secp
+            .sign_schnorr_no_aux_rand(&bitcoin::secp256k1::Message::from_digest(digest), &keypair);
+
+        let signed = NostrAdapter
+            .assemble(
+                unsigned,
+                &[public_key],
+                &inputs,
+                &[Some(sig.serialize().to_vec())],
+            )
+            .unwrap();
+        assert!(signed.verified);
+        let event: SignedEvent = serde_json::from_str(&signed.transaction).unwrap();
+        assert_eq!(event.id, hex::encode(digest));
+        assert_eq!(event.content, "gm");
+    }
+}
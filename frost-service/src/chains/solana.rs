@@ -0,0 +1,214 @@
+//! Solana adapter and wire transaction assembly
+//!
+//! A wire transaction is `compact-u16(n) || n * signature(64) || message`, with
+//! one signature slot per required signer in account-key order. Unsigned slots
+//! are left zeroed so another party can co-sign later.
+
+use anyhow::{Context, Result};
+use base64::prelude::*;
+
+use super::{ChainAdapter, SignedTransaction, SigningInput};
+use crate::curves::CurveType;
+use crate::node::tx_policy::{SolanaMessage, TransactionContext};
+
+/// Transactions are base64 legacy or v0 messages; passphrases map to signer slots
+pub struct SolanaAdapter {
+    cluster: String,
+}
+
+impl SolanaAdapter {
+    pub fn new(cluster: &str) -> Self {
+        Self {
+            cluster: cluster.to_string(),
+        }
+    }
+}
+
+impl ChainAdapter for SolanaAdapter {
+    fn name(&self) -> &'static str {
+        "solana"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["sol"]
+    }
+
+    fn curve(&self) -> CurveType {
+        CurveType::Ed25519
+    }
+
+    fn network(&self) -> String {
+        self.cluster.clone()
+    }
+
+    fn derive_address(&self, public_key: &[u8]) -> Result<String> {
+        if public_key.len() != 32 {
+            anyhow::bail!("Invalid Ed25519 pubkey length: {}", public_key.len());
+        }
+        // Solana address is simply the base58-encoded public key
+        Ok(bs58::encode(public_key).into_string())
+    }
+
+    /// Sign the whole message once per passphrase, each in its own signer slot
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>> {
+        let message = decode_message(transaction)?;
+        let parsed = SolanaMessage::parse(&message).context("Invalid Solana message")?;
+
+        tracing::info!(
+            "Solana {} message: {} required signers, {} passphrases",
+            if parsed.versioned { "v0" } else { "legacy" },
+            parsed.num_required_signatures,
+            public_keys.len()
+        );
+
+        let mut slots: Vec<usize> = Vec::with_capacity(public_keys.len());
+        for public_key in public_keys {
+            let address = self.derive_address(public_key)?;
+            // Lookup-table accounts can't sign, so only static keys are matched
+            let slot = slot_of(&parsed, public_key)
+                .with_context(|| format!("{} is not a required signer", address))?;
+            if slots.contains(&slot) {
+                anyhow::bail!("{} appears more than once", address);
+            }
+            slots.push(slot);
+        }
+
+        let context = TransactionContext {
+            kind: "solana".to_string(),
+            payload: hex::encode(&message),
+            input_index: None,
+        };
+        Ok((0..public_keys.len())
+            .map(|signer| SigningInput {
+                message: message.clone(),
+                signer,
+                context: Some(context.clone()),
+                optional: false,
+            })
+            .collect())
+    }
+
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction> {
+        let message = decode_message(transaction)?;
+        let parsed = SolanaMessage::parse(&message).context("Invalid Solana message")?;
+        let required = parsed.required_signers();
+
+        let mut slots = vec![[0u8; 64]; required.len()];
+        let mut signed = vec![false; required.len()];
+        for (input, signature) in inputs.iter().zip(signatures) {
+            let public_key = &public_keys[input.signer];
+            let address = self.derive_address(public_key)?;
+            let signature = signature
+                .as_deref()
+                .with_context(|| format!("Missing signature for {}", address))?;
+
+            if signature.len() != 64 || !verify_signature(public_key, &message, signature) {
+                anyhow::bail!("Signature for {} failed verification", address);
+            }
+
+            let slot = slot_of(&parsed, public_key)
+                .with_context(|| format!("{} is not a required signer", address))?;
+            slots[slot].copy_from_slice(signature);
+            signed[slot] = true;
+        }
+
+        let (mut signers, mut missing_signers) = (Vec::new(), Vec::new());
+        for (key, signed) in required.iter().zip(&signed) {
+            let address = bs58::encode(key).into_string();
+            if *signed {
+                signers.push(address);
+            } else {
+                missing_signers.push(address);
+            }
+        }
+
+        Ok(SignedTransaction {
+            transaction: BASE64_STANDARD.encode(wire_transaction(&slots, &message)),
+            // The fee payer (slot 0) signature doubles as the transaction id
+            tx_id: signed
+                .first()
+                .copied()
+                .unwrap_or(false)
+                .then(|| bs58::encode(slots[0]).into_string()),
+            verified: true,
+            signers,
+            missing_signers,
+            signature: None,
+        })
+    }
+}
+
+fn decode_message(transaction: &str) -> Result<Vec<u8>> {
+    BASE64_STANDARD
+        .decode(transaction)
+        .context("Invalid base64 message")
+}
+
+fn slot_of(message: &SolanaMessage, public_key: &[u8]) -> Option<usize> {
+    message
+        .required_signers()
+        .iter()
+        .position(|key| key[..] == public_key[..])
+}
+
+/// Solana's compact-u16 (1-3 byte little-endian varint)
+pub fn encode_compact_u16(mut value: u16, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        byte |= 0x80;
+        out.push(byte);
+    }
+}
+
+/// Serialize signatures + message into the format sendTransaction expects
+pub fn wire_transaction(signatures: &[[u8; 64]], message: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(3 + signatures.len() * 64 + message.len());
+    encode_compact_u16(signatures.len() as u16, &mut out);
+    for signature in signatures {
+        out.extend_from_slice(signature);
+    }
+    out.extend_from_slice(message);
+    out
+}
+
+/// Verify an Ed25519 signature with the FROST group key
+pub fn verify_signature(pubkey: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let Ok(key) = frost_ed25519::VerifyingKey::deserialize(pubkey) else {
+        return false;
+    };
+    let Ok(signature) = frost_ed25519::Signature::deserialize(signature) else {
+        return false;
+    };
+    key.verify(message, &signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_u16() {
+        for (value, expected) in [
+            (0u16, vec![0x00]),
+            (0x7f, vec![0x7f]),
+            (0x80, vec![0x80, 0x01]),
+            (0x3fff, vec![0xff, 0x7f]),
+            (0x4000, vec![0x80, 0x80, 0x01]),
+        ] {
+            let mut out = Vec::new();
+            encode_compact_u16(value, &mut out);
+            assert_eq!(out, expected, "value {:#x}", value);
+        }
+    }
+}
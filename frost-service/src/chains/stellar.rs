@@ -0,0 +1,249 @@
+//! Stellar adapter
+//!
+//! Account ids are StrKey-encoded Ed25519 group keys (`G...`). The transaction
+//! is an unsigned base64 `TransactionEnvelope`; FROST signs the hash of the
+//! network id and the tagged transaction, and the decorated signature is
+//! appended to the envelope.
+
+use anyhow::{Context, Result};
+use base64::prelude::*;
+use data_encoding::BASE32_NOPAD;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::solana::verify_signature;
+use super::{ChainAdapter, SignedTransaction, SigningInput};
+use crate::curves::CurveType;
+use crate::node::tx_policy::TransactionContext;
+
+/// StrKey version byte for ed25519 public keys ('G')
+const ACCOUNT_ID_VERSION: u8 = 6 << 3;
+
+/// EnvelopeType discriminants that carry a tagged transaction
+const ENVELOPE_TYPE_TX: u32 = 2;
+const ENVELOPE_TYPE_TX_FEE_BUMP: u32 = 5;
+
+/// Transaction context payload (kind "stellar") forwarded to signer nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignaturePayload {
+    pub network_passphrase: String,
+    pub envelope: String, // base64 XDR, unsigned
+}
+
+impl SignaturePayload {
+    /// sha256(sha256(network passphrase) || tagged transaction), the bytes a key signs
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        let envelope = BASE64_STANDARD
+            .decode(&self.envelope)
+            .context("Invalid base64 envelope")?;
+        let tagged = tagged_transaction(&envelope)?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(Sha256::digest(self.network_passphrase.as_bytes()));
+        hasher.update(tagged);
+        Ok(hasher.finalize().into())
+    }
+}
+
+pub struct StellarAdapter {
+    network: String,
+}
+
+impl StellarAdapter {
+    pub fn new(network: &str) -> Self {
+        Self {
+            network: network.to_string(),
+        }
+    }
+
+    fn network_passphrase(&self) -> &'static str {
+        match self.network.as_str() {
+            "testnet" => "Test SDF Network ; September 2015",
+            "futurenet" => "Test SDF Future Network ; October 2022",
+            _ => "Public Global Stellar Network ; September 2015",
+        }
+    }
+
+    fn payload(&self, transaction: &str) -> SignaturePayload {
+        SignaturePayload {
+            network_passphrase: self.network_passphrase().to_string(),
+            envelope: transaction.to_string(),
+        }
+    }
+}
+
+impl ChainAdapter for StellarAdapter {
+    fn name(&self) -> &'static str {
+        "stellar"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["xlm"]
+    }
+
+    fn curve(&self) -> CurveType {
+        CurveType::Ed25519
+    }
+
+    fn network(&self) -> String {
+        self.network.clone()
+    }
+
+    fn derive_address(&self, public_key: &[u8]) -> Result<String> {
+        Ok(encode_account_id(ed25519_key(public_key)?))
+    }
+
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>> {
+        if public_keys.len() != 1 {
+            anyhow::bail!("Stellar transactions take exactly one passphrase");
+        }
+        let payload = self.payload(transaction);
+
+        Ok(vec![SigningInput {
+            message: payload.hash()?.to_vec(),
+            signer: 0,
+            // Nodes recompute the signature payload hash before releasing shares
+            context: Some(TransactionContext {
+                kind: "stellar".to_string(),
+                payload: serde_json::to_string(&payload)?,
+                input_index: None,
+            }),
+            optional: false,
+        }])
+    }
+
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction> {
+        let envelope = BASE64_STANDARD.decode(transaction)?;
+        let tagged = tagged_transaction(&envelope)?;
+        let public_key = ed25519_key(&public_keys[0])?;
+        let signature = signatures
+            .first()
+            .and_then(Option::as_deref)
+            .context("Missing signature")?;
+
+        let verified = verify_signature(public_key, &inputs[0].message, signature);
+        if !verified {
+            tracing::error!("❌ Stellar signature does not verify");
+        }
+
+        // DecoratedSignature { hint: last 4 key bytes, signature: opaque<64> }
+        let mut decorated = public_key[28..].to_vec();
+        decorated.extend_from_slice(&(signature.len() as u32).to_be_bytes());
+        decorated.extend_from_slice(signature);
+
+        let mut signed = tagged.to_vec();
+        signed.extend_from_slice(&1u32.to_be_bytes());
+        signed.extend_from_slice(&decorated);
+
+        Ok(SignedTransaction {
+            transaction: BASE64_STANDARD.encode(signed),
+            tx_id: Some(hex::encode(&inputs[0].message)),
+            signers: vec![self.derive_address(public_key)?],
+            missing_signers: Vec::new(),
+            verified,
+            signature: Some(BASE64_STANDARD.encode(decorated)),
+        })
+    }
+}
+
+/// Envelope without its (empty) signature list: the tagged transaction
+fn tagged_transaction(envelope: &[u8]) -> Result<&[u8]> {
+    if envelope.len() < 8 {
+        anyhow::bail!("Envelope too short");
+    }
+    let envelope_type = u32::from_be_bytes(envelope[..4].try_into()?);
+    if envelope_type != ENVELOPE_TYPE_TX && envelope_type != ENVELOPE_TYPE_TX_FEE_BUMP {
+        anyhow::bail!(
+            "Unsupported envelope type {} (expected a v1 or fee-bump envelope)",
+            envelope_type
+        );
+    }
+    let (tagged, signature_count) = envelope.split_at(envelope.len() - 4);
+    if signature_count != [0, 0, 0, 0] {
+        anyhow::bail!("Envelope must be unsigned");
+    }
+    Ok(tagged)
+}
+
+fn encode_account_id(public_key: &[u8]) -> String {
+    let mut payload = vec![ACCOUNT_ID_VERSION];
+    payload.extend_from_slice(public_key);
+    let checksum = crc16_xmodem(&payload);
+    payload.extend_from_slice(&checksum.to_le_bytes());
+    BASE32_NOPAD.encode(&payload)
+}
+
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc = 0u16;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn ed25519_key(public_key: &[u8]) -> Result<&[u8]> {
+    if public_key.len() != 32 {
+        anyhow::bail!("Invalid Ed25519 pubkey length: {}", public_key.len());
+    }
+    Ok(public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_id_and_signing() {
+        // Round-trip a real account id through the StrKey encoder
+        let account = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+        let raw = BASE32_NOPAD.decode(account.as_bytes()).unwrap();
+        assert_eq!(raw[0], ACCOUNT_ID_VERSION);
+        assert_eq!(encode_account_id(&raw[1..33]), account);
+
+        let mut rng = rand::thread_rng();
+        let signing_key = frost_ed25519::SigningKey::new(&mut rng);
+        let public_key = frost_ed25519::VerifyingKey::from(&signing_key)
+            .serialize()
+            .unwrap();
+
+        // ENVELOPE_TYPE_TX || (transaction) || no signatures
+        let mut envelope = ENVELOPE_TYPE_TX.to_be_bytes().to_vec();
+        envelope.extend_from_slice(&[0x11; 60]);
+        envelope.extend_from_slice(&[0, 0, 0, 0]);
+        let tx = BASE64_STANDARD.encode(&envelope);
+
+        let adapter = StellarAdapter::new("testnet");
+        let inputs = adapter
+            .prepare(&tx, std::slice::from_ref(&public_key))
+            .unwrap();
+        let signature = signing_key.sign(&mut rng, &inputs[0].message);
+        let signed = adapter
+            .assemble(
+                &tx,
+                std::slice::from_ref(&public_key),
+                &inputs,
+                &[Some(signature.serialize().unwrap())],
+            )
+            .unwrap();
+        assert!(signed.verified);
+
+        let signed = BASE64_STANDARD.decode(signed.transaction).unwrap();
+        assert_eq!(&signed[..64], &envelope[..64]);
+        assert_eq!(&signed[64..68], &[0, 0, 0, 1]);
+        assert_eq!(&signed[68..72], &public_key[28..]);
+        assert_eq!(signed.len(), 64 + 4 + 4 + 4 + 64);
+    }
+}
@@ -0,0 +1,175 @@
+//! Sui adapter
+//!
+//! Addresses are blake2b-256(flag || pubkey) of the Ed25519 group key. The
+//! transaction is base64 BCS `TransactionData`; FROST signs the blake2b-256
+//! digest of the intent message and the result is Sui's serialized signature
+//! (flag || signature || pubkey), submitted next to the unchanged tx bytes.
+
+use anyhow::{Context, Result};
+use base64::prelude::*;
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+
+use super::solana::verify_signature;
+use super::{ChainAdapter, SignedTransaction, SigningInput};
+use crate::curves::CurveType;
+use crate::node::tx_policy::TransactionContext;
+
+/// Signature scheme flag for Ed25519
+const ED25519_FLAG: u8 = 0x00;
+
+/// Intent for transaction data: scope TransactionData, version V0, app Sui
+const TRANSACTION_INTENT: [u8; 3] = [0, 0, 0];
+
+type Blake2b256 = Blake2b<U32>;
+
+pub struct SuiAdapter {
+    network: String,
+}
+
+impl SuiAdapter {
+    pub fn new(network: &str) -> Self {
+        Self {
+            network: network.to_string(),
+        }
+    }
+}
+
+impl ChainAdapter for SuiAdapter {
+    fn name(&self) -> &'static str {
+        "sui"
+    }
+
+    fn curve(&self) -> CurveType {
+        CurveType::Ed25519
+    }
+
+    fn network(&self) -> String {
+        self.network.clone()
+    }
+
+    fn derive_address(&self, public_key: &[u8]) -> Result<String> {
+        let public_key = ed25519_key(public_key)?;
+        let mut hasher = Blake2b256::new();
+        hasher.update([ED25519_FLAG]);
+        hasher.update(public_key);
+        Ok(format!("0x{}", hex::encode(hasher.finalize())))
+    }
+
+    fn prepare(&self, transaction: &str, public_keys: &[Vec<u8>]) -> Result<Vec<SigningInput>> {
+        if public_keys.len() != 1 {
+            anyhow::bail!("Sui transactions take exactly one passphrase (the sender)");
+        }
+        let tx_bytes = BASE64_STANDARD
+            .decode(transaction)
+            .context("Invalid base64 transaction bytes")?;
+
+        Ok(vec![SigningInput {
+            message: intent_digest(&tx_bytes).to_vec(),
+            signer: 0,
+            // Nodes recompute the intent digest before releasing shares
+            context: Some(TransactionContext {
+                kind: "sui".to_string(),
+                payload: transaction.to_string(),
+                input_index: None,
+            }),
+            optional: false,
+        }])
+    }
+
+    fn assemble(
+        &self,
+        transaction: &str,
+        public_keys: &[Vec<u8>],
+        inputs: &[SigningInput],
+        signatures: &[Option<Vec<u8>>],
+    ) -> Result<SignedTransaction> {
+        let tx_bytes = BASE64_STANDARD.decode(transaction)?;
+        let public_key = ed25519_key(&public_keys[0])?;
+        let signature = signatures
+            .first()
+            .and_then(Option::as_deref)
+            .context("Missing signature")?;
+
+        let verified = verify_signature(public_key, &inputs[0].message, signature);
+        if !verified {
+            tracing::error!("❌ Sui signature does not verify");
+        }
+
+        let mut serialized = vec![ED25519_FLAG];
+        serialized.extend_from_slice(signature);
+        serialized.extend_from_slice(public_key);
+
+        Ok(SignedTransaction {
+            transaction: transaction.to_string(),
+            tx_id: Some(transaction_digest(&tx_bytes)),
+            signers: vec![self.derive_address(public_key)?],
+            missing_signers: Vec::new(),
+            verified,
+            signature: Some(BASE64_STANDARD.encode(serialized)),
+        })
+    }
+}
+
+/// blake2b-256(intent || tx_bytes), the bytes an Ed25519 Sui key signs
+pub fn intent_digest(tx_bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+    hasher.update(TRANSACTION_INTENT);
+    hasher.update(tx_bytes);
+    hasher.finalize().into()
+}
+
+/// base58 transaction digest as shown by explorers and RPC
+fn transaction_digest(tx_bytes: &[u8]) -> String {
+    let mut hasher = Blake2b256::new();
+    hasher.update(b"TransactionData::");
+    hasher.update(tx_bytes);
+    bs58::encode(hasher.finalize()).into_string()
+}
+
+fn ed25519_key(public_key: &[u8]) -> Result<&[u8]> {
+    if public_key.len() != 32 {
+        anyhow::bail!("Invalid Ed25519 pubkey length: {}", public_key.len());
+    }
+    Ok(public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sui_sign_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let signing_key = frost_ed25519::SigningKey::new(&mut rng);
+        let public_key = frost_ed25519::VerifyingKey::from(&signing_key)
+            .serialize()
+            .unwrap();
+
+        let adapter = SuiAdapter::new("mainnet");
+        let address = adapter.derive_address(&public_key).unwrap();
+        assert_eq!(address.len(), 66);
+
+        let tx = BASE64_STANDARD.encode(b"bcs transaction data");
+        let inputs = adapter
+            .prepare(&tx, std::slice::from_ref(&public_key))
+            .unwrap();
+        let signature = signing_key.sign(&mut rng, &inputs[0].message);
+
+        let signed = adapter
+            .assemble(
+                &tx,
+                std::slice::from_ref(&public_key),
+                &inputs,
+                &[Some(signature.serialize().unwrap())],
+            )
+            .unwrap();
+        assert!(signed.verified);
+        assert_eq!(signed.signers, vec![address]);
+
+        let serialized = BASE64_STANDARD.decode(signed.signature.unwrap()).unwrap();
+        assert_eq!(serialized.len(), 97);
+        assert_eq!(serialized[0], ED25519_FLAG);
+        assert_eq!(&serialized[65..], &public_key[..]);
+    }
+}
@@ -3,6 +3,8 @@ use bitcoin::Network;
 use serde::Deserialize;
 use std::fs;
 
+use crate::curves::CurveType;
+
 #[derive(Debug, Deserialize)]
 pub struct ConfigFile {
     pub network: Option<NetworkConfig>,
@@ -11,6 +13,27 @@ pub struct ConfigFile {
     pub node: Option<NodeConfig>,
     #[serde(default)]
     pub aggregator: Option<AggregatorConfig>,
+    #[serde(default)]
+    pub audit: AuditConfig,
+}
+
+/// Local hash-chained audit log (every role keeps its own)
+#[derive(Debug, Deserialize, Clone)]
+pub struct AuditConfig {
+    #[serde(default = "default_audit_path")]
+    pub path: String,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            path: default_audit_path(),
+        }
+    }
+}
+
+fn default_audit_path() -> String {
+    "./data/audit.log".to_string()
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -23,12 +46,31 @@ pub struct NetworkConfig {
     pub bitcoin_network: Option<String>, // "mainnet", "testnet", "signet", "regtest"
 
     #[serde(default)]
-    #[allow(dead_code)]
+    pub litecoin_network: Option<String>, // "mainnet", "testnet"
+
+    #[serde(default)]
+    pub bitcoin_cash_network: Option<String>, // "mainnet", "testnet", "regtest"
+
+    #[serde(default)]
     pub ethereum_network: Option<String>, // "mainnet", "sepolia", "goerli", "holesky"
 
     #[serde(default)]
-    #[allow(dead_code)]
     pub solana_network: Option<String>, // "mainnet-beta", "testnet", "devnet"
+
+    #[serde(default)]
+    pub sui_network: Option<String>, // "mainnet", "testnet", "devnet"
+
+    #[serde(default)]
+    pub aptos_network: Option<String>, // "mainnet", "testnet", "devnet"
+
+    #[serde(default)]
+    pub cardano_network: Option<String>, // "mainnet", "preprod", "preview"
+
+    #[serde(default)]
+    pub stellar_network: Option<String>, // "mainnet", "testnet", "futurenet"
+
+    #[serde(default)]
+    pub algorand_network: Option<String>, // "mainnet", "testnet", "betanet"
 }
 
 impl NetworkConfig {
@@ -47,7 +89,31 @@ impl NetworkConfig {
         }
     }
 
-    #[allow(dead_code)]
+    pub fn litecoin_network(&self) -> &str {
+        let network_str = self
+            .litecoin_network
+            .as_deref()
+            .unwrap_or(&self.network_type);
+
+        match network_str {
+            "testnet" => "testnet",
+            _ => "mainnet", // Default to mainnet
+        }
+    }
+
+    pub fn bitcoin_cash_network(&self) -> &str {
+        let network_str = self
+            .bitcoin_cash_network
+            .as_deref()
+            .unwrap_or(&self.network_type);
+
+        match network_str {
+            "testnet" => "testnet",
+            "regtest" => "regtest",
+            _ => "mainnet", // Default to mainnet
+        }
+    }
+
     pub fn ethereum_chain_id(&self) -> u64 {
         let network_str = self
             .ethereum_network
@@ -64,7 +130,6 @@ impl NetworkConfig {
         }
     }
 
-    #[allow(dead_code)]
     pub fn solana_cluster(&self) -> &str {
         let network_str = self.solana_network.as_deref().unwrap_or(&self.network_type);
 
@@ -75,6 +140,65 @@ impl NetworkConfig {
             _ => "mainnet-beta", // Default to mainnet
         }
     }
+
+    pub fn sui_network(&self) -> &str {
+        let network_str = self.sui_network.as_deref().unwrap_or(&self.network_type);
+
+        match network_str {
+            "testnet" => "testnet",
+            "devnet" => "devnet",
+            _ => "mainnet", // Default to mainnet
+        }
+    }
+
+    pub fn cardano_network(&self) -> &str {
+        let network_str = self
+            .cardano_network
+            .as_deref()
+            .unwrap_or(&self.network_type);
+
+        match network_str {
+            "testnet" | "preprod" => "preprod", // Default testnet = preprod
+            "preview" => "preview",
+            _ => "mainnet", // Default to mainnet
+        }
+    }
+
+    pub fn stellar_network(&self) -> &str {
+        let network_str = self
+            .stellar_network
+            .as_deref()
+            .unwrap_or(&self.network_type);
+
+        match network_str {
+            "testnet" => "testnet",
+            "futurenet" => "futurenet",
+            _ => "mainnet", // Default to mainnet
+        }
+    }
+
+    pub fn algorand_network(&self) -> &str {
+        let network_str = self
+            .algorand_network
+            .as_deref()
+            .unwrap_or(&self.network_type);
+
+        match network_str {
+            "testnet" => "testnet",
+            "betanet" => "betanet",
+            _ => "mainnet", // Default to mainnet
+        }
+    }
+
+    pub fn aptos_network(&self) -> &str {
+        let network_str = self.aptos_network.as_deref().unwrap_or(&self.network_type);
+
+        match network_str {
+            "testnet" => "testnet",
+            "devnet" => "devnet",
+            _ => "mainnet", // Default to mainnet
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -94,19 +218,227 @@ pub struct NodeConfig {
 
     #[serde(default = "default_storage_path")]
     pub storage_path: String,
+    // Share store backend: "rocksdb" (directory), "sqlite" (file) or "memory"
+    #[serde(default)]
+    pub storage_backend: crate::node::share_store::StorageBackend,
     #[serde(default = "default_max_signers")]
     pub max_signers: u16,
     #[serde(default = "default_min_signers")]
     pub min_signers: u16,
+
+    // Signer committee generation, recorded in key metadata (bump when nodes are replaced)
+    #[serde(default)]
+    pub committee_epoch: u64,
+
+    // Curves to serve (optional, defaults to every built-in ciphersuite)
+    #[serde(default)]
+    pub curves: Option<Vec<String>>,
+
+    // Local transaction policy checked before releasing a signature share
+    #[serde(default)]
+    pub policy: SigningPolicyConfig,
+
+    // Offline recovery keys that `/api/backup/export` archives are sealed to
+    #[serde(default)]
+    pub backup: BackupConfig,
+
+    // Background storage integrity checks
+    #[serde(default)]
+    pub scrub: ScrubConfig,
+
+    // Share store layout upgrades run at startup
+    #[serde(default)]
+    pub migration: MigrationConfig,
+
+    // Operators whose k-of-m signatures unfreeze or destroy keys (refused without)
+    #[serde(default)]
+    pub lifecycle: Option<LifecycleConfig>,
+}
+
+/// k-of-m operators for unfreezing and destroying keys, checked by the node itself
+#[derive(Debug, Deserialize, Clone)]
+pub struct LifecycleConfig {
+    pub required_approvals: usize,
+    pub operators: Vec<OperatorConfig>,
+}
+
+/// Share store schema migrations
+#[derive(Debug, Deserialize, Clone)]
+pub struct MigrationConfig {
+    /// Log the pending migrations and start without changing the store
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Copy the store before the first migration writes to it
+    #[serde(default = "default_migration_backup")]
+    pub backup: bool,
+    /// Where those copies go (defaults to `{storage_path}.backups`)
+    #[serde(default)]
+    pub backup_dir: Option<String>,
+}
+
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            backup: default_migration_backup(),
+            backup_dir: None,
+        }
+    }
+}
+
+fn default_migration_backup() -> bool {
+    true
+}
+
+/// Storage scrubber schedule
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScrubConfig {
+    /// Seconds between scrubs (0 disables the background job)
+    #[serde(default = "default_scrub_interval_secs")]
+    pub interval_secs: u64,
+}
+
+impl Default for ScrubConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_scrub_interval_secs(),
+        }
+    }
+}
+
+fn default_scrub_interval_secs() -> u64 {
+    60 * 60
+}
+
+/// Share backup recipients
+///
+/// Archives are only ever encrypted to these keys, never to a key supplied
+/// with the request, so API access alone can't exfiltrate shares.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct BackupConfig {
+    /// secp256k1 public keys (hex) of the offline recovery holders
+    #[serde(default)]
+    pub recovery_keys: Vec<String>,
+    /// Recovery keys needed to open an archive (defaults to 1)
+    #[serde(default = "default_recovery_threshold")]
+    pub threshold: usize,
+}
+
+fn default_recovery_threshold() -> usize {
+    1
+}
+
+/// Node-side signing policy
+///
+/// Applied to the full transaction the aggregator forwards with FROST round 2.
+/// The node recomputes the sighash itself, so a compromised aggregator can't
+/// get a share for anything the local policy doesn't allow.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SigningPolicyConfig {
+    /// Refuse bare message hashes (every round 2 must carry a transaction);
+    /// implied while any limit is set
+    #[serde(default)]
+    pub require_transaction: bool,
+    #[serde(default)]
+    pub bitcoin: BitcoinPolicyConfig,
+    /// Limits in litoshis / LTC addresses. Bitcoin and Litecoin PSBTs sign the
+    /// same sighash with the same key, so every PSBT must pass both policies
+    #[serde(default)]
+    pub litecoin: BitcoinPolicyConfig,
+    /// Limits for Bitcoin Cash (satoshis, CashAddr addresses)
+    #[serde(default)]
+    pub bitcoin_cash: BitcoinPolicyConfig,
+    #[serde(default)]
+    pub evm: EvmPolicyConfig,
+    #[serde(default)]
+    pub solana: SolanaPolicyConfig,
+    /// Kinds with no recipient/amount rules (sui, aptos, cardano, stellar,
+    /// algorand) to sign anyway while other chains have limits configured
+    #[serde(default)]
+    pub unchecked_kinds: Vec<String>,
+}
+
+impl SigningPolicyConfig {
+    /// Whether any recipient, amount or program limit is configured
+    pub fn has_limits(&self) -> bool {
+        let utxo =
+            |p: &BitcoinPolicyConfig| p.max_spend_sats.is_some() || !p.allowed_addresses.is_empty();
+        utxo(&self.bitcoin)
+            || utxo(&self.litecoin)
+            || utxo(&self.bitcoin_cash)
+            || !self.evm.allowed_chain_ids.is_empty()
+            || self.evm.max_value_wei.is_some()
+            || !self.evm.allowed_recipients.is_empty()
+            || !self.solana.allowed_program_ids.is_empty()
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct BitcoinPolicyConfig {
+    /// Max total sent to outputs that aren't change
+    #[serde(default)]
+    pub max_spend_sats: Option<u64>,
+    /// Allowed destination addresses for non-change outputs (empty = any)
+    #[serde(default)]
+    pub allowed_addresses: Vec<String>,
+    /// Extra change addresses; outputs to the signing key's own address always are
+    #[serde(default)]
+    pub change_addresses: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct EvmPolicyConfig {
+    /// Allowed EIP-155 chain ids (empty = any, but unprotected txs are always refused)
+    #[serde(default)]
+    pub allowed_chain_ids: Vec<u64>,
+    /// Max value per transaction in wei (decimal string, may exceed u64)
+    #[serde(default)]
+    pub max_value_wei: Option<String>,
+    /// Allowed `to` addresses (empty = any, contract creation needs an empty list)
+    #[serde(default)]
+    pub allowed_recipients: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SolanaPolicyConfig {
+    /// Allowed program ids (base58) for every instruction (empty = any)
+    #[serde(default)]
+    pub allowed_program_ids: Vec<String>,
 }
 
 impl NodeConfig {
+    /// Curves served by this node (names validated at startup)
+    pub fn curves(&self) -> Vec<CurveType> {
+        match &self.curves {
+            Some(names) => names
+                .iter()
+                .filter_map(|name| CurveType::from_name(name))
+                .collect(),
+            None => CurveType::ALL.to_vec(),
+        }
+    }
+
     /// Create key provider from configuration
     pub async fn create_key_provider(
         &self,
     ) -> anyhow::Result<Box<dyn crate::node::key_provider::MasterKeyProvider>> {
         self.key_provider.create_provider().await
     }
+
+    /// Schema migration settings for `MultiCurveStorage::open`
+    pub fn migration_options(&self) -> crate::node::schema::MigrationOptions {
+        let backup_dir = self.migration.backup.then(|| {
+            self.migration
+                .backup_dir
+                .clone()
+                .unwrap_or_else(|| format!("{}.backups", self.storage_path))
+                .into()
+        });
+        crate::node::schema::MigrationOptions {
+            dry_run: self.migration.dry_run,
+            backup_dir,
+        }
+    }
 }
 
 fn default_max_signers() -> u16 {
@@ -121,6 +453,42 @@ fn default_min_signers() -> u16 {
 pub struct AggregatorConfig {
     pub signer_nodes: Vec<String>,
     pub threshold: usize,
+
+    // Chain adapters to register (optional, defaults to every built-in chain)
+    #[serde(default)]
+    pub chains: Option<Vec<String>>,
+
+    // Multi-operator approval workflow (signing aggregator only, optional)
+    #[serde(default)]
+    pub approval: Option<ApprovalConfig>,
+}
+
+/// k-of-m operator approval for signing requests
+///
+/// When present, `/api/sign/*` is closed and every request must go through
+/// `/api/approvals` and collect `required_approvals` operator signatures first.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ApprovalConfig {
+    pub required_approvals: usize,
+    pub operators: Vec<OperatorConfig>,
+    #[serde(default = "default_approval_ttl_secs")]
+    pub ttl_secs: u64,
+    #[serde(default = "default_approval_storage_path")]
+    pub storage_path: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct OperatorConfig {
+    pub name: String,
+    pub public_key: String, // x-only secp256k1 key (hex), approvals are BIP340 signatures
+}
+
+fn default_approval_ttl_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_approval_storage_path() -> String {
+    "./data/approvals".to_string()
 }
 
 impl AggregatorConfig {
@@ -143,16 +511,47 @@ impl ConfigFile {
     pub fn validate(&self) -> Result<()> {
         match self.server.role.as_str() {
             "node" => {
-                if self.node.is_none() {
+                let Some(node) = &self.node else {
                     anyhow::bail!("Role 'node' requires [node] config section");
+                };
+
+                for name in node.curves.iter().flatten() {
+                    if CurveType::from_name(name).is_none() {
+                        anyhow::bail!("[node] curves: unknown curve '{}'", name);
+                    }
+                }
+
+                let backup = &node.backup;
+                for key in &backup.recovery_keys {
+                    crate::node::backup::parse_recovery_key(key)
+                        .map_err(|e| anyhow::anyhow!("[node.backup] recovery_keys: {}", e))?;
+                }
+                if !backup.recovery_keys.is_empty()
+                    && (backup.threshold == 0 || backup.threshold > backup.recovery_keys.len())
+                {
+                    anyhow::bail!(
+                        "[node.backup] threshold must be between 1 and {} (number of recovery keys)",
+                        backup.recovery_keys.len()
+                    );
                 }
             }
             "address" | "signer" => {
-                if self.aggregator.is_none() {
+                let Some(aggregator) = &self.aggregator else {
                     anyhow::bail!(
                         "Role '{}' requires [aggregator] config section",
                         self.server.role
                     );
+                };
+
+                if let Some(approval) = &aggregator.approval {
+                    if approval.required_approvals == 0
+                        || approval.required_approvals > approval.operators.len()
+                    {
+                        anyhow::bail!(
+                            "[aggregator.approval] required_approvals must be between 1 and {} (number of operators)",
+                            approval.operators.len()
+                        );
+                    }
                 }
             }
             _ => anyhow::bail!(
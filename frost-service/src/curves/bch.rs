@@ -0,0 +1,370 @@
+//! Bitcoin Cash Schnorr ciphersuite for FROST
+//!
+//! BCH Schnorr (2019 upgrade) differs from BIP340 in three ways:
+//! - the challenge is sha256(R.x || compressed P || m), untagged, over the
+//!   full 33-byte public key, so keys are never negated
+//! - R must have a y coordinate that is a quadratic residue (not an even y)
+//! - signatures are R.x || s, 64 bytes
+//!
+//! Group and scalar field are plain secp256k1 (shared with `frost_secp256k1`);
+//! only the hooks that pick R and compute the challenge change.
+
+use std::borrow::Cow;
+
+use frost_core::round1::{GroupCommitmentShare, Nonce, SigningNonces};
+use frost_core::round2::SignatureShare;
+use frost_core::{
+    BindingFactor, Challenge, Ciphersuite, Element, Field, Group, GroupCommitment, Identifier,
+    Signature, VerifyingKey,
+};
+use frost_secp256k1::{Secp256K1Group, Secp256K1ScalarField};
+use k256::elliptic_curve::bigint::U256;
+use k256::elliptic_curve::hash2curve::{hash_to_field, ExpandMsgXmd};
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::point::AffineCoordinates;
+use k256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use k256::elliptic_curve::PrimeField;
+use k256::{AffinePoint, EncodedPoint, FieldBytes, FieldElement, ProjectivePoint, Scalar};
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use super::{CurveOperations, CurveType};
+
+const CONTEXT_STRING: &str = "FROST-secp256k1-SHA256-BCH-v1";
+
+/// FROST(secp256k1, SHA-256) producing Bitcoin Cash Schnorr signatures
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Secp256K1Sha256Bch;
+
+type S = Secp256K1Sha256Bch;
+
+pub type Error = frost_core::Error<S>;
+
+pub type KeyPackage = frost_core::keys::KeyPackage<S>;
+
+pub struct BchOperations;
+
+impl CurveOperations for BchOperations {
+    type Suite = Secp256K1Sha256Bch;
+    const CURVE: CurveType = CurveType::Secp256k1Bch;
+    const RNG_PREFIX: &'static str = "bch";
+}
+
+fn hash_to_scalar(domain: &[&[u8]], msg: &[u8]) -> Scalar {
+    let mut u = [Scalar::ZERO];
+    hash_to_field::<ExpandMsgXmd<Sha256>, Scalar>(&[msg], domain, &mut u)
+        .expect("ExpandMsgXmd never fails for a 32-byte field");
+    u[0]
+}
+
+fn hash_to_array(inputs: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for input in inputs {
+        hasher.update(input);
+    }
+    hasher.finalize().into()
+}
+
+/// Whether the y coordinate of `point` is a quadratic residue mod p
+fn has_square_y(point: &ProjectivePoint) -> bool {
+    let encoded = point.to_affine().to_encoded_point(false);
+    let Some(y) = encoded.y() else {
+        return false; // identity
+    };
+    let y = FieldElement::from_bytes(y).unwrap_or(FieldElement::ZERO);
+    y.sqrt().is_some().into()
+}
+
+fn challenge_scalar(r_x: &[u8], public_key: &[u8; 33], message: &[u8]) -> Scalar {
+    let mut hasher = Sha256::new();
+    hasher.update(r_x);
+    hasher.update(public_key);
+    hasher.update(message);
+    Scalar::reduce(U256::from_be_slice(&hasher.finalize()))
+}
+
+impl Ciphersuite for Secp256K1Sha256Bch {
+    const ID: &'static str = CONTEXT_STRING;
+
+    type Group = Secp256K1Group;
+
+    type HashOutput = [u8; 32];
+
+    type SignatureSerialization = [u8; 64];
+
+    fn H1(m: &[u8]) -> Scalar {
+        hash_to_scalar(&[CONTEXT_STRING.as_bytes(), b"rho"], m)
+    }
+
+    /// Plain sha256, as the BCH challenge is untagged
+    fn H2(m: &[u8]) -> Scalar {
+        Scalar::reduce(U256::from_be_slice(&Sha256::digest(m)))
+    }
+
+    fn H3(m: &[u8]) -> Scalar {
+        hash_to_scalar(&[CONTEXT_STRING.as_bytes(), b"nonce"], m)
+    }
+
+    fn H4(m: &[u8]) -> Self::HashOutput {
+        hash_to_array(&[CONTEXT_STRING.as_bytes(), b"msg", m])
+    }
+
+    fn H5(m: &[u8]) -> Self::HashOutput {
+        hash_to_array(&[CONTEXT_STRING.as_bytes(), b"com", m])
+    }
+
+    fn HDKG(m: &[u8]) -> Option<Scalar> {
+        Some(hash_to_scalar(&[CONTEXT_STRING.as_bytes(), b"dkg"], m))
+    }
+
+    fn HID(m: &[u8]) -> Option<Scalar> {
+        Some(hash_to_scalar(&[CONTEXT_STRING.as_bytes(), b"id"], m))
+    }
+
+    // Single-party nonce, negated so R has a square y
+    fn generate_nonce<R: RngCore + CryptoRng>(rng: &mut R) -> (Scalar, ProjectivePoint) {
+        let k = frost_core::random_nonzero::<S, R>(rng);
+        let point = ProjectivePoint::GENERATOR * k;
+        if has_square_y(&point) {
+            (k, point)
+        } else {
+            (-k, -point)
+        }
+    }
+
+    // sha256(R.x || compressed P || m): only R.x is committed to, so R and -R
+    // give the same challenge
+    fn challenge(
+        r: &Element<S>,
+        verifying_key: &VerifyingKey<S>,
+        message: &[u8],
+    ) -> Result<Challenge<S>, Error> {
+        let public_key = Secp256K1Group::serialize(&verifying_key.to_element())?;
+        Ok(Challenge::from_scalar(challenge_scalar(
+            &r.to_affine().x(),
+            &public_key,
+            message,
+        )))
+    }
+
+    // The aggregate R is the raw group commitment; verify against its
+    // square-y form, which is what the negated nonces actually produced
+    fn pre_verify<'a>(
+        message: &'a [u8],
+        signature: &'a Signature<S>,
+        public_key: &'a VerifyingKey<S>,
+    ) -> Result<
+        (
+            Cow<'a, [u8]>,
+            Cow<'a, Signature<S>>,
+            Cow<'a, VerifyingKey<S>>,
+        ),
+        Error,
+    > {
+        let signature = if has_square_y(signature.R()) {
+            Cow::Borrowed(signature)
+        } else {
+            Cow::Owned(Signature::new(-*signature.R(), *signature.z()))
+        };
+        Ok((Cow::Borrowed(message), signature, Cow::Borrowed(public_key)))
+    }
+
+    // Negate this signer's nonces if the group commitment has a non-square y
+    fn compute_signature_share(
+        group_commitment: &GroupCommitment<S>,
+        signer_nonces: &SigningNonces<S>,
+        binding_factor: BindingFactor<S>,
+        lambda_i: Scalar,
+        key_package: &KeyPackage,
+        challenge: Challenge<S>,
+    ) -> SignatureShare<S> {
+        let signer_nonces = if has_square_y(&group_commitment.clone().to_element()) {
+            signer_nonces.clone()
+        } else {
+            SigningNonces::from_nonces(
+                Nonce::from_scalar(-signer_nonces.hiding().to_scalar()),
+                Nonce::from_scalar(-signer_nonces.binding().to_scalar()),
+            )
+        };
+
+        frost_core::round2::compute_signature_share(
+            &signer_nonces,
+            binding_factor,
+            lambda_i,
+            key_package,
+            challenge,
+        )
+    }
+
+    fn verify_share(
+        group_commitment: &GroupCommitment<S>,
+        signature_share: &SignatureShare<S>,
+        identifier: Identifier<S>,
+        group_commitment_share: &GroupCommitmentShare<S>,
+        verifying_share: &frost_core::keys::VerifyingShare<S>,
+        lambda_i: Scalar,
+        challenge: &Challenge<S>,
+    ) -> Result<(), Error> {
+        let group_commitment_share = if has_square_y(&group_commitment.clone().to_element()) {
+            *group_commitment_share
+        } else {
+            GroupCommitmentShare::from_element(-group_commitment_share.to_element())
+        };
+        signature_share.verify(
+            identifier,
+            &group_commitment_share,
+            verifying_share,
+            lambda_i,
+            challenge,
+        )
+    }
+
+    /// R.x || s
+    fn serialize_signature(signature: &Signature<S>) -> Result<Vec<u8>, Error> {
+        let mut bytes = signature.R().to_affine().x().to_vec();
+        bytes.extend_from_slice(&Secp256K1ScalarField::serialize(signature.z()));
+        Ok(bytes)
+    }
+
+    /// Lift R.x to the point with a square y
+    fn deserialize_signature(bytes: &[u8]) -> Result<Signature<S>, Error> {
+        if bytes.len() != 64 {
+            return Err(Error::MalformedSignature);
+        }
+        let mut r_bytes = [0u8; 33];
+        r_bytes[0] = 0x02;
+        r_bytes[1..].copy_from_slice(&bytes[..32]);
+        let r = Secp256K1Group::deserialize(&r_bytes)?;
+        let r = if has_square_y(&r) { r } else { -r };
+
+        let z = Secp256K1ScalarField::deserialize(bytes[32..].try_into().unwrap())?;
+        Ok(Signature::new(r, z))
+    }
+}
+
+/// Verify a BCH Schnorr signature (spec algorithm, independent of FROST)
+///
+/// `public_key` is the 33-byte compressed key, `signature` is R.x || s.
+pub fn verify_signature(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let Ok(encoded) = EncodedPoint::from_bytes(public_key) else {
+        return false;
+    };
+    let Some(point) = Option::<AffinePoint>::from(AffinePoint::from_encoded_point(&encoded)) else {
+        return false;
+    };
+    let Ok(compressed) = <[u8; 33]>::try_from(point.to_encoded_point(true).as_bytes()) else {
+        return false;
+    };
+    if signature.len() != 64 {
+        return false;
+    }
+
+    // r must be a field element and s a scalar, both below their moduli
+    if bool::from(FieldElement::from_bytes(FieldBytes::from_slice(&signature[..32])).is_none()) {
+        return false;
+    }
+    let Some(s) =
+        Option::<Scalar>::from(Scalar::from_repr(*FieldBytes::from_slice(&signature[32..])))
+    else {
+        return false;
+    };
+
+    let e = challenge_scalar(&signature[..32], &compressed, message);
+    let r = ProjectivePoint::GENERATOR * s - ProjectivePoint::from(point) * e;
+    r != ProjectivePoint::IDENTITY && has_square_y(&r) && r.to_affine().x()[..] == signature[..32]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frost_core::{SigningKey, SigningPackage};
+
+    fn sign_with_shares(
+        key_packages: &[KeyPackage],
+        public_key_package: &frost_core::keys::PublicKeyPackage<S>,
+        message: &[u8],
+    ) -> Signature<S> {
+        use std::collections::BTreeMap;
+
+        let mut rng = rand::thread_rng();
+        let mut nonces = BTreeMap::new();
+        let mut commitments = BTreeMap::new();
+        for key_package in key_packages {
+            let (n, c) = frost_core::round1::commit(key_package.signing_share(), &mut rng);
+            nonces.insert(*key_package.identifier(), n);
+            commitments.insert(*key_package.identifier(), c);
+        }
+
+        let signing_package = SigningPackage::new(commitments, message);
+        let shares = key_packages
+            .iter()
+            .map(|key_package| {
+                let id = *key_package.identifier();
+                let share =
+                    frost_core::round2::sign(&signing_package, &nonces[&id], key_package).unwrap();
+                (id, share)
+            })
+            .collect();
+
+        frost_core::aggregate(&signing_package, &shares, public_key_package).unwrap()
+    }
+
+    #[test]
+    fn test_spec_vector() {
+        // 2019-05-15 Schnorr upgrade spec, test vector 1 (private key 1, zero message)
+        let public_key =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        let signature = hex::decode(
+            "787a848e71043d280c50470e8e1532b2dd5d20ee912a45dbdd2bd1dfbf187ef6\
+             7031a98831859dc34dffeedda86831842ccd0079e1f92af177f7f22cc1dced05",
+        )
+        .unwrap();
+        assert!(verify_signature(&public_key, &[0u8; 32], &signature));
+        assert!(!verify_signature(&public_key, &[1u8; 32], &signature));
+    }
+
+    #[test]
+    fn test_threshold_signatures_verify_as_bch_schnorr() {
+        let mut rng = rand::thread_rng();
+        let (shares, public_key_package) = frost_core::keys::generate_with_dealer::<S, _>(
+            3,
+            2,
+            frost_core::keys::IdentifierList::Default,
+            &mut rng,
+        )
+        .unwrap();
+        let key_packages: Vec<KeyPackage> = shares
+            .into_values()
+            .take(2)
+            .map(|share| share.try_into().unwrap())
+            .collect();
+        let public_key = public_key_package.verifying_key().serialize().unwrap();
+        assert_eq!(public_key.len(), 33);
+
+        // Enough rounds to hit both square and non-square group commitments
+        for i in 0..16u8 {
+            let message = Sha256::digest([i]);
+            let signature = sign_with_shares(&key_packages, &public_key_package, &message);
+            let bytes = signature.serialize().unwrap();
+            assert_eq!(bytes.len(), 64);
+            assert!(verify_signature(&public_key, &message, &bytes));
+            assert!(!verify_signature(&public_key, &[0u8; 32], &bytes));
+
+            let parsed = Signature::<S>::deserialize(&bytes).unwrap();
+            assert!(public_key_package
+                .verifying_key()
+                .verify(&message, &parsed)
+                .is_ok());
+        }
+
+        let signing_key = SigningKey::<S>::new(&mut rng);
+        let verifying_key = VerifyingKey::from(&signing_key);
+        let signature = signing_key.sign(&mut rng, b"single party");
+        assert!(verify_signature(
+            &verifying_key.serialize().unwrap(),
+            b"single party",
+            &signature.serialize().unwrap()
+        ));
+    }
+}
@@ -1,9 +1,10 @@
-/// Ed25519 curve type information for storage routing
-use super::CurveOperations;
+/// Ed25519 ciphersuite for Solana, Sui, Aptos, Cardano, Stellar and Algorand
+use super::{CurveOperations, CurveType};
 
 pub struct Ed25519Operations;
 
 impl CurveOperations for Ed25519Operations {
-    type KeyPackage = frost_ed25519::keys::KeyPackage;
-    type PublicKeyPackage = frost_ed25519::keys::PublicKeyPackage;
+    type Suite = frost_ed25519::Ed25519Sha512;
+    const CURVE: CurveType = CurveType::Ed25519;
+    const RNG_PREFIX: &'static str = "ed25519";
 }
@@ -1,10 +1,11 @@
 /// Curve abstraction for multi-chain FROST signing
 ///
-/// This module provides minimal type information for curve-specific storage routing.
-/// The actual FROST operations are implemented directly in the node API for clarity.
-use serde::{de::DeserializeOwned, Serialize};
-use std::fmt::Debug;
+/// Every curve a signer node serves is a `CurveOperations` impl over a
+/// `frost_core::Ciphersuite`. The node API implements each protocol step once,
+/// generically, and `with_curve_operations!` routes a `CurveType` to its impl.
+use frost_core::{Ciphersuite, Signature};
 
+pub mod bch;
 pub mod ed25519;
 pub mod secp256k1;
 pub mod secp256k1_ecdsa;
@@ -14,11 +15,167 @@ pub enum CurveType {
     Secp256k1Taproot, // Schnorr signatures for Bitcoin Taproot
     Secp256k1Ecdsa,   // ECDSA signatures for Ethereum/EVM
     Ed25519,          // Ed25519 signatures for Solana
+    Secp256k1Bch,     // BCH Schnorr signatures for Bitcoin Cash
 }
 
-/// Minimal trait for curve type information
-/// Only defines associated types needed for storage - no methods!
-pub trait CurveOperations: Send + Sync {
-    type KeyPackage: Serialize + DeserializeOwned + Debug + Clone;
-    type PublicKeyPackage: Serialize + DeserializeOwned + Debug + Clone;
+impl CurveType {
+    /// Every built-in curve (default for `[node] curves`)
+    pub const ALL: [CurveType; 4] = [
+        CurveType::Secp256k1Taproot,
+        CurveType::Secp256k1Ecdsa,
+        CurveType::Ed25519,
+        CurveType::Secp256k1Bch,
+    ];
+
+    /// Curve name as used in node endpoints (`/api/dkg/{curve}/round1`, ...)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CurveType::Secp256k1Taproot => "secp256k1-tr",
+            CurveType::Secp256k1Ecdsa => "secp256k1",
+            CurveType::Ed25519 => "ed25519",
+            CurveType::Secp256k1Bch => "secp256k1-bch",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|curve| curve.as_str() == name)
+    }
+}
+
+/// A FROST ciphersuite served by signer nodes
+///
+/// DKG, signing and aggregation are implemented once over `Suite`; an impl
+/// only supplies what differs between curves.
+pub trait CurveOperations: Send + Sync + 'static {
+    type Suite: Ciphersuite;
+
+    /// Curve this ciphersuite is served as (endpoint name, storage column families)
+    const CURVE: CurveType;
+
+    /// Seed prefix for the deterministic DKG RNG, so each curve gets independent keys
+    const RNG_PREFIX: &'static str;
+
+    /// Signature bytes returned by `/api/frost/{curve}/aggregate`
+    fn signature_bytes(
+        signature: &Signature<Self::Suite>,
+    ) -> Result<Vec<u8>, frost_core::Error<Self::Suite>> {
+        signature.serialize()
+    }
+}
+
+pub type KeyPackage<C> = frost_core::keys::KeyPackage<<C as CurveOperations>::Suite>;
+pub type PublicKeyPackage<C> = frost_core::keys::PublicKeyPackage<<C as CurveOperations>::Suite>;
+
+/// Routing table from `CurveType` to its `CurveOperations` impl
+///
+/// Binds `$C` to the curve's impl and evaluates `$body`, e.g.
+/// `with_curve_operations!(curve, C => self.dkg_round1::<C>(req).await)`.
+macro_rules! with_curve_operations {
+    ($curve:expr, $C:ident => $body:expr) => {
+        match $curve {
+            $crate::curves::CurveType::Secp256k1Taproot => {
+                type $C = $crate::curves::secp256k1::Secp256k1Operations;
+                $body
+            }
+            $crate::curves::CurveType::Secp256k1Ecdsa => {
+                type $C = $crate::curves::secp256k1_ecdsa::Secp256k1EcdsaOperations;
+                $body
+            }
+            $crate::curves::CurveType::Ed25519 => {
+                type $C = $crate::curves::ed25519::Ed25519Operations;
+                $body
+            }
+            $crate::curves::CurveType::Secp256k1Bch => {
+                type $C = $crate::curves::bch::BchOperations;
+                $body
+            }
+        }
+    };
+}
+pub(crate) use with_curve_operations;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frost_core::keys::dkg;
+    use frost_core::Identifier;
+    use std::collections::BTreeMap;
+
+    /// 2-of-3 DKG, signing and aggregation over a ciphersuite, as the node runs them
+    fn dkg_and_sign<C: CurveOperations>() -> Vec<u8> {
+        let mut rng = rand::thread_rng();
+        let ids: Vec<Identifier<C::Suite>> = (1..=3u16).map(|i| i.try_into().unwrap()).collect();
+
+        let round1: BTreeMap<_, _> = ids
+            .iter()
+            .map(|id| (*id, dkg::part1(*id, 3, 2, &mut rng).unwrap()))
+            .collect();
+        let others = |id| {
+            round1
+                .iter()
+                .filter(|(other, _)| **other != id)
+                .map(|(other, (_, package))| (*other, package.clone()))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let round2: BTreeMap<_, _> = ids
+            .iter()
+            .map(|id| (*id, dkg::part2(round1[id].0.clone(), &others(*id)).unwrap()))
+            .collect();
+
+        let mut key_packages = BTreeMap::new();
+        let mut pubkey_package = None;
+        for id in &ids {
+            let received = round2
+                .iter()
+                .filter(|(sender, _)| *sender != id)
+                .map(|(sender, (_, packages))| (*sender, packages[id].clone()))
+                .collect();
+            let (key_package, public) = dkg::part3(&round2[id].0, &others(*id), &received).unwrap();
+            key_packages.insert(*id, key_package);
+            pubkey_package = Some(public);
+        }
+        let pubkey_package = pubkey_package.unwrap();
+
+        let message = b"frost-custody";
+        let signers = &ids[1..];
+        let mut nonces = BTreeMap::new();
+        let mut commitments = BTreeMap::new();
+        for id in signers {
+            let (n, c) = frost_core::round1::commit(key_packages[id].signing_share(), &mut rng);
+            nonces.insert(*id, n);
+            commitments.insert(*id, c);
+        }
+        let signing_package = frost_core::SigningPackage::new(commitments, message);
+        let shares: BTreeMap<_, _> = signers
+            .iter()
+            .map(|id| {
+                let share =
+                    frost_core::round2::sign(&signing_package, &nonces[id], &key_packages[id]);
+                (*id, share.unwrap())
+            })
+            .collect();
+
+        let signature = frost_core::aggregate(&signing_package, &shares, &pubkey_package).unwrap();
+        pubkey_package
+            .verifying_key()
+            .verify(message, &signature)
+            .unwrap();
+        C::signature_bytes(&signature).unwrap()
+    }
+
+    #[test]
+    fn test_every_curve_signs_through_routing_table() {
+        for curve in CurveType::ALL {
+            assert_eq!(CurveType::from_name(curve.as_str()), Some(curve));
+
+            let (served, signature) =
+                with_curve_operations!(curve, C => (C::CURVE, dkg_and_sign::<C>()));
+            assert_eq!(served, curve);
+            let expected_len = match curve {
+                CurveType::Secp256k1Ecdsa => 65, // r || s || v
+                _ => 64,
+            };
+            assert_eq!(signature.len(), expected_len, "{}", curve.as_str());
+        }
+    }
 }
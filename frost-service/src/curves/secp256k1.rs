@@ -1,9 +1,10 @@
-/// Secp256k1-tr (Taproot) curve type information for storage routing
-use super::CurveOperations;
+/// Secp256k1-tr (Taproot/BIP340 Schnorr) ciphersuite for Bitcoin, Litecoin and Nostr
+use super::{CurveOperations, CurveType};
 
 pub struct Secp256k1Operations;
 
 impl CurveOperations for Secp256k1Operations {
-    type KeyPackage = frost_secp256k1_tr::keys::KeyPackage;
-    type PublicKeyPackage = frost_secp256k1_tr::keys::PublicKeyPackage;
+    type Suite = frost_secp256k1_tr::Secp256K1Sha256TR;
+    const CURVE: CurveType = CurveType::Secp256k1Taproot;
+    const RNG_PREFIX: &'static str = "";
 }
@@ -1,9 +1,24 @@
-/// Secp256k1 ECDSA curve type information for storage routing  
-use super::CurveOperations;
+/// Secp256k1 ciphersuite for Ethereum/EVM
+use frost_secp256k1::{Error, Secp256K1Sha256, Signature};
+
+use super::{CurveOperations, CurveType};
 
 pub struct Secp256k1EcdsaOperations;
 
 impl CurveOperations for Secp256k1EcdsaOperations {
-    type KeyPackage = frost_secp256k1::keys::KeyPackage;
-    type PublicKeyPackage = frost_secp256k1::keys::PublicKeyPackage;
+    type Suite = Secp256K1Sha256;
+    const CURVE: CurveType = CurveType::Secp256k1Ecdsa;
+    const RNG_PREFIX: &'static str = "ecdsa";
+
+    /// Ethereum layout `r || s || v`: the FROST encoding minus its R parity
+    /// prefix, plus recovery id 0 (only ecrecover() needs the real one)
+    fn signature_bytes(signature: &Signature) -> Result<Vec<u8>, Error> {
+        let serialized = signature.serialize()?;
+        let mut bytes = match serialized.first() {
+            Some(0x02 | 0x03) if serialized.len() == 65 => serialized[1..].to_vec(),
+            _ => serialized,
+        };
+        bytes.push(0);
+        Ok(bytes)
+    }
 }
@@ -2,6 +2,8 @@ use anyhow::Result;
 use std::env;
 
 mod address_aggregator;
+mod audit;
+mod chains;
 mod config;
 mod curves;
 mod node;
@@ -24,13 +26,21 @@ async fn main() -> Result<()> {
     let config = config::ConfigFile::load(&config_path)?;
     config.validate()?;
 
+    // Every role keeps its own tamper-evident audit log
+    let audit_source = match &config.node {
+        Some(node) if config.server.role == "node" => format!("node-{}", node.node_index),
+        _ => format!("{}-aggregator", config.server.role),
+    };
+    let audit_log = std::sync::Arc::new(audit::AuditLog::open(&config.audit.path, audit_source)?);
+    tracing::info!("✅ Audit log opened at {}", audit_log.path().display());
+
     // Dispatch based on role
     match config.server.role.as_str() {
         "node" => {
             tracing::info!("Starting FROST Service in NODE mode");
 
             let node_config = config.node.expect("Node config validated");
-            node::run(config.server, node_config).await
+            node::run(config.server, node_config, audit_log).await
         }
         "address" => {
             tracing::info!("Starting ADDRESS AGGREGATOR mode (DKG orchestration)");
@@ -40,13 +50,13 @@ async fn main() -> Result<()> {
             }
 
             let agg_config = config.aggregator.expect("Aggregator config validated");
-            address_aggregator::run(config.server, agg_config, config.network).await
+            address_aggregator::run(config.server, agg_config, config.network, audit_log).await
         }
         "signer" => {
             tracing::info!("Starting SIGNING AGGREGATOR mode (FROST signing orchestration)");
 
             let agg_config = config.aggregator.expect("Aggregator config validated");
-            signing_aggregator::run(config.server, agg_config).await
+            signing_aggregator::run(config.server, agg_config, config.network, audit_log).await
         }
         role => {
             anyhow::bail!("Unknown role: {}", role)
@@ -0,0 +1,563 @@
+//! Encrypted share backups
+//!
+//! A backup archive holds a node's key and pubkey packages, decrypted from
+//! storage, sealed to offline recovery keys so shares survive the loss of the
+//! node's HSM:
+//! - the payload is AES-256-GCM encrypted under a random data key, with the
+//!   archive header as associated data, so any edit fails restore
+//! - the data key is split k-of-m (Shamir over the secp256k1 scalar field) and
+//!   each share is wrapped to one recovery public key (ECDH + AES-256-GCM)
+//! - with threshold 1, every recovery key alone opens the archive
+//!
+//! Exports read from a storage snapshot (a RocksDB checkpoint on the default
+//! backend), so a node can be backed up online.
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{Context, Result};
+use base64::Engine;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::{Field, PrimeField};
+use k256::{FieldBytes, ProjectivePoint, PublicKey, Scalar, SecretKey};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::key_provider::MasterKeyProvider;
+use super::lifecycle::{KeyState, KeyStateRecord};
+use super::multi_storage::{verify_packages, KeyMetadata, MultiCurveStorage};
+use super::share_store::WriteBatch;
+use crate::curves::{
+    with_curve_operations, CurveOperations, CurveType, KeyPackage, PublicKeyPackage,
+};
+
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Sealed backup, serialized as JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupArchive {
+    #[serde(flatten)]
+    pub header: ArchiveHeader,
+    pub nonce: String,      // hex, AES-256-GCM nonce of the payload
+    pub ciphertext: String, // base64, encrypted `Vec<BackupEntry>`
+}
+
+/// Everything outside the ciphertext, authenticated as associated data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveHeader {
+    pub version: u32,
+    pub node_index: u16,
+    pub created_at: u64,
+    pub threshold: usize,
+    pub ephemeral_key: String, // compressed secp256k1 key (hex) the shares are wrapped with
+    pub recipients: Vec<RecipientShare>,
+}
+
+/// One recovery key's share of the data key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipientShare {
+    pub public_key: String, // compressed secp256k1 key (hex)
+    pub index: u16,         // Shamir x-coordinate
+    pub nonce: String,
+    pub wrapped_share: String,
+}
+
+/// Plaintext packages for one curve and passphrase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupEntry {
+    pub curve: String,
+    pub passphrase: String,
+    pub key_package: serde_json::Value,
+    pub pubkey_package: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<KeyMetadata>,
+    /// Frozen or retired keys come back in the same state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<KeyStateRecord>,
+}
+
+/// Parse a compressed or uncompressed secp256k1 recovery public key (hex)
+pub fn parse_recovery_key(key_hex: &str) -> Result<PublicKey> {
+    let bytes = hex::decode(key_hex).context("Recovery key is not hex")?;
+    PublicKey::from_sec1_bytes(&bytes).context("Invalid secp256k1 recovery key")
+}
+
+/// Parse a 32-byte secp256k1 recovery secret key (hex)
+pub fn parse_recovery_secret(secret_hex: &str) -> Result<SecretKey> {
+    let bytes = hex::decode(secret_hex).context("Recovery secret is not hex")?;
+    SecretKey::from_slice(&bytes).context("Invalid secp256k1 recovery secret")
+}
+
+/// sha256 of the archive as exported, for out-of-band comparison on restore
+pub fn archive_digest(archive_json: &str) -> String {
+    hex::encode(Sha256::digest(archive_json.as_bytes()))
+}
+
+/// Encrypt `entries` to `recovery_keys`, any `threshold` of which can open it
+pub fn seal(
+    node_index: u16,
+    entries: &[BackupEntry],
+    recovery_keys: &[PublicKey],
+    threshold: usize,
+) -> Result<BackupArchive> {
+    anyhow::ensure!(!recovery_keys.is_empty(), "No recovery keys configured");
+    anyhow::ensure!(
+        threshold >= 1 && threshold <= recovery_keys.len(),
+        "Recovery threshold must be between 1 and {}",
+        recovery_keys.len()
+    );
+
+    let mut rng = rand::thread_rng();
+    let data_key = Scalar::random(&mut rng);
+    let ephemeral = SecretKey::random(&mut rng);
+    let ephemeral_public = ephemeral.public_key();
+
+    let recipients = split_secret(data_key, threshold, recovery_keys.len())
+        .into_iter()
+        .zip(recovery_keys)
+        .map(|((index, share), recipient)| {
+            let shared = recipient.to_projective() * *ephemeral.to_nonzero_scalar();
+            let kek = wrapping_key(shared, &ephemeral_public, recipient);
+            let (nonce, wrapped) = aead_seal(&kek, &share.to_bytes(), b"")?;
+            Ok(RecipientShare {
+                public_key: encode_key(recipient),
+                index,
+                nonce: hex::encode(nonce),
+                wrapped_share: hex::encode(wrapped),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let header = ArchiveHeader {
+        version: ARCHIVE_VERSION,
+        node_index,
+        created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        threshold,
+        ephemeral_key: encode_key(&ephemeral_public),
+        recipients,
+    };
+
+    let payload = serde_json::to_vec(entries).context("Failed to serialize backup entries")?;
+    let aad = serde_json::to_vec(&header)?;
+    let (nonce, ciphertext) = aead_seal(&payload_key(&data_key), &payload, &aad)?;
+
+    Ok(BackupArchive {
+        header,
+        nonce: hex::encode(nonce),
+        ciphertext: base64::engine::general_purpose::STANDARD.encode(ciphertext),
+    })
+}
+
+/// Recover the data key from `recovery_secrets` and decrypt the entries
+pub fn open(archive: &BackupArchive, recovery_secrets: &[SecretKey]) -> Result<Vec<BackupEntry>> {
+    let header = &archive.header;
+    anyhow::ensure!(
+        header.version == ARCHIVE_VERSION,
+        "Unsupported backup archive version {}",
+        header.version
+    );
+    anyhow::ensure!(header.threshold >= 1, "Invalid archive threshold");
+
+    let ephemeral = parse_recovery_key(&header.ephemeral_key)?;
+    let mut shares: Vec<(u16, Scalar)> = Vec::new();
+    for secret in recovery_secrets {
+        let public = secret.public_key();
+        let encoded = encode_key(&public);
+        let recipient = header
+            .recipients
+            .iter()
+            .find(|r| r.public_key == encoded)
+            .with_context(|| {
+                format!(
+                    "Recovery key {} is not a recipient of this archive",
+                    encoded
+                )
+            })?;
+        if shares.iter().any(|(index, _)| *index == recipient.index) {
+            continue;
+        }
+
+        let shared = ephemeral.to_projective() * *secret.to_nonzero_scalar();
+        let kek = wrapping_key(shared, &ephemeral, &public);
+        let wrapped = hex::decode(&recipient.wrapped_share).context("Wrapped share is not hex")?;
+        let bytes = aead_open(&kek, &recipient.nonce, &wrapped, b"")
+            .with_context(|| format!("Failed to unwrap the data key share for {}", encoded))?;
+        anyhow::ensure!(bytes.len() == 32, "Invalid data key share");
+        let share = Option::<Scalar>::from(Scalar::from_repr(FieldBytes::clone_from_slice(&bytes)))
+            .context("Invalid data key share")?;
+        shares.push((recipient.index, share));
+    }
+    anyhow::ensure!(
+        shares.len() >= header.threshold,
+        "Archive needs {} recovery keys, got {}",
+        header.threshold,
+        shares.len()
+    );
+
+    let data_key = combine_shares(&shares[..header.threshold])?;
+    let ciphertext = base64::engine::general_purpose::STANDARD
+        .decode(&archive.ciphertext)
+        .context("Archive ciphertext is not base64")?;
+    let aad = serde_json::to_vec(header)?;
+    let payload = aead_open(&payload_key(&data_key), &archive.nonce, &ciphertext, &aad)
+        .context("Archive integrity check failed")?;
+
+    serde_json::from_slice(&payload).context("Failed to parse backup entries")
+}
+
+/// Plaintext packages for `curves`, limited to `passphrases` when given
+///
+/// Pass a snapshot (`MultiCurveStorage::snapshot`) for a consistent export.
+pub async fn collect_entries(
+    storage: &MultiCurveStorage,
+    key_provider: &dyn MasterKeyProvider,
+    curves: &[CurveType],
+    passphrases: Option<&[String]>,
+) -> Result<Vec<BackupEntry>> {
+    let mut entries = Vec::new();
+    for &curve in curves {
+        for passphrase in storage.passphrases(curve, key_provider).await? {
+            if passphrases.is_some_and(|wanted| !wanted.contains(&passphrase)) {
+                continue;
+            }
+            let entry = with_curve_operations!(curve, C => {
+                export_entry::<C>(storage, key_provider, passphrase).await
+            })?;
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+async fn export_entry<C: CurveOperations>(
+    storage: &MultiCurveStorage,
+    key_provider: &dyn MasterKeyProvider,
+    passphrase: String,
+) -> Result<BackupEntry> {
+    let curve = C::CURVE.as_str();
+    let key_package = storage
+        .get_key_package::<C>(&passphrase, key_provider)
+        .await?
+        .with_context(|| format!("{} key package disappeared during export", curve))?;
+    let pubkey_package = storage
+        .get_pubkey_package::<C>(&passphrase, key_provider)
+        .await?
+        .with_context(|| format!("{} key package stored without its pubkey package", curve))?;
+
+    let metadata = storage
+        .get_metadata(C::CURVE, &passphrase, key_provider)
+        .await?;
+    let state = storage
+        .get_state(C::CURVE, &passphrase, key_provider)
+        .await?;
+
+    Ok(BackupEntry {
+        curve: curve.to_string(),
+        passphrase,
+        key_package: serde_json::to_value(&key_package)?,
+        pubkey_package: serde_json::to_value(&pubkey_package)?,
+        metadata,
+        state: (state.state != KeyState::Active).then_some(state),
+    })
+}
+
+/// Verify every entry and re-encrypt it with this node's key provider
+///
+/// Returns one batch for `MultiCurveStorage::write_batch`, so a restore is
+/// all or nothing. Refuses shares of another node, curves this node doesn't
+/// serve and (unless `overwrite`) passphrases that already have shares.
+pub async fn prepare_restore(
+    storage: &MultiCurveStorage,
+    key_provider: &dyn MasterKeyProvider,
+    entries: &[BackupEntry],
+    node_index: u16,
+    curves: &[CurveType],
+    overwrite: bool,
+) -> Result<WriteBatch> {
+    let mut batch = WriteBatch::default();
+    for entry in entries {
+        let curve = CurveType::from_name(&entry.curve)
+            .filter(|curve| curves.contains(curve))
+            .with_context(|| {
+                format!("Archive holds shares for unsupported curve {}", entry.curve)
+            })?;
+        // A tombstone is final, even over an older backup
+        if storage
+            .get_state(curve, &entry.passphrase, key_provider)
+            .await?
+            .state
+            == KeyState::Destroyed
+        {
+            anyhow::bail!(
+                "{} shares for a passphrase in the archive were destroyed on this node",
+                entry.curve
+            );
+        }
+        if !overwrite
+            && storage
+                .has_passphrase(curve, &entry.passphrase, key_provider)
+                .await?
+        {
+            anyhow::bail!(
+                "{} shares for a passphrase in the archive already exist (set overwrite to replace them)",
+                entry.curve
+            );
+        }
+
+        with_curve_operations!(curve, C => {
+            stage_entry::<C>(storage, key_provider, &mut batch, entry, node_index).await
+        })?;
+    }
+
+    Ok(batch)
+}
+
+async fn stage_entry<C: CurveOperations>(
+    storage: &MultiCurveStorage,
+    key_provider: &dyn MasterKeyProvider,
+    batch: &mut WriteBatch,
+    entry: &BackupEntry,
+    node_index: u16,
+) -> Result<()> {
+    let key_package: KeyPackage<C> = serde_json::from_value(entry.key_package.clone())
+        .with_context(|| format!("Invalid {} key package in archive", entry.curve))?;
+    let pubkey_package: PublicKeyPackage<C> = serde_json::from_value(entry.pubkey_package.clone())
+        .with_context(|| format!("Invalid {} pubkey package in archive", entry.curve))?;
+    verify_packages::<C>(&key_package, &pubkey_package, node_index)
+        .with_context(|| format!("Rejected {} shares in archive", entry.curve))?;
+
+    storage
+        .stage_packages::<C>(
+            batch,
+            &entry.passphrase,
+            &key_package,
+            &pubkey_package,
+            entry.metadata.as_ref(),
+            key_provider,
+        )
+        .await?;
+    if let Some(state) = &entry.state {
+        anyhow::ensure!(
+            state.state != KeyState::Destroyed,
+            "Archive holds a destroyed {} key",
+            entry.curve
+        );
+        storage
+            .stage_state(batch, C::CURVE, &entry.passphrase, state, key_provider)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Shamir shares `(x, f(x))` for x = 1..=count of a random degree threshold-1 polynomial
+fn split_secret(secret: Scalar, threshold: usize, count: usize) -> Vec<(u16, Scalar)> {
+    let mut rng = rand::thread_rng();
+    let coefficients: Vec<Scalar> = std::iter::once(secret)
+        .chain((1..threshold).map(|_| Scalar::random(&mut rng)))
+        .collect();
+
+    (1..=count as u16)
+        .map(|x| {
+            let x_scalar = Scalar::from(x as u64);
+            let y = coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::ZERO, |acc, c| acc * x_scalar + c);
+            (x, y)
+        })
+        .collect()
+}
+
+/// Lagrange interpolation at x = 0
+fn combine_shares(shares: &[(u16, Scalar)]) -> Result<Scalar> {
+    let mut secret = Scalar::ZERO;
+    for (i, (xi, yi)) in shares.iter().enumerate() {
+        let xi = Scalar::from(*xi as u64);
+        let mut numerator = Scalar::ONE;
+        let mut denominator = Scalar::ONE;
+        for (j, (xj, _)) in shares.iter().enumerate() {
+            if i != j {
+                let xj = Scalar::from(*xj as u64);
+                numerator *= xj;
+                denominator *= xj - xi;
+            }
+        }
+        let inverse = Option::<Scalar>::from(denominator.invert())
+            .context("Duplicate data key share index")?;
+        secret += *yi * numerator * inverse;
+    }
+    Ok(secret)
+}
+
+fn encode_key(key: &PublicKey) -> String {
+    hex::encode(key.to_encoded_point(true).as_bytes())
+}
+
+fn payload_key(data_key: &Scalar) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(b"frost-backup/payload")
+        .chain_update(data_key.to_bytes())
+        .finalize()
+        .into()
+}
+
+fn wrapping_key(shared: ProjectivePoint, ephemeral: &PublicKey, recipient: &PublicKey) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(b"frost-backup/wrap")
+        .chain_update(shared.to_affine().to_encoded_point(true).as_bytes())
+        .chain_update(ephemeral.to_encoded_point(true).as_bytes())
+        .chain_update(recipient.to_encoded_point(true).as_bytes())
+        .finalize()
+        .into()
+}
+
+fn aead_seal(key: &[u8; 32], plaintext: &[u8], aad: &[u8]) -> Result<([u8; 12], Vec<u8>)> {
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = Aes256Gcm::new(key.into())
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|e| anyhow::anyhow!("Encryption failed: {}", e))?;
+    Ok((nonce, ciphertext))
+}
+
+fn aead_open(key: &[u8; 32], nonce_hex: &str, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let nonce = hex::decode(nonce_hex).context("Nonce is not hex")?;
+    anyhow::ensure!(nonce.len() == 12, "Invalid nonce length");
+    Aes256Gcm::new(key.into())
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("authentication failed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::secp256k1::Secp256k1Operations;
+    use crate::node::key_provider::TestKeyProvider;
+    use crate::node::share_store::MemoryStore;
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    fn recovery_keys(count: usize) -> Vec<SecretKey> {
+        (0..count)
+            .map(|_| SecretKey::random(&mut rand::thread_rng()))
+            .collect()
+    }
+
+    #[test]
+    fn test_seal_open_threshold() {
+        let entries = vec![BackupEntry {
+            curve: "secp256k1-tr".to_string(),
+            passphrase: "p".to_string(),
+            key_package: serde_json::json!({"share": 1}),
+            pubkey_package: serde_json::json!({"group": 2}),
+            metadata: None,
+            state: None,
+        }];
+        let secrets = recovery_keys(3);
+        let publics: Vec<_> = secrets.iter().map(SecretKey::public_key).collect();
+
+        let archive = seal(0, &entries, &publics, 2).unwrap();
+        let json = serde_json::to_string(&archive).unwrap();
+        let archive: BackupArchive = serde_json::from_str(&json).unwrap();
+
+        // Any two recovery keys open it, one doesn't
+        let opened = open(&archive, &[secrets[2].clone(), secrets[0].clone()]).unwrap();
+        assert_eq!(opened[0].passphrase, "p");
+        assert!(open(&archive, &secrets[1..2]).is_err());
+        assert!(open(&archive, &recovery_keys(2)).is_err());
+
+        // The header is authenticated with the payload
+        let mut tampered = archive.clone();
+        tampered.header.node_index = 1;
+        assert!(open(&tampered, &secrets).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_export_restore_round_trip() {
+        type C = Secp256k1Operations;
+        let (shares, pubkey_package) =
+            frost_core::keys::generate_with_dealer::<<C as CurveOperations>::Suite, _>(
+                3,
+                2,
+                frost_core::keys::IdentifierList::Default,
+                &mut rand::thread_rng(),
+            )
+            .unwrap();
+        let shares: BTreeMap<_, _> = shares.into_iter().collect();
+        let node0: frost_core::Identifier<_> = 1u16.try_into().unwrap();
+        let key_package = KeyPackage::<C>::try_from(shares[&node0].clone()).unwrap();
+
+        let provider = TestKeyProvider;
+        let source = MultiCurveStorage::new(Arc::new(MemoryStore::new()));
+        let metadata = KeyMetadata::new::<C>(
+            &pubkey_package,
+            "session".to_string(),
+            2,
+            vec![0, 1, 2],
+            0,
+            crate::node::key_provider::KeyDerivation::AesEcb,
+        )
+        .unwrap();
+        source
+            .store_packages::<C>(
+                "wallet",
+                &key_package,
+                &pubkey_package,
+                &metadata,
+                &provider,
+            )
+            .await
+            .unwrap();
+
+        let curves = CurveType::ALL;
+        let entries = collect_entries(&source.snapshot().unwrap(), &provider, &curves, None)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+
+        let secrets = recovery_keys(1);
+        let archive = seal(0, &entries, &[secrets[0].public_key()], 1).unwrap();
+        let restored = open(&archive, &secrets).unwrap();
+
+        // Shares only restore onto the node they belong to
+        let target = MultiCurveStorage::new(Arc::new(MemoryStore::new()));
+        assert!(
+            prepare_restore(&target, &provider, &restored, 1, &curves, false)
+                .await
+                .is_err()
+        );
+        let batch = prepare_restore(&target, &provider, &restored, 0, &curves, false)
+            .await
+            .unwrap();
+        target.write_batch(batch).unwrap();
+        let round_trip = target
+            .get_key_package::<C>("wallet", &provider)
+            .await
+            .unwrap();
+        assert_eq!(round_trip, Some(key_package));
+        let restored_metadata = target
+            .get_metadata(CurveType::Secp256k1Taproot, "wallet", &provider)
+            .await
+            .unwrap();
+        assert_eq!(restored_metadata, Some(metadata));
+
+        // Existing shares aren't replaced without overwrite
+        assert!(
+            prepare_restore(&target, &provider, &restored, 0, &curves, false)
+                .await
+                .is_err()
+        );
+    }
+}
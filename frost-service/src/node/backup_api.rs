@@ -0,0 +1,59 @@
+use poem_openapi::payload::Json;
+use poem_openapi::{ApiResponse, Object};
+
+use super::dkg_api::ErrorResponse;
+
+#[derive(Debug, Object)]
+pub struct BackupExportRequest {
+    /// Curves to export (default: every curve this node serves)
+    pub curves: Option<Vec<String>>,
+    /// Passphrases to export (default: all)
+    pub passphrases: Option<Vec<String>>,
+}
+
+#[derive(Debug, Object)]
+pub struct BackupExportResponse {
+    /// Sealed archive (JSON), encrypted to `[node.backup] recovery_keys`
+    pub archive: String,
+    /// sha256 of `archive`, recorded in the audit log
+    pub sha256: String,
+    pub entries: u32,
+    pub threshold: u32,
+}
+
+#[derive(Debug, Object)]
+pub struct BackupRestoreRequest {
+    pub archive: String,
+    /// Recovery secret keys (hex), at least the archive's threshold
+    pub recovery_secrets: Vec<String>,
+    /// Refuse the archive unless its sha256 matches (from export time)
+    pub expected_sha256: Option<String>,
+    /// Replace shares that already exist for a passphrase
+    #[oai(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Object)]
+pub struct BackupRestoreResponse {
+    pub restored: u32,
+}
+
+#[derive(ApiResponse)]
+pub enum BackupExportResult {
+    #[oai(status = 200)]
+    Ok(Json<BackupExportResponse>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+pub enum BackupRestoreResult {
+    #[oai(status = 200)]
+    Ok(Json<BackupRestoreResponse>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
@@ -1,23 +1,35 @@
 //! Unified Signer Node API (Chain-Agnostic)
 //!
 //! This API provides all signer node functionality:
-//! - Public key queries (secp256k1, Ed25519)
+//! - Public key queries
 //! - DKG protocol (creates threshold keys)
 //! - FROST signing protocol (uses threshold keys)
 //!
-//! All operations are curve-based. Chain logic lives in aggregator.
-
-use poem_openapi::param::Query;
-use poem_openapi::payload::Json;
+//! All operations are curve-based: every endpoint takes a `{curve}` path
+//! segment and runs the same generic step for that curve's ciphersuite.
+//! Chain logic lives in aggregator.
+
+use frost_core::keys::dkg;
+use frost_core::{Ciphersuite, Identifier, SigningPackage};
+use poem_openapi::param::{Path, Query};
+use poem_openapi::payload::{Json, PlainText};
 use poem_openapi::{ApiResponse, Object, OpenApi};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use crate::audit::{AuditEvent, AuditKind, AuditLog};
 use crate::config::NodeConfig;
-use crate::curves::ed25519::Ed25519Operations;
-use crate::curves::secp256k1::Secp256k1Operations;
-use crate::curves::CurveType;
-use crate::node::key_provider::MasterKeyProvider;
-use crate::node::multi_storage::{CurveStorage, MultiCurveStorage};
+use crate::curves::{with_curve_operations, CurveOperations, CurveType};
+use crate::node::backup::{self, BackupArchive};
+use crate::node::backup_api::*;
+use crate::node::derivation;
+use crate::node::inventory_api::*;
+use crate::node::key_provider::{KeyDerivation, MasterKeyProvider};
+use crate::node::lifecycle::{self, KeyState, KeyStateRecord};
+use crate::node::lifecycle_api::*;
+use crate::node::multi_storage::{CurveStorage, KeyMetadata, MultiCurveStorage};
+use crate::node::scrub::{ScrubReport, Scrubber};
 use crate::node::unlock_api::*;
 
 pub struct UnifiedApi {
@@ -25,11 +37,17 @@ pub struct UnifiedApi {
     pub storage: Arc<MultiCurveStorage>,
     pub dkg_state: Arc<crate::node::dkg_state::DkgState>,
     pub key_provider: Arc<dyn MasterKeyProvider>,
+    pub audit: Arc<AuditLog>,
+    pub scrubber: Arc<Scrubber>,
 }
 
 #[derive(Debug, Object)]
 pub struct DkgRound1Request {
     pub passphrase: String,
+    /// Key derivation scheme ("aes-ecb", "hmac-sha256", "sp800-108"); only
+    /// needed to recover a key whose records are gone and whose scheme isn't
+    /// this node's current one
+    pub derivation: Option<String>,
 }
 
 #[derive(Debug, Object)]
@@ -84,6 +102,10 @@ pub struct ErrorResponse {
 pub enum DkgRound1Result {
     #[oai(status = 200)]
     Ok(Json<DkgRound1Response>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
     #[oai(status = 500)]
     InternalError(Json<ErrorResponse>),
 }
@@ -92,6 +114,8 @@ pub enum DkgRound1Result {
 pub enum DkgRound2Result {
     #[oai(status = 200)]
     Ok(Json<DkgRound2Response>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
     #[oai(status = 500)]
     InternalError(Json<ErrorResponse>),
 }
@@ -102,6 +126,8 @@ pub enum DkgFinalizeResult {
     Ok(Json<DkgFinalizeResponse>),
     #[oai(status = 400)]
     BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
     #[oai(status = 500)]
     InternalError(Json<ErrorResponse>),
 }
@@ -115,7 +141,27 @@ impl UnifiedApi {
     /// Unlock HSM with PIN
     #[oai(path = "/api/hsm/unlock", method = "post")]
     async fn unlock_hsm(&self, req: Json<UnlockRequest>) -> UnlockResult {
-        match self.key_provider.unlock(&req.pin) {
+        let result = self.key_provider.unlock(&req.pin);
+        self.audit.record(match &result {
+            Ok(true) => AuditEvent::new(AuditKind::HsmUnlock, "success"),
+            Ok(false) => AuditEvent::new(AuditKind::HsmUnlock, "already_unlocked"),
+            Err(e) => AuditEvent::new(AuditKind::HsmUnlock, "failure").detail(e.to_string()),
+        });
+
+        // First unlock after an upgrade: run migrations that need the key provider
+        if let Ok(true) = result {
+            if let Err(e) = crate::node::schema::migrate(
+                &self.storage,
+                self.key_provider.as_ref(),
+                &self.config.migration_options(),
+            )
+            .await
+            {
+                tracing::error!("Storage schema migration failed: {}", e);
+            }
+        }
+
+        match result {
             Ok(true) => UnlockResult::Ok(Json(UnlockResponse {
                 success: true,
                 message: "HSM unlocked successfully".to_string(),
@@ -136,6 +182,8 @@ impl UnifiedApi {
     #[oai(path = "/api/hsm/lock", method = "post")]
     async fn lock_hsm(&self) -> UnlockResult {
         self.key_provider.lock();
+        self.audit
+            .record(AuditEvent::new(AuditKind::HsmLock, "success"));
 
         UnlockResult::Ok(Json(UnlockResponse {
             success: true,
@@ -156,336 +204,575 @@ impl UnifiedApi {
     // Public Key Queries
     // ========================================================================
 
-    /// Get secp256k1-tr (Taproot) public key
-    #[oai(path = "/api/curve/secp256k1-tr/pubkey", method = "get")]
-    async fn get_secp256k1_tr_pubkey(
+    /// Get a curve's group public key (e.g. `/api/curve/secp256k1-tr/pubkey`)
+    #[oai(path = "/api/curve/:curve/pubkey", method = "get")]
+    async fn get_pubkey(
         &self,
+        Path(curve): Path<String>,
         Query(passphrase): Query<String>,
     ) -> Result<Json<PublicKeyResponse>, ApiError> {
-        let curve_storage = CurveStorage::<Secp256k1Operations>::new(
-            self.storage.clone(),
-            CurveType::Secp256k1Taproot,
-        );
+        let curve = self.curve(&curve).map_err(ApiError::BadRequest)?;
+        with_curve_operations!(curve, C => self.pubkey_for::<C>(passphrase).await)
+    }
 
-        let pubkey_package = curve_storage
-            .get_pubkey_package(&passphrase, self.key_provider.as_ref())
-            .await
-            .map_err(|e| {
-                ApiError::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
-            })?
-            .ok_or_else(|| {
-                ApiError::BadRequest(Json(ErrorResponse {
-                    error: "Secp256k1 shares not found. Run DKG first.".to_string(),
-                }))
-            })?;
+    // ========================================================================
+    // DKG Protocol
+    // ========================================================================
 
-        let pubkey_bytes = pubkey_package.verifying_key().serialize().map_err(|e| {
-            ApiError::InternalError(Json(ErrorResponse {
-                error: format!("Failed to serialize pubkey: {:?}", e),
-            }))
-        })?;
+    /// DKG Round 1: Generate commitment
+    #[oai(path = "/api/dkg/:curve/round1", method = "post")]
+    async fn dkg_round1(
+        &self,
+        Path(curve): Path<String>,
+        req: Json<DkgRound1Request>,
+    ) -> DkgRound1Result {
+        match self.curve(&curve) {
+            Ok(curve) => with_curve_operations!(curve, C => self.dkg_round1_for::<C>(req.0).await),
+            Err(e) => DkgRound1Result::BadRequest(e),
+        }
+    }
 
-        Ok(Json(PublicKeyResponse {
-            curve: "secp256k1-tr".to_string(),
-            passphrase,
-            public_key: hex::encode(&pubkey_bytes),
-        }))
+    /// DKG Round 2: Process round1 packages and generate round2 packages
+    #[oai(path = "/api/dkg/:curve/round2", method = "post")]
+    async fn dkg_round2(
+        &self,
+        Path(curve): Path<String>,
+        req: Json<DkgRound2Request>,
+    ) -> DkgRound2Result {
+        match self.curve(&curve) {
+            Ok(curve) => with_curve_operations!(curve, C => self.dkg_round2_for::<C>(req.0).await),
+            Err(e) => DkgRound2Result::BadRequest(e),
+        }
+    }
+
+    /// DKG Finalize: Complete key generation and store shares
+    #[oai(path = "/api/dkg/:curve/finalize", method = "post")]
+    async fn dkg_finalize(
+        &self,
+        Path(curve): Path<String>,
+        req: Json<DkgFinalizeRequest>,
+    ) -> DkgFinalizeResult {
+        match self.curve(&curve) {
+            Ok(curve) => {
+                with_curve_operations!(curve, C => self.dkg_finalize_for::<C>(req.0).await)
+            }
+            Err(e) => DkgFinalizeResult::BadRequest(e),
+        }
+    }
+
+    // ========================================================================
+    // FROST Signing
+    // ========================================================================
+
+    /// FROST Round 1: Generate signing commitments
+    #[oai(path = "/api/frost/:curve/round1", method = "post")]
+    async fn frost_round1(
+        &self,
+        Path(curve): Path<String>,
+        req: Json<FrostRound1Request>,
+    ) -> FrostRound1Result {
+        match self.curve(&curve) {
+            Ok(curve) => {
+                with_curve_operations!(curve, C => self.frost_round1_for::<C>(req.0).await)
+            }
+            Err(e) => FrostRound1Result::BadRequest(e),
+        }
+    }
+
+    /// FROST Round 2: Generate signature share
+    #[oai(path = "/api/frost/:curve/round2", method = "post")]
+    async fn frost_round2(
+        &self,
+        Path(curve): Path<String>,
+        req: Json<FrostRound2Request>,
+    ) -> FrostRound2Result {
+        match self.curve(&curve) {
+            Ok(curve) => {
+                with_curve_operations!(curve, C => self.frost_round2_for::<C>(req.0).await)
+            }
+            Err(e) => FrostRound2Result::BadRequest(e),
+        }
+    }
+
+    /// FROST Aggregate: Combine signature shares into final signature
+    #[oai(path = "/api/frost/:curve/aggregate", method = "post")]
+    async fn frost_aggregate(
+        &self,
+        Path(curve): Path<String>,
+        req: Json<FrostAggregateRequest>,
+    ) -> FrostAggregateResult {
+        match self.curve(&curve) {
+            Ok(curve) => {
+                with_curve_operations!(curve, C => self.frost_aggregate_for::<C>(req.0).await)
+            }
+            Err(e) => FrostAggregateResult::BadRequest(e),
+        }
+    }
+
+    // ========================================================================
+    // Key Inventory
+    // ========================================================================
+
+    /// List stored keys for a curve by blinded identifier, with their metadata
+    #[oai(path = "/api/keys/:curve", method = "get")]
+    async fn list_keys(
+        &self,
+        Path(curve): Path<String>,
+        Query(cursor): Query<Option<String>>,
+        Query(limit): Query<Option<u32>>,
+    ) -> KeyListResult {
+        let curve = match self.curve(&curve) {
+            Ok(curve) => curve,
+            Err(e) => return KeyListResult::BadRequest(e),
+        };
+        let limit = limit.unwrap_or(100).clamp(1, 1000) as usize;
+
+        match self.storage.list_keys(curve, cursor.as_deref(), limit) {
+            Ok(keys) => {
+                let next_cursor = match keys.last() {
+                    Some((id, _, _)) if keys.len() == limit => Some(id.clone()),
+                    _ => None,
+                };
+                KeyListResult::Ok(Json(KeyListResponse {
+                    curve: curve.as_str().to_string(),
+                    keys: keys
+                        .into_iter()
+                        .map(|(id, metadata, state)| KeyInventoryEntry {
+                            id,
+                            metadata,
+                            state: state.as_str().to_string(),
+                        })
+                        .collect(),
+                    next_cursor,
+                }))
+            }
+            Err(e) => KeyListResult::InternalError(error_json(format!("Storage error: {}", e))),
+        }
     }
 
-    /// Get secp256k1 (ECDSA) public key for Ethereum/EVM
-    #[oai(path = "/api/curve/secp256k1/pubkey", method = "get")]
-    async fn get_secp256k1_ecdsa_pubkey(
+    /// Lifecycle state of a passphrase's key
+    #[oai(path = "/api/keys/:curve/state", method = "get")]
+    async fn get_key_state(
         &self,
+        Path(curve): Path<String>,
         Query(passphrase): Query<String>,
-    ) -> Result<Json<PublicKeyResponse>, ApiError> {
-        let curve_storage =
-            CurveStorage::<crate::curves::secp256k1_ecdsa::Secp256k1EcdsaOperations>::new(
-                self.storage.clone(),
-                CurveType::Secp256k1Ecdsa,
-            );
+    ) -> KeyStateResult {
+        let curve = match self.curve(&curve) {
+            Ok(curve) => curve,
+            Err(e) => return KeyStateResult::BadRequest(e),
+        };
 
-        let pubkey_package = curve_storage
-            .get_pubkey_package(&passphrase, self.key_provider.as_ref())
+        match self
+            .storage
+            .get_state(curve, &passphrase, self.key_provider.as_ref())
             .await
-            .map_err(|e| {
-                ApiError::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
-            })?
-            .ok_or_else(|| {
-                ApiError::BadRequest(Json(ErrorResponse {
-                    error: "Secp256k1 ECDSA shares not found. Run DKG first.".to_string(),
+        {
+            Ok(record) => KeyStateResult::Ok(Json(KeyStateResponse::new(
+                self.config.node_index,
+                curve.as_str(),
+                record,
+            ))),
+            Err(e) => KeyStateResult::InternalError(error_json(format!("Storage error: {}", e))),
+        }
+    }
+
+    /// Freeze, unfreeze, retire or destroy a passphrase's key
+    ///
+    /// Destroying erases the shares and leaves a tombstone. Unfreezing and
+    /// destroying need `[node.lifecycle]` operator approvals, verified here
+    /// whatever the aggregator checked.
+    #[oai(path = "/api/keys/:curve/state", method = "post")]
+    async fn set_key_state(
+        &self,
+        Path(curve): Path<String>,
+        req: Json<KeyStateRequest>,
+    ) -> KeyStateResult {
+        let curve = match self.curve(&curve) {
+            Ok(curve) => curve,
+            Err(e) => return KeyStateResult::BadRequest(e),
+        };
+        let result = self.change_key_state(curve, req.0).await;
+
+        if let KeyStateResult::BadRequest(Json(error))
+        | KeyStateResult::Conflict(Json(error))
+        | KeyStateResult::InternalError(Json(error)) = &result
+        {
+            self.audit.record(
+                AuditEvent::new(AuditKind::KeyLifecycle, "failure")
+                    .curve(curve.as_str())
+                    .detail(error.error.clone()),
+            );
+        }
+        result
+    }
+
+    /// Key counts, usage and share store size
+    #[oai(path = "/api/storage/stats", method = "get")]
+    async fn storage_stats(&self) -> StorageStatsResult {
+        let stats = self
+            .config
+            .curves()
+            .into_iter()
+            .map(|curve| self.storage.curve_stats(curve))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .and_then(|curves| {
+                let schema_version = self.storage.schema_version()?.map(|v| v.version);
+                Ok((curves, self.storage.size_bytes()?, schema_version))
+            });
+
+        match stats {
+            Ok((curves, size_bytes, schema_version)) => {
+                StorageStatsResult::Ok(Json(StorageStatsResponse {
+                    node_index: self.config.node_index,
+                    backend: self.storage.description(),
+                    size_bytes,
+                    schema_version,
+                    curves,
                 }))
-            })?;
+            }
+            Err(e) => {
+                StorageStatsResult::InternalError(error_json(format!("Storage error: {}", e)))
+            }
+        }
+    }
 
-        let pubkey_bytes = pubkey_package.verifying_key().serialize().map_err(|e| {
-            ApiError::InternalError(Json(ErrorResponse {
-                error: format!("Failed to serialize pubkey: {:?}", e),
-            }))
-        })?;
+    /// Latest storage integrity scrub
+    #[oai(path = "/api/storage/scrub", method = "get")]
+    async fn scrub_report(&self) -> Json<Option<ScrubReport>> {
+        Json(self.scrubber.latest())
+    }
 
-        Ok(Json(PublicKeyResponse {
-            curve: "secp256k1".to_string(),
-            passphrase,
-            public_key: hex::encode(&pubkey_bytes),
-        }))
+    /// Run a storage integrity scrub now
+    #[oai(path = "/api/storage/scrub", method = "post")]
+    async fn run_scrub(&self) -> Json<ScrubReport> {
+        Json(self.scrubber.run().await)
+    }
+
+    /// Prometheus metrics
+    #[oai(path = "/metrics", method = "get")]
+    async fn metrics(&self) -> PlainText<String> {
+        PlainText(self.scrubber.metrics())
+    }
+
+    // ========================================================================
+    // Share Backups
+    // ========================================================================
+
+    /// Export shares into an archive sealed to `[node.backup] recovery_keys`
+    #[oai(path = "/api/backup/export", method = "post")]
+    async fn backup_export(&self, req: Json<BackupExportRequest>) -> BackupExportResult {
+        let curves = match &req.curves {
+            Some(names) => match names.iter().map(|name| self.curve(name)).collect() {
+                Ok(curves) => curves,
+                Err(e) => return BackupExportResult::BadRequest(e),
+            },
+            None => self.config.curves(),
+        };
+
+        match self.export_backup(&curves, req.0.passphrases).await {
+            Ok(response) => {
+                self.audit
+                    .record(
+                        AuditEvent::new(AuditKind::BackupExport, "success").detail(format!(
+                            "{} entries, archive sha256 {}",
+                            response.entries, response.sha256
+                        )),
+                    );
+                BackupExportResult::Ok(Json(response))
+            }
+            Err(e) => {
+                if let BackupExportResult::BadRequest(Json(error))
+                | BackupExportResult::InternalError(Json(error)) = &e
+                {
+                    self.audit.record(
+                        AuditEvent::new(AuditKind::BackupExport, "failure")
+                            .detail(error.error.clone()),
+                    );
+                }
+                e
+            }
+        }
+    }
+
+    /// Verify a backup archive and re-import its shares
+    #[oai(path = "/api/backup/restore", method = "post")]
+    async fn backup_restore(&self, req: Json<BackupRestoreRequest>) -> BackupRestoreResult {
+        let sha256 = backup::archive_digest(&req.archive);
+        match self.restore_backup(req.0, &sha256).await {
+            Ok(restored) => {
+                self.audit.record(
+                    AuditEvent::new(AuditKind::BackupRestore, "success")
+                        .detail(format!("{} entries, archive sha256 {}", restored, sha256)),
+                );
+                BackupRestoreResult::Ok(Json(BackupRestoreResponse { restored }))
+            }
+            Err(e) => {
+                if let BackupRestoreResult::BadRequest(Json(error))
+                | BackupRestoreResult::InternalError(Json(error)) = &e
+                {
+                    self.audit.record(
+                        AuditEvent::new(AuditKind::BackupRestore, "failure")
+                            .detail(format!("{}, archive sha256 {}", error.error, sha256)),
+                    );
+                }
+                e
+            }
+        }
     }
 
-    /// Get Ed25519 public key
-    #[oai(path = "/api/curve/ed25519/pubkey", method = "get")]
-    async fn get_ed25519_pubkey(
+    // ========================================================================
+    // Health Check
+    // ========================================================================
+
+    #[oai(path = "/health", method = "get")]
+    async fn health(&self) -> Json<HealthResponse> {
+        let scrub = self.scrubber.latest();
+        let integrity_issues = scrub
+            .as_ref()
+            .map_or(0, |report| report.issues.len() as u64);
+        let last_scrub_failed = scrub.as_ref().is_some_and(|report| report.error.is_some());
+        Json(HealthResponse {
+            status: if integrity_issues == 0 && !last_scrub_failed {
+                "ok"
+            } else {
+                "degraded"
+            }
+            .to_string(),
+            node_index: self.config.node_index,
+            supported_curves: self
+                .config
+                .curves()
+                .iter()
+                .map(|curve| curve.as_str().to_string())
+                .collect(),
+            integrity_issues,
+            last_scrub_failed,
+            last_scrub_at: scrub.map(|report| report.started_at),
+        })
+    }
+}
+
+// ============================================================================
+// Protocol steps, implemented once for every ciphersuite
+// ============================================================================
+
+impl UnifiedApi {
+    /// Resolve a `{curve}` path segment to a curve this node serves
+    fn curve(&self, name: &str) -> Result<CurveType, Json<ErrorResponse>> {
+        match CurveType::from_name(name) {
+            Some(curve) if self.config.curves().contains(&curve) => Ok(curve),
+            _ => Err(error_json(format!("Unsupported curve: {}", name))),
+        }
+    }
+
+    async fn pubkey_for<C: CurveOperations>(
         &self,
-        Query(passphrase): Query<String>,
+        passphrase: String,
     ) -> Result<Json<PublicKeyResponse>, ApiError> {
-        let curve_storage =
-            CurveStorage::<Ed25519Operations>::new(self.storage.clone(), CurveType::Ed25519);
-
-        let pubkey_package = curve_storage
+        let pubkey_package = CurveStorage::<C>::new(self.storage.clone())
             .get_pubkey_package(&passphrase, self.key_provider.as_ref())
             .await
-            .map_err(|e| {
-                ApiError::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
-            })?
+            .map_err(|e| ApiError::InternalError(error_json(format!("Storage error: {}", e))))?
             .ok_or_else(|| {
-                ApiError::BadRequest(Json(ErrorResponse {
-                    error: "Ed25519 shares not found. Run Ed25519 DKG first.".to_string(),
-                }))
+                ApiError::BadRequest(error_json(format!(
+                    "{} shares not found. Run DKG first.",
+                    C::CURVE.as_str()
+                )))
             })?;
 
         let pubkey_bytes = pubkey_package.verifying_key().serialize().map_err(|e| {
-            ApiError::InternalError(Json(ErrorResponse {
-                error: format!("Failed to serialize Ed25519 pubkey: {:?}", e),
-            }))
+            ApiError::InternalError(error_json(format!("Failed to serialize pubkey: {:?}", e)))
         })?;
 
         Ok(Json(PublicKeyResponse {
-            curve: "ed25519".to_string(),
+            curve: C::CURVE.as_str().to_string(),
             passphrase,
             public_key: hex::encode(&pubkey_bytes),
         }))
     }
 
-    // ========================================================================
-    // DKG Protocol - Secp256k1-tr (Taproot/Schnorr for Bitcoin)
-    // ========================================================================
+    async fn dkg_round1_for<C: CurveOperations>(&self, req: DkgRound1Request) -> DkgRound1Result {
+        let curve = C::CURVE.as_str();
+
+        tracing::info!("DKG Round 1 for passphrase ({})", curve);
 
-    /// DKG Round 1: Generate secp256k1-tr (Taproot) commitment
-    #[oai(path = "/api/dkg/secp256k1-tr/round1", method = "post")]
-    async fn dkg_taproot_round1(&self, req: Json<DkgRound1Request>) -> DkgRound1Result {
-        let passphrase = req.0.passphrase;
+        match self
+            .lifecycle_refusal(C::CURVE, &req.passphrase, false)
+            .await
+        {
+            Ok(None) => {}
+            Ok(Some(reason)) => return DkgRound1Result::Forbidden(error_json(reason)),
+            Err(e) => {
+                return DkgRound1Result::InternalError(error_json(format!("Storage error: {}", e)))
+            }
+        }
 
-        tracing::info!("DKG Round 1 for passphrase (secp256k1)");
+        let key_derivation = match self
+            .dkg_derivation(C::CURVE, &req.passphrase, req.derivation.as_deref())
+            .await
+        {
+            Ok(Ok(key_derivation)) => key_derivation,
+            Ok(Err(reason)) => return DkgRound1Result::BadRequest(error_json(reason)),
+            Err(e) => {
+                return DkgRound1Result::InternalError(error_json(format!("Storage error: {}", e)))
+            }
+        };
 
         // Generate round1 package with deterministic RNG
-        match crate::node::derivation::dkg_part1_with_provider(
+        let (secret_package, package) = match derivation::dkg_part1_with_provider::<C>(
             self.key_provider.as_ref(),
-            &passphrase,
+            key_derivation,
+            &req.passphrase,
             self.config.node_index,
             self.config.max_signers,
             self.config.min_signers,
         )
         .await
         {
-            Ok((secret_package, package)) => {
-                // Store secret package for round 2
-                self.dkg_state
-                    .store_round1_secret(&passphrase, secret_package);
-
-                // Serialize package for network transmission
-                let package_json = serde_json::to_vec(&package).unwrap();
-
-                DkgRound1Result::Ok(Json(DkgRound1Response {
-                    package: hex::encode(package_json),
-                    node_index: self.config.node_index,
-                }))
-            }
+            Ok(result) => result,
             Err(e) => {
-                tracing::error!("DKG round1 failed: {}", e);
-                DkgRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("DKG round1 failed: {}", e),
-                }))
-            }
-        }
-    }
-
-    /// DKG Round 2: Process secp256k1-tr round1 packages and generate round2 packages
-    #[oai(path = "/api/dkg/secp256k1-tr/round2", method = "post")]
-    async fn dkg_taproot_round2(&self, req: Json<DkgRound2Request>) -> DkgRound2Result {
-        let req = req.0;
-
-        tracing::info!("DKG Round 2 for passphrase (secp256k1)");
-
-        // Retrieve our round1 secret
-        let round1_secret = match self.dkg_state.get_round1_secret(&req.passphrase) {
-            Some(s) => s,
-            None => {
-                return DkgRound2Result::InternalError(Json(ErrorResponse {
-                    error: "Round1 secret not found. Must call round1 first.".to_string(),
-                }))
+                tracing::error!("{} DKG round1 failed: {}", curve, e);
+                return DkgRound1Result::InternalError(error_json(format!(
+                    "DKG round1 failed: {}",
+                    e
+                )));
             }
         };
 
-        // Parse all round1 packages (excluding our own)
-        let mut round1_packages = std::collections::BTreeMap::new();
-        for pkg in req.round1_packages {
-            if pkg.node_index == self.config.node_index {
-                tracing::debug!("Skipping own package (node {})", pkg.node_index);
-                continue;
-            }
+        // Store secret package for round 2, and the scheme for finalize's metadata
+        if let Err(e) = self
+            .dkg_state
+            .store_secret(C::CURVE, &req.passphrase, 1, &secret_package)
+        {
+            return DkgRound1Result::InternalError(error_json(e.to_string()));
+        }
+        self.dkg_state
+            .store_derivation(C::CURVE, &req.passphrase, key_derivation);
 
-            let pkg_bytes = match hex::decode(&pkg.package) {
-                Ok(b) => b,
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to decode package from node {}: {}",
-                        pkg.node_index,
-                        e
-                    );
-                    continue;
-                }
-            };
+        // Serialize package for network transmission
+        let package_json = serde_json::to_vec(&package).unwrap();
 
-            let package: frost_secp256k1_tr::keys::dkg::round1::Package =
-                match serde_json::from_slice(&pkg_bytes) {
-                    Ok(p) => p,
-                    Err(e) => {
-                        tracing::warn!(
-                            "Failed to parse package from node {}: {}",
-                            pkg.node_index,
-                            e
-                        );
-                        continue;
-                    }
-                };
+        DkgRound1Result::Ok(Json(DkgRound1Response {
+            package: hex::encode(package_json),
+            node_index: self.config.node_index,
+        }))
+    }
 
-            let sender_id = match frost_secp256k1_tr::Identifier::try_from(pkg.node_index + 1) {
-                Ok(id) => id,
-                Err(e) => {
-                    tracing::warn!("Invalid node index {}: {:?}", pkg.node_index, e);
-                    continue;
+    async fn dkg_round2_for<C: CurveOperations>(&self, req: DkgRound2Request) -> DkgRound2Result {
+        tracing::info!("DKG Round 2 for passphrase ({})", C::CURVE.as_str());
+
+        // Retrieve our round1 secret
+        let round1_secret: dkg::round1::SecretPackage<C::Suite> =
+            match self.dkg_state.get_secret(C::CURVE, &req.passphrase, 1) {
+                Ok(Some(s)) => s,
+                Ok(None) => {
+                    return DkgRound2Result::InternalError(error_json(
+                        "Round1 secret not found. Must call round1 first.".to_string(),
+                    ))
                 }
+                Err(e) => return DkgRound2Result::InternalError(error_json(e.to_string())),
             };
 
-            round1_packages.insert(sender_id, package);
-        }
+        let (round1_packages, node_indices) =
+            parse_round1_packages::<C::Suite>(req.round1_packages, self.config.node_index);
 
         // Run DKG part2
-        let (round2_secret, round2_packages) =
-            match frost_secp256k1_tr::keys::dkg::part2(round1_secret, &round1_packages) {
-                Ok(result) => result,
-                Err(e) => {
-                    return DkgRound2Result::InternalError(Json(ErrorResponse {
-                        error: format!("DKG round2 failed: {:?}", e),
-                    }))
-                }
-            };
+        let (round2_secret, round2_packages) = match dkg::part2(round1_secret, &round1_packages) {
+            Ok(result) => result,
+            Err(e) => {
+                return DkgRound2Result::InternalError(error_json(format!(
+                    "DKG round2 failed: {:?}",
+                    e
+                )))
+            }
+        };
 
         // Store round2 secret for finalize
-        self.dkg_state
-            .store_round2_secret(&req.passphrase, round2_secret);
-
-        // Convert packages to response format
-        let mut response_packages = Vec::new();
-        for (recipient_id, package) in round2_packages {
-            // Convert FROST identifier back to node index
-            // FROST identifiers are 32-byte scalars in big-endian, so small values (1,2,3)
-            // are at the END of the byte array, not the beginning!
-            let recipient_bytes = recipient_id.serialize();
-            let recipient_index = if !recipient_bytes.is_empty() {
-                // Read the last byte (identifiers 1, 2, 3 fit in one byte)
-                let last_byte = recipient_bytes[recipient_bytes.len() - 1];
-                last_byte.saturating_sub(1) as u16 // Convert 1->0, 2->1, 3->2
-            } else {
-                0
-            };
-
-            let package_json = serde_json::to_vec(&package).unwrap();
+        if let Err(e) = self
+            .dkg_state
+            .store_secret(C::CURVE, &req.passphrase, 2, &round2_secret)
+        {
+            return DkgRound2Result::InternalError(error_json(e.to_string()));
+        }
 
-            response_packages.push(DkgPackageEntry {
+        // part2 returns one package per round1 sender, so every recipient has a node index
+        let packages = round2_packages
+            .into_iter()
+            .map(|(recipient_id, package)| DkgPackageEntry {
                 sender_index: self.config.node_index,
-                recipient_index,
-                package: hex::encode(package_json),
-            });
-        }
+                recipient_index: node_indices[&recipient_id],
+                package: hex::encode(serde_json::to_vec(&package).unwrap()),
+            })
+            .collect();
 
-        DkgRound2Result::Ok(Json(DkgRound2Response {
-            packages: response_packages,
-        }))
+        DkgRound2Result::Ok(Json(DkgRound2Response { packages }))
     }
 
-    /// DKG Finalize: Complete secp256k1-tr key generation and store shares
-    #[oai(path = "/api/dkg/secp256k1-tr/finalize", method = "post")]
-    async fn dkg_taproot_finalize(&self, req: Json<DkgFinalizeRequest>) -> DkgFinalizeResult {
-        let req = req.0;
+    async fn dkg_finalize_for<C: CurveOperations>(
+        &self,
+        req: DkgFinalizeRequest,
+    ) -> DkgFinalizeResult {
+        let curve = C::CURVE.as_str();
 
-        tracing::info!("DKG Finalize for passphrase (secp256k1)");
+        tracing::info!("DKG Finalize for passphrase ({})", curve);
 
-        // Retrieve round2 secret
-        let round2_secret = match self.dkg_state.get_round2_secret(&req.passphrase) {
-            Some(s) => s,
-            None => {
-                return DkgFinalizeResult::BadRequest(Json(ErrorResponse {
-                    error: "Round2 secret not found. Must call round1 and round2 first."
-                        .to_string(),
-                }))
+        // A destroy may have landed while the ceremony was running
+        match self
+            .lifecycle_refusal(C::CURVE, &req.passphrase, false)
+            .await
+        {
+            Ok(None) => {}
+            Ok(Some(reason)) => return DkgFinalizeResult::Forbidden(error_json(reason)),
+            Err(e) => {
+                return DkgFinalizeResult::InternalError(error_json(format!(
+                    "Storage error: {}",
+                    e
+                )))
             }
-        };
+        }
 
-        // Parse round1 packages (skip own package)
-        let mut round1_packages = std::collections::BTreeMap::new();
-        for pkg in req.round1_packages {
-            // Skip own package - we don't include it in DKG
-            if pkg.node_index == self.config.node_index {
-                tracing::debug!("Skipping own round1 package (node {})", pkg.node_index);
-                continue;
-            }
+        let participants: Vec<u16> = req.round1_packages.iter().map(|p| p.node_index).collect();
+        let dkg_session = dkg_session_id(&req.round1_packages);
 
-            let pkg_bytes = match hex::decode(&pkg.package) {
-                Ok(b) => b,
-                Err(_) => continue,
+        // Retrieve round2 secret
+        let round2_secret: dkg::round2::SecretPackage<C::Suite> =
+            match self.dkg_state.get_secret(C::CURVE, &req.passphrase, 2) {
+                Ok(Some(s)) => s,
+                Ok(None) => {
+                    return DkgFinalizeResult::BadRequest(error_json(
+                        "Round2 secret not found. Must call round1 and round2 first.".to_string(),
+                    ))
+                }
+                Err(e) => return DkgFinalizeResult::InternalError(error_json(e.to_string())),
             };
 
-            let package: frost_secp256k1_tr::keys::dkg::round1::Package =
-                match serde_json::from_slice(&pkg_bytes) {
-                    Ok(p) => p,
-                    Err(_) => continue,
-                };
-
-            let sender_id = match frost_secp256k1_tr::Identifier::try_from(pkg.node_index + 1) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
+        // Scheme round 1 derived our randomness with, recorded in the metadata
+        let Some(key_derivation) = self.dkg_state.get_derivation(C::CURVE, &req.passphrase) else {
+            return DkgFinalizeResult::BadRequest(error_json(
+                "DKG round1 state not found. Must call round1 and round2 first.".to_string(),
+            ));
+        };
 
-            round1_packages.insert(sender_id, package);
-        }
+        let (round1_packages, _) =
+            parse_round1_packages::<C::Suite>(req.round1_packages, self.config.node_index);
 
         // Parse round2 packages (only ones for us)
-        let mut round2_packages = std::collections::BTreeMap::new();
+        let mut round2_packages = BTreeMap::new();
         for pkg in req.round2_packages {
-            let pkg_bytes = match hex::decode(&pkg.package) {
-                Ok(b) => b,
-                Err(_) => continue,
+            let Ok(pkg_bytes) = hex::decode(&pkg.package) else {
+                continue;
             };
-
-            let package: frost_secp256k1_tr::keys::dkg::round2::Package =
-                match serde_json::from_slice(&pkg_bytes) {
-                    Ok(p) => p,
-                    Err(_) => continue,
-                };
-
-            let sender_id = match frost_secp256k1_tr::Identifier::try_from(pkg.sender_index + 1) {
-                Ok(id) => id,
-                Err(_) => continue,
+            let Ok(package) = serde_json::from_slice::<dkg::round2::Package<C::Suite>>(&pkg_bytes)
+            else {
+                continue;
+            };
+            let Ok(sender_id) = Identifier::<C::Suite>::try_from(pkg.sender_index + 1) else {
+                continue;
             };
 
             round2_packages.insert(sender_id, package);
         }
 
-        // Debug: Log package counts
         tracing::info!(
             "DKG part3: {} round1 packages, {} round2 packages (expected: {} and {})",
             round1_packages.len(),
@@ -495,52 +782,83 @@ impl UnifiedApi {
         );
 
         // Run DKG part3 (finalize)
-        let (key_package, pubkey_package) = match frost_secp256k1_tr::keys::dkg::part3(
-            &round2_secret,
-            &round1_packages,
-            &round2_packages,
+        let (key_package, pubkey_package) =
+            match dkg::part3(&round2_secret, &round1_packages, &round2_packages) {
+                Ok(result) => result,
+                Err(e) => {
+                    tracing::error!("{} DKG finalize failed: {:?}", curve, e);
+                    self.audit.record(
+                        AuditEvent::new(AuditKind::DkgCeremony, "failure")
+                            .curve(curve)
+                            .passphrase(&req.passphrase)
+                            .participants(&participants)
+                            .detail(format!("{:?}", e)),
+                    );
+                    return DkgFinalizeResult::InternalError(error_json(format!(
+                        "DKG finalize failed: {:?}",
+                        e
+                    )));
+                }
+            };
+
+        // Store key packages in multi-curve storage (encrypted), with their metadata
+        let curve_storage = CurveStorage::<C>::new(self.storage.clone());
+        let stored = match KeyMetadata::new::<C>(
+            &pubkey_package,
+            dkg_session,
+            self.config.min_signers,
+            participants.clone(),
+            self.config.committee_epoch,
+            key_derivation,
         ) {
-            Ok(result) => result,
-            Err(e) => {
-                tracing::error!("DKG finalize failed: {:?}", e);
-                return DkgFinalizeResult::InternalError(Json(ErrorResponse {
-                    error: format!("DKG finalize failed: {:?}", e),
-                }));
+            Ok(metadata) => {
+                curve_storage
+                    .store_packages(
+                        &req.passphrase,
+                        &key_package,
+                        &pubkey_package,
+                        &metadata,
+                        self.key_provider.as_ref(),
+                    )
+                    .await
             }
+            Err(e) => Err(e),
         };
-
-        // Store key packages in multi-curve storage (encrypted)
-        let curve_storage = CurveStorage::<Secp256k1Operations>::new(
-            self.storage.clone(),
-            CurveType::Secp256k1Taproot,
-        );
-
-        if let Err(e) = curve_storage
-            .store_key_package(&req.passphrase, &key_package, self.key_provider.as_ref())
-            .await
-        {
-            tracing::error!("Failed to store key package: {}", e);
-        }
-
-        if let Err(e) = curve_storage
-            .store_pubkey_package(&req.passphrase, &pubkey_package, self.key_provider.as_ref())
-            .await
-        {
-            tracing::error!("Failed to store pubkey package: {}", e);
+        if let Err(e) = stored {
+            tracing::error!("Failed to store {} key packages: {}", curve, e);
+            self.audit.record(
+                AuditEvent::new(AuditKind::DkgCeremony, "failure")
+                    .curve(curve)
+                    .passphrase(&req.passphrase)
+                    .participants(&participants)
+                    .detail(format!("store failed: {}", e)),
+            );
+            return DkgFinalizeResult::InternalError(error_json(format!(
+                "Failed to store key packages: {}",
+                e
+            )));
         }
 
         // Return raw public key hex (aggregator derives chain-specific addresses)
         let pubkey_hex = match pubkey_package.verifying_key().serialize() {
             Ok(bytes) => hex::encode(bytes),
             Err(e) => {
-                tracing::error!("Failed to serialize pubkey: {:?}", e);
-                return DkgFinalizeResult::InternalError(Json(ErrorResponse {
-                    error: format!("Failed to serialize pubkey: {:?}", e),
-                }));
+                tracing::error!("Failed to serialize {} pubkey: {:?}", curve, e);
+                return DkgFinalizeResult::InternalError(error_json(format!(
+                    "Failed to serialize pubkey: {:?}",
+                    e
+                )));
             }
         };
 
-        tracing::info!("✅ DKG complete, FROST key shares stored");
+        tracing::info!("✅ {} DKG complete, FROST key shares stored", curve);
+        self.audit.record(
+            AuditEvent::new(AuditKind::DkgCeremony, "success")
+                .curve(curve)
+                .passphrase(&req.passphrase)
+                .participants(&participants)
+                .detail(format!("pubkey={}", pubkey_hex)),
+        );
 
         DkgFinalizeResult::Ok(Json(DkgFinalizeResponse {
             success: true,
@@ -548,1417 +866,160 @@ impl UnifiedApi {
         }))
     }
 
-    // ========================================================================
-    // FROST Signing - Secp256k1-tr (Taproot/Schnorr for Bitcoin)
-    // ========================================================================
-
-    /// FROST Round 1: Generate secp256k1-tr signing commitments
-    #[oai(path = "/api/frost/secp256k1-tr/round1", method = "post")]
-    async fn frost_taproot_round1(&self, req: Json<FrostRound1Request>) -> FrostRound1Result {
-        let req = req.0;
+    async fn frost_round1_for<C: CurveOperations>(
+        &self,
+        req: FrostRound1Request,
+    ) -> FrostRound1Result {
+        tracing::info!("FROST Round 1 for passphrase ({})", C::CURVE.as_str());
 
-        tracing::info!("FROST Round 1 for passphrase");
+        match self
+            .lifecycle_refusal(C::CURVE, &req.passphrase, true)
+            .await
+        {
+            Ok(None) => {}
+            Ok(Some(reason)) => return FrostRound1Result::Forbidden(error_json(reason)),
+            Err(e) => {
+                return FrostRound1Result::InternalError(error_json(format!(
+                    "Storage error: {}",
+                    e
+                )))
+            }
+        }
 
         // Decode message
-        let _message = match hex::decode(&req.message) {
+        let message = match hex::decode(&req.message) {
             Ok(msg) => msg,
             Err(e) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("Invalid message hex: {}", e),
-                }))
+                return FrostRound1Result::BadRequest(error_json(format!(
+                    "Invalid message hex: {}",
+                    e
+                )))
             }
         };
 
         // Get key package for this passphrase
-        let curve_storage = CurveStorage::<Secp256k1Operations>::new(
-            self.storage.clone(),
-            CurveType::Secp256k1Taproot,
-        );
-
-        let key_package = match curve_storage
+        let key_package = match CurveStorage::<C>::new(self.storage.clone())
             .get_key_package(&req.passphrase, self.key_provider.as_ref())
             .await
         {
             Ok(Some(pkg)) => pkg,
             Ok(None) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: "Key package not found for passphrase".to_string(),
-                }))
+                return FrostRound1Result::InternalError(error_json(
+                    "Key package not found for passphrase".to_string(),
+                ))
             }
             Err(e) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
+                return FrostRound1Result::InternalError(error_json(format!(
+                    "Storage error: {}",
+                    e
+                )))
             }
         };
 
         // Generate commitments (scope rng to avoid Send across await)
-        let (commitments, identifier_hex, message_bytes, nonces_json) = {
+        let (commitments, nonces_json) = {
             let mut rng = rand::thread_rng();
             let (nonces, commitments) =
-                frost_secp256k1_tr::round1::commit(key_package.signing_share(), &mut rng);
-            let identifier_hex = hex::encode(key_package.identifier().serialize());
-            let message_bytes = hex::decode(&req.message).unwrap();
-            let nonces_json = serde_json::to_vec(&nonces).unwrap();
-            (commitments, identifier_hex, message_bytes, nonces_json)
+                frost_core::round1::commit(key_package.signing_share(), &mut rng);
+            (commitments, serde_json::to_vec(&nonces).unwrap())
         };
 
-        // Serialize commitments
-        let commitments_json = serde_json::to_vec(&commitments).unwrap();
-
         // Encrypt nonces (bound to message)
         let encrypted_nonces = match super::crypto::encrypt_nonces_with_provider(
             &nonces_json,
-            &message_bytes,
+            &message,
             self.key_provider.as_ref(),
         )
         .await
         {
             Ok(enc) => enc,
             Err(e) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("Failed to encrypt nonces: {}", e),
-                }))
+                return FrostRound1Result::InternalError(error_json(format!(
+                    "Failed to encrypt nonces: {}",
+                    e
+                )))
             }
         };
 
         FrostRound1Result::Ok(Json(FrostRound1Response {
-            identifier: identifier_hex,
-            commitments: hex::encode(commitments_json),
+            identifier: hex::encode(key_package.identifier().serialize()),
+            commitments: hex::encode(serde_json::to_vec(&commitments).unwrap()),
             encrypted_nonces,
             node_index: self.config.node_index,
         }))
     }
 
-    /// FROST Round 2: Generate secp256k1-tr signature share
-    #[oai(path = "/api/frost/secp256k1-tr/round2", method = "post")]
-    async fn frost_taproot_round2(&self, req: Json<FrostRound2Request>) -> FrostRound2Result {
-        let req = req.0;
+    async fn frost_round2_for<C: CurveOperations>(
+        &self,
+        req: FrostRound2Request,
+    ) -> FrostRound2Result {
+        let curve = C::CURVE.as_str();
+
+        tracing::info!("FROST Round 2 for passphrase ({})", curve);
 
-        tracing::info!("FROST Round 2 for passphrase");
+        let participants: Vec<String> = req
+            .all_commitments
+            .iter()
+            .map(|c| signer_label::<C::Suite>(&c.identifier, self.config.max_signers))
+            .collect();
 
         // Decode message
         let message = match hex::decode(&req.message) {
             Ok(msg) => msg,
             Err(e) => {
-                return FrostRound2Result::BadRequest(Json(ErrorResponse {
-                    error: format!("Invalid message hex: {}", e),
-                }))
+                return FrostRound2Result::BadRequest(error_json(format!(
+                    "Invalid message hex: {}",
+                    e
+                )))
             }
         };
 
-        // Decrypt nonces
-        let nonces_json = match super::crypto::decrypt_nonces_with_provider(
-            &req.encrypted_nonces,
-            &message,
-            self.key_provider.as_ref(),
-        )
-        .await
+        // Checked again here: the key may have been frozen since round 1
+        match self
+            .lifecycle_refusal(C::CURVE, &req.passphrase, true)
+            .await
         {
-            Ok(json) => json,
+            Ok(None) => {}
+            Ok(Some(reason)) => return FrostRound2Result::Forbidden(error_json(reason)),
             Err(e) => {
-                return FrostRound2Result::BadRequest(Json(ErrorResponse {
-                    error: format!("Failed to decrypt nonces: {}", e),
-                }))
+                return FrostRound2Result::InternalError(error_json(format!(
+                    "Storage error: {}",
+                    e
+                )))
             }
-        };
-
-        let nonces: frost_secp256k1_tr::round1::SigningNonces =
-            match serde_json::from_slice(&nonces_json) {
-                Ok(n) => n,
-                Err(e) => {
-                    return FrostRound2Result::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to deserialize nonces: {}", e),
-                    }))
-                }
-            };
-
-        // Parse commitments
-        let mut commitments_map = std::collections::BTreeMap::new();
-        for entry in req.all_commitments {
-            let id_str = entry.identifier.trim_matches('"');
-            let identifier = match frost_secp256k1_tr::Identifier::deserialize(
-                &hex::decode(id_str).unwrap_or_default(),
-            ) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            let comm_json = match hex::decode(&entry.commitments) {
-                Ok(j) => j,
-                Err(_) => continue,
-            };
-
-            let commitments: frost_secp256k1_tr::round1::SigningCommitments =
-                match serde_json::from_slice(&comm_json) {
-                    Ok(c) => c,
-                    Err(_) => continue,
-                };
-
-            commitments_map.insert(identifier, commitments);
         }
 
         // Get key package for this passphrase
-        let curve_storage = CurveStorage::<Secp256k1Operations>::new(
-            self.storage.clone(),
-            CurveType::Secp256k1Taproot,
-        );
-
-        let key_package = match curve_storage
+        let key_package = match CurveStorage::<C>::new(self.storage.clone())
             .get_key_package(&req.passphrase, self.key_provider.as_ref())
             .await
         {
             Ok(Some(pkg)) => pkg,
             Ok(None) => {
-                return FrostRound2Result::BadRequest(Json(ErrorResponse {
-                    error: "Key package not found for passphrase".to_string(),
-                }))
-            }
-            Err(e) => {
-                return FrostRound2Result::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
-            }
-        };
-
-        // Create signing package and sign
-        let signing_package = frost_secp256k1_tr::SigningPackage::new(commitments_map, &message);
-        let signature_share =
-            match frost_secp256k1_tr::round2::sign(&signing_package, &nonces, &key_package) {
-                Ok(share) => share,
-                Err(e) => {
-                    return FrostRound2Result::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to sign: {:?}", e),
-                    }))
-                }
-            };
-
-        let share_json = serde_json::to_vec(&signature_share).unwrap();
-        let identifier_hex = hex::encode(key_package.identifier().serialize());
-
-        FrostRound2Result::Ok(Json(FrostRound2Response {
-            identifier: identifier_hex,
-            signature_share: hex::encode(share_json),
-            node_index: self.config.node_index,
-        }))
-    }
-
-    /// FROST Aggregate: Combine secp256k1-tr signature shares into final signature
-    #[oai(path = "/api/frost/secp256k1-tr/aggregate", method = "post")]
-    async fn frost_taproot_aggregate(
-        &self,
-        req: Json<FrostAggregateRequest>,
-    ) -> FrostAggregateResult {
-        let req = req.0;
-
-        tracing::info!("FROST Aggregate for passphrase");
-
-        // Decode message
-        let message = match hex::decode(&req.message) {
-            Ok(msg) => msg,
-            Err(e) => {
-                return FrostAggregateResult::BadRequest(Json(ErrorResponse {
-                    error: format!("Invalid message hex: {}", e),
-                }))
-            }
-        };
-
-        // Parse commitments
-        let mut commitments_map = std::collections::BTreeMap::new();
-        for entry in req.all_commitments {
-            let id_str = entry.identifier.trim_matches('"');
-            let identifier = match frost_secp256k1_tr::Identifier::deserialize(
-                &hex::decode(id_str).unwrap_or_default(),
-            ) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            let comm_json = match hex::decode(&entry.commitments) {
-                Ok(j) => j,
-                Err(_) => continue,
-            };
-
-            let commitments: frost_secp256k1_tr::round1::SigningCommitments =
-                match serde_json::from_slice(&comm_json) {
-                    Ok(c) => c,
-                    Err(_) => continue,
-                };
-
-            commitments_map.insert(identifier, commitments);
-        }
-
-        // Parse signature shares
-        let mut shares_map = std::collections::BTreeMap::new();
-        for entry in req.signature_shares {
-            let id_str = entry.identifier.trim_matches('"');
-            let identifier = match frost_secp256k1_tr::Identifier::deserialize(
-                &hex::decode(id_str).unwrap_or_default(),
-            ) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            let share_json = match hex::decode(&entry.share) {
-                Ok(j) => j,
-                Err(_) => continue,
-            };
-
-            let share: frost_secp256k1_tr::round2::SignatureShare =
-                match serde_json::from_slice(&share_json) {
-                    Ok(s) => s,
-                    Err(_) => continue,
-                };
-
-            shares_map.insert(identifier, share);
-        }
-
-        // Get pubkey package for verification
-        let curve_storage = CurveStorage::<Secp256k1Operations>::new(
-            self.storage.clone(),
-            CurveType::Secp256k1Taproot,
-        );
-
-        let pubkey_package = match curve_storage
-            .get_pubkey_package(&req.passphrase, self.key_provider.as_ref())
-            .await
-        {
-            Ok(Some(pkg)) => pkg,
-            Ok(None) => {
-                return FrostAggregateResult::BadRequest(Json(ErrorResponse {
-                    error: "Pubkey package not found for passphrase".to_string(),
-                }))
-            }
-            Err(e) => {
-                return FrostAggregateResult::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
-            }
-        };
-
-        // Aggregate signature
-        let signing_package = frost_secp256k1_tr::SigningPackage::new(commitments_map, &message);
-        let signature =
-            match frost_secp256k1_tr::aggregate(&signing_package, &shares_map, &pubkey_package) {
-                Ok(sig) => sig,
-                Err(e) => {
-                    return FrostAggregateResult::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to aggregate: {:?}", e),
-                    }))
-                }
-            };
-
-        // Verify signature
-        let verified = pubkey_package
-            .verifying_key()
-            .verify(&message, &signature)
-            .is_ok();
-
-        let sig_bytes = signature
-            .serialize()
-            .map_err(|e| format!("Failed to serialize signature: {:?}", e))
-            .unwrap_or_default();
-
-        FrostAggregateResult::Ok(Json(FrostAggregateResponse {
-            signature: hex::encode(&sig_bytes),
-            verified,
-        }))
-    }
-
-    // ========================================================================
-    // DKG Protocol - Secp256k1 (ECDSA for Ethereum/EVM)
-    // ========================================================================
-
-    /// DKG Round 1: Generate secp256k1 (ECDSA) commitment
-    #[oai(path = "/api/dkg/secp256k1/round1", method = "post")]
-    async fn dkg_ecdsa_round1(&self, req: Json<DkgRound1Request>) -> DkgRound1Result {
-        let passphrase = req.0.passphrase;
-
-        tracing::info!("DKG Round 1 for passphrase (secp256k1 ECDSA)");
-
-        // Use same deterministic DKG as Taproot but with different seed prefix
-        match crate::node::derivation::dkg_part1_ecdsa_with_provider(
-            self.key_provider.as_ref(),
-            &passphrase,
-            self.config.node_index,
-            self.config.max_signers,
-            self.config.min_signers,
-        )
-        .await
-        {
-            Ok((secret_package, package)) => {
-                // Store secret package for round 2 (use different key to avoid collision)
-                let key = format!("ecdsa:{}", passphrase);
-                self.dkg_state.generic_secrets.lock().unwrap().insert(
-                    format!("{}:r1", key),
-                    serde_json::to_vec(&secret_package).unwrap(),
-                );
-
-                let package_json = serde_json::to_vec(&package).unwrap();
-
-                DkgRound1Result::Ok(Json(DkgRound1Response {
-                    package: hex::encode(package_json),
-                    node_index: self.config.node_index,
-                }))
-            }
-            Err(e) => {
-                tracing::error!("ECDSA DKG round1 failed: {}", e);
-                DkgRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("ECDSA DKG round1 failed: {}", e),
-                }))
-            }
-        }
-    }
-
-    /// DKG Round 2: Process secp256k1 ECDSA round1 packages  
-    #[oai(path = "/api/dkg/secp256k1/round2", method = "post")]
-    async fn dkg_ecdsa_round2(&self, req: Json<DkgRound2Request>) -> DkgRound2Result {
-        let req = req.0;
-
-        tracing::info!("DKG Round 2 for passphrase (secp256k1 ECDSA)");
-
-        // Retrieve our round1 secret
-        let key = format!("ecdsa:{}:r1", req.passphrase);
-        let round1_secret_bytes = match self.dkg_state.generic_secrets.lock().unwrap().get(&key) {
-            Some(s) => s.clone(),
-            None => {
-                return DkgRound2Result::InternalError(Json(ErrorResponse {
-                    error: "ECDSA Round1 secret not found. Must call round1 first.".to_string(),
-                }))
-            }
-        };
-
-        let round1_secret: frost_secp256k1::keys::dkg::round1::SecretPackage =
-            match serde_json::from_slice(&round1_secret_bytes) {
-                Ok(s) => s,
-                Err(e) => {
-                    return DkgRound2Result::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to deserialize ECDSA round1 secret: {}", e),
-                    }))
-                }
-            };
-
-        // Parse all round1 packages (excluding our own)
-        let mut round1_packages = std::collections::BTreeMap::new();
-        for pkg in req.round1_packages {
-            if pkg.node_index == self.config.node_index {
-                tracing::debug!("Skipping own ECDSA package (node {})", pkg.node_index);
-                continue;
-            }
-
-            let pkg_bytes = match hex::decode(&pkg.package) {
-                Ok(b) => b,
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to decode ECDSA package from node {}: {}",
-                        pkg.node_index,
-                        e
-                    );
-                    continue;
-                }
-            };
-
-            let package: frost_secp256k1::keys::dkg::round1::Package =
-                match serde_json::from_slice(&pkg_bytes) {
-                    Ok(p) => p,
-                    Err(e) => {
-                        tracing::warn!(
-                            "Failed to parse ECDSA package from node {}: {}",
-                            pkg.node_index,
-                            e
-                        );
-                        continue;
-                    }
-                };
-
-            let sender_id = match frost_secp256k1::Identifier::try_from(pkg.node_index + 1) {
-                Ok(id) => id,
-                Err(e) => {
-                    tracing::warn!("Invalid ECDSA node index {}: {:?}", pkg.node_index, e);
-                    continue;
-                }
-            };
-
-            round1_packages.insert(sender_id, package);
-        }
-
-        // Run ECDSA DKG part2
-        let (round2_secret, round2_packages) =
-            match frost_secp256k1::keys::dkg::part2(round1_secret, &round1_packages) {
-                Ok(result) => result,
-                Err(e) => {
-                    return DkgRound2Result::InternalError(Json(ErrorResponse {
-                        error: format!("ECDSA DKG round2 failed: {:?}", e),
-                    }))
-                }
-            };
-
-        // Store round2 secret for finalize
-        let key = format!("ecdsa:{}:r2", req.passphrase);
-        self.dkg_state
-            .generic_secrets
-            .lock()
-            .unwrap()
-            .insert(key, serde_json::to_vec(&round2_secret).unwrap());
-
-        // Convert packages to response format (big-endian like Taproot)
-        let mut response_packages = Vec::new();
-        for (recipient_id, package) in round2_packages {
-            let recipient_bytes = recipient_id.serialize();
-            let recipient_index = if !recipient_bytes.is_empty() {
-                let last_byte = recipient_bytes[recipient_bytes.len() - 1];
-                last_byte.saturating_sub(1) as u16
-            } else {
-                0
-            };
-
-            let package_json = serde_json::to_vec(&package).unwrap();
-
-            response_packages.push(DkgPackageEntry {
-                sender_index: self.config.node_index,
-                recipient_index,
-                package: hex::encode(package_json),
-            });
-        }
-
-        DkgRound2Result::Ok(Json(DkgRound2Response {
-            packages: response_packages,
-        }))
-    }
-
-    /// DKG Finalize: Complete secp256k1 ECDSA key generation
-    #[oai(path = "/api/dkg/secp256k1/finalize", method = "post")]
-    async fn dkg_ecdsa_finalize(&self, req: Json<DkgFinalizeRequest>) -> DkgFinalizeResult {
-        let req = req.0;
-
-        tracing::info!("DKG Finalize for passphrase (secp256k1 ECDSA)");
-
-        // Retrieve round2 secret
-        let key = format!("ecdsa:{}:r2", req.passphrase);
-        let round2_secret_bytes = match self.dkg_state.generic_secrets.lock().unwrap().get(&key) {
-            Some(s) => s.clone(),
-            None => {
-                return DkgFinalizeResult::BadRequest(Json(ErrorResponse {
-                    error: "ECDSA Round2 secret not found. Must call round1 and round2 first."
-                        .to_string(),
-                }))
-            }
-        };
-
-        let round2_secret: frost_secp256k1::keys::dkg::round2::SecretPackage =
-            match serde_json::from_slice(&round2_secret_bytes) {
-                Ok(s) => s,
-                Err(e) => {
-                    return DkgFinalizeResult::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to deserialize ECDSA round2 secret: {}", e),
-                    }))
-                }
-            };
-
-        // Parse round1 packages (skip own package)
-        let mut round1_packages = std::collections::BTreeMap::new();
-        for pkg in req.round1_packages {
-            if pkg.node_index == self.config.node_index {
-                tracing::debug!(
-                    "Skipping own ECDSA round1 package (node {})",
-                    pkg.node_index
-                );
-                continue;
-            }
-
-            let pkg_bytes = match hex::decode(&pkg.package) {
-                Ok(b) => b,
-                Err(_) => continue,
-            };
-
-            let package: frost_secp256k1::keys::dkg::round1::Package =
-                match serde_json::from_slice(&pkg_bytes) {
-                    Ok(p) => p,
-                    Err(_) => continue,
-                };
-
-            let sender_id = match frost_secp256k1::Identifier::try_from(pkg.node_index + 1) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            round1_packages.insert(sender_id, package);
-        }
-
-        // Parse round2 packages
-        let mut round2_packages = std::collections::BTreeMap::new();
-        for pkg in req.round2_packages {
-            let pkg_bytes = match hex::decode(&pkg.package) {
-                Ok(b) => b,
-                Err(_) => continue,
-            };
-
-            let package: frost_secp256k1::keys::dkg::round2::Package =
-                match serde_json::from_slice(&pkg_bytes) {
-                    Ok(p) => p,
-                    Err(_) => continue,
-                };
-
-            let sender_id = match frost_secp256k1::Identifier::try_from(pkg.sender_index + 1) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            round2_packages.insert(sender_id, package);
-        }
-
-        tracing::debug!(
-            "ECDSA DKG part3: {} round1 packages, {} round2 packages",
-            round1_packages.len(),
-            round2_packages.len()
-        );
-
-        // Run ECDSA DKG part3 (finalize)
-        let (key_package, pubkey_package) = match frost_secp256k1::keys::dkg::part3(
-            &round2_secret,
-            &round1_packages,
-            &round2_packages,
-        ) {
-            Ok(result) => result,
-            Err(e) => {
-                tracing::error!("ECDSA DKG finalize failed: {:?}", e);
-                return DkgFinalizeResult::InternalError(Json(ErrorResponse {
-                    error: format!("ECDSA DKG finalize failed: {:?}", e),
-                }));
-            }
-        };
-
-        // Store key packages in ECDSA column family
-        let curve_storage =
-            CurveStorage::<crate::curves::secp256k1_ecdsa::Secp256k1EcdsaOperations>::new(
-                self.storage.clone(),
-                CurveType::Secp256k1Ecdsa,
-            );
-        if let Err(e) = curve_storage
-            .store_key_package(&req.passphrase, &key_package, self.key_provider.as_ref())
-            .await
-        {
-            tracing::error!("Failed to store ECDSA key package: {}", e);
-        }
-        if let Err(e) = curve_storage
-            .store_pubkey_package(&req.passphrase, &pubkey_package, self.key_provider.as_ref())
-            .await
-        {
-            tracing::error!("Failed to store ECDSA pubkey package: {}", e);
-        }
-
-        // Return raw public key hex
-        let pubkey_hex = match pubkey_package.verifying_key().serialize() {
-            Ok(bytes) => hex::encode(bytes),
-            Err(e) => {
-                tracing::error!("Failed to serialize ECDSA pubkey: {:?}", e);
-                return DkgFinalizeResult::InternalError(Json(ErrorResponse {
-                    error: format!("Failed to serialize ECDSA pubkey: {:?}", e),
-                }));
-            }
-        };
-
-        tracing::info!("✅ ECDSA DKG complete, FROST key shares stored");
-
-        DkgFinalizeResult::Ok(Json(DkgFinalizeResponse {
-            success: true,
-            pubkey_hex,
-        }))
-    }
-
-    // ========================================================================
-    // DKG Protocol - Ed25519 (for Solana)
-    // ========================================================================
-
-    /// DKG Round 1: Generate Ed25519 commitment
-    #[oai(path = "/api/dkg/ed25519/round1", method = "post")]
-    async fn dkg_ed25519_round1(&self, req: Json<DkgRound1Request>) -> DkgRound1Result {
-        let passphrase = req.0.passphrase;
-
-        tracing::info!("DKG Round 1 for passphrase (Ed25519)");
-
-        // Generate round1 package with deterministic RNG
-        match crate::node::derivation::dkg_part1_ed25519_with_provider(
-            self.key_provider.as_ref(),
-            &passphrase,
-            self.config.node_index,
-            self.config.max_signers,
-            self.config.min_signers,
-        )
-        .await
-        {
-            Ok((secret_package, package)) => {
-                // Store secret package for round 2
-                let secret_key = format!("ed25519:r1:{}", passphrase);
-                self.dkg_state
-                    .generic_secrets
-                    .lock()
-                    .unwrap()
-                    .insert(secret_key, serde_json::to_vec(&secret_package).unwrap());
-
-                let package_json = serde_json::to_vec(&package).unwrap();
-
-                DkgRound1Result::Ok(Json(DkgRound1Response {
-                    package: hex::encode(package_json),
-                    node_index: self.config.node_index,
-                }))
-            }
-            Err(e) => {
-                tracing::error!("Ed25519 DKG round1 failed: {}", e);
-                DkgRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("Ed25519 DKG round1 failed: {}", e),
-                }))
-            }
-        }
-    }
-
-    /// DKG Round 2: Process Ed25519 round1 packages and generate round2 packages
-    #[oai(path = "/api/dkg/ed25519/round2", method = "post")]
-    async fn dkg_ed25519_round2(&self, req: Json<DkgRound2Request>) -> DkgRound2Result {
-        let req = req.0;
-
-        tracing::info!("DKG Round 2 for passphrase (Ed25519)");
-
-        // Retrieve our round1 secret
-        let secret_key = format!("ed25519:r1:{}", req.passphrase);
-        let round1_secret_bytes = match self
-            .dkg_state
-            .generic_secrets
-            .lock()
-            .unwrap()
-            .get(&secret_key)
-        {
-            Some(s) => s.clone(),
-            None => {
-                return DkgRound2Result::InternalError(Json(ErrorResponse {
-                    error: "Ed25519 Round1 secret not found. Must call round1 first.".to_string(),
-                }))
-            }
-        };
-
-        let round1_secret: frost_ed25519::keys::dkg::round1::SecretPackage =
-            match serde_json::from_slice(&round1_secret_bytes) {
-                Ok(s) => s,
-                Err(e) => {
-                    return DkgRound2Result::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to deserialize Ed25519 round1 secret: {}", e),
-                    }))
-                }
-            };
-
-        // Parse all round1 packages (Ed25519 DKG part2 needs all n-1 OTHER packages, excluding own)
-        let mut round1_packages = std::collections::BTreeMap::new();
-        for pkg in req.round1_packages {
-            // Skip own package
-            if pkg.node_index == self.config.node_index {
-                tracing::debug!(
-                    "Ed25519 round2: Skipping own package (node {})",
-                    pkg.node_index
-                );
-                continue;
-            }
-
-            let pkg_bytes = match hex::decode(&pkg.package) {
-                Ok(b) => b,
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to decode Ed25519 package from node {}: {}",
-                        pkg.node_index,
-                        e
-                    );
-                    continue;
-                }
-            };
-
-            let package: frost_ed25519::keys::dkg::round1::Package =
-                match serde_json::from_slice(&pkg_bytes) {
-                    Ok(p) => p,
-                    Err(e) => {
-                        tracing::warn!(
-                            "Failed to parse Ed25519 package from node {}: {}",
-                            pkg.node_index,
-                            e
-                        );
-                        continue;
-                    }
-                };
-
-            let sender_id = match frost_ed25519::Identifier::try_from(pkg.node_index + 1) {
-                Ok(id) => id,
-                Err(e) => {
-                    tracing::warn!("Invalid Ed25519 node index {}: {:?}", pkg.node_index, e);
-                    continue;
-                }
-            };
-
-            round1_packages.insert(sender_id, package);
-        }
-
-        // Run Ed25519 DKG part2
-        let (round2_secret, round2_packages) =
-            match frost_ed25519::keys::dkg::part2(round1_secret, &round1_packages) {
-                Ok(result) => result,
-                Err(e) => {
-                    return DkgRound2Result::InternalError(Json(ErrorResponse {
-                        error: format!("Ed25519 DKG round2 failed: {:?}", e),
-                    }))
-                }
-            };
-
-        // Store round2 secret for finalize
-        let secret_key = format!("ed25519:r2:{}", req.passphrase);
-        self.dkg_state
-            .generic_secrets
-            .lock()
-            .unwrap()
-            .insert(secret_key, serde_json::to_vec(&round2_secret).unwrap());
-
-        // Convert packages to response format
-        let mut response_packages = Vec::new();
-        for (recipient_id, package) in round2_packages {
-            // Convert FROST Ed25519 identifier back to node index
-            let recipient_bytes = recipient_id.serialize();
-
-            // Ed25519 identifiers are 32 bytes. Log the conversion.
-            tracing::info!(
-                "Ed25519 round2 node {}: recipient_id hex={}, bytes len={}, first={:02x}, last={:02x}",
-                self.config.node_index,
-                hex::encode(&recipient_bytes),
-                recipient_bytes.len(),
-                recipient_bytes.first().copied().unwrap_or(0),
-                recipient_bytes.last().copied().unwrap_or(0)
-            );
-
-            let recipient_index = if !recipient_bytes.is_empty() {
-                // Ed25519 uses LITTLE-ENDIAN encoding, so value is in FIRST byte (not last!)
-                let first_byte = recipient_bytes[0];
-                let index = first_byte.saturating_sub(1) as u16;
-                tracing::info!(
-                    "Ed25519 round2 node {}: first_byte={}, calculated recipient_index={}",
-                    self.config.node_index,
-                    first_byte,
-                    index
-                );
-                index
-            } else {
-                0
-            };
-
-            let package_json = serde_json::to_vec(&package).unwrap();
-
-            response_packages.push(DkgPackageEntry {
-                sender_index: self.config.node_index,
-                recipient_index,
-                package: hex::encode(package_json),
-            });
-        }
-
-        DkgRound2Result::Ok(Json(DkgRound2Response {
-            packages: response_packages,
-        }))
-    }
-
-    /// DKG Finalize: Complete Ed25519 key generation
-    #[oai(path = "/api/dkg/ed25519/finalize", method = "post")]
-    async fn dkg_ed25519_finalize(&self, req: Json<DkgFinalizeRequest>) -> DkgFinalizeResult {
-        let req = req.0;
-
-        tracing::info!("DKG Finalize for passphrase (Ed25519)");
-
-        // Retrieve round2 secret
-        let secret_key = format!("ed25519:r2:{}", req.passphrase);
-        let round2_secret_bytes =
-            match self
-                .dkg_state
-                .generic_secrets
-                .lock()
-                .unwrap()
-                .get(&secret_key)
-            {
-                Some(s) => s.clone(),
-                None => return DkgFinalizeResult::BadRequest(Json(ErrorResponse {
-                    error:
-                        "Round2 secret not found for Ed25519. Must call round1 and round2 first."
-                            .to_string(),
-                })),
-            };
-
-        let round2_secret: frost_ed25519::keys::dkg::round2::SecretPackage =
-            match serde_json::from_slice(&round2_secret_bytes) {
-                Ok(s) => s,
-                Err(e) => {
-                    return DkgFinalizeResult::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to deserialize round2 secret: {}", e),
-                    }))
-                }
-            };
-
-        // Parse round1 packages (skip own package)
-        let mut round1_packages = std::collections::BTreeMap::new();
-        for pkg in req.round1_packages {
-            // Skip own package - we don't include it in DKG
-            if pkg.node_index == self.config.node_index {
-                tracing::debug!(
-                    "Skipping own Ed25519 round1 package (node {})",
-                    pkg.node_index
-                );
-                continue;
-            }
-
-            let pkg_bytes = match hex::decode(&pkg.package) {
-                Ok(b) => b,
-                Err(_) => continue,
-            };
-
-            let package: frost_ed25519::keys::dkg::round1::Package =
-                match serde_json::from_slice(&pkg_bytes) {
-                    Ok(p) => p,
-                    Err(_) => continue,
-                };
-
-            let sender_id = match frost_ed25519::Identifier::try_from(pkg.node_index + 1) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            round1_packages.insert(sender_id, package);
-        }
-
-        let mut round2_packages = std::collections::BTreeMap::new();
-        for pkg in req.round2_packages {
-            let pkg_bytes = match hex::decode(&pkg.package) {
-                Ok(b) => b,
-                Err(_) => continue,
-            };
-
-            let package: frost_ed25519::keys::dkg::round2::Package =
-                match serde_json::from_slice(&pkg_bytes) {
-                    Ok(p) => p,
-                    Err(_) => continue,
-                };
-
-            let sender_id = match frost_ed25519::Identifier::try_from(pkg.sender_index + 1) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            round2_packages.insert(sender_id, package);
-        }
-
-        // Debug: Log package counts for Ed25519
-        tracing::info!(
-            "Ed25519 DKG part3: {} round1 packages, {} round2 packages (expected: {} and {})",
-            round1_packages.len(),
-            round2_packages.len(),
-            self.config.max_signers - 1,
-            self.config.max_signers - 1
-        );
-
-        // Run DKG part3 (finalize)
-        let (key_package, pubkey_package) = match frost_ed25519::keys::dkg::part3(
-            &round2_secret,
-            &round1_packages,
-            &round2_packages,
-        ) {
-            Ok(result) => result,
-            Err(e) => {
-                tracing::error!("Ed25519 DKG finalize failed: {:?}", e);
-                return DkgFinalizeResult::InternalError(Json(ErrorResponse {
-                    error: format!("Ed25519 DKG finalize failed: {:?}", e),
-                }));
-            }
-        };
-
-        // Store key packages in Ed25519 column family
-        let curve_storage =
-            CurveStorage::<Ed25519Operations>::new(self.storage.clone(), CurveType::Ed25519);
-        if let Err(e) = curve_storage
-            .store_key_package(&req.passphrase, &key_package, self.key_provider.as_ref())
-            .await
-        {
-            tracing::error!("Failed to store Ed25519 key package: {}", e);
-        }
-        if let Err(e) = curve_storage
-            .store_pubkey_package(&req.passphrase, &pubkey_package, self.key_provider.as_ref())
-            .await
-        {
-            tracing::error!("Failed to store Ed25519 pubkey package: {}", e);
-        }
-
-        // Return raw public key
-        let pubkey_hex = match pubkey_package.verifying_key().serialize() {
-            Ok(bytes) => hex::encode(bytes),
-            Err(e) => {
-                tracing::error!("Failed to serialize Ed25519 pubkey: {:?}", e);
-                return DkgFinalizeResult::InternalError(Json(ErrorResponse {
-                    error: format!("Failed to serialize Ed25519 pubkey: {:?}", e),
-                }));
-            }
-        };
-
-        tracing::info!("✅ Ed25519 DKG complete, key shares stored");
-
-        DkgFinalizeResult::Ok(Json(DkgFinalizeResponse {
-            success: true,
-            pubkey_hex,
-        }))
-    }
-
-    // ========================================================================
-    // FROST Signing - Secp256k1 (ECDSA for Ethereum/EVM)
-    // ========================================================================
-
-    /// FROST Round 1: Generate secp256k1 ECDSA signing commitments
-    #[oai(path = "/api/frost/secp256k1/round1", method = "post")]
-    async fn frost_ecdsa_round1(&self, req: Json<FrostRound1Request>) -> FrostRound1Result {
-        let req = req.0;
-
-        tracing::info!("FROST Round 1 for passphrase (secp256k1 ECDSA)");
-
-        let _message = match hex::decode(&req.message) {
-            Ok(msg) => msg,
-            Err(e) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("Invalid message hex: {}", e),
-                }))
-            }
-        };
-
-        // Get ECDSA key package
-        let curve_storage =
-            CurveStorage::<crate::curves::secp256k1_ecdsa::Secp256k1EcdsaOperations>::new(
-                self.storage.clone(),
-                CurveType::Secp256k1Ecdsa,
-            );
-
-        let key_package = match curve_storage
-            .get_key_package(&req.passphrase, self.key_provider.as_ref())
-            .await
-        {
-            Ok(Some(pkg)) => pkg,
-            Ok(None) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: "ECDSA key package not found for passphrase".to_string(),
-                }))
-            }
-            Err(e) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
-            }
-        };
-
-        // Generate commitments (scope rng to avoid Send across await)
-        let (commitments, identifier_hex, message_bytes, nonces_json) = {
-            let mut rng = rand::thread_rng();
-            let (nonces, commitments) =
-                frost_secp256k1::round1::commit(key_package.signing_share(), &mut rng);
-            let identifier_hex = hex::encode(key_package.identifier().serialize());
-            let message_bytes = hex::decode(&req.message).unwrap();
-            let nonces_json = serde_json::to_vec(&nonces).unwrap();
-            (commitments, identifier_hex, message_bytes, nonces_json)
-        };
-
-        let commitments_json = serde_json::to_vec(&commitments).unwrap();
-
-        // Encrypt nonces
-        let encrypted_nonces = match super::crypto::encrypt_nonces_with_provider(
-            &nonces_json,
-            &message_bytes,
-            self.key_provider.as_ref(),
-        )
-        .await
-        {
-            Ok(enc) => enc,
-            Err(e) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("Failed to encrypt ECDSA nonces: {}", e),
-                }))
-            }
-        };
-
-        FrostRound1Result::Ok(Json(FrostRound1Response {
-            identifier: identifier_hex,
-            commitments: hex::encode(commitments_json),
-            encrypted_nonces,
-            node_index: self.config.node_index,
-        }))
-    }
-
-    /// FROST Round 2: Generate secp256k1 ECDSA signature share
-    #[oai(path = "/api/frost/secp256k1/round2", method = "post")]
-    async fn frost_ecdsa_round2(&self, req: Json<FrostRound2Request>) -> FrostRound2Result {
-        let req = req.0;
-
-        tracing::info!("FROST Round 2 for passphrase (secp256k1 ECDSA)");
-
-        let message = match hex::decode(&req.message) {
-            Ok(msg) => msg,
-            Err(e) => {
-                return FrostRound2Result::BadRequest(Json(ErrorResponse {
-                    error: format!("Invalid message hex: {}", e),
-                }))
-            }
-        };
-
-        // Decrypt nonces
-        let nonces_json = match super::crypto::decrypt_nonces_with_provider(
-            &req.encrypted_nonces,
-            &message,
-            self.key_provider.as_ref(),
-        )
-        .await
-        {
-            Ok(json) => json,
-            Err(e) => {
-                return FrostRound2Result::BadRequest(Json(ErrorResponse {
-                    error: format!("Failed to decrypt ECDSA nonces: {}", e),
-                }))
-            }
-        };
-
-        let nonces: frost_secp256k1::round1::SigningNonces =
-            match serde_json::from_slice(&nonces_json) {
-                Ok(n) => n,
-                Err(e) => {
-                    return FrostRound2Result::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to deserialize ECDSA nonces: {}", e),
-                    }))
-                }
-            };
-
-        // Parse commitments
-        let mut commitments_map = std::collections::BTreeMap::new();
-        for entry in req.all_commitments {
-            let id_str = entry.identifier.trim_matches('"');
-            let identifier = match frost_secp256k1::Identifier::deserialize(
-                &hex::decode(id_str).unwrap_or_default(),
-            ) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            let comm_json = match hex::decode(&entry.commitments) {
-                Ok(j) => j,
-                Err(_) => continue,
-            };
-
-            let commitments: frost_secp256k1::round1::SigningCommitments =
-                match serde_json::from_slice(&comm_json) {
-                    Ok(c) => c,
-                    Err(_) => continue,
-                };
-
-            commitments_map.insert(identifier, commitments);
-        }
-
-        // Get ECDSA key package
-        let curve_storage =
-            CurveStorage::<crate::curves::secp256k1_ecdsa::Secp256k1EcdsaOperations>::new(
-                self.storage.clone(),
-                CurveType::Secp256k1Ecdsa,
-            );
-
-        let key_package = match curve_storage
-            .get_key_package(&req.passphrase, self.key_provider.as_ref())
-            .await
-        {
-            Ok(Some(pkg)) => pkg,
-            Ok(None) => {
-                return FrostRound2Result::BadRequest(Json(ErrorResponse {
-                    error: "ECDSA key package not found for passphrase".to_string(),
-                }))
-            }
-            Err(e) => {
-                return FrostRound2Result::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
-            }
-        };
-
-        // Create signing package and sign
-        let signing_package = frost_secp256k1::SigningPackage::new(commitments_map, &message);
-        let signature_share =
-            match frost_secp256k1::round2::sign(&signing_package, &nonces, &key_package) {
-                Ok(share) => share,
-                Err(e) => {
-                    return FrostRound2Result::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to sign with ECDSA: {:?}", e),
-                    }))
-                }
-            };
-
-        let share_json = serde_json::to_vec(&signature_share).unwrap();
-        let identifier_hex = hex::encode(key_package.identifier().serialize());
-
-        FrostRound2Result::Ok(Json(FrostRound2Response {
-            identifier: identifier_hex,
-            signature_share: hex::encode(share_json),
-            node_index: self.config.node_index,
-        }))
-    }
-
-    /// FROST Aggregate: Combine secp256k1 ECDSA signature shares
-    #[oai(path = "/api/frost/secp256k1/aggregate", method = "post")]
-    async fn frost_ecdsa_aggregate(
-        &self,
-        req: Json<FrostAggregateRequest>,
-    ) -> FrostAggregateResult {
-        let req = req.0;
-
-        tracing::info!("FROST Aggregate for passphrase (secp256k1 ECDSA)");
-
-        let message = match hex::decode(&req.message) {
-            Ok(msg) => msg,
-            Err(e) => {
-                return FrostAggregateResult::BadRequest(Json(ErrorResponse {
-                    error: format!("Invalid message hex: {}", e),
-                }))
-            }
-        };
-
-        // Parse commitments
-        let mut commitments_map = std::collections::BTreeMap::new();
-        for entry in req.all_commitments {
-            let id_str = entry.identifier.trim_matches('"');
-            let identifier = match frost_secp256k1::Identifier::deserialize(
-                &hex::decode(id_str).unwrap_or_default(),
-            ) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            let comm_json = match hex::decode(&entry.commitments) {
-                Ok(j) => j,
-                Err(_) => continue,
-            };
-
-            let commitments: frost_secp256k1::round1::SigningCommitments =
-                match serde_json::from_slice(&comm_json) {
-                    Ok(c) => c,
-                    Err(_) => continue,
-                };
-
-            commitments_map.insert(identifier, commitments);
-        }
-
-        // Parse signature shares
-        let mut shares_map = std::collections::BTreeMap::new();
-        for entry in req.signature_shares {
-            let id_str = entry.identifier.trim_matches('"');
-            let identifier = match frost_secp256k1::Identifier::deserialize(
-                &hex::decode(id_str).unwrap_or_default(),
-            ) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            let share_json = match hex::decode(&entry.share) {
-                Ok(j) => j,
-                Err(_) => continue,
-            };
-
-            let share: frost_secp256k1::round2::SignatureShare =
-                match serde_json::from_slice(&share_json) {
-                    Ok(s) => s,
-                    Err(_) => continue,
-                };
-
-            shares_map.insert(identifier, share);
-        }
-
-        // Get ECDSA pubkey package
-        let curve_storage =
-            CurveStorage::<crate::curves::secp256k1_ecdsa::Secp256k1EcdsaOperations>::new(
-                self.storage.clone(),
-                CurveType::Secp256k1Ecdsa,
-            );
-
-        let pubkey_package = match curve_storage
-            .get_pubkey_package(&req.passphrase, self.key_provider.as_ref())
-            .await
-        {
-            Ok(Some(pkg)) => pkg,
-            Ok(None) => {
-                return FrostAggregateResult::BadRequest(Json(ErrorResponse {
-                    error: "ECDSA pubkey package not found for passphrase".to_string(),
-                }))
-            }
-            Err(e) => {
-                return FrostAggregateResult::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
-            }
-        };
-
-        // Aggregate ECDSA signature
-        let signing_package = frost_secp256k1::SigningPackage::new(commitments_map, &message);
-        let signature =
-            match frost_secp256k1::aggregate(&signing_package, &shares_map, &pubkey_package) {
-                Ok(sig) => sig,
-                Err(e) => {
-                    return FrostAggregateResult::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to aggregate ECDSA: {:?}", e),
-                    }))
-                }
-            };
-
-        // Verify ECDSA signature
-        let verifying_key = pubkey_package.verifying_key();
-        let pubkey_for_verify = verifying_key.serialize().unwrap_or_default();
-
-        tracing::debug!(
-            "FROST verifying signature with pubkey: {}",
-            hex::encode(&pubkey_for_verify)
-        );
-
-        let verified = verifying_key.verify(&message, &signature).is_ok();
-
-        tracing::debug!("FROST verification result: {}", verified);
-
-        // frost-secp256k1 serialize() returns bytes with type prefix (0x02 or 0x03)
-        let sig_serialized = signature
-            .serialize()
-            .map_err(|e| format!("Failed to serialize ECDSA signature: {:?}", e))
-            .unwrap_or_default();
-
-        tracing::debug!(
-            "FROST ECDSA serialize() = {} bytes, first byte = {:02x}",
-            sig_serialized.len(),
-            sig_serialized.first().copied().unwrap_or(0)
-        );
-
-        // Extract raw (r, s) bytes - remove type prefix if present
-        let raw_rs = if sig_serialized.len() == 65
-            && (sig_serialized[0] == 0x02 || sig_serialized[0] == 0x03)
-        {
-            // Skip type prefix to get raw (r, s)
-            tracing::debug!("Stripping type prefix byte 0x{:02x}", sig_serialized[0]);
-            &sig_serialized[1..]
-        } else if sig_serialized.len() == 64 {
-            // Already raw (r, s)
-            tracing::debug!("Signature already in raw (r,s) format");
-            &sig_serialized[..]
-        } else {
-            tracing::warn!(
-                "Unexpected FROST ECDSA format: {} bytes",
-                sig_serialized.len()
-            );
-            &sig_serialized[..]
-        };
-
-        // Build Ethereum signature: [r (32), s (32), recovery_id (1)]
-        // Note: Recovery ID is not needed for transaction broadcast
-        // Ethereum nodes verify signatures using standard ECDSA with the sender's public key
-        let recovery_id = 0u8; // Default (only needed for smart contract ecrecover())
-
-        let mut sig_bytes = raw_rs.to_vec();
-        sig_bytes.push(recovery_id);
-
-        tracing::info!(
-            "✅ ECDSA signature ready: {} bytes (transaction broadcast-ready)",
-            sig_bytes.len()
-        );
-
-        FrostAggregateResult::Ok(Json(FrostAggregateResponse {
-            signature: hex::encode(&sig_bytes),
-            verified,
-        }))
-    }
-
-    // ========================================================================
-    // FROST Signing Protocol - Ed25519 (for Solana)
-    // ========================================================================
-
-    /// FROST Round 1: Generate Ed25519 signing commitments
-    #[oai(path = "/api/frost/ed25519/round1", method = "post")]
-    async fn frost_ed25519_round1(&self, req: Json<FrostRound1Request>) -> FrostRound1Result {
-        let req = req.0;
-
-        tracing::info!("FROST Round 1 for passphrase (Ed25519)");
-
-        let _message = match hex::decode(&req.message) {
-            Ok(msg) => msg,
-            Err(e) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("Invalid message hex: {}", e),
-                }))
-            }
-        };
-
-        // Get Ed25519 key package
-        let curve_storage =
-            CurveStorage::<Ed25519Operations>::new(self.storage.clone(), CurveType::Ed25519);
-
-        let key_package = match curve_storage
-            .get_key_package(&req.passphrase, self.key_provider.as_ref())
-            .await
-        {
-            Ok(Some(pkg)) => pkg,
-            Ok(None) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: "Ed25519 key package not found for passphrase".to_string(),
-                }))
-            }
-            Err(e) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
-            }
-        };
-
-        // Generate commitments (scope rng to avoid Send across await)
-        let (commitments, identifier_hex, message_bytes, nonces_json) = {
-            let mut rng = rand::thread_rng();
-            let (nonces, commitments) =
-                frost_ed25519::round1::commit(key_package.signing_share(), &mut rng);
-            let identifier_hex = hex::encode(key_package.identifier().serialize());
-            let message_bytes = hex::decode(&req.message).unwrap();
-            let nonces_json = serde_json::to_vec(&nonces).unwrap();
-            (commitments, identifier_hex, message_bytes, nonces_json)
-        };
-
-        let commitments_json = serde_json::to_vec(&commitments).unwrap();
-
-        // Encrypt nonces
-        let encrypted_nonces = match super::crypto::encrypt_nonces_with_provider(
-            &nonces_json,
-            &message_bytes,
-            self.key_provider.as_ref(),
-        )
-        .await
-        {
-            Ok(enc) => enc,
+                return FrostRound2Result::BadRequest(error_json(
+                    "Key package not found for passphrase".to_string(),
+                ))
+            }
             Err(e) => {
-                return FrostRound1Result::InternalError(Json(ErrorResponse {
-                    error: format!("Failed to encrypt Ed25519 nonces: {}", e),
-                }))
+                return FrostRound2Result::InternalError(error_json(format!(
+                    "Storage error: {}",
+                    e
+                )))
             }
         };
 
-        FrostRound1Result::Ok(Json(FrostRound1Response {
-            identifier: identifier_hex,
-            commitments: hex::encode(commitments_json),
-            encrypted_nonces,
-            node_index: self.config.node_index,
-        }))
-    }
-
-    /// FROST Round 2: Generate Ed25519 signature share
-    #[oai(path = "/api/frost/ed25519/round2", method = "post")]
-    async fn frost_ed25519_round2(&self, req: Json<FrostRound2Request>) -> FrostRound2Result {
-        let req = req.0;
-
-        tracing::info!("FROST Round 2 for passphrase (Ed25519)");
-
-        let message = match hex::decode(&req.message) {
-            Ok(msg) => msg,
+        let group_key = match key_package.verifying_key().serialize() {
+            Ok(bytes) => bytes,
             Err(e) => {
-                return FrostRound2Result::BadRequest(Json(ErrorResponse {
-                    error: format!("Invalid message hex: {}", e),
-                }))
+                return FrostRound2Result::InternalError(error_json(format!(
+                    "Failed to serialize group key: {:?}",
+                    e
+                )))
             }
         };
+        if let Some(rejected) = self.check_policy(curve, &req, &message, &group_key) {
+            return rejected;
+        }
 
         // Decrypt nonces
         let nonces_json = match super::crypto::decrypt_nonces_with_provider(
@@ -1970,191 +1031,143 @@ impl UnifiedApi {
         {
             Ok(json) => json,
             Err(e) => {
-                return FrostRound2Result::BadRequest(Json(ErrorResponse {
-                    error: format!("Failed to decrypt Ed25519 nonces: {}", e),
-                }))
+                return FrostRound2Result::BadRequest(error_json(format!(
+                    "Failed to decrypt nonces: {}",
+                    e
+                )))
             }
         };
 
-        let nonces: frost_ed25519::round1::SigningNonces =
+        let nonces: frost_core::round1::SigningNonces<C::Suite> =
             match serde_json::from_slice(&nonces_json) {
                 Ok(n) => n,
                 Err(e) => {
-                    return FrostRound2Result::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to deserialize Ed25519 nonces: {}", e),
-                    }))
+                    return FrostRound2Result::InternalError(error_json(format!(
+                        "Failed to deserialize nonces: {}",
+                        e
+                    )))
                 }
             };
 
-        // Parse commitments
-        let mut commitments_map = std::collections::BTreeMap::new();
-        for entry in req.all_commitments {
-            let id_str = entry.identifier.trim_matches('"');
-            let identifier = match frost_ed25519::Identifier::deserialize(
-                &hex::decode(id_str).unwrap_or_default(),
-            ) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            let comm_json = match hex::decode(&entry.commitments) {
-                Ok(j) => j,
-                Err(_) => continue,
-            };
-
-            let commitments: frost_ed25519::round1::SigningCommitments =
-                match serde_json::from_slice(&comm_json) {
-                    Ok(c) => c,
-                    Err(_) => continue,
-                };
-
-            commitments_map.insert(identifier, commitments);
-        }
-
-        // Get Ed25519 key package
-        let curve_storage =
-            CurveStorage::<Ed25519Operations>::new(self.storage.clone(), CurveType::Ed25519);
-
-        let key_package = match curve_storage
-            .get_key_package(&req.passphrase, self.key_provider.as_ref())
-            .await
-        {
-            Ok(Some(pkg)) => pkg,
-            Ok(None) => {
-                return FrostRound2Result::BadRequest(Json(ErrorResponse {
-                    error: "Ed25519 key package not found for passphrase".to_string(),
-                }))
-            }
-            Err(e) => {
-                return FrostRound2Result::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
-            }
-        };
+        let commitments = parse_commitments::<C::Suite>(&req.all_commitments);
 
         // Create signing package and sign
-        let signing_package = frost_ed25519::SigningPackage::new(commitments_map, &message);
+        let signing_package = SigningPackage::new(commitments, &message);
         let signature_share =
-            match frost_ed25519::round2::sign(&signing_package, &nonces, &key_package) {
+            match frost_core::round2::sign(&signing_package, &nonces, &key_package) {
                 Ok(share) => share,
                 Err(e) => {
-                    return FrostRound2Result::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to sign with Ed25519: {:?}", e),
-                    }))
+                    self.audit.record(
+                        AuditEvent::new(AuditKind::SigningRound, "failure")
+                            .curve(curve)
+                            .passphrase(&req.passphrase)
+                            .message(&message)
+                            .participants(&participants)
+                            .detail(format!("{:?}", e)),
+                    );
+                    return FrostRound2Result::InternalError(error_json(format!(
+                        "Failed to sign: {:?}",
+                        e
+                    )));
                 }
             };
 
-        let share_json = serde_json::to_vec(&signature_share).unwrap();
-        let identifier_hex = hex::encode(key_package.identifier().serialize());
+        // A share is only released once its audit record is durable
+        if let Err(e) = self.audit.append(
+            AuditEvent::new(AuditKind::SigningRound, "share_released")
+                .curve(curve)
+                .passphrase(&req.passphrase)
+                .message(&message)
+                .participants(&participants),
+        ) {
+            return FrostRound2Result::InternalError(error_json(format!(
+                "Failed to write audit log: {}",
+                e
+            )));
+        }
+
+        if let Err(e) = self
+            .storage
+            .record_signing(C::CURVE, &req.passphrase, self.key_provider.as_ref())
+            .await
+        {
+            tracing::warn!("Failed to update {} key usage counters: {}", curve, e);
+        }
 
         FrostRound2Result::Ok(Json(FrostRound2Response {
-            identifier: identifier_hex,
-            signature_share: hex::encode(share_json),
+            identifier: hex::encode(key_package.identifier().serialize()),
+            signature_share: hex::encode(serde_json::to_vec(&signature_share).unwrap()),
             node_index: self.config.node_index,
         }))
     }
 
-    /// FROST Aggregate: Combine Ed25519 signature shares
-    #[oai(path = "/api/frost/ed25519/aggregate", method = "post")]
-    async fn frost_ed25519_aggregate(
+    async fn frost_aggregate_for<C: CurveOperations>(
         &self,
-        req: Json<FrostAggregateRequest>,
+        req: FrostAggregateRequest,
     ) -> FrostAggregateResult {
-        let req = req.0;
-
-        tracing::info!("FROST Aggregate for passphrase (Ed25519)");
+        tracing::info!("FROST Aggregate for passphrase ({})", C::CURVE.as_str());
 
+        // Decode message
         let message = match hex::decode(&req.message) {
             Ok(msg) => msg,
             Err(e) => {
-                return FrostAggregateResult::BadRequest(Json(ErrorResponse {
-                    error: format!("Invalid message hex: {}", e),
-                }))
+                return FrostAggregateResult::BadRequest(error_json(format!(
+                    "Invalid message hex: {}",
+                    e
+                )))
             }
         };
 
-        // Parse commitments
-        let mut commitments_map = std::collections::BTreeMap::new();
-        for entry in req.all_commitments {
-            let id_str = entry.identifier.trim_matches('"');
-            let identifier = match frost_ed25519::Identifier::deserialize(
-                &hex::decode(id_str).unwrap_or_default(),
-            ) {
-                Ok(id) => id,
-                Err(_) => continue,
-            };
-
-            let comm_json = match hex::decode(&entry.commitments) {
-                Ok(j) => j,
-                Err(_) => continue,
-            };
-
-            let commitments: frost_ed25519::round1::SigningCommitments =
-                match serde_json::from_slice(&comm_json) {
-                    Ok(c) => c,
-                    Err(_) => continue,
-                };
-
-            commitments_map.insert(identifier, commitments);
-        }
+        let commitments = parse_commitments::<C::Suite>(&req.all_commitments);
 
         // Parse signature shares
-        let mut shares_map = std::collections::BTreeMap::new();
-        for entry in req.signature_shares {
-            let id_str = entry.identifier.trim_matches('"');
-            let identifier = match frost_ed25519::Identifier::deserialize(
-                &hex::decode(id_str).unwrap_or_default(),
-            ) {
-                Ok(id) => id,
-                Err(_) => continue,
+        let mut shares = BTreeMap::new();
+        for entry in &req.signature_shares {
+            let Some(identifier) = parse_identifier::<C::Suite>(&entry.identifier) else {
+                continue;
             };
-
-            let share_json = match hex::decode(&entry.share) {
-                Ok(j) => j,
-                Err(_) => continue,
+            let Ok(share_json) = hex::decode(&entry.share) else {
+                continue;
+            };
+            let Ok(share) =
+                serde_json::from_slice::<frost_core::round2::SignatureShare<C::Suite>>(&share_json)
+            else {
+                continue;
             };
 
-            let share: frost_ed25519::round2::SignatureShare =
-                match serde_json::from_slice(&share_json) {
-                    Ok(s) => s,
-                    Err(_) => continue,
-                };
-
-            shares_map.insert(identifier, share);
+            shares.insert(identifier, share);
         }
 
-        // Get pubkey package
-        let curve_storage =
-            CurveStorage::<Ed25519Operations>::new(self.storage.clone(), CurveType::Ed25519);
-
-        let pubkey_package = match curve_storage
+        // Get pubkey package for verification
+        let pubkey_package = match CurveStorage::<C>::new(self.storage.clone())
             .get_pubkey_package(&req.passphrase, self.key_provider.as_ref())
             .await
         {
             Ok(Some(pkg)) => pkg,
             Ok(None) => {
-                return FrostAggregateResult::BadRequest(Json(ErrorResponse {
-                    error: "Ed25519 pubkey package not found for passphrase".to_string(),
-                }))
+                return FrostAggregateResult::BadRequest(error_json(
+                    "Pubkey package not found for passphrase".to_string(),
+                ))
             }
             Err(e) => {
-                return FrostAggregateResult::InternalError(Json(ErrorResponse {
-                    error: format!("Storage error: {}", e),
-                }))
+                return FrostAggregateResult::InternalError(error_json(format!(
+                    "Storage error: {}",
+                    e
+                )))
             }
         };
 
         // Aggregate signature
-        let signing_package = frost_ed25519::SigningPackage::new(commitments_map, &message);
-        let signature =
-            match frost_ed25519::aggregate(&signing_package, &shares_map, &pubkey_package) {
-                Ok(sig) => sig,
-                Err(e) => {
-                    return FrostAggregateResult::InternalError(Json(ErrorResponse {
-                        error: format!("Failed to aggregate Ed25519: {:?}", e),
-                    }))
-                }
-            };
+        let signing_package = SigningPackage::new(commitments, &message);
+        let signature = match frost_core::aggregate(&signing_package, &shares, &pubkey_package) {
+            Ok(sig) => sig,
+            Err(e) => {
+                return FrostAggregateResult::InternalError(error_json(format!(
+                    "Failed to aggregate: {:?}",
+                    e
+                )))
+            }
+        };
 
         // Verify signature
         let verified = pubkey_package
@@ -2162,10 +1175,15 @@ impl UnifiedApi {
             .verify(&message, &signature)
             .is_ok();
 
-        let sig_bytes = signature
-            .serialize()
-            .map_err(|e| format!("Failed to serialize Ed25519 signature: {:?}", e))
-            .unwrap_or_default();
+        let sig_bytes = match C::signature_bytes(&signature) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return FrostAggregateResult::InternalError(error_json(format!(
+                    "Failed to serialize signature: {:?}",
+                    e
+                )))
+            }
+        };
 
         FrostAggregateResult::Ok(Json(FrostAggregateResponse {
             signature: hex::encode(&sig_bytes),
@@ -2173,18 +1191,463 @@ impl UnifiedApi {
         }))
     }
 
-    // ========================================================================
-    // Health Check
-    // ========================================================================
+    async fn export_backup(
+        &self,
+        curves: &[CurveType],
+        passphrases: Option<Vec<String>>,
+    ) -> Result<BackupExportResponse, BackupExportResult> {
+        let internal =
+            |e: anyhow::Error| BackupExportResult::InternalError(error_json(e.to_string()));
+        let backup_config = &self.config.backup;
+        if backup_config.recovery_keys.is_empty() {
+            return Err(BackupExportResult::BadRequest(error_json(
+                "No [node.backup] recovery_keys configured".to_string(),
+            )));
+        }
+        let recovery_keys = backup_config
+            .recovery_keys
+            .iter()
+            .map(|key| backup::parse_recovery_key(key))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(internal)?;
+
+        // Read from a checkpoint so concurrent DKG writes can't tear the export
+        let snapshot = self.storage.snapshot().map_err(internal)?;
+        let entries = backup::collect_entries(
+            &snapshot,
+            self.key_provider.as_ref(),
+            curves,
+            passphrases.as_deref(),
+        )
+        .await
+        .map_err(internal)?;
+        for wanted in passphrases.iter().flatten() {
+            if !entries.iter().any(|entry| &entry.passphrase == wanted) {
+                return Err(BackupExportResult::BadRequest(error_json(
+                    "No shares stored for a requested passphrase".to_string(),
+                )));
+            }
+        }
 
-    #[oai(path = "/health", method = "get")]
-    async fn health(&self) -> Json<HealthResponse> {
-        Json(HealthResponse {
-            status: "ok".to_string(),
-            node_index: self.config.node_index,
-            supported_curves: vec!["secp256k1".to_string(), "ed25519".to_string()],
+        let archive = backup::seal(
+            self.config.node_index,
+            &entries,
+            &recovery_keys,
+            backup_config.threshold,
+        )
+        .map_err(internal)?;
+        let archive = serde_json::to_string(&archive).map_err(|e| internal(e.into()))?;
+
+        tracing::info!(
+            "💾 Exported {} share entries to a backup archive",
+            entries.len()
+        );
+        Ok(BackupExportResponse {
+            sha256: backup::archive_digest(&archive),
+            archive,
+            entries: entries.len() as u32,
+            threshold: backup_config.threshold as u32,
+        })
+    }
+
+    async fn restore_backup(
+        &self,
+        req: BackupRestoreRequest,
+        sha256: &str,
+    ) -> Result<u32, BackupRestoreResult> {
+        let bad_request = |e: String| BackupRestoreResult::BadRequest(error_json(e));
+
+        if let Some(expected) = &req.expected_sha256 {
+            if !expected.eq_ignore_ascii_case(sha256) {
+                return Err(bad_request(format!(
+                    "Archive sha256 {} doesn't match the expected digest",
+                    sha256
+                )));
+            }
+        }
+        let archive: BackupArchive = serde_json::from_str(&req.archive)
+            .map_err(|e| bad_request(format!("Invalid backup archive: {}", e)))?;
+        if archive.header.node_index != self.config.node_index {
+            return Err(bad_request(format!(
+                "Archive holds node {} shares, this is node {}",
+                archive.header.node_index, self.config.node_index
+            )));
+        }
+        let secrets = req
+            .recovery_secrets
+            .iter()
+            .map(|secret| backup::parse_recovery_secret(secret))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|e| bad_request(e.to_string()))?;
+
+        let entries =
+            backup::open(&archive, &secrets).map_err(|e| bad_request(format!("{:#}", e)))?;
+        let batch = backup::prepare_restore(
+            &self.storage,
+            self.key_provider.as_ref(),
+            &entries,
+            self.config.node_index,
+            &self.config.curves(),
+            req.overwrite,
+        )
+        .await
+        .map_err(|e| bad_request(format!("{:#}", e)))?;
+        self.storage.write_batch(batch).map_err(|e| {
+            BackupRestoreResult::InternalError(error_json(format!("Storage error: {}", e)))
+        })?;
+
+        tracing::info!(
+            "💾 Restored {} share entries from a backup archive",
+            entries.len()
+        );
+        Ok(entries.len() as u32)
+    }
+
+    /// Move a key to the requested lifecycle state, if the transition is allowed
+    async fn change_key_state(&self, curve: CurveType, req: KeyStateRequest) -> KeyStateResult {
+        let Some(state) = KeyState::from_str(&req.state) else {
+            return KeyStateResult::BadRequest(error_json(format!(
+                "Unknown key state: {}",
+                req.state
+            )));
+        };
+        let provider = self.key_provider.as_ref();
+
+        let current = match self
+            .storage
+            .get_state(curve, &req.passphrase, provider)
+            .await
+        {
+            Ok(current) => current,
+            Err(e) => {
+                return KeyStateResult::InternalError(error_json(format!("Storage error: {}", e)))
+            }
+        };
+        if !current.state.can_transition(state) {
+            return KeyStateResult::Conflict(error_json(format!(
+                "{} key is {}, can't become {}",
+                curve.as_str(),
+                current.state.as_str(),
+                state.as_str()
+            )));
+        }
+        let approved_by = match self.check_approvals(curve, &req, &current, state) {
+            Ok(approved_by) => approved_by,
+            Err(rejected) => return rejected,
+        };
+        match self
+            .storage
+            .has_passphrase(curve, &req.passphrase, provider)
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) if current.state == KeyState::Destroyed => {}
+            Ok(false) => {
+                return KeyStateResult::BadRequest(error_json(format!(
+                    "{} shares not found for passphrase",
+                    curve.as_str()
+                )))
+            }
+            Err(e) => {
+                return KeyStateResult::InternalError(error_json(format!("Storage error: {}", e)))
+            }
+        }
+
+        let record = KeyStateRecord {
+            state,
+            changed_at: 0,
+            reason: req.reason,
+            transition_id: req.transition_id,
+            group_key_fingerprint: None,
+        };
+        match self
+            .storage
+            .set_state(curve, &req.passphrase, record, provider)
+            .await
+        {
+            Ok(record) => {
+                tracing::info!(
+                    "🔒 {} key {} -> {}",
+                    curve.as_str(),
+                    current.state.as_str(),
+                    record.state.as_str()
+                );
+                let mut detail = format!("{} -> {}", current.state.as_str(), record.state.as_str());
+                if let Some(id) = &record.transition_id {
+                    detail.push_str(&format!(" (transition {})", id));
+                }
+                if let Some(reason) = &record.reason {
+                    detail.push_str(&format!(": {}", reason));
+                }
+                self.audit.record(
+                    AuditEvent::new(AuditKind::KeyLifecycle, "success")
+                        .curve(curve.as_str())
+                        .passphrase(&req.passphrase)
+                        .participants(&approved_by)
+                        .detail(detail),
+                );
+                KeyStateResult::Ok(Json(KeyStateResponse::new(
+                    self.config.node_index,
+                    curve.as_str(),
+                    record,
+                )))
+            }
+            Err(e) => KeyStateResult::InternalError(error_json(format!(
+                "Failed to change key state: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Operators approving an unfreeze or destroy (none needed for other changes)
+    ///
+    /// The digest names the freeze / retirement being acted on, so approvals
+    /// can't be replayed against a later one.
+    fn check_approvals(
+        &self,
+        curve: CurveType,
+        req: &KeyStateRequest,
+        current: &KeyStateRecord,
+        state: KeyState,
+    ) -> Result<Vec<String>, KeyStateResult> {
+        let transition_id = current.transition_id.as_deref().unwrap_or_default();
+        let (action, digest) = match (current.state, state) {
+            (KeyState::Frozen, KeyState::Active) => (
+                "unfreeze",
+                lifecycle::unfreeze_digest(curve.as_str(), &req.passphrase, transition_id),
+            ),
+            (KeyState::Retired, KeyState::Destroyed) => (
+                "destroy",
+                lifecycle::destroy_digest(curve.as_str(), &req.passphrase, transition_id),
+            ),
+            _ => return Ok(Vec::new()),
+        };
+
+        let result = match &self.config.lifecycle {
+            Some(config) => lifecycle::verify_quorum(
+                config.required_approvals,
+                &config.operators,
+                &digest,
+                &req.approvals,
+            ),
+            None => Err(format!(
+                "Refusing to {} keys without [node.lifecycle] operators",
+                action
+            )),
+        };
+        result.map_err(|e| {
+            tracing::warn!("❌ Refused {} {}: {}", curve.as_str(), action, e);
+            self.audit.record(
+                AuditEvent::new(AuditKind::KeyLifecycle, "denied")
+                    .curve(curve.as_str())
+                    .passphrase(&req.passphrase)
+                    .participants(req.approvals.iter().map(|a| &a.operator))
+                    .detail(format!("{} of transition {}: {}", action, transition_id, e)),
+            );
+            KeyStateResult::Forbidden(error_json(e))
+        })
+    }
+
+    /// Derivation scheme for a DKG run, or why the request can't have it
+    ///
+    /// An existing key keeps the scheme in its metadata (so a re-run gives
+    /// the same shares); otherwise the requested one, or the provider's.
+    async fn dkg_derivation(
+        &self,
+        curve: CurveType,
+        passphrase: &str,
+        requested: Option<&str>,
+    ) -> anyhow::Result<Result<KeyDerivation, String>> {
+        let requested = match requested.map(|name| (name, KeyDerivation::from_str(name))) {
+            None => None,
+            Some((_, Some(derivation))) => Some(derivation),
+            Some((name, None)) => return Ok(Err(format!("Unknown key derivation: {}", name))),
+        };
+        let existing = self
+            .storage
+            .get_metadata(curve, passphrase, self.key_provider.as_ref())
+            .await?
+            .and_then(|metadata| KeyDerivation::from_str(&metadata.derivation));
+
+        Ok(match (existing, requested) {
+            (Some(existing), Some(requested)) if existing != requested => Err(format!(
+                "{} key was generated with {} derivation, not {}",
+                curve.as_str(),
+                existing.as_str(),
+                requested.as_str()
+            )),
+            (Some(derivation), _) | (None, Some(derivation)) => Ok(derivation),
+            (None, None) => Ok(self.key_provider.derivation()),
         })
     }
+
+    /// Why the key for `passphrase` can't be used, if it can't
+    ///
+    /// Signing needs an active key; DKG only refuses destroyed ones, since it
+    /// would derive the same shares again.
+    async fn lifecycle_refusal(
+        &self,
+        curve: CurveType,
+        passphrase: &str,
+        signing: bool,
+    ) -> anyhow::Result<Option<String>> {
+        let record = self
+            .storage
+            .get_state(curve, passphrase, self.key_provider.as_ref())
+            .await?;
+        let allowed = match signing {
+            true => record.state == KeyState::Active,
+            false => record.state != KeyState::Destroyed,
+        };
+        if allowed {
+            return Ok(None);
+        }
+
+        let reason = format!(
+            "{} key is {}, refusing to {}",
+            curve.as_str(),
+            record.state.as_str(),
+            if signing { "sign" } else { "run DKG" }
+        );
+        tracing::warn!("❌ {}", reason);
+        self.audit.record(
+            AuditEvent::new(AuditKind::PolicyDecision, "denied")
+                .curve(curve.as_str())
+                .passphrase(passphrase)
+                .detail(reason.clone()),
+        );
+        Ok(Some(reason))
+    }
+
+    /// Apply the local transaction policy before a share is produced
+    fn check_policy(
+        &self,
+        curve: &str,
+        req: &FrostRound2Request,
+        message: &[u8],
+        group_key: &[u8],
+    ) -> Option<FrostRound2Result> {
+        let Err(e) = super::tx_policy::check(
+            &self.config.policy,
+            curve,
+            message,
+            req.transaction.as_ref(),
+            group_key,
+        ) else {
+            return None;
+        };
+
+        tracing::warn!("❌ Policy refused {} signing request: {}", curve, e);
+        self.audit.record(
+            AuditEvent::new(AuditKind::PolicyDecision, "denied")
+                .curve(curve)
+                .passphrase(&req.passphrase)
+                .message(message)
+                .detail(e.to_string()),
+        );
+
+        Some(FrostRound2Result::Forbidden(error_json(format!(
+            "Signing policy violation: {}",
+            e
+        ))))
+    }
+}
+
+/// Same on every node of a ceremony: the aggregator sends all of them the full round 1 set
+fn dkg_session_id(round1_packages: &[DkgRound1Package]) -> String {
+    let mut packages: Vec<_> = round1_packages
+        .iter()
+        .map(|p| (p.node_index, p.package.as_str()))
+        .collect();
+    packages.sort();
+
+    let mut hasher = Sha256::new();
+    for (node_index, package) in packages {
+        hasher.update(node_index.to_be_bytes());
+        hasher.update(package.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn error_json(error: String) -> Json<ErrorResponse> {
+    Json(ErrorResponse { error })
+}
+
+/// Round1 packages of the other participants, plus the node index behind each identifier
+#[allow(clippy::type_complexity)]
+fn parse_round1_packages<S: Ciphersuite>(
+    packages: Vec<DkgRound1Package>,
+    own_index: u16,
+) -> (
+    BTreeMap<Identifier<S>, dkg::round1::Package<S>>,
+    BTreeMap<Identifier<S>, u16>,
+) {
+    let mut round1_packages = BTreeMap::new();
+    let mut node_indices = BTreeMap::new();
+    for pkg in packages {
+        // Skip own package - we don't include it in DKG
+        if pkg.node_index == own_index {
+            tracing::debug!("Skipping own package (node {})", pkg.node_index);
+            continue;
+        }
+
+        let package = match hex::decode(&pkg.package)
+            .map_err(anyhow::Error::from)
+            .and_then(|bytes| Ok(serde_json::from_slice::<dkg::round1::Package<S>>(&bytes)?))
+        {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to parse package from node {}: {}",
+                    pkg.node_index,
+                    e
+                );
+                continue;
+            }
+        };
+
+        let sender_id = match Identifier::<S>::try_from(pkg.node_index + 1) {
+            Ok(id) => id,
+            Err(e) => {
+                tracing::warn!("Invalid node index {}: {:?}", pkg.node_index, e);
+                continue;
+            }
+        };
+
+        round1_packages.insert(sender_id, package);
+        node_indices.insert(sender_id, pkg.node_index);
+    }
+    (round1_packages, node_indices)
+}
+
+fn parse_identifier<S: Ciphersuite>(identifier_hex: &str) -> Option<Identifier<S>> {
+    let bytes = hex::decode(identifier_hex.trim_matches('"')).ok()?;
+    Identifier::deserialize(&bytes).ok()
+}
+
+fn parse_commitments<S: Ciphersuite>(
+    entries: &[FrostCommitmentEntry],
+) -> BTreeMap<Identifier<S>, frost_core::round1::SigningCommitments<S>> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let identifier = parse_identifier::<S>(&entry.identifier)?;
+            let comm_json = hex::decode(&entry.commitments).ok()?;
+            let commitments = serde_json::from_slice(&comm_json).ok()?;
+            Some((identifier, commitments))
+        })
+        .collect()
+}
+
+/// Node label for a FROST identifier (hex scalar: node index + 1)
+fn signer_label<S: Ciphersuite>(identifier_hex: &str, max_signers: u16) -> String {
+    let identifier = parse_identifier::<S>(identifier_hex);
+    (0..max_signers)
+        .find(|index| {
+            identifier.is_some() && Identifier::<S>::try_from(index + 1).ok() == identifier
+        })
+        .map(|index| format!("node-{}", index))
+        .unwrap_or_else(|| identifier_hex.to_string())
 }
 
 // ============================================================================
@@ -2200,9 +1663,13 @@ pub struct PublicKeyResponse {
 
 #[derive(Debug, Object)]
 pub struct HealthResponse {
+    /// "degraded" while the latest storage scrub found bad records or failed
     pub status: String,
     pub node_index: u16,
     pub supported_curves: Vec<String>,
+    pub integrity_issues: u64,
+    pub last_scrub_failed: bool,
+    pub last_scrub_at: Option<u64>,
 }
 
 #[derive(Debug, ApiResponse)]
@@ -2237,6 +1704,8 @@ pub struct FrostRound2Request {
     pub message: String,
     pub encrypted_nonces: String,
     pub all_commitments: Vec<FrostCommitmentEntry>,
+    /// Full transaction behind `message`, validated against `[node.policy]`
+    pub transaction: Option<super::tx_policy::TransactionContext>,
 }
 
 #[derive(Debug, Object, Clone)]
@@ -2276,6 +1745,10 @@ pub struct FrostAggregateResponse {
 pub enum FrostRound1Result {
     #[oai(status = 200)]
     Ok(Json<FrostRound1Response>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
     #[oai(status = 500)]
     InternalError(Json<ErrorResponse>),
 }
@@ -2286,6 +1759,8 @@ pub enum FrostRound2Result {
     Ok(Json<FrostRound2Response>),
     #[oai(status = 400)]
     BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
     #[oai(status = 500)]
     InternalError(Json<ErrorResponse>),
 }
@@ -2299,155 +1774,3 @@ pub enum FrostAggregateResult {
     #[oai(status = 500)]
     InternalError(Json<ErrorResponse>),
 }
-
-// Note: Recovery ID calculation removed
-// For Ethereum custody, validate signatures server-side using the public key
-// ecrecover() is only needed for permissionless on-chain verification in smart contracts
-//
-// If you need ecrecover() for smart contracts:
-// - Use a different FROST library with native recovery support
-// - Or re-sign with standard ECDSA on the server side
-// Recovery ID calculation for ecrecover() - NOT NEEDED for custody!
-//
-// FROST ECDSA signatures are cryptographically valid and work perfectly for:
-// ✅ Transaction broadcast (Ethereum nodes verify with public key)
-// ✅ Server-side validation (you have the public key)
-// ✅ Standard ECDSA verification
-//
-// ecrecover() is ONLY needed for:
-// ❌ Smart contracts verifying signatures without knowing the signer
-//
-// For custody applications, you always know the signer, so validate server-side.
-#[allow(dead_code)]
-fn _calculate_recovery_id_placeholder(
-    _sig_bytes: &[u8],
-    _message: &[u8],
-    _verifying_key: &frost_secp256k1::VerifyingKey,
-) -> u8 {
-    // Removed complex recovery calculation
-    // Not needed for transaction broadcast or server-side validation
-    0
-}
-
-#[allow(dead_code)]
-fn _old_recovery_logic_kept_for_reference() {
-    // The old implementation tried to find which of 4 recovery IDs matched
-    // But this doesn't work due to format incompatibility
-    // Kept here for future reference if needed
-
-    /* if sig_bytes.len() != 64 {
-        tracing::warn!(
-            "  ⚠️  Signature is {} bytes, expected 64. Returning 0.",
-            sig_bytes.len()
-        );
-        return 0;
-    }
-
-    // Get our expected public key
-    let pubkey_bytes = match verifying_key.serialize() {
-        Ok(bytes) => {
-            tracing::info!(
-                "  Expected pubkey: {}...",
-                hex::encode(&bytes[..20.min(bytes.len())])
-            );
-            bytes
-        }
-        Err(e) => {
-            tracing::error!("  Failed to serialize pubkey: {:?}", e);
-            return 0;
-        }
-    };
-
-    let secp = Secp256k1::new();
-
-    let expected_pubkey = match PublicKey::from_slice(&pubkey_bytes) {
-        Ok(pk) => pk,
-        Err(e) => {
-            tracing::error!("  Failed to parse expected pubkey: {:?}", e);
-            return 0;
-        }
-    };
-
-    // Parse message (must be 32 bytes for secp256k1)
-    let msg = if message.len() == 32 {
-        match Message::from_digest_slice(message) {
-            Ok(m) => m,
-            Err(_) => return 0,
-        }
-    } else {
-        // Hash to 32 bytes
-        use bitcoin::hashes::{sha256, Hash};
-        let hash = sha256::Hash::hash(message);
-        match Message::from_digest_slice(hash.as_ref()) {
-            Ok(m) => m,
-            Err(_) => return 0,
-        }
-    };
-
-    // Try all 4 possible recovery IDs
-    tracing::info!("Calculating recovery_id for ECDSA:");
-    tracing::info!(
-        "  Message: {} bytes = {}",
-        message.len(),
-        hex::encode(&message[..16.min(message.len())])
-    );
-    tracing::info!("  Signature (r,s): {} bytes", sig_bytes.len());
-    tracing::info!("  Expected pubkey (full): {}", hex::encode(&pubkey_bytes));
-
-    for recovery_id in 0..4u8 {
-        use bitcoin::secp256k1::ecdsa::RecoveryId;
-
-        let rec_id = match RecoveryId::from_i32(recovery_id as i32) {
-            Ok(id) => id,
-            Err(e) => {
-                tracing::warn!(
-                    "  recovery_id={}: failed to create RecoveryId: {:?}",
-                    recovery_id,
-                    e
-                );
-                continue;
-            }
-        };
-
-        let rec_sig = match RecoverableSignature::from_compact(&sig_bytes, rec_id) {
-            Ok(sig) => sig,
-            Err(e) => {
-                tracing::warn!(
-                    "  recovery_id={}: failed to create RecoverableSignature: {:?}",
-                    recovery_id,
-                    e
-                );
-                continue;
-            }
-        };
-
-        let recovered_pubkey = match secp.recover_ecdsa(&msg, &rec_sig) {
-            Ok(pk) => pk,
-            Err(e) => {
-                tracing::warn!("  recovery_id={}: failed to recover: {:?}", recovery_id, e);
-                continue;
-            }
-        };
-
-        let recovered_bytes = recovered_pubkey.serialize();
-        let matches = recovered_pubkey == expected_pubkey;
-
-        tracing::info!(
-            "  recovery_id={}: recovered={} match={}",
-            recovery_id,
-            hex::encode(&recovered_bytes),
-            matches
-        );
-
-        if matches {
-            tracing::info!(
-                "✅✅ Found correct recovery_id = {} for ECDSA!",
-                recovery_id
-            );
-            return recovery_id;
-        }
-    }
-
-    ... old implementation removed ...
-    */
-}
@@ -1,75 +1,83 @@
-use frost_secp256k1_tr as frost;
+use anyhow::{Context, Result};
+use serde::{de::DeserializeOwned, Serialize};
 use std::collections::HashMap;
 use std::sync::Mutex;
 
+use crate::curves::CurveType;
+use crate::node::key_provider::KeyDerivation;
+
 /// Temporary storage for DKG in-progress state
-/// Holds secret packages during DKG rounds
+/// Holds serialized secret packages between DKG rounds, keyed by curve,
+/// passphrase and round (`{curve}:{passphrase}:r1`), and the key derivation
+/// scheme round 1 used, which finalize records in the key's metadata
 pub struct DkgState {
-    round1_secrets: Mutex<HashMap<String, frost::keys::dkg::round1::SecretPackage>>,
-    round2_secrets: Mutex<HashMap<String, frost::keys::dkg::round2::SecretPackage>>,
-    // Generic storage for other curves (Ed25519, etc.) as serialized bytes
-    pub generic_secrets: Mutex<HashMap<String, Vec<u8>>>,
+    secrets: Mutex<HashMap<String, Vec<u8>>>,
+    derivations: Mutex<HashMap<String, KeyDerivation>>,
 }
 
 impl DkgState {
     pub fn new() -> Self {
         Self {
-            round1_secrets: Mutex::new(HashMap::new()),
-            round2_secrets: Mutex::new(HashMap::new()),
-            generic_secrets: Mutex::new(HashMap::new()),
+            secrets: Mutex::new(HashMap::new()),
+            derivations: Mutex::new(HashMap::new()),
         }
     }
 
-    pub fn store_round1_secret(
-        &self,
-        passphrase: &str,
-        secret: frost::keys::dkg::round1::SecretPackage,
-    ) {
-        self.round1_secrets
+    fn session(curve: CurveType, passphrase: &str) -> String {
+        format!("{}:{}", curve.as_str(), passphrase)
+    }
+
+    pub fn store_derivation(&self, curve: CurveType, passphrase: &str, derivation: KeyDerivation) {
+        self.derivations
             .lock()
             .unwrap()
-            .insert(passphrase.to_string(), secret);
+            .insert(Self::session(curve, passphrase), derivation);
     }
 
-    pub fn get_round1_secret(
-        &self,
-        passphrase: &str,
-    ) -> Option<frost::keys::dkg::round1::SecretPackage> {
-        self.round1_secrets.lock().unwrap().get(passphrase).cloned()
+    /// Scheme round 1 derived this node's DKG randomness with
+    pub fn get_derivation(&self, curve: CurveType, passphrase: &str) -> Option<KeyDerivation> {
+        self.derivations
+            .lock()
+            .unwrap()
+            .get(&Self::session(curve, passphrase))
+            .copied()
     }
 
-    #[allow(dead_code)]
-    pub fn remove_round1_secret(
-        &self,
-        passphrase: &str,
-    ) -> Option<frost::keys::dkg::round1::SecretPackage> {
-        self.round1_secrets.lock().unwrap().remove(passphrase)
+    fn key(curve: CurveType, passphrase: &str, round: u8) -> String {
+        format!("{}:{}:r{}", curve.as_str(), passphrase, round)
     }
 
-    pub fn store_round2_secret(
+    pub fn store_secret<T: Serialize>(
         &self,
+        curve: CurveType,
         passphrase: &str,
-        secret: frost::keys::dkg::round2::SecretPackage,
-    ) {
-        self.round2_secrets
+        round: u8,
+        secret: &T,
+    ) -> Result<()> {
+        let bytes = serde_json::to_vec(secret).context("Failed to serialize DKG secret")?;
+        self.secrets
             .lock()
             .unwrap()
-            .insert(passphrase.to_string(), secret);
-    }
-
-    #[allow(dead_code)]
-    pub fn get_round2_secret(
-        &self,
-        passphrase: &str,
-    ) -> Option<frost::keys::dkg::round2::SecretPackage> {
-        self.round2_secrets.lock().unwrap().get(passphrase).cloned()
+            .insert(Self::key(curve, passphrase, round), bytes);
+        Ok(())
     }
 
-    #[allow(dead_code)]
-    pub fn remove_round2_secret(
+    /// Secret from an earlier round (kept, so a failed round can be retried)
+    pub fn get_secret<T: DeserializeOwned>(
         &self,
+        curve: CurveType,
         passphrase: &str,
-    ) -> Option<frost::keys::dkg::round2::SecretPackage> {
-        self.round2_secrets.lock().unwrap().remove(passphrase)
+        round: u8,
+    ) -> Result<Option<T>> {
+        let bytes = self
+            .secrets
+            .lock()
+            .unwrap()
+            .get(&Self::key(curve, passphrase, round))
+            .cloned();
+        bytes
+            .map(|bytes| serde_json::from_slice(&bytes))
+            .transpose()
+            .context("Failed to deserialize DKG secret")
     }
 }
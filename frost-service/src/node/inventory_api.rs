@@ -0,0 +1,51 @@
+use poem_openapi::payload::Json;
+use poem_openapi::{ApiResponse, Object};
+
+use super::dkg_api::ErrorResponse;
+use super::multi_storage::{CurveStats, KeyMetadata};
+
+/// A stored key, identified only by its blinded passphrase
+#[derive(Debug, Object)]
+pub struct KeyInventoryEntry {
+    pub id: String,
+    /// Missing for keys stored before metadata records existed
+    pub metadata: Option<KeyMetadata>,
+    /// Lifecycle state ("active", "frozen" or "retired")
+    pub state: String,
+}
+
+#[derive(Debug, Object)]
+pub struct KeyListResponse {
+    pub curve: String,
+    pub keys: Vec<KeyInventoryEntry>,
+    /// Pass as `cursor` for the next page (absent on the last page)
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Object)]
+pub struct StorageStatsResponse {
+    pub node_index: u16,
+    pub backend: String,
+    pub size_bytes: u64,
+    /// Layout version (see `schema::MIGRATIONS`); none until the first migration run
+    pub schema_version: Option<u32>,
+    pub curves: Vec<CurveStats>,
+}
+
+#[derive(ApiResponse)]
+pub enum KeyListResult {
+    #[oai(status = 200)]
+    Ok(Json<KeyListResponse>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+pub enum StorageStatsResult {
+    #[oai(status = 200)]
+    Ok(Json<StorageStatsResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
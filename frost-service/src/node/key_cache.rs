@@ -0,0 +1,110 @@
+//! Short-lived cache of HSM-derived storage keys
+//!
+//! Reading one key costs an HSM round trip per derivation (storage key,
+//! blinding key, ...). With a TTL set, derived values are kept in memory for
+//! that long, zeroized when evicted, expired or on `lock`. Entries are
+//! indexed by a hash of (derivation, input), so the cache doesn't hold
+//! passphrases.
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use bitcoin::hashes::{sha256, Hash, HashEngine};
+use zeroize::Zeroizing;
+
+struct CachedKey {
+    key: Zeroizing<[u8; 32]>,
+    expires_at: Instant,
+}
+
+pub struct DerivedKeyCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<[u8; 32], CachedKey>>,
+}
+
+impl DerivedKeyCache {
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries: max_entries.max(1),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn index(derivation: &str, input: &str) -> [u8; 32] {
+        let mut engine = sha256::Hash::engine();
+        engine.input(derivation.as_bytes());
+        engine.input(&[0]);
+        engine.input(input.as_bytes());
+        sha256::Hash::from_engine(engine).to_byte_array()
+    }
+
+    pub fn get(&self, derivation: &str, input: &str) -> Option<Zeroizing<[u8; 32]>> {
+        let index = Self::index(derivation, input);
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(&index) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.key.clone()),
+            Some(_) => {
+                entries.remove(&index);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Keep `key` for the TTL, evicting expired entries and then the oldest when full
+    pub fn insert(&self, derivation: &str, input: &str, key: &[u8; 32]) {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.max_entries {
+            entries.retain(|_, entry| entry.expires_at > now);
+        }
+        if entries.len() >= self.max_entries {
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.expires_at)
+                .map(|(index, _)| *index)
+            {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            Self::index(derivation, input),
+            CachedKey {
+                key: Zeroizing::new(*key),
+                expires_at: now + self.ttl,
+            },
+        );
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiry_and_eviction() {
+        let cache = DerivedKeyCache::new(Duration::from_secs(60), 2);
+        cache.insert("storage-encryption", "a", &[1; 32]);
+        cache.insert("storage-encryption", "b", &[2; 32]);
+        assert_eq!(*cache.get("storage-encryption", "a").unwrap(), [1; 32]);
+        assert!(cache.get("storage-index", "a").is_none());
+
+        // Full: the oldest entry makes room
+        cache.insert("storage-encryption", "c", &[3; 32]);
+        assert!(cache.get("storage-encryption", "a").is_none());
+        assert_eq!(*cache.get("storage-encryption", "c").unwrap(), [3; 32]);
+
+        cache.clear();
+        assert!(cache.get("storage-encryption", "c").is_none());
+
+        let expired = DerivedKeyCache::new(Duration::ZERO, 2);
+        expired.insert("storage-encryption", "a", &[1; 32]);
+        assert!(expired.get("storage-encryption", "a").is_none());
+    }
+}
@@ -0,0 +1,225 @@
+//! Key lifecycle: active → frozen → retired → destroyed
+//!
+//! Every (passphrase, curve) starts active. Frozen keys can be thawed again,
+//! retired keys only destroyed. Nodes refuse to sign with anything but an
+//! active key, and a destroyed key leaves a tombstone so DKG (which is
+//! deterministic) and backup restores can't bring it back.
+//!
+//! ```text
+//! active ⇄ frozen
+//! active, frozen → retired → destroyed
+//! ```
+//!
+//! Unfreezing and destroying take k-of-m `[node.lifecycle]` operator
+//! signatures, checked by every node itself: over `unfreeze_digest`, naming
+//! the freeze being lifted, or `destroy_digest`, naming the retirement.
+use bitcoin::hashes::{sha256, Hash};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::config::OperatorConfig;
+use crate::signing_aggregator::approval_store::verify_operator_signature;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyState {
+    Active,
+    Frozen,
+    Retired,
+    Destroyed,
+}
+
+impl KeyState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyState::Active => "active",
+            KeyState::Frozen => "frozen",
+            KeyState::Retired => "retired",
+            KeyState::Destroyed => "destroyed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "active" => Some(KeyState::Active),
+            "frozen" => Some(KeyState::Frozen),
+            "retired" => Some(KeyState::Retired),
+            "destroyed" => Some(KeyState::Destroyed),
+            _ => None,
+        }
+    }
+
+    /// Whether a key in this state may move to `to`
+    ///
+    /// Staying in the same state is allowed so orchestrators can retry a
+    /// partially applied change.
+    pub fn can_transition(self, to: KeyState) -> bool {
+        use KeyState::*;
+        matches!(
+            (self, to),
+            (Active, Active)
+                | (Active, Frozen)
+                | (Frozen, Frozen)
+                | (Frozen, Active)
+                | (Active, Retired)
+                | (Frozen, Retired)
+                | (Retired, Retired)
+                | (Retired, Destroyed)
+                | (Destroyed, Destroyed)
+        )
+    }
+}
+
+/// Stored in `{curve}_state` under the blinded passphrase; no record means active
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyStateRecord {
+    pub state: KeyState,
+    pub changed_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Set by the aggregator so every node records the same change; operators
+    /// sign over the freeze's id to unfreeze
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transition_id: Option<String>,
+    /// Kept on tombstones, whose metadata is gone with the key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_key_fingerprint: Option<String>,
+}
+
+impl KeyStateRecord {
+    pub fn active() -> Self {
+        Self {
+            state: KeyState::Active,
+            changed_at: 0,
+            reason: None,
+            transition_id: None,
+            group_key_fingerprint: None,
+        }
+    }
+}
+
+/// One operator's signature over a lifecycle digest
+#[derive(Debug, Clone, Object, Serialize, Deserialize)]
+pub struct OperatorApproval {
+    pub operator: String,
+    /// hex BIP340 signature over `unfreeze_digest` / `destroy_digest`
+    pub signature: String,
+}
+
+/// What operators sign to lift the freeze `freeze_id`
+pub fn unfreeze_digest(curve: &str, passphrase: &str, freeze_id: &str) -> [u8; 32] {
+    lifecycle_digest("unfreeze", curve, passphrase, freeze_id)
+}
+
+/// What operators sign to destroy a key retired by `retire_id`
+pub fn destroy_digest(curve: &str, passphrase: &str, retire_id: &str) -> [u8; 32] {
+    lifecycle_digest("destroy", curve, passphrase, retire_id)
+}
+
+fn lifecycle_digest(action: &str, curve: &str, passphrase: &str, transition_id: &str) -> [u8; 32] {
+    let preimage = format!(
+        "frost-lifecycle:v1:{}:{}:{}:{}",
+        action, curve, passphrase, transition_id
+    );
+    sha256::Hash::hash(preimage.as_bytes()).to_byte_array()
+}
+
+/// Operators whose signatures over `digest` are valid, if they reach the quorum
+pub fn verify_quorum(
+    required_approvals: usize,
+    operators: &[OperatorConfig],
+    digest: &[u8; 32],
+    approvals: &[OperatorApproval],
+) -> Result<Vec<String>, String> {
+    let mut approved = BTreeSet::new();
+    for entry in approvals {
+        let Some(operator) = operators.iter().find(|op| op.name == entry.operator) else {
+            return Err(format!("Unknown operator '{}'", entry.operator));
+        };
+        verify_operator_signature(operator, digest, &entry.signature)
+            .map_err(|e| format!("Operator '{}': {}", entry.operator, e))?;
+        approved.insert(operator.name.clone());
+    }
+
+    // A quorum of zero would let anyone through
+    let required = required_approvals.max(1);
+    if approved.len() < required {
+        return Err(format!(
+            "Needs {} operator approvals, got {}",
+            required,
+            approved.len()
+        ));
+    }
+    Ok(approved.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitions() {
+        use KeyState::*;
+        assert!(Active.can_transition(Frozen));
+        assert!(Frozen.can_transition(Active));
+        assert!(Frozen.can_transition(Retired));
+        assert!(Retired.can_transition(Destroyed));
+        assert!(Destroyed.can_transition(Destroyed));
+
+        assert!(!Retired.can_transition(Active));
+        assert!(!Retired.can_transition(Frozen));
+        assert!(!Active.can_transition(Destroyed));
+        assert!(!Frozen.can_transition(Destroyed));
+        assert!(!Destroyed.can_transition(Active));
+    }
+
+    #[test]
+    fn test_operator_quorum() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let keypairs: Vec<_> = [1u8, 2, 3]
+            .iter()
+            .map(|seed| {
+                bitcoin::secp256k1::Keypair::from_seckey_slice(&secp, &[*seed; 32]).unwrap()
+            })
+            .collect();
+        let operators: Vec<_> = ["alice", "bob", "carol"]
+            .iter()
+            .zip(&keypairs)
+            .map(|(name, keypair)| OperatorConfig {
+                name: name.to_string(),
+                public_key: hex::encode(keypair.x_only_public_key().0.serialize()),
+            })
+            .collect();
+        let sign = |operator: &str, keypair_index: usize, digest: [u8; 32]| OperatorApproval {
+            operator: operator.to_string(),
+            signature: hex::encode(
+                secp.sign_schnorr_no_aux_rand(
+                    &bitcoin::secp256k1::Message::from_digest(digest),
+                    &keypairs[keypair_index],
+                )
+                .as_ref(),
+            ),
+        };
+
+        let digest = unfreeze_digest("secp256k1-tr", "wallet", "freeze-1");
+        let quorum = [sign("alice", 0, digest), sign("carol", 2, digest)];
+        assert_eq!(
+            verify_quorum(2, &operators, &digest, &quorum).unwrap(),
+            vec!["alice".to_string(), "carol".to_string()]
+        );
+
+        // The same operator twice is one approval
+        let repeated = [sign("alice", 0, digest), sign("alice", 0, digest)];
+        assert!(verify_quorum(2, &operators, &digest, &repeated).is_err());
+
+        // Approvals of an earlier freeze don't lift a later one, nor destroy the key
+        let later = unfreeze_digest("secp256k1-tr", "wallet", "freeze-2");
+        assert!(verify_quorum(2, &operators, &later, &quorum).is_err());
+        let destroy = destroy_digest("secp256k1-tr", "wallet", "freeze-1");
+        assert!(verify_quorum(2, &operators, &destroy, &quorum).is_err());
+
+        // No approvals never pass, whatever the configured quorum
+        assert!(verify_quorum(0, &operators, &digest, &[]).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use poem_openapi::payload::Json;
+use poem_openapi::{ApiResponse, Object};
+
+use super::dkg_api::ErrorResponse;
+use super::lifecycle::{KeyStateRecord, OperatorApproval};
+
+#[derive(Debug, Object)]
+pub struct KeyStateRequest {
+    pub passphrase: String,
+    /// "active", "frozen", "retired" or "destroyed"
+    pub state: String,
+    pub reason: Option<String>,
+    /// Identifier of the change, shared by every node it is sent to
+    pub transition_id: Option<String>,
+    /// `[node.lifecycle]` operator signatures, needed to unfreeze or destroy
+    #[oai(default)]
+    pub approvals: Vec<OperatorApproval>,
+}
+
+#[derive(Debug, Object)]
+pub struct KeyStateResponse {
+    pub node_index: u16,
+    pub curve: String,
+    pub state: String,
+    /// 0 for keys that never changed state
+    pub changed_at: u64,
+    pub reason: Option<String>,
+    pub transition_id: Option<String>,
+}
+
+impl KeyStateResponse {
+    pub fn new(node_index: u16, curve: &str, record: KeyStateRecord) -> Self {
+        Self {
+            node_index,
+            curve: curve.to_string(),
+            state: record.state.as_str().to_string(),
+            changed_at: record.changed_at,
+            reason: record.reason,
+            transition_id: record.transition_id,
+        }
+    }
+}
+
+#[derive(ApiResponse)]
+pub enum KeyStateResult {
+    #[oai(status = 200)]
+    Ok(Json<KeyStateResponse>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
+    #[oai(status = 409)]
+    Conflict(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
@@ -1,11 +1,23 @@
 // FROST signer node - handles DKG and signing rounds
 
+pub mod backup;
+pub mod backup_api;
 pub mod crypto;
 pub mod derivation;
 pub mod dkg_api;
 pub mod dkg_state;
+pub mod inventory_api;
+pub mod key_cache;
 pub mod key_provider;
+pub mod lifecycle;
+pub mod lifecycle_api;
 pub mod multi_storage;
+#[cfg(feature = "pkcs11")]
+pub mod pkcs11_pool;
+pub mod schema;
+pub mod scrub;
+pub mod share_store;
+pub mod tx_policy;
 pub mod unlock_api;
 
 use anyhow::Result;
@@ -17,6 +29,7 @@ use tokio::signal;
 pub async fn run(
     server_config: crate::config::ServerConfig,
     node_config: crate::config::NodeConfig,
+    audit_log: Arc<crate::audit::AuditLog>,
 ) -> Result<()> {
     // Load node configuration (network is ignored - signers are chain-agnostic)
 
@@ -25,16 +38,15 @@ pub async fn run(
         "Starting FROST multi-chain signer node {}",
         node_config.node_index
     );
-    tracing::info!("Supported curves: secp256k1, Ed25519");
-
-    // Create multi-curve storage
-    let multi_storage = Arc::new(multi_storage::MultiCurveStorage::open(
-        &node_config.storage_path,
-    )?);
-    tracing::info!("✅ Multi-curve storage opened");
-
-    // Create shared DKG state
-    let dkg_state = Arc::new(dkg_state::DkgState::new());
+    tracing::info!(
+        "Supported curves: {}",
+        node_config
+            .curves()
+            .iter()
+            .map(|curve| curve.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
 
     // Create key provider (async for AWS KMS)
     let key_provider = node_config.create_key_provider().await?;
@@ -43,12 +55,54 @@ pub async fn run(
         key_provider.description()
     );
 
+    // Open multi-curve storage on the configured backend, migrating old layouts
+    let share_store = node_config
+        .storage_backend
+        .open(&node_config.storage_path)?;
+    let multi_storage = Arc::new(
+        multi_storage::MultiCurveStorage::open(
+            share_store,
+            key_provider.as_ref(),
+            &node_config.migration_options(),
+        )
+        .await?,
+    );
+    tracing::info!(
+        "✅ Multi-curve storage opened: {}",
+        multi_storage.description()
+    );
+
+    // Create shared DKG state
+    let dkg_state = Arc::new(dkg_state::DkgState::new());
+
+    let key_provider: Arc<dyn key_provider::MasterKeyProvider> = Arc::from(key_provider);
+
+    // Periodic integrity check of every stored record
+    let scrubber = Arc::new(scrub::Scrubber::new(
+        multi_storage.clone(),
+        key_provider.clone(),
+        audit_log.clone(),
+        node_config.node_index,
+        node_config.curves(),
+    ));
+    if node_config.scrub.interval_secs > 0 {
+        scrubber.clone().spawn(std::time::Duration::from_secs(
+            node_config.scrub.interval_secs,
+        ));
+        tracing::info!(
+            "✅ Storage scrub every {}s",
+            node_config.scrub.interval_secs
+        );
+    }
+
     // Create unified API (pubkey queries + DKG + FROST signing all in one)
     let api = dkg_api::UnifiedApi {
         config: Arc::new(node_config.clone()),
         storage: multi_storage,
         dkg_state,
-        key_provider: Arc::from(key_provider),
+        key_provider,
+        audit: audit_log,
+        scrubber,
     };
 
     // Single unified API service
@@ -71,6 +125,10 @@ pub async fn run(
     tracing::info!("   📊 GET /api/curve/secp256k1/pubkey?passphrase=<uuid>");
     tracing::info!("   🔧 POST /api/dkg/round1|round2|finalize (DKG protocol)");
     tracing::info!("   ✍️  POST /api/frost/round1|round2|aggregate (FROST signing)");
+    tracing::info!("   💾 POST /api/backup/export|restore (encrypted share backups)");
+    tracing::info!("   🗂️  GET /api/keys/{{curve}}, /api/storage/stats (key inventory)");
+    tracing::info!("   🔒 GET|POST /api/keys/{{curve}}/state (key lifecycle)");
+    tracing::info!("   🩺 GET|POST /api/storage/scrub, GET /metrics (integrity checks)");
     tracing::info!(
         "   📖 Documentation: http://{}:{}/docs",
         server_config.host,
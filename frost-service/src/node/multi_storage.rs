@@ -1,197 +1,1135 @@
-/// Multi-curve storage using RocksDB column families
+/// Multi-curve share storage
+///
+/// Encrypts key packages with the master key provider and keeps them in a
+/// `ShareStore` backend. Every curve gets its own pair of tables, named after
+/// the curve (`secp256k1_tr_keys` / `secp256k1_tr_pubkeys`, ...).
 ///
-/// This storage layer supports multiple elliptic curves in a single database:
-/// - secp256k1 (Bitcoin, Ethereum)
-/// - Ed25519 (Solana)
+/// Records are indexed by `MasterKeyProvider::blind_passphrase`, never by the
+/// passphrase itself:
+/// - `{curve}_keys`: `key:{blind}` (key package), `passphrase:{blind}` (the
+///   passphrase, encrypted, so backups can enumerate what's stored)
+/// - `{curve}_pubkeys`: `pubkey:{blind}` (public key package)
+/// - `{curve}_metadata`: `{blind}` (`KeyMetadata`, JSON, not encrypted)
+/// - `{curve}_state`: `{blind}` (`KeyStateRecord`, JSON, only once the key
+///   left the active state; tombstones outlive the key)
+/// - `schema`: `version` (`SchemaVersion`, see `schema::MIGRATIONS`),
+///   `index_derivation` (`KeyDerivation` of the blinding key)
 use anyhow::{Context, Result};
-use rocksdb::{ColumnFamilyDescriptor, Options, DB};
-use serde::{de::DeserializeOwned, Serialize};
-use std::path::Path;
-use std::sync::Arc;
-
-use super::key_provider::MasterKeyProvider;
-use crate::curves::{CurveOperations, CurveType};
-
-/// Column family names
-const CF_SECP256K1_TR_KEYS: &str = "secp256k1_tr_keys"; // Taproot/Schnorr
-const CF_SECP256K1_TR_PUBKEYS: &str = "secp256k1_tr_pubkeys";
-const CF_SECP256K1_KEYS: &str = "secp256k1_keys"; // ECDSA
-const CF_SECP256K1_PUBKEYS: &str = "secp256k1_pubkeys";
-const CF_ED25519_KEYS: &str = "ed25519_keys";
-const CF_ED25519_PUBKEYS: &str = "ed25519_pubkeys";
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bitcoin::hashes::Hash;
+use frost_core::keys::VerifyingShare;
+use frost_core::Identifier;
+
+use super::key_provider::{is_legacy_sealed, KeyDerivation, MasterKeyProvider};
+use super::lifecycle::{KeyState, KeyStateRecord};
+use super::schema::{self, MigrationOptions};
+use super::scrub::ScrubIssue;
+use super::share_store::{ShareStore, WriteBatch, WriteOp};
+use crate::curves::{CurveOperations, CurveType, KeyPackage, PublicKeyPackage};
+
+const KEY_PREFIX: &str = "key:";
+const PUBKEY_PREFIX: &str = "pubkey:";
+const PASSPHRASE_PREFIX: &str = "passphrase:";
+
+/// Bookkeeping stored next to each key, listed by `/api/keys/{curve}`
+///
+/// Holds nothing secret: no passphrase, share or group key, only a
+/// fingerprint of the group key so inventories can be reconciled across nodes.
+#[derive(Debug, Clone, PartialEq, Object, Serialize, Deserialize)]
+pub struct KeyMetadata {
+    pub created_at: u64,
+    /// sha256 over every participant's round 1 package, the same on every node
+    pub dkg_session: String,
+    pub min_signers: u16,
+    pub max_signers: u16,
+    pub participants: Vec<u16>,
+    pub committee_epoch: u64,
+    pub software_version: String,
+    /// First 16 bytes (hex) of sha256(group public key)
+    pub group_key_fingerprint: String,
+    #[serde(default)]
+    pub signing_count: u64,
+    #[serde(default)]
+    pub last_signed_at: Option<u64>,
+    /// `KeyDerivation` the shares were generated with; DKG re-runs must use it
+    #[serde(default = "legacy_derivation")]
+    pub derivation: String,
+}
+
+fn legacy_derivation() -> String {
+    KeyDerivation::AesEcb.as_str().to_string()
+}
+
+impl KeyMetadata {
+    pub fn new<C: CurveOperations>(
+        pubkey_package: &PublicKeyPackage<C>,
+        dkg_session: String,
+        min_signers: u16,
+        participants: Vec<u16>,
+        committee_epoch: u64,
+        derivation: KeyDerivation,
+    ) -> Result<Self> {
+        Ok(Self {
+            created_at: unix_now(),
+            dkg_session,
+            min_signers,
+            max_signers: pubkey_package.verifying_shares().len() as u16,
+            participants,
+            committee_epoch,
+            software_version: env!("CARGO_PKG_VERSION").to_string(),
+            group_key_fingerprint: group_key_fingerprint::<C>(pubkey_package)?,
+            signing_count: 0,
+            last_signed_at: None,
+            derivation: derivation.as_str().to_string(),
+        })
+    }
+
+    /// Keep the history of `previous` when the same key is stored again
+    ///
+    /// A DKG re-run produces the same group key; its creation time and
+    /// signing counters belong to the key, not to the latest ceremony.
+    fn carry_over(&mut self, previous: &KeyMetadata) {
+        if previous.group_key_fingerprint != self.group_key_fingerprint {
+            return;
+        }
+        self.created_at = previous.created_at;
+        self.signing_count = previous.signing_count;
+        self.last_signed_at = previous.last_signed_at;
+    }
+}
+
+/// Per-curve totals for `/api/storage/stats`
+#[derive(Debug, Clone, Default, Object)]
+pub struct CurveStats {
+    pub curve: String,
+    pub keys: u64,
+    /// Keys stored before metadata records existed have none
+    pub keys_with_metadata: u64,
+    pub signing_count: u64,
+    pub frozen: u64,
+    pub retired: u64,
+    /// Tombstones; their keys are no longer counted in `keys`
+    pub destroyed: u64,
+}
+
+/// Stored under `schema` / `version`, written after every migration step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaVersion {
+    pub version: u32,
+    pub updated_at: u64,
+    /// Release that wrote the record
+    pub software_version: String,
+}
+
+impl SchemaVersion {
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            updated_at: unix_now(),
+            software_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+const SCHEMA_TABLE: &str = "schema";
+const SCHEMA_VERSION_KEY: &[u8] = b"version";
+// `KeyDerivation` of the blinding index key; absent means aes-ecb
+const INDEX_DERIVATION_KEY: &[u8] = b"index_derivation";
+
+// Pre-blinding layout (`keypackage:{passphrase}`), moved by `migrate_blinded_keys`
+const LEGACY_KEY_PREFIX: &str = "keypackage:";
+const LEGACY_PUBKEY_PREFIX: &str = "pubkeypackage:";
 
 /// Multi-curve share storage
 pub struct MultiCurveStorage {
-    db: Arc<DB>,
+    store: Arc<dyn ShareStore>,
+    index_derivation: RwLock<Option<KeyDerivation>>,
 }
 
 impl MultiCurveStorage {
-    /// Open or create storage with column families
-    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let mut opts = Options::default();
-        opts.create_if_missing(true);
-        opts.create_missing_column_families(true);
-
-        // Define column families
-        let cfs = vec![
-            ColumnFamilyDescriptor::new(CF_SECP256K1_TR_KEYS, Options::default()),
-            ColumnFamilyDescriptor::new(CF_SECP256K1_TR_PUBKEYS, Options::default()),
-            ColumnFamilyDescriptor::new(CF_SECP256K1_KEYS, Options::default()),
-            ColumnFamilyDescriptor::new(CF_SECP256K1_PUBKEYS, Options::default()),
-            ColumnFamilyDescriptor::new(CF_ED25519_KEYS, Options::default()),
-            ColumnFamilyDescriptor::new(CF_ED25519_PUBKEYS, Options::default()),
-        ];
+    pub fn new(store: Arc<dyn ShareStore>) -> Self {
+        Self {
+            store,
+            index_derivation: RwLock::new(None),
+        }
+    }
 
-        let db = DB::open_cf_descriptors(&opts, path, cfs)
-            .context("Failed to open RocksDB with column families")?;
+    /// Wrap `store` and bring its layout up to `schema::CURRENT_VERSION`
+    ///
+    /// Steps that need the key provider wait if it's locked; the unlock
+    /// handler calls `schema::migrate` again.
+    pub async fn open(
+        store: Arc<dyn ShareStore>,
+        key_provider: &dyn MasterKeyProvider,
+        options: &MigrationOptions,
+    ) -> Result<Self> {
+        let storage = Self::new(store);
+        schema::migrate(&storage, key_provider, options).await?;
+        Ok(storage)
+    }
 
-        Ok(Self { db: Arc::new(db) })
+    /// Backend description for startup logs
+    pub fn description(&self) -> String {
+        self.store.description()
     }
 
-    /// Get column family names for curve type
-    fn cf_names(&self, curve_type: CurveType) -> (&'static str, &'static str) {
-        match curve_type {
-            CurveType::Secp256k1Taproot => (CF_SECP256K1_TR_KEYS, CF_SECP256K1_TR_PUBKEYS),
-            CurveType::Secp256k1Ecdsa => (CF_SECP256K1_KEYS, CF_SECP256K1_PUBKEYS),
-            CurveType::Ed25519 => (CF_ED25519_KEYS, CF_ED25519_PUBKEYS),
-        }
+    /// Store a DKG result (encrypted at rest)
+    ///
+    /// Key package and public key package are written in one batch, so a
+    /// crash can't leave a share without its group key or the reverse.
+    /// Storing a key again keeps the creation time and signing counters of
+    /// its existing metadata (see `KeyMetadata::carry_over`).
+    pub async fn store_packages<C: CurveOperations>(
+        &self,
+        passphrase: &str,
+        key_package: &KeyPackage<C>,
+        pubkey_package: &PublicKeyPackage<C>,
+        metadata: &KeyMetadata,
+        key_provider: &dyn MasterKeyProvider,
+    ) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        self.stage_packages::<C>(
+            &mut batch,
+            passphrase,
+            key_package,
+            pubkey_package,
+            None,
+            key_provider,
+        )
+        .await?;
+        let blind = self.blind(key_provider, passphrase).await?;
+        let table = metadata_table(C::CURVE);
+
+        self.store
+            .transaction(|tx| {
+                let mut metadata = metadata.clone();
+                if let Some(json) = tx.get(&table, blind.as_bytes())? {
+                    let previous: KeyMetadata =
+                        serde_json::from_slice(&json).context("Corrupt key metadata")?;
+                    metadata.carry_over(&previous);
+                }
+                for op in batch.ops() {
+                    match op {
+                        WriteOp::Put { table, key, value } => tx.put(table, key, value),
+                        WriteOp::Delete { table, key } => tx.delete(table, key),
+                    }
+                }
+                tx.put(&table, blind.as_bytes(), &serde_json::to_vec(&metadata)?);
+                Ok(())
+            })
+            .context("Failed to store encrypted key packages")?;
+
+        tracing::debug!(
+            "Stored encrypted key packages for passphrase in {:?}",
+            C::CURVE
+        );
+        Ok(())
     }
 
-    /// Store key package for passphrase (encrypted at rest)
-    pub async fn store_key_package<C: CurveOperations>(
+    /// Encrypt a key/pubkey package pair into `batch` without writing it
+    ///
+    /// Lets callers such as backup restore commit many pairs atomically.
+    pub async fn stage_packages<C: CurveOperations>(
         &self,
-        curve_type: CurveType,
+        batch: &mut WriteBatch,
         passphrase: &str,
-        key_package: &C::KeyPackage,
+        key_package: &KeyPackage<C>,
+        pubkey_package: &PublicKeyPackage<C>,
+        metadata: Option<&KeyMetadata>,
         key_provider: &dyn MasterKeyProvider,
-    ) -> Result<()>
-    where
-        C::KeyPackage: Serialize,
-    {
-        let (cf_keys, _) = self.cf_names(curve_type);
-        let key = format!("keypackage:{}", passphrase);
-        let plaintext =
+    ) -> Result<()> {
+        let (keys_table, pubkeys_table) = table_names(C::CURVE);
+        let blind = self.blind(key_provider, passphrase).await?;
+
+        let key_plaintext =
             serde_json::to_vec(key_package).context("Failed to serialize key package")?;
+        let pubkey_plaintext =
+            serde_json::to_vec(pubkey_package).context("Failed to serialize pubkey package")?;
 
         // Encrypt with AES-256-GCM (async)
-        let ciphertext = key_provider.encrypt_storage(passphrase, &plaintext).await?;
+        let key_ciphertext = key_provider
+            .encrypt_storage(passphrase, &key_plaintext)
+            .await?;
+        let pubkey_ciphertext = key_provider
+            .encrypt_storage(passphrase, &pubkey_plaintext)
+            .await?;
+        let passphrase_ciphertext = key_provider
+            .encrypt_storage(&catalog_label(&blind), passphrase.as_bytes())
+            .await?;
 
-        // Store encrypted (get cf after await)
-        let cf = self
-            .db
-            .cf_handle(cf_keys)
-            .ok_or_else(|| anyhow::anyhow!("Column family {} not found", cf_keys))?;
+        batch.put(
+            &keys_table,
+            format!("{}{}", KEY_PREFIX, blind).as_bytes(),
+            &key_ciphertext,
+        );
+        batch.put(
+            &keys_table,
+            format!("{}{}", PASSPHRASE_PREFIX, blind).as_bytes(),
+            &passphrase_ciphertext,
+        );
+        batch.put(
+            &pubkeys_table,
+            format!("{}{}", PUBKEY_PREFIX, blind).as_bytes(),
+            &pubkey_ciphertext,
+        );
 
-        self.db
-            .put_cf(&cf, key.as_bytes(), ciphertext)
-            .context("Failed to store encrypted key package")?;
+        if let Some(metadata) = metadata {
+            batch.put(
+                &metadata_table(C::CURVE),
+                blind.as_bytes(),
+                &serde_json::to_vec(metadata)?,
+            );
+        }
 
-        tracing::debug!(
-            "Stored encrypted key package for passphrase in {:?}",
-            curve_type
-        );
+        // Replaces a not yet migrated record for the same passphrase. Only
+        // delete what exists: a tombstone's key names the passphrase in clear
+        for (table, prefix) in [
+            (&keys_table, LEGACY_KEY_PREFIX),
+            (&pubkeys_table, LEGACY_PUBKEY_PREFIX),
+        ] {
+            let legacy = format!("{}{}", prefix, passphrase);
+            if self.store.get(table, legacy.as_bytes())?.is_some() {
+                batch.delete(table, legacy.as_bytes());
+            }
+        }
         Ok(())
     }
 
-    /// Retrieve key package for passphrase (decrypt from storage)
-    pub async fn get_key_package<C: CurveOperations>(
+    pub fn write_batch(&self, batch: WriteBatch) -> Result<()> {
+        self.store
+            .write_batch(batch)
+            .context("Failed to store encrypted key packages")
+    }
+
+    /// Point-in-time view of every curve's shares (see `ShareStore::snapshot`)
+    pub fn snapshot(&self) -> Result<MultiCurveStorage> {
+        Ok(Self {
+            store: Arc::from(self.store.snapshot()?),
+            index_derivation: RwLock::new(*self.index_derivation.read().unwrap()),
+        })
+    }
+
+    /// Scheme of the node-wide blinding key, fixed when the store was created
+    ///
+    /// Changing it would orphan every record, so it doesn't follow the
+    /// provider's `derivation` setting; stores from before it was recorded
+    /// use aes-ecb.
+    pub fn index_derivation(&self) -> Result<KeyDerivation> {
+        if let Some(derivation) = *self.index_derivation.read().unwrap() {
+            return Ok(derivation);
+        }
+        let derivation = match self.store.get(SCHEMA_TABLE, INDEX_DERIVATION_KEY)? {
+            Some(json) => {
+                serde_json::from_slice(&json).context("Corrupt index derivation record")?
+            }
+            None => KeyDerivation::AesEcb,
+        };
+        *self.index_derivation.write().unwrap() = Some(derivation);
+        Ok(derivation)
+    }
+
+    pub fn set_index_derivation(&self, derivation: KeyDerivation) -> Result<()> {
+        self.store
+            .put(
+                SCHEMA_TABLE,
+                INDEX_DERIVATION_KEY,
+                &serde_json::to_vec(&derivation)?,
+            )
+            .context("Failed to write index derivation")?;
+        *self.index_derivation.write().unwrap() = Some(derivation);
+        Ok(())
+    }
+
+    async fn blind(
+        &self,
+        key_provider: &dyn MasterKeyProvider,
+        passphrase: &str,
+    ) -> Result<String> {
+        key_provider
+            .blind_passphrase(self.index_derivation()?, passphrase)
+            .await
+    }
+
+    /// Layout version recorded by the last migration (None before versioning)
+    pub fn schema_version(&self) -> Result<Option<SchemaVersion>> {
+        self.store
+            .get(SCHEMA_TABLE, SCHEMA_VERSION_KEY)?
+            .map(|json| serde_json::from_slice(&json).context("Corrupt schema version record"))
+            .transpose()
+    }
+
+    pub fn set_schema_version(&self, version: &SchemaVersion) -> Result<()> {
+        self.store
+            .put(
+                SCHEMA_TABLE,
+                SCHEMA_VERSION_KEY,
+                &serde_json::to_vec(version)?,
+            )
+            .context("Failed to write schema version")
+    }
+
+    /// True when no curve has a key or pubkey record (a new node)
+    pub fn is_empty(&self) -> Result<bool> {
+        for curve_type in CurveType::ALL {
+            let (keys_table, pubkeys_table) = table_names(curve_type);
+            if !self.store.list_page(&keys_table, b"", None, 1)?.is_empty()
+                || !self
+                    .store
+                    .list_page(&pubkeys_table, b"", None, 1)?
+                    .is_empty()
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Copy the whole store to `path` (see `ShareStore::backup_to`)
+    pub fn backup_to(&self, path: &std::path::Path) -> Result<()> {
+        self.store.backup_to(path)
+    }
+
+    /// Passphrases with a stored key package for `curve_type`, ascending
+    pub async fn passphrases(
+        &self,
+        curve_type: CurveType,
+        key_provider: &dyn MasterKeyProvider,
+    ) -> Result<Vec<String>> {
+        let (keys_table, _) = table_names(curve_type);
+        let mut passphrases = BTreeSet::new();
+
+        for key in self.store.list(&keys_table, PASSPHRASE_PREFIX.as_bytes())? {
+            let blind = std::str::from_utf8(&key[PASSPHRASE_PREFIX.len()..])
+                .context("Corrupt passphrase index")?;
+            let Some(ciphertext) = self.store.get(&keys_table, &key)? else {
+                continue;
+            };
+            let plaintext = key_provider
+                .decrypt_storage(&catalog_label(blind), &ciphertext)
+                .await?;
+            passphrases
+                .insert(String::from_utf8(plaintext).context("Stored passphrase is not UTF-8")?);
+        }
+        passphrases.extend(self.legacy_passphrases(&keys_table)?);
+
+        Ok(passphrases.into_iter().collect())
+    }
+
+    /// Records still stored under clear passphrases, on every curve
+    pub fn legacy_key_count(&self) -> Result<usize> {
+        CurveType::ALL
+            .into_iter()
+            .map(|curve_type| Ok(self.legacy_passphrases(&table_names(curve_type).0)?.len()))
+            .sum()
+    }
+
+    /// Move records stored under clear passphrases to their blinded keys
+    ///
+    /// Needs the key provider unlocked (schema migration 2). Each passphrase
+    /// moves in one transaction, so an interrupted migration just resumes on
+    /// the next run. `progress` gets (done, total) after every passphrase.
+    pub async fn migrate_blinded_keys(
+        &self,
+        key_provider: &dyn MasterKeyProvider,
+        progress: &mut (dyn FnMut(usize, usize) + Send),
+    ) -> Result<usize> {
+        let total = self.legacy_key_count()?;
+        let mut migrated = 0;
+        for curve_type in CurveType::ALL {
+            let (keys_table, pubkeys_table) = table_names(curve_type);
+            for passphrase in self.legacy_passphrases(&keys_table)? {
+                let blind = self.blind(key_provider, &passphrase).await?;
+                let passphrase_ciphertext = key_provider
+                    .encrypt_storage(&catalog_label(&blind), passphrase.as_bytes())
+                    .await?;
+
+                self.store.transaction(|tx| {
+                    let legacy_key = format!("{}{}", LEGACY_KEY_PREFIX, passphrase);
+                    let legacy_pubkey = format!("{}{}", LEGACY_PUBKEY_PREFIX, passphrase);
+                    let key = format!("{}{}", KEY_PREFIX, blind);
+
+                    // A DKG run since startup may already have stored blinded records
+                    if tx.get(&keys_table, key.as_bytes())?.is_none() {
+                        let Some(key_ciphertext) = tx.get(&keys_table, legacy_key.as_bytes())?
+                        else {
+                            return Ok(());
+                        };
+                        tx.put(&keys_table, key.as_bytes(), &key_ciphertext);
+                        tx.put(
+                            &keys_table,
+                            format!("{}{}", PASSPHRASE_PREFIX, blind).as_bytes(),
+                            &passphrase_ciphertext,
+                        );
+                        if let Some(pubkey_ciphertext) =
+                            tx.get(&pubkeys_table, legacy_pubkey.as_bytes())?
+                        {
+                            tx.put(
+                                &pubkeys_table,
+                                format!("{}{}", PUBKEY_PREFIX, blind).as_bytes(),
+                                &pubkey_ciphertext,
+                            );
+                        }
+                    }
+                    tx.delete(&keys_table, legacy_key.as_bytes());
+                    if tx.get(&pubkeys_table, legacy_pubkey.as_bytes())?.is_some() {
+                        tx.delete(&pubkeys_table, legacy_pubkey.as_bytes());
+                    }
+                    Ok(())
+                })?;
+                migrated += 1;
+                progress(migrated, total);
+            }
+        }
+
+        // The clear-passphrase keys linger in logs and data files until purged
+        if migrated > 0 {
+            let tables: Vec<String> = CurveType::ALL
+                .into_iter()
+                .flat_map(|curve_type| {
+                    let (keys_table, pubkeys_table) = table_names(curve_type);
+                    [keys_table, pubkeys_table]
+                })
+                .collect();
+            let tables: Vec<&str> = tables.iter().map(String::as_str).collect();
+            self.store
+                .purge(&tables)
+                .context("Keys migrated, but purging the old records failed")?;
+        }
+        Ok(migrated)
+    }
+
+    /// Records still encrypted with the passphrase-derived nonce, on every curve
+    pub fn legacy_sealed_count(&self) -> Result<usize> {
+        let mut count = 0;
+        for curve_type in CurveType::ALL {
+            let (keys_table, pubkeys_table) = table_names(curve_type);
+            for (table, prefix) in [
+                (&keys_table, KEY_PREFIX),
+                (&keys_table, PASSPHRASE_PREFIX),
+                (&keys_table, LEGACY_KEY_PREFIX),
+                (&pubkeys_table, PUBKEY_PREFIX),
+                (&pubkeys_table, LEGACY_PUBKEY_PREFIX),
+            ] {
+                for key in self.store.list(table, prefix.as_bytes())? {
+                    if self
+                        .store
+                        .get(table, &key)?
+                        .is_some_and(|value| is_legacy_sealed(&value))
+                    {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    /// Re-encrypt records that use the passphrase-derived nonce with random ones
+    ///
+    /// Needs the key provider unlocked (schema migration 3). Each passphrase's
+    /// records are replaced in one transaction, and only if they still hold
+    /// what was read, so a DKG run in the meantime wins.
+    pub async fn reseal_records(
+        &self,
+        key_provider: &dyn MasterKeyProvider,
+        progress: &mut (dyn FnMut(usize, usize) + Send),
+    ) -> Result<usize> {
+        let total = self.legacy_sealed_count()?;
+        let mut resealed = 0;
+        for curve_type in CurveType::ALL {
+            let (keys_table, pubkeys_table) = table_names(curve_type);
+            for index_key in self.store.list(&keys_table, PASSPHRASE_PREFIX.as_bytes())? {
+                let blind = std::str::from_utf8(&index_key[PASSPHRASE_PREFIX.len()..])
+                    .context("Corrupt passphrase index")?
+                    .to_string();
+                let Some(catalog) = self.store.get(&keys_table, &index_key)? else {
+                    continue;
+                };
+                let passphrase = String::from_utf8(
+                    key_provider
+                        .decrypt_storage(&catalog_label(&blind), &catalog)
+                        .await?,
+                )
+                .context("Stored passphrase is not UTF-8")?;
+
+                let records = [
+                    (&keys_table, index_key.clone(), catalog_label(&blind)),
+                    (
+                        &keys_table,
+                        format!("{}{}", KEY_PREFIX, blind).into_bytes(),
+                        passphrase.clone(),
+                    ),
+                    (
+                        &pubkeys_table,
+                        format!("{}{}", PUBKEY_PREFIX, blind).into_bytes(),
+                        passphrase,
+                    ),
+                ];
+                let mut updates = Vec::new();
+                for (table, key, label) in records {
+                    let Some(old) = self.store.get(table, &key)? else {
+                        continue;
+                    };
+                    if !is_legacy_sealed(&old) {
+                        continue;
+                    }
+                    let plaintext = key_provider.decrypt_storage(&label, &old).await?;
+                    let sealed = key_provider.encrypt_storage(&label, &plaintext).await?;
+                    updates.push((table, key, old, sealed));
+                }
+                if updates.is_empty() {
+                    continue;
+                }
+
+                self.store.transaction(|tx| {
+                    for (table, key, old, sealed) in &updates {
+                        if tx.get(table, key)?.as_deref() == Some(old.as_slice()) {
+                            tx.put(table, key, sealed);
+                        }
+                    }
+                    Ok(())
+                })?;
+                resealed += updates.len();
+                progress(resealed, total);
+            }
+        }
+        Ok(resealed)
+    }
+
+    fn legacy_passphrases(&self, keys_table: &str) -> Result<Vec<String>> {
+        self.store
+            .list(keys_table, LEGACY_KEY_PREFIX.as_bytes())?
+            .into_iter()
+            .map(|key| {
+                String::from_utf8(key[LEGACY_KEY_PREFIX.len()..].to_vec())
+                    .context("Stored passphrase is not UTF-8")
+            })
+            .collect()
+    }
+
+    /// Read a record by blinded key, falling back to the not yet migrated layout
+    async fn get_record(
+        &self,
+        table: &str,
+        prefix: &str,
+        legacy_prefix: &str,
+        passphrase: &str,
+        key_provider: &dyn MasterKeyProvider,
+    ) -> Result<Option<Vec<u8>>> {
+        let blind = self.blind(key_provider, passphrase).await?;
+        match self
+            .store
+            .get(table, format!("{}{}", prefix, blind).as_bytes())?
+        {
+            Some(ciphertext) => Ok(Some(ciphertext)),
+            None => self
+                .store
+                .get(table, format!("{}{}", legacy_prefix, passphrase).as_bytes()),
+        }
+    }
+
+    /// Metadata of the key stored for `passphrase`, if it has any
+    pub async fn get_metadata(
         &self,
         curve_type: CurveType,
         passphrase: &str,
         key_provider: &dyn MasterKeyProvider,
-    ) -> Result<Option<C::KeyPackage>>
-    where
-        C::KeyPackage: DeserializeOwned,
-    {
-        let (cf_keys, _) = self.cf_names(curve_type);
-        let key = format!("keypackage:{}", passphrase);
-
-        // Get ciphertext (drop cf before await)
-        let ciphertext_opt = {
-            let cf = self
-                .db
-                .cf_handle(cf_keys)
-                .ok_or_else(|| anyhow::anyhow!("Column family {} not found", cf_keys))?;
-            self.db.get_cf(&cf, key.as_bytes())?
+    ) -> Result<Option<KeyMetadata>> {
+        let blind = self.blind(key_provider, passphrase).await?;
+        self.metadata_by_id(curve_type, &blind)
+    }
+
+    fn metadata_by_id(&self, curve_type: CurveType, blind: &str) -> Result<Option<KeyMetadata>> {
+        self.store
+            .get(&metadata_table(curve_type), blind.as_bytes())?
+            .map(|json| serde_json::from_slice(&json).context("Corrupt key metadata"))
+            .transpose()
+    }
+
+    /// Count a released signature share against the key's metadata
+    pub async fn record_signing(
+        &self,
+        curve_type: CurveType,
+        passphrase: &str,
+        key_provider: &dyn MasterKeyProvider,
+    ) -> Result<()> {
+        let blind = self.blind(key_provider, passphrase).await?;
+        let table = metadata_table(curve_type);
+
+        self.store.transaction(|tx| {
+            let Some(json) = tx.get(&table, blind.as_bytes())? else {
+                return Ok(()); // stored before metadata existed
+            };
+            let mut metadata: KeyMetadata =
+                serde_json::from_slice(&json).context("Corrupt key metadata")?;
+            metadata.signing_count += 1;
+            metadata.last_signed_at = Some(unix_now());
+            tx.put(&table, blind.as_bytes(), &serde_json::to_vec(&metadata)?);
+            Ok(())
+        })
+    }
+
+    /// One page of blinded key identifiers with their metadata and state, ascending
+    pub fn list_keys(
+        &self,
+        curve_type: CurveType,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<(String, Option<KeyMetadata>, KeyState)>> {
+        let (keys_table, _) = table_names(curve_type);
+        let after = after.map(|id| format!("{}{}", KEY_PREFIX, id));
+
+        self.store
+            .list_page(
+                &keys_table,
+                KEY_PREFIX.as_bytes(),
+                after.as_deref().map(str::as_bytes),
+                limit,
+            )?
+            .into_iter()
+            .map(|key| {
+                let id = String::from_utf8(key[KEY_PREFIX.len()..].to_vec())
+                    .context("Corrupt key index")?;
+                let metadata = self.metadata_by_id(curve_type, &id)?;
+                let state = self.state_by_id(curve_type, &id)?.state;
+                Ok((id, metadata, state))
+            })
+            .collect()
+    }
+
+    /// Key counts and usage for `curve_type`
+    pub fn curve_stats(&self, curve_type: CurveType) -> Result<CurveStats> {
+        let (keys_table, _) = table_names(curve_type);
+        let mut stats = CurveStats {
+            curve: curve_type.as_str().to_string(),
+            ..Default::default()
         };
 
-        match ciphertext_opt {
-            Some(ciphertext) => {
-                // Decrypt with AES-256-GCM (async)
-                let plaintext = key_provider
-                    .decrypt_storage(passphrase, &ciphertext)
-                    .await?;
-                let key_package: C::KeyPackage = serde_json::from_slice(&plaintext)
-                    .context("Failed to deserialize key package")?;
-                Ok(Some(key_package))
+        for key in self.store.list(&keys_table, KEY_PREFIX.as_bytes())? {
+            stats.keys += 1;
+            let id = String::from_utf8_lossy(&key[KEY_PREFIX.len()..]).into_owned();
+            if let Some(metadata) = self.metadata_by_id(curve_type, &id)? {
+                stats.keys_with_metadata += 1;
+                stats.signing_count += metadata.signing_count;
+            }
+        }
+        // Not yet migrated records count as keys without metadata
+        stats.keys += self.legacy_passphrases(&keys_table)?.len() as u64;
+
+        for key in self.store.list(&state_table(curve_type), b"")? {
+            let id = String::from_utf8_lossy(&key).into_owned();
+            match self.state_by_id(curve_type, &id)?.state {
+                KeyState::Active => {}
+                KeyState::Frozen => stats.frozen += 1,
+                KeyState::Retired => stats.retired += 1,
+                KeyState::Destroyed => stats.destroyed += 1,
             }
-            None => Ok(None),
         }
+        Ok(stats)
     }
 
-    /// Store public key package for passphrase (encrypted at rest)
-    pub async fn store_pubkey_package<C: CurveOperations>(
+    /// Lifecycle state of the key for `passphrase` (active unless changed)
+    pub async fn get_state(
         &self,
         curve_type: CurveType,
         passphrase: &str,
-        pubkey_package: &C::PublicKeyPackage,
         key_provider: &dyn MasterKeyProvider,
-    ) -> Result<()>
-    where
-        C::PublicKeyPackage: Serialize,
-    {
-        let (_, cf_pubkeys) = self.cf_names(curve_type);
-        let key = format!("pubkeypackage:{}", passphrase);
-
-        // Serialize
-        let plaintext =
-            serde_json::to_vec(pubkey_package).context("Failed to serialize pubkey package")?;
+    ) -> Result<KeyStateRecord> {
+        let blind = self.blind(key_provider, passphrase).await?;
+        self.state_by_id(curve_type, &blind)
+    }
 
-        // Encrypt with AES-256-GCM (async)
-        let ciphertext = key_provider.encrypt_storage(passphrase, &plaintext).await?;
+    fn state_by_id(&self, curve_type: CurveType, blind: &str) -> Result<KeyStateRecord> {
+        match self.store.get(&state_table(curve_type), blind.as_bytes())? {
+            Some(json) => serde_json::from_slice(&json).context("Corrupt key state record"),
+            None => Ok(KeyStateRecord::active()),
+        }
+    }
 
-        // Store encrypted (get cf after await)
-        let cf = self
-            .db
-            .cf_handle(cf_pubkeys)
-            .ok_or_else(|| anyhow::anyhow!("Column family {} not found", cf_pubkeys))?;
+    /// Move the key for `passphrase` to `record.state`
+    ///
+    /// Fails unless the key exists and `KeyState::can_transition` allows the
+    /// change. Repeating the current state is a no-op, except that a repeated
+    /// freeze takes the new transition id. Destroying deletes every record of
+    /// the key in the same batch that writes its tombstone, then purges the
+    /// backend's logs and data files of those tables.
+    pub async fn set_state(
+        &self,
+        curve_type: CurveType,
+        passphrase: &str,
+        mut record: KeyStateRecord,
+        key_provider: &dyn MasterKeyProvider,
+    ) -> Result<KeyStateRecord> {
+        let blind = self.blind(key_provider, passphrase).await?;
+        let (keys_table, pubkeys_table) = table_names(curve_type);
+        let state_table = state_table(curve_type);
+        let metadata_table = metadata_table(curve_type);
+        let legacy_key = format!("{}{}", LEGACY_KEY_PREFIX, passphrase);
+        let key = format!("{}{}", KEY_PREFIX, blind);
 
-        self.db
-            .put_cf(&cf, key.as_bytes(), ciphertext)
-            .context("Failed to store encrypted pubkey package")?;
+        let destroyed = self.store.transaction(|tx| {
+            let current = match tx.get(&state_table, blind.as_bytes())? {
+                Some(json) => serde_json::from_slice::<KeyStateRecord>(&json)
+                    .context("Corrupt key state record")?,
+                None => KeyStateRecord::active(),
+            };
+            let exists = tx.get(&keys_table, key.as_bytes())?.is_some()
+                || tx.get(&keys_table, legacy_key.as_bytes())?.is_some();
+            anyhow::ensure!(
+                exists || current.state == KeyState::Destroyed,
+                "No {} key stored for passphrase",
+                curve_type.as_str()
+            );
+            anyhow::ensure!(
+                current.state.can_transition(record.state),
+                "{} key is {}, can't become {}",
+                curve_type.as_str(),
+                current.state.as_str(),
+                record.state.as_str()
+            );
+            if current.state == record.state && record.state != KeyState::Frozen {
+                record = current;
+                return Ok(false);
+            }
 
+            if record.state == KeyState::Destroyed {
+                record.group_key_fingerprint = match tx.get(&metadata_table, blind.as_bytes())? {
+                    Some(json) => serde_json::from_slice::<KeyMetadata>(&json)
+                        .ok()
+                        .map(|metadata| metadata.group_key_fingerprint),
+                    None => None,
+                };
+                tx.delete(&keys_table, key.as_bytes());
+                tx.delete(
+                    &keys_table,
+                    format!("{}{}", PASSPHRASE_PREFIX, blind).as_bytes(),
+                );
+                tx.delete(
+                    &pubkeys_table,
+                    format!("{}{}", PUBKEY_PREFIX, blind).as_bytes(),
+                );
+                tx.delete(&metadata_table, blind.as_bytes());
+                let legacy_pubkey = format!("{}{}", LEGACY_PUBKEY_PREFIX, passphrase);
+                for (table, legacy) in
+                    [(&keys_table, &legacy_key), (&pubkeys_table, &legacy_pubkey)]
+                {
+                    if tx.get(table, legacy.as_bytes())?.is_some() {
+                        tx.delete(table, legacy.as_bytes());
+                    }
+                }
+            }
+            record.changed_at = unix_now();
+            tx.put(
+                &state_table,
+                blind.as_bytes(),
+                &serde_json::to_vec(&record)?,
+            );
+            Ok(record.state == KeyState::Destroyed)
+        })?;
+
+        if destroyed {
+            self.store
+                .purge(&[&keys_table, &pubkeys_table, &metadata_table])
+                .context("Key records deleted, but purging the share store failed")?;
+        }
+        Ok(record)
+    }
+
+    /// Add a state record for `passphrase` to `batch` (backup restore)
+    pub async fn stage_state(
+        &self,
+        batch: &mut WriteBatch,
+        curve_type: CurveType,
+        passphrase: &str,
+        record: &KeyStateRecord,
+        key_provider: &dyn MasterKeyProvider,
+    ) -> Result<()> {
+        let blind = self.blind(key_provider, passphrase).await?;
+        batch.put(
+            &state_table(curve_type),
+            blind.as_bytes(),
+            &serde_json::to_vec(record)?,
+        );
         Ok(())
     }
 
+    /// Verify every `C` record: decryption, share vs. group key, metadata
+    /// fingerprint and records left without their key
+    ///
+    /// Returns how many keys were checked and what was wrong. Issues name
+    /// keys by blinded id only. Run it on a `snapshot` so records written
+    /// during the walk don't show up as orphans.
+    pub async fn check_records<C: CurveOperations>(
+        &self,
+        key_provider: &dyn MasterKeyProvider,
+        node_index: u16,
+    ) -> Result<(u64, Vec<ScrubIssue>)> {
+        let curve_type = C::CURVE;
+        let (keys_table, pubkeys_table) = table_names(curve_type);
+        let mut issues = Vec::new();
+        let mut checked = 0;
+        let mut key_ids = BTreeSet::new();
+
+        for key in self.store.list(&keys_table, KEY_PREFIX.as_bytes())? {
+            let id = String::from_utf8_lossy(&key[KEY_PREFIX.len()..]).into_owned();
+            key_ids.insert(id.clone());
+            checked += 1;
+
+            let issue = |kind: &str, detail: String| ScrubIssue::new(curve_type, &id, kind, detail);
+            let catalog = match self.store.get(
+                &keys_table,
+                format!("{}{}", PASSPHRASE_PREFIX, id).as_bytes(),
+            ) {
+                Ok(Some(catalog)) => catalog,
+                Ok(None) => {
+                    issues.push(issue(
+                        "missing_passphrase_record",
+                        "no passphrase record, the key package can't be decrypted".to_string(),
+                    ));
+                    continue;
+                }
+                Err(e) => {
+                    issues.push(issue("read_failed", format!("passphrase record: {}", e)));
+                    continue;
+                }
+            };
+            let passphrase = match key_provider
+                .decrypt_storage(&catalog_label(&id), &catalog)
+                .await
+                .and_then(|plaintext| Ok(String::from_utf8(plaintext)?))
+            {
+                Ok(passphrase) => passphrase,
+                Err(e) => {
+                    issues.push(issue("decrypt_failed", format!("passphrase record: {}", e)));
+                    continue;
+                }
+            };
+            match self.blind(key_provider, &passphrase).await {
+                Ok(blind) if blind == id => {}
+                Ok(_) => {
+                    issues.push(issue(
+                        "blind_mismatch",
+                        "passphrase record belongs to another key".to_string(),
+                    ));
+                    continue;
+                }
+                Err(e) => {
+                    issues.push(issue("blind_failed", e.to_string()));
+                    continue;
+                }
+            }
+
+            let pubkey = format!("{}{}", PUBKEY_PREFIX, id);
+            let pubkey_package = match self
+                .check_pair::<C>(
+                    &key,
+                    pubkey.as_bytes(),
+                    &passphrase,
+                    key_provider,
+                    node_index,
+                )
+                .await
+            {
+                Ok(pubkey_package) => pubkey_package,
+                Err((kind, detail)) => {
+                    issues.push(issue(kind, detail));
+                    continue;
+                }
+            };
+
+            match self.metadata_by_id(curve_type, &id) {
+                Ok(Some(metadata)) => match group_key_fingerprint::<C>(&pubkey_package) {
+                    Ok(fingerprint) if metadata.group_key_fingerprint != fingerprint => {
+                        issues.push(issue(
+                            "group_key_mismatch",
+                            format!(
+                                "metadata fingerprint {} but group key is {}",
+                                metadata.group_key_fingerprint, fingerprint
+                            ),
+                        ));
+                    }
+                    Ok(_) => {}
+                    Err(e) => issues.push(issue("corrupt_package", format!("group key: {}", e))),
+                },
+                Ok(None) => {}
+                Err(e) => issues.push(issue("corrupt_metadata", e.to_string())),
+            }
+            let destroyed = self
+                .state_by_id(curve_type, &id)
+                .is_ok_and(|record| record.state == KeyState::Destroyed);
+            if destroyed {
+                issues.push(issue(
+                    "tombstone_conflict",
+                    "key is destroyed but its shares are still stored".to_string(),
+                ));
+            }
+        }
+
+        // Not yet migrated records are keyed by passphrase
+        for passphrase in self.legacy_passphrases(&keys_table)? {
+            checked += 1;
+            // The id names the record in reports; the passphrase must not
+            let id = match self.blind(key_provider, &passphrase).await {
+                Ok(id) => id,
+                Err(e) => {
+                    issues.push(ScrubIssue::new(
+                        curve_type,
+                        "legacy",
+                        "blind_failed",
+                        e.to_string(),
+                    ));
+                    continue;
+                }
+            };
+            let key = format!("{}{}", LEGACY_KEY_PREFIX, passphrase);
+            let pubkey = format!("{}{}", LEGACY_PUBKEY_PREFIX, passphrase);
+            if let Err((kind, detail)) = self
+                .check_pair::<C>(
+                    key.as_bytes(),
+                    pubkey.as_bytes(),
+                    &passphrase,
+                    key_provider,
+                    node_index,
+                )
+                .await
+            {
+                issues.push(ScrubIssue::new(curve_type, &id, kind, detail));
+            }
+        }
+
+        // Records whose key package is gone
+        let orphans = [
+            (&keys_table, PASSPHRASE_PREFIX),
+            (&pubkeys_table, PUBKEY_PREFIX),
+            (&metadata_table(curve_type), ""),
+        ];
+        for (table, prefix) in orphans {
+            for key in self.store.list(table, prefix.as_bytes())? {
+                let id = String::from_utf8_lossy(&key[prefix.len()..]).into_owned();
+                if !key_ids.contains(&id) {
+                    issues.push(ScrubIssue::new(
+                        curve_type,
+                        &id,
+                        "orphan_record",
+                        format!("{}{} without a key package", table, prefix),
+                    ));
+                }
+            }
+        }
+
+        Ok((checked, issues))
+    }
+
+    /// Decrypt a key/pubkey package pair and check they belong together
+    ///
+    /// Fails with an issue kind and detail; returns the pubkey package.
+    async fn check_pair<C: CurveOperations>(
+        &self,
+        key: &[u8],
+        pubkey: &[u8],
+        passphrase: &str,
+        key_provider: &dyn MasterKeyProvider,
+        node_index: u16,
+    ) -> std::result::Result<PublicKeyPackage<C>, (&'static str, String)> {
+        let (keys_table, pubkeys_table) = table_names(C::CURVE);
+        let read = |table: &str, key: &[u8]| {
+            self.store
+                .get(table, key)
+                .map_err(|e| ("read_failed", e.to_string()))
+        };
+
+        let key_ciphertext =
+            read(&keys_table, key)?.ok_or(("read_failed", "key package vanished".to_string()))?;
+        let Some(pubkey_ciphertext) = read(&pubkeys_table, pubkey)? else {
+            return Err((
+                "missing_pubkey_package",
+                "key package stored without its pubkey package".to_string(),
+            ));
+        };
+
+        let mut plaintexts = Vec::new();
+        for (what, ciphertext) in [("key", key_ciphertext), ("pubkey", pubkey_ciphertext)] {
+            let plaintext = key_provider
+                .decrypt_storage(passphrase, &ciphertext)
+                .await
+                .map_err(|e| ("decrypt_failed", format!("{} package: {}", what, e)))?;
+            plaintexts.push(plaintext);
+        }
+        let key_package: KeyPackage<C> = serde_json::from_slice(&plaintexts[0])
+            .map_err(|e| ("corrupt_package", format!("key package: {}", e)))?;
+        let pubkey_package: PublicKeyPackage<C> = serde_json::from_slice(&plaintexts[1])
+            .map_err(|e| ("corrupt_package", format!("pubkey package: {}", e)))?;
+
+        verify_packages::<C>(&key_package, &pubkey_package, node_index)
+            .map_err(|e| ("share_mismatch", e.to_string()))?;
+        Ok(pubkey_package)
+    }
+
+    /// Approximate size of the share store (see `ShareStore::size_bytes`)
+    pub fn size_bytes(&self) -> Result<u64> {
+        self.store.size_bytes()
+    }
+
+    /// Retrieve key package for passphrase (decrypt from storage)
+    pub async fn get_key_package<C: CurveOperations>(
+        &self,
+        passphrase: &str,
+        key_provider: &dyn MasterKeyProvider,
+    ) -> Result<Option<KeyPackage<C>>> {
+        let (keys_table, _) = table_names(C::CURVE);
+        let record = self
+            .get_record(
+                &keys_table,
+                KEY_PREFIX,
+                LEGACY_KEY_PREFIX,
+                passphrase,
+                key_provider,
+            )
+            .await?;
+
+        match record {
+            Some(ciphertext) => {
+                // Decrypt with AES-256-GCM (async)
+                let plaintext = key_provider
+                    .decrypt_storage(passphrase, &ciphertext)
+                    .await?;
+                let key_package: KeyPackage<C> = serde_json::from_slice(&plaintext)
+                    .context("Failed to deserialize key package")?;
+                Ok(Some(key_package))
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Retrieve public key package for passphrase (decrypt from storage)
     pub async fn get_pubkey_package<C: CurveOperations>(
         &self,
-        curve_type: CurveType,
         passphrase: &str,
         key_provider: &dyn MasterKeyProvider,
-    ) -> Result<Option<C::PublicKeyPackage>>
-    where
-        C::PublicKeyPackage: DeserializeOwned,
-    {
-        let (_, cf_pubkeys) = self.cf_names(curve_type);
-        let key = format!("pubkeypackage:{}", passphrase);
-
-        // Get ciphertext (drop cf before await)
-        let ciphertext_opt = {
-            let cf = self
-                .db
-                .cf_handle(cf_pubkeys)
-                .ok_or_else(|| anyhow::anyhow!("Column family {} not found", cf_pubkeys))?;
-            self.db.get_cf(&cf, key.as_bytes())?
-        };
+    ) -> Result<Option<PublicKeyPackage<C>>> {
+        let (_, pubkeys_table) = table_names(C::CURVE);
+        let record = self
+            .get_record(
+                &pubkeys_table,
+                PUBKEY_PREFIX,
+                LEGACY_PUBKEY_PREFIX,
+                passphrase,
+                key_provider,
+            )
+            .await?;
 
-        match ciphertext_opt {
+        match record {
             Some(ciphertext) => {
                 // Decrypt with AES-256-GCM (async)
                 let plaintext = key_provider
                     .decrypt_storage(passphrase, &ciphertext)
                     .await?;
-                let pubkey_package: C::PublicKeyPackage = serde_json::from_slice(&plaintext)
+                let pubkey_package: PublicKeyPackage<C> = serde_json::from_slice(&plaintext)
                     .context("Failed to deserialize pubkey package")?;
                 Ok(Some(pubkey_package))
             }
@@ -200,45 +1138,122 @@ impl MultiCurveStorage {
     }
 
     /// Check if we have shares for this passphrase and curve
-    #[allow(dead_code)]
-    pub fn has_passphrase(&self, curve_type: CurveType, passphrase: &str) -> bool {
-        let (cf_keys, _) = self.cf_names(curve_type);
-        if let Some(cf) = self.db.cf_handle(cf_keys) {
-            let key = format!("keypackage:{}", passphrase);
-            self.db.get_cf(&cf, key.as_bytes()).ok().flatten().is_some()
-        } else {
-            false
-        }
+    pub async fn has_passphrase(
+        &self,
+        curve_type: CurveType,
+        passphrase: &str,
+        key_provider: &dyn MasterKeyProvider,
+    ) -> Result<bool> {
+        let (keys_table, _) = table_names(curve_type);
+        let record = self
+            .get_record(
+                &keys_table,
+                KEY_PREFIX,
+                LEGACY_KEY_PREFIX,
+                passphrase,
+                key_provider,
+            )
+            .await?;
+        Ok(record.is_some())
     }
 }
 
+/// A key package must be this node's share of the pubkey package's group key
+pub fn verify_packages<C: CurveOperations>(
+    key_package: &KeyPackage<C>,
+    pubkey_package: &PublicKeyPackage<C>,
+    node_index: u16,
+) -> Result<()> {
+    let identifier = Identifier::<C::Suite>::try_from(node_index + 1)
+        .map_err(|e| anyhow::anyhow!("Invalid node index: {:?}", e))?;
+    anyhow::ensure!(
+        *key_package.identifier() == identifier,
+        "key package belongs to another node"
+    );
+    anyhow::ensure!(
+        key_package.verifying_key() == pubkey_package.verifying_key(),
+        "key package and pubkey package have different group keys"
+    );
+
+    let verifying_share = VerifyingShare::<C::Suite>::from(*key_package.signing_share());
+    anyhow::ensure!(
+        *key_package.verifying_share() == verifying_share
+            && pubkey_package.verifying_shares().get(&identifier) == Some(&verifying_share),
+        "signing share doesn't match the group's verifying share"
+    );
+    Ok(())
+}
+
+/// First 16 bytes (hex) of sha256(group public key)
+pub fn group_key_fingerprint<C: CurveOperations>(
+    pubkey_package: &PublicKeyPackage<C>,
+) -> Result<String> {
+    let group_key = pubkey_package
+        .verifying_key()
+        .serialize()
+        .map_err(|e| anyhow::anyhow!("Failed to serialize group key: {:?}", e))?;
+    let fingerprint = bitcoin::hashes::sha256::Hash::hash(&group_key);
+    Ok(hex::encode(&fingerprint[..16]))
+}
+
+/// Label the encrypted passphrase under `passphrase:{blind}` is keyed with
+fn catalog_label(blind: &str) -> String {
+    format!("passphrase-index:{}", blind)
+}
+
+/// Metadata table for curve type (`{curve}_metadata`)
+fn metadata_table(curve_type: CurveType) -> String {
+    format!("{}_metadata", curve_type.as_str().replace('-', "_"))
+}
+
+/// Lifecycle state table for curve type (`{curve}_state`)
+fn state_table(curve_type: CurveType) -> String {
+    format!("{}_state", curve_type.as_str().replace('-', "_"))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Table names for curve type (`{curve}_keys`, `{curve}_pubkeys`)
+fn table_names(curve_type: CurveType) -> (String, String) {
+    let prefix = curve_type.as_str().replace('-', "_");
+    (format!("{}_keys", prefix), format!("{}_pubkeys", prefix))
+}
+
 /// Curve-specific storage wrapper
 pub struct CurveStorage<C: CurveOperations> {
     storage: Arc<MultiCurveStorage>,
-    curve_type: CurveType,
     _marker: std::marker::PhantomData<C>,
 }
 
 impl<C: CurveOperations> CurveStorage<C> {
-    pub fn new(storage: Arc<MultiCurveStorage>, curve_type: CurveType) -> Self {
+    pub fn new(storage: Arc<MultiCurveStorage>) -> Self {
         Self {
             storage,
-            curve_type,
             _marker: std::marker::PhantomData,
         }
     }
 
-    pub async fn store_key_package(
+    pub async fn store_packages(
         &self,
         passphrase: &str,
-        key_package: &C::KeyPackage,
+        key_package: &KeyPackage<C>,
+        pubkey_package: &PublicKeyPackage<C>,
+        metadata: &KeyMetadata,
         key_provider: &dyn MasterKeyProvider,
-    ) -> Result<()>
-    where
-        C::KeyPackage: Serialize,
-    {
+    ) -> Result<()> {
         self.storage
-            .store_key_package::<C>(self.curve_type, passphrase, key_package, key_provider)
+            .store_packages::<C>(
+                passphrase,
+                key_package,
+                pubkey_package,
+                metadata,
+                key_provider,
+            )
             .await
     }
 
@@ -246,44 +1261,422 @@ impl<C: CurveOperations> CurveStorage<C> {
         &self,
         passphrase: &str,
         key_provider: &dyn MasterKeyProvider,
-    ) -> Result<Option<C::KeyPackage>>
-    where
-        C::KeyPackage: DeserializeOwned,
-    {
+    ) -> Result<Option<KeyPackage<C>>> {
         self.storage
-            .get_key_package::<C>(self.curve_type, passphrase, key_provider)
+            .get_key_package::<C>(passphrase, key_provider)
             .await
     }
 
-    pub async fn store_pubkey_package(
+    pub async fn get_pubkey_package(
         &self,
         passphrase: &str,
-        pubkey_package: &C::PublicKeyPackage,
         key_provider: &dyn MasterKeyProvider,
-    ) -> Result<()>
-    where
-        C::PublicKeyPackage: Serialize,
-    {
+    ) -> Result<Option<PublicKeyPackage<C>>> {
         self.storage
-            .store_pubkey_package::<C>(self.curve_type, passphrase, pubkey_package, key_provider)
+            .get_pubkey_package::<C>(passphrase, key_provider)
             .await
     }
 
-    pub async fn get_pubkey_package(
+    #[allow(dead_code)]
+    pub async fn has_passphrase(
         &self,
         passphrase: &str,
         key_provider: &dyn MasterKeyProvider,
-    ) -> Result<Option<C::PublicKeyPackage>>
-    where
-        C::PublicKeyPackage: DeserializeOwned,
-    {
+    ) -> Result<bool> {
         self.storage
-            .get_pubkey_package::<C>(self.curve_type, passphrase, key_provider)
+            .has_passphrase(C::CURVE, passphrase, key_provider)
             .await
     }
+}
 
-    #[allow(dead_code)]
-    pub fn has_passphrase(&self, passphrase: &str) -> bool {
-        self.storage.has_passphrase(self.curve_type, passphrase)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::secp256k1::Secp256k1Operations;
+    use crate::node::key_provider::TestKeyProvider;
+    use crate::node::share_store::MemoryStore;
+    use std::collections::BTreeMap;
+
+    #[tokio::test]
+    async fn test_blinded_keys_and_migration() {
+        type C = Secp256k1Operations;
+        let (shares, pubkey_package) =
+            frost_core::keys::generate_with_dealer::<<C as CurveOperations>::Suite, _>(
+                3,
+                2,
+                frost_core::keys::IdentifierList::Default,
+                &mut rand::thread_rng(),
+            )
+            .unwrap();
+        let shares: BTreeMap<_, _> = shares.into_iter().collect();
+        let key_package =
+            KeyPackage::<C>::try_from(shares.values().next().unwrap().clone()).unwrap();
+
+        let provider = TestKeyProvider;
+        let store: Arc<dyn ShareStore> = Arc::new(MemoryStore::new());
+        let storage = MultiCurveStorage::new(store.clone());
+        let (keys_table, pubkeys_table) = table_names(CurveType::Secp256k1Taproot);
+        let metadata = KeyMetadata::new::<C>(
+            &pubkey_package,
+            "session".to_string(),
+            2,
+            vec![0, 1, 2],
+            0,
+            KeyDerivation::AesEcb,
+        )
+        .unwrap();
+
+        // Records written in clear before blinding
+        let key_ciphertext = provider
+            .encrypt_storage("legacy-wallet", &serde_json::to_vec(&key_package).unwrap())
+            .await
+            .unwrap();
+        let pubkey_ciphertext = provider
+            .encrypt_storage(
+                "legacy-wallet",
+                &serde_json::to_vec(&pubkey_package).unwrap(),
+            )
+            .await
+            .unwrap();
+        store
+            .put(&keys_table, b"keypackage:legacy-wallet", &key_ciphertext)
+            .unwrap();
+        store
+            .put(
+                &pubkeys_table,
+                b"pubkeypackage:legacy-wallet",
+                &pubkey_ciphertext,
+            )
+            .unwrap();
+        storage
+            .store_packages::<C>(
+                "new-wallet",
+                &key_package,
+                &pubkey_package,
+                &metadata,
+                &provider,
+            )
+            .await
+            .unwrap();
+
+        // Readable before and after migration
+        assert!(storage
+            .get_key_package::<C>("legacy-wallet", &provider)
+            .await
+            .unwrap()
+            .is_some());
+        let mut progress = |_, _| {};
+        assert_eq!(storage.legacy_key_count().unwrap(), 1);
+        assert_eq!(
+            storage
+                .migrate_blinded_keys(&provider, &mut progress)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(storage.legacy_key_count().unwrap(), 0);
+        assert_eq!(
+            storage
+                .get_pubkey_package::<C>("legacy-wallet", &provider)
+                .await
+                .unwrap(),
+            Some(pubkey_package)
+        );
+        assert_eq!(
+            storage
+                .passphrases(CurveType::Secp256k1Taproot, &provider)
+                .await
+                .unwrap(),
+            vec!["legacy-wallet".to_string(), "new-wallet".to_string()]
+        );
+
+        // Inventory lists blinded ids; only DKG-stored keys have metadata
+        storage
+            .record_signing(CurveType::Secp256k1Taproot, "new-wallet", &provider)
+            .await
+            .unwrap();
+        let keys = storage
+            .list_keys(CurveType::Secp256k1Taproot, None, 10)
+            .unwrap();
+        assert_eq!(keys.len(), 2);
+        let counted: Vec<_> = keys.iter().filter_map(|(_, m, _)| m.as_ref()).collect();
+        assert_eq!(counted.len(), 1);
+        assert_eq!(counted[0].signing_count, 1);
+        let page = storage
+            .list_keys(CurveType::Secp256k1Taproot, Some(&keys[0].0), 10)
+            .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].0, keys[1].0);
+
+        // No stored key mentions a passphrase
+        for table in [
+            &keys_table,
+            &pubkeys_table,
+            &metadata_table(CurveType::Secp256k1Taproot),
+        ] {
+            for key in store.list(table, b"").unwrap() {
+                let key = String::from_utf8(key).unwrap();
+                assert!(!key.contains("wallet"), "{}", key);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_and_destroy() {
+        type C = Secp256k1Operations;
+        let curve = CurveType::Secp256k1Taproot;
+        let (shares, pubkey_package) =
+            frost_core::keys::generate_with_dealer::<<C as CurveOperations>::Suite, _>(
+                3,
+                2,
+                frost_core::keys::IdentifierList::Default,
+                &mut rand::thread_rng(),
+            )
+            .unwrap();
+        let share = shares.into_values().next().unwrap();
+        let key_package = KeyPackage::<C>::try_from(share).unwrap();
+        let metadata = KeyMetadata::new::<C>(
+            &pubkey_package,
+            "session".to_string(),
+            2,
+            vec![0, 1, 2],
+            0,
+            KeyDerivation::AesEcb,
+        )
+        .unwrap();
+
+        let path = std::env::temp_dir().join(format!("frost-lifecycle-{}.db", std::process::id()));
+        let store: Arc<dyn ShareStore> =
+            Arc::new(crate::node::share_store::SqliteStore::open(path.to_str().unwrap()).unwrap());
+        let storage = MultiCurveStorage::new(store.clone());
+        let provider = TestKeyProvider;
+        storage
+            .store_packages::<C>(
+                "wallet",
+                &key_package,
+                &pubkey_package,
+                &metadata,
+                &provider,
+            )
+            .await
+            .unwrap();
+
+        let to = |state| KeyStateRecord {
+            state,
+            ..KeyStateRecord::active()
+        };
+        let set = |state| storage.set_state(curve, "wallet", to(state), &provider);
+
+        assert!(set(KeyState::Destroyed).await.is_err());
+        assert_eq!(set(KeyState::Frozen).await.unwrap().state, KeyState::Frozen);
+        assert_eq!(set(KeyState::Active).await.unwrap().state, KeyState::Active);
+        set(KeyState::Retired).await.unwrap();
+        assert!(set(KeyState::Active).await.is_err());
+        assert_eq!(storage.curve_stats(curve).unwrap().retired, 1);
+
+        let tombstone = set(KeyState::Destroyed).await.unwrap();
+        assert_eq!(
+            tombstone.group_key_fingerprint,
+            Some(metadata.group_key_fingerprint.clone())
+        );
+        assert!(!storage
+            .has_passphrase(curve, "wallet", &provider)
+            .await
+            .unwrap());
+        assert!(storage
+            .passphrases(curve, &provider)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            storage.get_state(curve, "wallet", &provider).await.unwrap(),
+            tombstone
+        );
+        assert!(set(KeyState::Active).await.is_err());
+
+        // Only the tombstone is left
+        let (keys_table, pubkeys_table) = table_names(curve);
+        for table in [&keys_table, &pubkeys_table, &metadata_table(curve)] {
+            assert!(store.list(table, b"").unwrap().is_empty());
+        }
+        drop(storage);
+        drop(store);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_inventory_pages_and_restore() {
+        type C = Secp256k1Operations;
+        let curve = CurveType::Secp256k1Taproot;
+        let (shares, pubkey_package) =
+            frost_core::keys::generate_with_dealer::<<C as CurveOperations>::Suite, _>(
+                3,
+                2,
+                frost_core::keys::IdentifierList::Default,
+                &mut rand::thread_rng(),
+            )
+            .unwrap();
+        let share = shares.into_values().next().unwrap();
+        let key_package = KeyPackage::<C>::try_from(share).unwrap();
+        let metadata = |session: &str| {
+            KeyMetadata::new::<C>(
+                &pubkey_package,
+                session.to_string(),
+                2,
+                vec![0, 1, 2],
+                0,
+                KeyDerivation::AesEcb,
+            )
+            .unwrap()
+        };
+
+        let storage = MultiCurveStorage::new(Arc::new(MemoryStore::new()));
+        let provider = TestKeyProvider;
+        for passphrase in ["a", "b", "c", "d", "e"] {
+            storage
+                .store_packages::<C>(
+                    passphrase,
+                    &key_package,
+                    &pubkey_package,
+                    &metadata("first"),
+                    &provider,
+                )
+                .await
+                .unwrap();
+        }
+
+        // Pages of two cover every key once, in order
+        let mut listed = Vec::new();
+        let mut after = None;
+        loop {
+            let page = storage.list_keys(curve, after.as_deref(), 2).unwrap();
+            assert!(page.len() <= 2);
+            let Some((last, _, _)) = page.last() else {
+                break;
+            };
+            after = Some(last.clone());
+            listed.extend(page.into_iter().map(|(id, _, _)| id));
+        }
+        assert_eq!(listed.len(), 5);
+        assert!(listed.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // Storing a key again (a DKG re-run) keeps its history
+        let mut before = storage
+            .get_metadata(curve, "c", &provider)
+            .await
+            .unwrap()
+            .unwrap();
+        before.created_at -= 1000;
+        storage
+            .store
+            .put(
+                &metadata_table(curve),
+                storage.blind(&provider, "c").await.unwrap().as_bytes(),
+                &serde_json::to_vec(&before).unwrap(),
+            )
+            .unwrap();
+        storage.record_signing(curve, "c", &provider).await.unwrap();
+        storage
+            .store_packages::<C>(
+                "c",
+                &key_package,
+                &pubkey_package,
+                &metadata("second"),
+                &provider,
+            )
+            .await
+            .unwrap();
+        let after = storage
+            .get_metadata(curve, "c", &provider)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(after.dkg_session, "second");
+        assert_eq!(after.created_at, before.created_at);
+        assert_eq!(after.signing_count, 1);
+        assert!(after.last_signed_at.is_some());
+        assert_eq!(storage.list_keys(curve, None, 10).unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_rocksdb_keys_never_name_passphrases() {
+        type C = Secp256k1Operations;
+        let curve = CurveType::Secp256k1Taproot;
+        let (shares, pubkey_package) =
+            frost_core::keys::generate_with_dealer::<<C as CurveOperations>::Suite, _>(
+                3,
+                2,
+                frost_core::keys::IdentifierList::Default,
+                &mut rand::thread_rng(),
+            )
+            .unwrap();
+        let share = shares.into_values().next().unwrap();
+        let key_package = KeyPackage::<C>::try_from(share).unwrap();
+        let metadata = KeyMetadata::new::<C>(
+            &pubkey_package,
+            "session".to_string(),
+            2,
+            vec![0, 1, 2],
+            0,
+            KeyDerivation::AesEcb,
+        )
+        .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("frost-blind-rocks-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store: Arc<dyn ShareStore> =
+            Arc::new(crate::node::share_store::RocksDbStore::open(dir.to_str().unwrap()).unwrap());
+        let storage = MultiCurveStorage::new(store.clone());
+        let provider = TestKeyProvider;
+        let (keys_table, pubkeys_table) = table_names(curve);
+        let raw_keys = || {
+            let mut keys = Vec::new();
+            for table in [
+                keys_table.clone(),
+                pubkeys_table.clone(),
+                metadata_table(curve),
+                state_table(curve),
+            ] {
+                for key in store.list(&table, b"").unwrap() {
+                    keys.push(String::from_utf8_lossy(&key).into_owned());
+                }
+            }
+            keys
+        };
+
+        // A fresh store never writes the passphrase, not even as a tombstone
+        storage
+            .store_packages::<C>(
+                "fresh-wallet",
+                &key_package,
+                &pubkey_package,
+                &metadata,
+                &provider,
+            )
+            .await
+            .unwrap();
+        assert!(raw_keys().iter().all(|key| !key.contains("fresh-wallet")));
+        assert!(crate::node::share_store::files_containing(&dir, b"fresh-wallet").is_empty());
+
+        // Records from before blinding are gone from keys and files once migrated
+        let ciphertext = provider
+            .encrypt_storage("legacy-wallet", &serde_json::to_vec(&key_package).unwrap())
+            .await
+            .unwrap();
+        store
+            .put(&keys_table, b"keypackage:legacy-wallet", &ciphertext)
+            .unwrap();
+        assert!(raw_keys().iter().any(|key| key.contains("legacy-wallet")));
+        let mut progress = |_, _| {};
+        storage
+            .migrate_blinded_keys(&provider, &mut progress)
+            .await
+            .unwrap();
+        assert!(raw_keys().iter().all(|key| !key.contains("wallet")));
+        assert!(crate::node::share_store::files_containing(&dir, b"legacy-wallet").is_empty());
+
+        drop(storage);
+        drop(store);
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
@@ -0,0 +1,191 @@
+//! Bounded pool of logged-in PKCS#11 sessions
+//!
+//! Opening a session, logging in and searching for a key by label costs
+//! several HSM round trips, and logging out one session logs out all of
+//! them. Sessions are therefore opened on demand up to `max_sessions`, kept
+//! logged in with each key handle found once, and reused. Callers beyond that
+//! wait up to `acquire_timeout` for a free session and then fail with "HSM
+//! busy" instead of queueing without bound.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use cryptoki::context::Pkcs11;
+use cryptoki::error::{Error as Pkcs11Error, RvError};
+use cryptoki::object::{Attribute, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::slot::Slot;
+use cryptoki::types::AuthPin;
+use tokio::sync::Semaphore;
+
+struct PooledSession {
+    session: Session,
+    /// Key handles by label, looked up on first use
+    keys: HashMap<String, ObjectHandle>,
+    /// Pool generation at login; sessions from before a `clear` are closed
+    generation: u64,
+}
+
+pub struct SessionPool {
+    pkcs11: Pkcs11,
+    slot: Slot,
+    max_sessions: usize,
+    acquire_timeout: Duration,
+    permits: Semaphore,
+    idle: Mutex<Vec<PooledSession>>,
+    generation: AtomicU64,
+}
+
+impl SessionPool {
+    pub fn new(pkcs11: Pkcs11, slot: Slot, max_sessions: usize, acquire_timeout: Duration) -> Self {
+        let max_sessions = max_sessions.max(1);
+        Self {
+            pkcs11,
+            slot,
+            max_sessions,
+            acquire_timeout,
+            permits: Semaphore::new(max_sessions),
+            idle: Mutex::new(Vec::new()),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Run `op` with a logged-in session and the handle of the key labelled `key_label`
+    ///
+    /// A session whose handle went stale (token reset, HSM failover) is
+    /// dropped and `op` retried once on a fresh one.
+    pub async fn run<T>(
+        &self,
+        pin: &str,
+        key_label: &str,
+        op: impl Fn(&Session, ObjectHandle) -> cryptoki::error::Result<T>,
+    ) -> Result<T> {
+        let _permit = tokio::time::timeout(self.acquire_timeout, self.permits.acquire())
+            .await
+            .map_err(|_| {
+                tracing::warn!(
+                    "HSM busy: all {} PKCS#11 sessions in use for {:?}",
+                    self.max_sessions,
+                    self.acquire_timeout
+                );
+                anyhow::anyhow!(
+                    "HSM busy: no PKCS#11 session free within {:?}",
+                    self.acquire_timeout
+                )
+            })?
+            .context("PKCS#11 session pool closed")?;
+
+        let mut retried = false;
+        loop {
+            let mut pooled = match self.idle.lock().unwrap().pop() {
+                Some(pooled) => pooled,
+                None => self.open(pin)?,
+            };
+            let key = match pooled.keys.get(key_label) {
+                Some(key) => *key,
+                None => {
+                    let key = find_key(&pooled.session, key_label);
+                    // A missing key isn't the session's fault
+                    let key = match key {
+                        Ok(key) => key,
+                        Err(e) => {
+                            self.release(pooled);
+                            return Err(e);
+                        }
+                    };
+                    pooled.keys.insert(key_label.to_string(), key);
+                    key
+                }
+            };
+            match op(&pooled.session, key) {
+                Ok(value) => {
+                    self.release(pooled);
+                    return Ok(value);
+                }
+                Err(e) if !retried && is_stale(&e) => {
+                    tracing::warn!("Dropping stale PKCS#11 session: {}", e);
+                    retried = true;
+                }
+                Err(e) => {
+                    // Keep the session unless the error says it's unusable
+                    if !is_stale(&e) {
+                        self.release(pooled);
+                    }
+                    return Err(e.into());
+                }
+            }
+        }
+    }
+
+    fn open(&self, pin: &str) -> Result<PooledSession> {
+        let generation = self.generation.load(Ordering::SeqCst);
+        let session = self
+            .pkcs11
+            .open_ro_session(self.slot)
+            .context("Failed to open PKCS#11 session")?;
+
+        // Login state is shared by all of the application's sessions
+        let auth_pin = AuthPin::new(pin.to_string());
+        match session.login(UserType::User, Some(&auth_pin)) {
+            Ok(()) | Err(Pkcs11Error::Pkcs11(RvError::UserAlreadyLoggedIn, _)) => {}
+            Err(e) => return Err(e).context("Failed to login to PKCS#11 token"),
+        }
+
+        Ok(PooledSession {
+            session,
+            keys: HashMap::new(),
+            generation,
+        })
+    }
+
+    fn release(&self, pooled: PooledSession) {
+        if pooled.generation == self.generation.load(Ordering::SeqCst) {
+            self.idle.lock().unwrap().push(pooled);
+        }
+    }
+
+    /// Log out and close every idle session; sessions in use close when returned
+    pub fn clear(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        let sessions = std::mem::take(&mut *self.idle.lock().unwrap());
+        if let Some(pooled) = sessions.first() {
+            let _ = pooled.session.logout(); // Ignore errors on logout
+        }
+    }
+}
+
+fn find_key(session: &Session, key_label: &str) -> Result<ObjectHandle> {
+    let template = vec![
+        Attribute::Label(key_label.as_bytes().to_vec()),
+        Attribute::Class(ObjectClass::SECRET_KEY), // AES or HMAC key
+    ];
+
+    let objects = session
+        .find_objects(&template)
+        .context("Failed to find HSM key objects")?;
+
+    objects
+        .first()
+        .copied()
+        .context(format!("HSM key with label '{}' not found", key_label))
+}
+
+/// Errors after which the session or key handle can't be used again
+fn is_stale(error: &Pkcs11Error) -> bool {
+    matches!(
+        error,
+        Pkcs11Error::Pkcs11(
+            RvError::SessionHandleInvalid
+                | RvError::SessionClosed
+                | RvError::ObjectHandleInvalid
+                | RvError::KeyHandleInvalid
+                | RvError::UserNotLoggedIn
+                | RvError::DeviceRemoved
+                | RvError::DeviceError
+                | RvError::TokenNotPresent,
+            _
+        )
+    )
+}
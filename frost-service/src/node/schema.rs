@@ -0,0 +1,310 @@
+//! Versioned share store layout and its migrations
+//!
+//! The store records the layout version it was last migrated to (see
+//! `SchemaVersion`). Stores from before versioning are version 1; a new,
+//! empty store starts at `CURRENT_VERSION`. `migrate` runs the missing steps
+//! of `MIGRATIONS` in order on `MultiCurveStorage::open` and again after an
+//! HSM unlock, because some steps need the key provider.
+//!
+//! To change the layout (table names, key prefixes, encryption, the frost
+//! package encoding), add a `Step`, append it to `MIGRATIONS` with the next
+//! version and keep read fallbacks for the old layout until it has run.
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::key_provider::MasterKeyProvider;
+use super::multi_storage::{MultiCurveStorage, SchemaVersion};
+
+pub struct Migration {
+    /// Layout version once this step has run
+    pub version: u32,
+    pub description: &'static str,
+    step: Step,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    BlindKeys,
+    ResealRecords,
+}
+
+/// Every layout change since version 1, oldest first
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 2,
+        description: "index records by blinded passphrase",
+        step: Step::BlindKeys,
+    },
+    Migration {
+        version: 3,
+        description: "re-encrypt records with random nonces",
+        step: Step::ResealRecords,
+    },
+];
+
+pub const CURRENT_VERSION: u32 = 3;
+
+impl Step {
+    fn needs_key_provider(self) -> bool {
+        match self {
+            Step::BlindKeys | Step::ResealRecords => true,
+        }
+    }
+
+    /// Records the step would rewrite
+    fn pending(self, storage: &MultiCurveStorage) -> Result<usize> {
+        match self {
+            Step::BlindKeys => storage.legacy_key_count(),
+            Step::ResealRecords => storage.legacy_sealed_count(),
+        }
+    }
+
+    async fn apply(
+        self,
+        storage: &MultiCurveStorage,
+        key_provider: &dyn MasterKeyProvider,
+        progress: &mut (dyn FnMut(usize, usize) + Send),
+    ) -> Result<usize> {
+        match self {
+            Step::BlindKeys => storage.migrate_blinded_keys(key_provider, progress).await,
+            Step::ResealRecords => storage.reseal_records(key_provider, progress).await,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MigrationOptions {
+    /// Report what would change without writing anything
+    pub dry_run: bool,
+    /// Copy the store into a new directory here before the first step
+    pub backup_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    /// Version found in the store (`CURRENT_VERSION` for a new store)
+    pub from: u32,
+    pub to: u32,
+    /// (version, records rewritten), or records pending on a dry run
+    pub steps: Vec<(u32, usize)>,
+    pub backup: Option<PathBuf>,
+}
+
+/// Bring `storage` up to `CURRENT_VERSION`, as far as the key provider allows
+///
+/// Every step is recorded as soon as it completes; a step interrupted by a
+/// crash or a locked HSM resumes on the next call. A store newer than this
+/// release is refused rather than read with the wrong layout.
+pub async fn migrate(
+    storage: &MultiCurveStorage,
+    key_provider: &dyn MasterKeyProvider,
+    options: &MigrationOptions,
+) -> Result<MigrationReport> {
+    let from = match storage.schema_version()? {
+        Some(record) => record.version,
+        None if storage.is_empty()? => {
+            // A new store also fixes its blinding key's scheme for good
+            if !options.dry_run {
+                storage.set_index_derivation(key_provider.derivation())?;
+                storage.set_schema_version(&SchemaVersion::new(CURRENT_VERSION))?;
+            }
+            return Ok(MigrationReport {
+                from: CURRENT_VERSION,
+                to: CURRENT_VERSION,
+                ..Default::default()
+            });
+        }
+        None => 1,
+    };
+    anyhow::ensure!(
+        from <= CURRENT_VERSION,
+        "Share store has schema version {} but this release only knows up to {}; refusing to open it",
+        from,
+        CURRENT_VERSION
+    );
+
+    let mut report = MigrationReport {
+        from,
+        to: from,
+        ..Default::default()
+    };
+    let todo: Vec<_> = MIGRATIONS.iter().filter(|m| m.version > from).collect();
+    if !todo.is_empty() {
+        tracing::info!(
+            "📦 Share store at schema v{}, {} migration(s) to v{} pending",
+            report.from,
+            todo.len(),
+            CURRENT_VERSION
+        );
+    }
+
+    if options.dry_run {
+        for migration in todo {
+            let pending = migration.step.pending(storage)?;
+            tracing::info!(
+                "🧪 Dry run: schema v{} ({}) would rewrite {} records",
+                migration.version,
+                migration.description,
+                pending
+            );
+            report.steps.push((migration.version, pending));
+        }
+        return Ok(report);
+    }
+
+    for migration in todo {
+        if migration.step.needs_key_provider() && key_provider.is_locked() {
+            tracing::warn!(
+                "⏸️  Schema v{} ({}) waits for the HSM to be unlocked",
+                migration.version,
+                migration.description
+            );
+            break;
+        }
+
+        if report.backup.is_none() {
+            if let Some(dir) = &options.backup_dir {
+                report.backup = Some(backup(storage, dir, from)?);
+            }
+        }
+
+        tracing::info!(
+            "🔧 Migrating share store to schema v{}: {}",
+            migration.version,
+            migration.description
+        );
+        let version = migration.version;
+        let mut progress = |done: usize, total: usize| {
+            if done == total || done.is_multiple_of(100) {
+                tracing::info!("   schema v{}: {}/{} records", version, done, total);
+            }
+        };
+        let rewritten = migration
+            .step
+            .apply(storage, key_provider, &mut progress)
+            .await
+            .with_context(|| format!("Schema migration to v{} failed", migration.version))?;
+        storage.set_schema_version(&SchemaVersion::new(migration.version))?;
+
+        tracing::info!(
+            "✅ Share store at schema v{} ({} records rewritten)",
+            migration.version,
+            rewritten
+        );
+        report.steps.push((migration.version, rewritten));
+        report.to = migration.version;
+    }
+
+    Ok(report)
+}
+
+/// Copy the store to `{dir}/schema-v{from}-{unix time}`
+fn backup(storage: &MultiCurveStorage, dir: &std::path::Path, from: u32) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create backup directory {}", dir.display()))?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let path = dir.join(format!("schema-v{}-{}", from, now));
+
+    storage.backup_to(&path)?;
+    tracing::info!("💾 Backed up share store to {}", path.display());
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::secp256k1::Secp256k1Operations;
+    use crate::curves::{CurveOperations, KeyPackage};
+    use crate::node::key_provider::{legacy_encrypt_storage, TestKeyProvider};
+    use crate::node::share_store::{ShareStore, SqliteStore};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_migrate_unversioned_store() {
+        type C = Secp256k1Operations;
+        let (shares, pubkey_package) =
+            frost_core::keys::generate_with_dealer::<<C as CurveOperations>::Suite, _>(
+                3,
+                2,
+                frost_core::keys::IdentifierList::Default,
+                &mut rand::thread_rng(),
+            )
+            .unwrap();
+        let key_package = KeyPackage::<C>::try_from(shares.into_values().next().unwrap()).unwrap();
+        let provider = TestKeyProvider;
+
+        let dir = std::env::temp_dir().join(format!("frost-schema-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let store: Arc<dyn ShareStore> =
+            Arc::new(SqliteStore::open(dir.join("shares.db").to_str().unwrap()).unwrap());
+
+        // A version 1 store: clear passphrase keys, no version record
+        for (key, package) in [
+            (
+                "keypackage:wallet",
+                serde_json::to_vec(&key_package).unwrap(),
+            ),
+            (
+                "pubkeypackage:wallet",
+                serde_json::to_vec(&pubkey_package).unwrap(),
+            ),
+        ] {
+            let table = if key.starts_with("key") {
+                "secp256k1_tr_keys"
+            } else {
+                "secp256k1_tr_pubkeys"
+            };
+            let ciphertext = legacy_encrypt_storage(&provider, "wallet", &package).await;
+            store.put(table, key.as_bytes(), &ciphertext).unwrap();
+        }
+        let storage = MultiCurveStorage::new(store.clone());
+
+        let dry_run = MigrationOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let report = migrate(&storage, &provider, &dry_run).await.unwrap();
+        assert_eq!((report.from, report.steps), (1, vec![(2, 1), (3, 2)]));
+        assert_eq!(storage.schema_version().unwrap(), None);
+        assert_eq!(storage.legacy_key_count().unwrap(), 1);
+
+        let options = MigrationOptions {
+            dry_run: false,
+            backup_dir: Some(dir.join("backups")),
+        };
+        let report = migrate(&storage, &provider, &options).await.unwrap();
+        assert_eq!((report.from, report.to), (1, CURRENT_VERSION));
+        assert_eq!(storage.schema_version().unwrap().unwrap().version, 3);
+        assert_eq!(storage.legacy_key_count().unwrap(), 0);
+        assert_eq!(storage.legacy_sealed_count().unwrap(), 0);
+        assert!(storage
+            .get_key_package::<C>("wallet", &provider)
+            .await
+            .unwrap()
+            .is_some());
+
+        // The backup still has the old layout
+        let backup = SqliteStore::open(report.backup.unwrap().to_str().unwrap()).unwrap();
+        assert!(backup
+            .get("secp256k1_tr_keys", b"keypackage:wallet")
+            .unwrap()
+            .is_some());
+
+        // Nothing left to do; a newer store is refused
+        let report = migrate(&storage, &provider, &options).await.unwrap();
+        assert!(report.steps.is_empty() && report.backup.is_none());
+        storage
+            .set_schema_version(&SchemaVersion::new(CURRENT_VERSION + 1))
+            .unwrap();
+        assert!(migrate(&storage, &provider, &options).await.is_err());
+
+        assert!(
+            MultiCurveStorage::new(Arc::new(crate::node::share_store::MemoryStore::new()))
+                .is_empty()
+                .unwrap()
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,422 @@
+//! Background storage integrity scrubber
+//!
+//! Periodically walks a snapshot of every curve's records and checks that
+//! they decrypt, that each key package is this node's share of its stored
+//! group key and that metadata fingerprints match. New problems go to the
+//! audit log; the latest report backs `/health`, `/api/storage/scrub` and
+//! `/metrics`, so a bad disk or a changed master key shows up before a
+//! withdrawal fails.
+use poem_openapi::Object;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::key_provider::MasterKeyProvider;
+use super::multi_storage::MultiCurveStorage;
+use crate::audit::{AuditEvent, AuditKind, AuditLog};
+use crate::curves::{with_curve_operations, CurveType};
+
+/// One bad record, named by its blinded key id
+#[derive(Debug, Clone, PartialEq, Object)]
+pub struct ScrubIssue {
+    pub curve: String,
+    pub id: String,
+    /// "decrypt_failed", "share_mismatch", "group_key_mismatch", "orphan_record", ...
+    pub kind: String,
+    pub detail: String,
+}
+
+impl ScrubIssue {
+    pub fn new(curve: CurveType, id: &str, kind: &str, detail: String) -> Self {
+        Self {
+            curve: curve.as_str().to_string(),
+            id: id.to_string(),
+            kind: kind.to_string(),
+            detail,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Object)]
+pub struct ScrubReport {
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub keys_checked: u64,
+    pub issues: Vec<ScrubIssue>,
+    /// Why the run didn't check anything (e.g. the HSM is locked); such runs
+    /// don't replace the latest report
+    pub skipped: Option<String>,
+    /// Why the store couldn't be walked; failed runs do replace it
+    pub error: Option<String>,
+}
+
+pub struct Scrubber {
+    storage: Arc<MultiCurveStorage>,
+    key_provider: Arc<dyn MasterKeyProvider>,
+    audit: Arc<AuditLog>,
+    node_index: u16,
+    curves: Vec<CurveType>,
+    latest: Mutex<Option<ScrubReport>>,
+    runs: AtomicU64,
+    failures: AtomicU64,
+}
+
+impl Scrubber {
+    pub fn new(
+        storage: Arc<MultiCurveStorage>,
+        key_provider: Arc<dyn MasterKeyProvider>,
+        audit: Arc<AuditLog>,
+        node_index: u16,
+        curves: Vec<CurveType>,
+    ) -> Self {
+        Self {
+            storage,
+            key_provider,
+            audit,
+            node_index,
+            curves,
+            latest: Mutex::new(None),
+            runs: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+        }
+    }
+
+    /// Scrub every `interval`, starting one interval after startup
+    pub fn spawn(self: Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await; // the first tick fires immediately
+            loop {
+                ticker.tick().await;
+                self.run().await;
+            }
+        });
+    }
+
+    /// Check every record now and keep the report
+    pub async fn run(&self) -> ScrubReport {
+        let started = SystemTime::now();
+        let started_at = started
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let mut report = ScrubReport {
+            started_at,
+            duration_ms: 0,
+            keys_checked: 0,
+            issues: Vec::new(),
+            skipped: None,
+            error: None,
+        };
+
+        if self.key_provider.is_locked() {
+            report.skipped = Some("key provider is locked".to_string());
+        } else {
+            match self.check_all().await {
+                Ok((keys_checked, issues)) => {
+                    report.keys_checked = keys_checked;
+                    report.issues = issues;
+                }
+                Err(e) => {
+                    tracing::error!("Storage scrub failed: {}", e);
+                    self.failures.fetch_add(1, Ordering::Relaxed);
+                    report.error = Some(e.to_string());
+                }
+            }
+        }
+        report.duration_ms = started.elapsed().map(|d| d.as_millis() as u64).unwrap_or(0);
+        self.runs.fetch_add(1, Ordering::Relaxed);
+
+        // A skipped run says nothing new, so health and metrics keep the last
+        // real one; a failed run is news, the store can't be checked
+        if report.skipped.is_none() {
+            self.record(&report);
+            *self.latest.lock().unwrap() = Some(report.clone());
+        }
+        report
+    }
+
+    async fn check_all(&self) -> anyhow::Result<(u64, Vec<ScrubIssue>)> {
+        let snapshot = self.storage.snapshot()?;
+        let provider = self.key_provider.as_ref();
+        let mut keys_checked = 0;
+        let mut issues = Vec::new();
+        for &curve in &self.curves {
+            let (checked, found) = with_curve_operations!(curve, C => {
+                snapshot.check_records::<C>(provider, self.node_index).await
+            })?;
+            keys_checked += checked;
+            issues.extend(found);
+        }
+        Ok((keys_checked, issues))
+    }
+
+    /// Audit issues (and a failed run) the previous run didn't already report
+    fn record(&self, report: &ScrubReport) {
+        let previous = self.latest.lock().unwrap().clone();
+        if let Some(error) = &report.error {
+            if previous
+                .as_ref()
+                .is_none_or(|previous| previous.error.is_none())
+            {
+                self.audit.record(
+                    AuditEvent::new(AuditKind::StorageScrub, "failure").detail(error.clone()),
+                );
+            }
+            return;
+        }
+        let previous_issues = previous
+            .as_ref()
+            .map(|report| report.issues.as_slice())
+            .unwrap_or_default();
+
+        for issue in &report.issues {
+            tracing::error!(
+                "❌ Storage integrity: {} key {}: {} ({})",
+                issue.curve,
+                issue.id,
+                issue.kind,
+                issue.detail
+            );
+            if !previous_issues.contains(issue) {
+                self.audit.record(
+                    AuditEvent::new(AuditKind::StorageScrub, issue.kind.clone())
+                        .curve(issue.curve.clone())
+                        .detail(format!("key {}: {}", issue.id, issue.detail)),
+                );
+            }
+        }
+        let recovered = previous
+            .as_ref()
+            .is_some_and(|previous| previous.error.is_some());
+        if report.issues.is_empty() && (!previous_issues.is_empty() || recovered) {
+            self.audit.record(
+                AuditEvent::new(AuditKind::StorageScrub, "success")
+                    .detail(format!("{} keys verified", report.keys_checked)),
+            );
+        }
+    }
+
+    pub fn latest(&self) -> Option<ScrubReport> {
+        self.latest.lock().unwrap().clone()
+    }
+
+    /// Prometheus text exposition of the scrub counters and latest report
+    pub fn metrics(&self) -> String {
+        let mut out = String::new();
+        push_metric(
+            &mut out,
+            "frost_scrub_runs_total",
+            "counter",
+            "Storage scrub runs since startup",
+            &[(String::new(), self.runs.load(Ordering::Relaxed))],
+        );
+        push_metric(
+            &mut out,
+            "frost_scrub_failures_total",
+            "counter",
+            "Storage scrub runs that could not complete",
+            &[(String::new(), self.failures.load(Ordering::Relaxed))],
+        );
+
+        let Some(report) = self.latest() else {
+            return out;
+        };
+        push_metric(
+            &mut out,
+            "frost_scrub_last_run_timestamp_seconds",
+            "gauge",
+            "Start of the latest storage scrub that wasn't skipped",
+            &[(String::new(), report.started_at)],
+        );
+        push_metric(
+            &mut out,
+            "frost_scrub_last_run_failed",
+            "gauge",
+            "1 if the latest storage scrub couldn't walk the store",
+            &[(String::new(), u64::from(report.error.is_some()))],
+        );
+        push_metric(
+            &mut out,
+            "frost_scrub_keys_checked",
+            "gauge",
+            "Keys checked by the latest storage scrub",
+            &[(String::new(), report.keys_checked)],
+        );
+
+        let mut counts = std::collections::BTreeMap::new();
+        for issue in &report.issues {
+            *counts.entry((&issue.curve, &issue.kind)).or_insert(0) += 1;
+        }
+        let samples: Vec<_> = counts
+            .into_iter()
+            .map(|((curve, kind), count)| {
+                (format!("{{curve=\"{}\",kind=\"{}\"}}", curve, kind), count)
+            })
+            .collect();
+        push_metric(
+            &mut out,
+            "frost_scrub_issues",
+            "gauge",
+            "Bad records found by the latest storage scrub",
+            &samples,
+        );
+        out
+    }
+}
+
+fn push_metric(out: &mut String, name: &str, kind: &str, help: &str, samples: &[(String, u64)]) {
+    out.push_str(&format!(
+        "# HELP {} {}\n# TYPE {} {}\n",
+        name, help, name, kind
+    ));
+    for (labels, value) in samples {
+        out.push_str(&format!("{}{} {}\n", name, labels, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::secp256k1::Secp256k1Operations;
+    use crate::curves::{CurveOperations, KeyPackage};
+    use crate::node::key_provider::TestKeyProvider;
+    use crate::node::multi_storage::KeyMetadata;
+    use crate::node::share_store::{MemoryStore, ShareStore};
+
+    #[tokio::test]
+    async fn test_scrub_finds_corruption() {
+        type C = Secp256k1Operations;
+        let (shares, pubkey_package) =
+            frost_core::keys::generate_with_dealer::<<C as CurveOperations>::Suite, _>(
+                3,
+                2,
+                frost_core::keys::IdentifierList::Default,
+                &mut rand::thread_rng(),
+            )
+            .unwrap();
+        let share = shares.into_values().next().unwrap();
+        let key_package = KeyPackage::<C>::try_from(share).unwrap();
+        let metadata = KeyMetadata::new::<C>(
+            &pubkey_package,
+            "session".to_string(),
+            2,
+            vec![0, 1, 2],
+            0,
+            crate::node::key_provider::KeyDerivation::AesEcb,
+        )
+        .unwrap();
+
+        let store: Arc<dyn ShareStore> = Arc::new(MemoryStore::new());
+        let storage = Arc::new(MultiCurveStorage::new(store.clone()));
+        let provider = Arc::new(TestKeyProvider);
+        for passphrase in ["good", "bad"] {
+            storage
+                .store_packages::<C>(
+                    passphrase,
+                    &key_package,
+                    &pubkey_package,
+                    &metadata,
+                    provider.as_ref(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let dir = std::env::temp_dir().join(format!("frost-scrub-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let audit = Arc::new(AuditLog::open(dir.join("audit.log"), "node-0").unwrap());
+        // Share of node 0 (identifier 1)
+        let scrubber = Scrubber::new(
+            storage,
+            provider.clone(),
+            audit,
+            0,
+            vec![CurveType::Secp256k1Taproot],
+        );
+        let report = scrubber.run().await;
+        assert_eq!(report.keys_checked, 2);
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+
+        // Flip a byte in one key package
+        let bad = provider
+            .blind_passphrase(crate::node::key_provider::KeyDerivation::AesEcb, "bad")
+            .await
+            .unwrap();
+        let record = format!("key:{}", bad);
+        let mut ciphertext = store
+            .get("secp256k1_tr_keys", record.as_bytes())
+            .unwrap()
+            .unwrap();
+        *ciphertext.last_mut().unwrap() ^= 1;
+        store
+            .put("secp256k1_tr_keys", record.as_bytes(), &ciphertext)
+            .unwrap();
+
+        let report = scrubber.run().await;
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].id, bad);
+        assert_eq!(report.issues[0].kind, "decrypt_failed");
+        assert!(scrubber
+            .metrics()
+            .contains("frost_scrub_issues{curve=\"secp256k1-tr\",kind=\"decrypt_failed\"} 1"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Store that can't be read at all, like a failed disk
+    struct BrokenStore(std::sync::Mutex<()>);
+
+    impl ShareStore for BrokenStore {
+        fn get(&self, _table: &str, _key: &[u8]) -> anyhow::Result<Option<Vec<u8>>> {
+            anyhow::bail!("I/O error")
+        }
+        fn list(&self, _table: &str, _prefix: &[u8]) -> anyhow::Result<Vec<Vec<u8>>> {
+            anyhow::bail!("I/O error")
+        }
+        fn write_batch(&self, _batch: crate::node::share_store::WriteBatch) -> anyhow::Result<()> {
+            anyhow::bail!("I/O error")
+        }
+        fn transaction_lock(&self) -> &std::sync::Mutex<()> {
+            &self.0
+        }
+        fn description(&self) -> String {
+            "broken".to_string()
+        }
+        fn size_bytes(&self) -> anyhow::Result<u64> {
+            Ok(0)
+        }
+        fn snapshot(&self) -> anyhow::Result<Box<dyn ShareStore>> {
+            anyhow::bail!("I/O error")
+        }
+        fn backup_to(&self, _path: &std::path::Path) -> anyhow::Result<()> {
+            anyhow::bail!("I/O error")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_scrub_is_reported() {
+        let storage = Arc::new(MultiCurveStorage::new(Arc::new(BrokenStore(
+            Default::default(),
+        ))));
+        let dir = std::env::temp_dir().join(format!("frost-scrub-failed-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let audit = Arc::new(AuditLog::open(dir.join("audit.log"), "node-0").unwrap());
+        let scrubber = Scrubber::new(
+            storage,
+            Arc::new(TestKeyProvider),
+            audit,
+            0,
+            vec![CurveType::Secp256k1Taproot],
+        );
+
+        let report = scrubber.run().await;
+        assert!(report.error.is_some());
+        assert!(report.skipped.is_none());
+        // Health reads the latest report, so it has to show the failure
+        assert!(scrubber.latest().unwrap().error.is_some());
+        assert!(scrubber.metrics().contains("frost_scrub_last_run_failed 1"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
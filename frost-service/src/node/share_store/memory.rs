@@ -0,0 +1,114 @@
+//! In-memory share store (tests, throwaway nodes)
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use super::{ShareStore, WriteBatch, WriteOp};
+
+type Tables = BTreeMap<String, BTreeMap<Vec<u8>, Vec<u8>>>;
+
+pub struct MemoryStore {
+    tables: Mutex<Tables>,
+    tx_lock: Mutex<()>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::from_tables(BTreeMap::new())
+    }
+
+    fn from_tables(tables: Tables) -> Self {
+        Self {
+            tables: Mutex::new(tables),
+            tx_lock: Mutex::new(()),
+        }
+    }
+}
+
+impl ShareStore for MemoryStore {
+    fn get(&self, table: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let tables = self.tables.lock().unwrap();
+        Ok(tables.get(table).and_then(|t| t.get(key)).cloned())
+    }
+
+    fn list(&self, table: &str, prefix: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let tables = self.tables.lock().unwrap();
+        Ok(tables
+            .get(table)
+            .map(|t| {
+                t.range(prefix.to_vec()..)
+                    .take_while(|(key, _)| key.starts_with(prefix))
+                    .map(|(key, _)| key.clone())
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn list_page(
+        &self,
+        table: &str,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Vec<u8>>> {
+        let tables = self.tables.lock().unwrap();
+        Ok(tables
+            .get(table)
+            .map(|t| {
+                t.range(prefix.to_vec()..)
+                    .take_while(|(key, _)| key.starts_with(prefix))
+                    .filter(|(key, _)| start_after.is_none_or(|after| key.as_slice() > after))
+                    .take(limit)
+                    .map(|(key, _)| key.clone())
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn write_batch(&self, batch: WriteBatch) -> Result<()> {
+        let mut tables = self.tables.lock().unwrap();
+        for op in batch.ops() {
+            match op {
+                WriteOp::Put { table, key, value } => {
+                    tables
+                        .entry(table.clone())
+                        .or_default()
+                        .insert(key.clone(), value.clone());
+                }
+                WriteOp::Delete { table, key } => {
+                    if let Some(t) = tables.get_mut(table) {
+                        t.remove(key);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn transaction_lock(&self) -> &Mutex<()> {
+        &self.tx_lock
+    }
+
+    fn description(&self) -> String {
+        "in-memory".to_string()
+    }
+
+    fn size_bytes(&self) -> Result<u64> {
+        let tables = self.tables.lock().unwrap();
+        Ok(tables
+            .values()
+            .flat_map(|t| t.iter())
+            .map(|(key, value)| (key.len() + value.len()) as u64)
+            .sum())
+    }
+
+    fn snapshot(&self) -> Result<Box<dyn ShareStore>> {
+        let tables = self.tables.lock().unwrap().clone();
+        Ok(Box::new(MemoryStore::from_tables(tables)))
+    }
+
+    /// Nothing outlives the process, so there is nothing worth copying
+    fn backup_to(&self, _path: &std::path::Path) -> Result<()> {
+        Ok(())
+    }
+}
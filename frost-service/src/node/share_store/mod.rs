@@ -0,0 +1,348 @@
+//! Pluggable key-value backend for share storage
+//!
+//! `MultiCurveStorage` encrypts key packages before they reach a `ShareStore`,
+//! so backends only see tables, keys and opaque bytes:
+//! - `rocksdb`: one column family per table (default)
+//! - `sqlite`: a single file, for small deployments
+//! - `memory`: nothing persisted, for tests
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+pub mod memory;
+pub mod rocks;
+pub mod sqlite;
+
+pub use memory::MemoryStore;
+pub use rocks::RocksDbStore;
+pub use sqlite::SqliteStore;
+
+/// One write in a `WriteBatch`
+#[derive(Debug, Clone)]
+pub enum WriteOp {
+    Put {
+        table: String,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    },
+    Delete {
+        table: String,
+        key: Vec<u8>,
+    },
+}
+
+/// Writes applied atomically by `ShareStore::write_batch`
+#[derive(Debug, Clone, Default)]
+pub struct WriteBatch {
+    ops: Vec<WriteOp>,
+}
+
+impl WriteBatch {
+    pub fn put(&mut self, table: &str, key: &[u8], value: &[u8]) {
+        self.ops.push(WriteOp::Put {
+            table: table.to_string(),
+            key: key.to_vec(),
+            value: value.to_vec(),
+        });
+    }
+
+    pub fn delete(&mut self, table: &str, key: &[u8]) {
+        self.ops.push(WriteOp::Delete {
+            table: table.to_string(),
+            key: key.to_vec(),
+        });
+    }
+
+    pub fn ops(&self) -> &[WriteOp] {
+        &self.ops
+    }
+}
+
+/// Key-value backend holding encrypted shares
+///
+/// Tables are created on first write; reading or listing a table that was
+/// never written returns nothing.
+pub trait ShareStore: Send + Sync {
+    fn get(&self, table: &str, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+    /// Keys in `table` starting with `prefix`, ascending
+    fn list(&self, table: &str, prefix: &[u8]) -> Result<Vec<Vec<u8>>>;
+
+    /// Up to `limit` keys of `list(table, prefix)` sorting after `start_after`
+    fn list_page(
+        &self,
+        table: &str,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Vec<u8>>> {
+        Ok(self
+            .list(table, prefix)?
+            .into_iter()
+            .filter(|key| start_after.is_none_or(|after| key.as_slice() > after))
+            .take(limit)
+            .collect())
+    }
+
+    /// Apply every write in `batch` or none of them
+    fn write_batch(&self, batch: WriteBatch) -> Result<()>;
+
+    /// Serializes `transaction` calls on this store
+    fn transaction_lock(&self) -> &Mutex<()>;
+
+    fn description(&self) -> String;
+
+    /// Approximate bytes used (on disk for persistent backends)
+    fn size_bytes(&self) -> Result<u64>;
+
+    /// Point-in-time copy of every table, for reading while writes continue
+    ///
+    /// Backups export from a snapshot so the archive is consistent even
+    /// while a DKG ceremony is storing new shares.
+    fn snapshot(&self) -> Result<Box<dyn ShareStore>>;
+
+    /// Consistent on-disk copy of every table at `path` (must not exist)
+    ///
+    /// Taken before schema migrations; the copy opens with the same backend.
+    fn backup_to(&self, path: &std::path::Path) -> Result<()>;
+
+    /// Push deleted records of `tables` out of logs, memtables and data files
+    ///
+    /// Called after destroying a key so its ciphertext doesn't linger in
+    /// a file the backend would otherwise keep around. Backups and snapshots
+    /// taken earlier still hold their own copies.
+    fn purge(&self, _tables: &[&str]) -> Result<()> {
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    fn put(&self, table: &str, key: &[u8], value: &[u8]) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        batch.put(table, key, value);
+        self.write_batch(batch)
+    }
+
+    #[allow(dead_code)]
+    fn delete(&self, table: &str, key: &[u8]) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        batch.delete(table, key);
+        self.write_batch(batch)
+    }
+}
+
+impl<'s> dyn ShareStore + 's {
+    /// Read-modify-write with the writes committed as one batch
+    ///
+    /// Transactions run one at a time and see their own uncommitted writes.
+    /// Nothing is written if `f` fails.
+    pub fn transaction<T>(&self, f: impl FnOnce(&mut Transaction<'_>) -> Result<T>) -> Result<T> {
+        let _guard = self
+            .transaction_lock()
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Transaction lock poisoned: {}", e))?;
+
+        let mut tx = Transaction {
+            store: self,
+            pending: BTreeMap::new(),
+        };
+        let result = f(&mut tx)?;
+
+        let mut batch = WriteBatch::default();
+        for ((table, key), value) in tx.pending {
+            match value {
+                Some(value) => batch.put(&table, &key, &value),
+                None => batch.delete(&table, &key),
+            }
+        }
+        self.write_batch(batch)?;
+        Ok(result)
+    }
+}
+
+/// Uncommitted view of a store inside `ShareStore::transaction`
+pub struct Transaction<'a> {
+    store: &'a dyn ShareStore,
+    pending: BTreeMap<(String, Vec<u8>), Option<Vec<u8>>>,
+}
+
+#[allow(dead_code)]
+impl Transaction<'_> {
+    pub fn get(&self, table: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        match self.pending.get(&(table.to_string(), key.to_vec())) {
+            Some(value) => Ok(value.clone()),
+            None => self.store.get(table, key),
+        }
+    }
+
+    pub fn list(&self, table: &str, prefix: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let mut keys: std::collections::BTreeSet<Vec<u8>> =
+            self.store.list(table, prefix)?.into_iter().collect();
+        for ((t, key), value) in &self.pending {
+            if t == table && key.starts_with(prefix) {
+                match value {
+                    Some(_) => keys.insert(key.clone()),
+                    None => keys.remove(key),
+                };
+            }
+        }
+        Ok(keys.into_iter().collect())
+    }
+
+    pub fn put(&mut self, table: &str, key: &[u8], value: &[u8]) {
+        self.pending
+            .insert((table.to_string(), key.to_vec()), Some(value.to_vec()));
+    }
+
+    pub fn delete(&mut self, table: &str, key: &[u8]) {
+        self.pending.insert((table.to_string(), key.to_vec()), None);
+    }
+}
+
+/// Share store backend, selected with `[node] storage_backend`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Rocksdb,
+    Sqlite,
+    Memory,
+}
+
+impl StorageBackend {
+    /// Open the backend at `path` (a directory for RocksDB, a file for SQLite)
+    pub fn open(&self, path: &str) -> Result<Arc<dyn ShareStore>> {
+        let store: Arc<dyn ShareStore> = match self {
+            StorageBackend::Rocksdb => Arc::new(RocksDbStore::open(path)?),
+            StorageBackend::Sqlite => Arc::new(SqliteStore::open(path)?),
+            StorageBackend::Memory => {
+                tracing::warn!("⚠️  In-memory share store: key shares are lost on restart");
+                Arc::new(MemoryStore::new())
+            }
+        };
+        Ok(store)
+    }
+}
+
+/// Total size of the files under `path` (a file or a directory)
+fn disk_usage(path: &std::path::Path) -> Result<u64> {
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        total += disk_usage(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+/// Files under `dir` holding `needle` anywhere in their bytes
+#[cfg(test)]
+pub(crate) fn files_containing(dir: &std::path::Path, needle: &[u8]) -> Vec<std::path::PathBuf> {
+    let mut found = Vec::new();
+    for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            found.extend(files_containing(&path, needle));
+        } else if std::fs::read(&path)
+            .is_ok_and(|data| data.windows(needle.len()).any(|window| window == needle))
+        {
+            found.push(path);
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exercise(store: &dyn ShareStore) {
+        store.put("t", b"a:1", b"one").unwrap();
+        store.put("t", b"a:2", b"two").unwrap();
+        store.put("t", b"b:1", b"other").unwrap();
+        store.put("u", b"a:3", b"elsewhere").unwrap();
+
+        assert_eq!(store.get("t", b"a:1").unwrap(), Some(b"one".to_vec()));
+        assert_eq!(store.get("missing", b"a:1").unwrap(), None);
+        assert_eq!(
+            store.list("t", b"a:").unwrap(),
+            vec![b"a:1".to_vec(), b"a:2".to_vec()]
+        );
+        assert!(store.list("missing", b"").unwrap().is_empty());
+        assert_eq!(
+            store.list_page("t", b"", Some(b"a:1"), 1).unwrap(),
+            vec![b"a:2".to_vec()]
+        );
+
+        store.delete("t", b"a:1").unwrap();
+        assert_eq!(store.get("t", b"a:1").unwrap(), None);
+
+        // A failed transaction writes nothing
+        let failed: Result<()> = store.transaction(|tx| {
+            tx.put("t", b"a:4", b"four");
+            anyhow::bail!("abort")
+        });
+        assert!(failed.is_err());
+        assert_eq!(store.get("t", b"a:4").unwrap(), None);
+
+        store
+            .transaction(|tx| {
+                tx.put("t", b"a:4", b"four");
+                tx.delete("t", b"a:2");
+                assert_eq!(tx.get("t", b"a:4")?, Some(b"four".to_vec()));
+                assert_eq!(tx.list("t", b"a:")?, vec![b"a:4".to_vec()]);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(store.list("t", b"a:").unwrap(), vec![b"a:4".to_vec()]);
+    }
+
+    #[test]
+    fn test_backends() {
+        let dir = std::env::temp_dir().join(format!("frost-share-store-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let memory = MemoryStore::new();
+        let sqlite = SqliteStore::open(dir.join("shares.sqlite").to_str().unwrap()).unwrap();
+        let rocks = RocksDbStore::open(dir.join("rocksdb").to_str().unwrap()).unwrap();
+        for store in [&memory as &dyn ShareStore, &sqlite, &rocks] {
+            exercise(store);
+
+            // Purged values are gone from every file the backend keeps
+            let secret = b"purged-share-0123456789abcdef";
+            store.put("k", b"key:1", secret).unwrap();
+            store.delete("k", b"key:1").unwrap();
+            store.purge(&["k"]).unwrap();
+            assert!(
+                files_containing(&dir, secret).is_empty(),
+                "{} kept a deleted value",
+                store.description()
+            );
+
+            // Snapshots don't see later writes
+            let snapshot = store.snapshot().unwrap();
+            store.put("t", b"a:5", b"five").unwrap();
+            assert_eq!(snapshot.list("t", b"a:").unwrap(), vec![b"a:4".to_vec()]);
+            assert_eq!(
+                snapshot.get("u", b"a:3").unwrap(),
+                Some(b"elsewhere".to_vec())
+            );
+        }
+
+        // RocksDB reopens the column families it created
+        let reopened = RocksDbStore::open(dir.join("rocksdb").to_str().unwrap()).unwrap();
+        assert_eq!(
+            reopened.get("u", b"a:3").unwrap(),
+            Some(b"elsewhere".to_vec())
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
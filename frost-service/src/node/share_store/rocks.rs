@@ -0,0 +1,267 @@
+//! RocksDB share store: one column family per table
+use anyhow::{Context, Result};
+use rocksdb::checkpoint::Checkpoint;
+use rocksdb::{
+    BottommostLevelCompaction, ColumnFamilyDescriptor, CompactOptions, Direction, IteratorMode,
+    Options, DB,
+};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{disk_usage, ShareStore, WriteBatch, WriteOp};
+
+pub struct RocksDbStore {
+    db: DB,
+    path: String,
+    tx_lock: Mutex<()>,
+}
+
+impl RocksDbStore {
+    /// Open or create the database, with every column family it already has
+    pub fn open(path: &str) -> Result<Self> {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+
+        let cfs = if Path::new(path).exists() {
+            DB::list_cf(&opts, path).context("Failed to list RocksDB column families")?
+        } else {
+            Vec::new()
+        };
+        let descriptors = cfs
+            .into_iter()
+            .map(|name| ColumnFamilyDescriptor::new(name, Options::default()));
+
+        let db = DB::open_cf_descriptors(&opts, path, descriptors)
+            .context("Failed to open RocksDB share store")?;
+
+        Ok(Self {
+            db,
+            path: path.to_string(),
+            tx_lock: Mutex::new(()),
+        })
+    }
+
+    /// Online checkpoint of the whole database into `dir` (must not exist)
+    ///
+    /// Checkpoints hard-link SST files where possible, so they're cheap and
+    /// consistent across column families.
+    pub fn checkpoint(&self, dir: &Path) -> Result<()> {
+        Checkpoint::new(&self.db)
+            .and_then(|checkpoint| checkpoint.create_checkpoint(dir))
+            .context(format!(
+                "Failed to create RocksDB checkpoint {}",
+                dir.display()
+            ))
+    }
+
+    fn create_cf(&self, table: &str) -> Result<()> {
+        if self.db.cf_handle(table).is_none() {
+            self.db
+                .create_cf(table, &Options::default())
+                .context(format!("Failed to create column family {}", table))?;
+        }
+        Ok(())
+    }
+}
+
+impl ShareStore for RocksDbStore {
+    fn get(&self, table: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        match self.db.cf_handle(table) {
+            Some(cf) => Ok(self.db.get_cf(&cf, key)?),
+            None => Ok(None),
+        }
+    }
+
+    fn list(&self, table: &str, prefix: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let Some(cf) = self.db.cf_handle(table) else {
+            return Ok(Vec::new());
+        };
+
+        let mut keys = Vec::new();
+        for item in self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(prefix, Direction::Forward))
+        {
+            let (key, _) = item?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            keys.push(key.to_vec());
+        }
+        Ok(keys)
+    }
+
+    fn list_page(
+        &self,
+        table: &str,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Vec<u8>>> {
+        let Some(cf) = self.db.cf_handle(table) else {
+            return Ok(Vec::new());
+        };
+        let start = match start_after {
+            Some(after) if after > prefix => after,
+            _ => prefix,
+        };
+
+        let mut keys = Vec::new();
+        for item in self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(start, Direction::Forward))
+        {
+            let (key, _) = item?;
+            if !key.starts_with(prefix) || keys.len() == limit {
+                break;
+            }
+            if start_after.is_some_and(|after| &*key <= after) {
+                continue;
+            }
+            keys.push(key.to_vec());
+        }
+        Ok(keys)
+    }
+
+    fn write_batch(&self, batch: WriteBatch) -> Result<()> {
+        let mut rocks_batch = rocksdb::WriteBatch::default();
+        for op in batch.ops() {
+            match op {
+                WriteOp::Put { table, key, value } => {
+                    self.create_cf(table)?;
+                    let cf = self.db.cf_handle(table).context("Column family vanished")?;
+                    rocks_batch.put_cf(&cf, key, value);
+                }
+                WriteOp::Delete { table, key } => {
+                    if let Some(cf) = self.db.cf_handle(table) {
+                        rocks_batch.delete_cf(&cf, key);
+                    }
+                }
+            }
+        }
+        self.db
+            .write(rocks_batch)
+            .context("Failed to write RocksDB batch")
+    }
+
+    fn transaction_lock(&self) -> &Mutex<()> {
+        &self.tx_lock
+    }
+
+    fn description(&self) -> String {
+        format!("RocksDB ({})", self.path)
+    }
+
+    fn size_bytes(&self) -> Result<u64> {
+        disk_usage(Path::new(&self.path))
+    }
+
+    fn backup_to(&self, path: &Path) -> Result<()> {
+        self.checkpoint(path)
+    }
+
+    /// Flushes every memtable, syncs the WAL and compacts `tables` down to the
+    /// bottommost level, which drops deleted values together with their
+    /// tombstones and removes the SST files that held them
+    ///
+    /// Checkpoints hard-link SST files, so a checkpoint taken before the
+    /// purge (a migration backup, say) keeps the old values.
+    fn purge(&self, tables: &[&str]) -> Result<()> {
+        // A WAL file is only dropped once every column family written to it is flushed
+        let cfs = DB::list_cf(&Options::default(), &self.path)
+            .context("Failed to list RocksDB column families")?;
+        for name in cfs {
+            if let Some(cf) = self.db.cf_handle(&name) {
+                self.db
+                    .flush_cf(&cf)
+                    .context(format!("Failed to flush column family {}", name))?;
+            }
+        }
+        self.db
+            .flush_wal(true)
+            .context("Failed to sync RocksDB WAL")?;
+
+        let mut opts = CompactOptions::default();
+        opts.set_exclusive_manual_compaction(true);
+        opts.set_bottommost_level_compaction(BottommostLevelCompaction::Force);
+        for table in tables {
+            if let Some(cf) = self.db.cf_handle(table) {
+                self.db
+                    .compact_range_cf_opt(&cf, None::<&[u8]>, None::<&[u8]>, &opts);
+            }
+        }
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Result<Box<dyn ShareStore>> {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let dir = PathBuf::from(format!("{}.checkpoint-{}", self.path, nanos));
+        self.checkpoint(&dir)?;
+
+        let store = RocksDbStore::open(dir.to_str().context("Non-UTF-8 checkpoint path")?)?;
+        Ok(Box::new(CheckpointStore {
+            store: Some(store),
+            dir,
+        }))
+    }
+}
+
+/// Temporary checkpoint opened read-only by convention, removed on drop
+struct CheckpointStore {
+    store: Option<RocksDbStore>,
+    dir: PathBuf,
+}
+
+impl CheckpointStore {
+    fn store(&self) -> &RocksDbStore {
+        self.store
+            .as_ref()
+            .expect("checkpoint store is open until drop")
+    }
+}
+
+impl ShareStore for CheckpointStore {
+    fn get(&self, table: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        self.store().get(table, key)
+    }
+
+    fn list(&self, table: &str, prefix: &[u8]) -> Result<Vec<Vec<u8>>> {
+        self.store().list(table, prefix)
+    }
+
+    fn write_batch(&self, _batch: WriteBatch) -> Result<()> {
+        anyhow::bail!("RocksDB checkpoints are read-only")
+    }
+
+    fn transaction_lock(&self) -> &Mutex<()> {
+        self.store().transaction_lock()
+    }
+
+    fn description(&self) -> String {
+        format!("RocksDB checkpoint ({})", self.dir.display())
+    }
+
+    fn size_bytes(&self) -> Result<u64> {
+        self.store().size_bytes()
+    }
+
+    fn snapshot(&self) -> Result<Box<dyn ShareStore>> {
+        self.store().snapshot()
+    }
+
+    fn backup_to(&self, path: &Path) -> Result<()> {
+        self.store().checkpoint(path)
+    }
+}
+
+impl Drop for CheckpointStore {
+    fn drop(&mut self) {
+        // Close the database before deleting its files
+        drop(self.store.take());
+        if let Err(e) = std::fs::remove_dir_all(&self.dir) {
+            tracing::warn!("Failed to remove checkpoint {}: {}", self.dir.display(), e);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! SQLite share store: every table in one `shares` table of a single file
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::Mutex;
+
+use super::{disk_usage, MemoryStore, ShareStore, WriteBatch, WriteOp};
+
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+    path: String,
+    tx_lock: Mutex<()>,
+}
+
+impl SqliteStore {
+    pub fn open(path: &str) -> Result<Self> {
+        if let Some(parent) = Path::new(path).parent() {
+            std::fs::create_dir_all(parent).context("Failed to create SQLite directory")?;
+        }
+
+        let conn = Connection::open(path).context("Failed to open SQLite share store")?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA secure_delete = ON;
+             CREATE TABLE IF NOT EXISTS shares (
+                 tbl   TEXT NOT NULL,
+                 key   BLOB NOT NULL,
+                 value BLOB NOT NULL,
+                 PRIMARY KEY (tbl, key)
+             ) WITHOUT ROWID;",
+        )
+        .context("Failed to initialize SQLite schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            path: path.to_string(),
+            tx_lock: Mutex::new(()),
+        })
+    }
+}
+
+impl ShareStore for SqliteStore {
+    fn get(&self, table: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT value FROM shares WHERE tbl = ?1 AND key = ?2",
+            params![table, key],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("SQLite read failed")
+    }
+
+    fn list(&self, table: &str, prefix: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT key FROM shares WHERE tbl = ?1 AND substr(key, 1, ?2) = ?3 ORDER BY key",
+        )?;
+        let keys = stmt
+            .query_map(params![table, prefix.len() as i64, prefix], |row| {
+                row.get(0)
+            })?
+            .collect::<rusqlite::Result<Vec<Vec<u8>>>>()
+            .context("SQLite list failed")?;
+        Ok(keys)
+    }
+
+    fn list_page(
+        &self,
+        table: &str,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Vec<u8>>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT key FROM shares WHERE tbl = ?1 AND substr(key, 1, ?2) = ?3 AND key > ?4
+             ORDER BY key LIMIT ?5",
+        )?;
+        let keys = stmt
+            .query_map(
+                params![
+                    table,
+                    prefix.len() as i64,
+                    prefix,
+                    start_after.unwrap_or_default(),
+                    limit as i64
+                ],
+                |row| row.get(0),
+            )?
+            .collect::<rusqlite::Result<Vec<Vec<u8>>>>()
+            .context("SQLite list failed")?;
+        Ok(keys)
+    }
+
+    fn write_batch(&self, batch: WriteBatch) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for op in batch.ops() {
+            match op {
+                WriteOp::Put { table, key, value } => {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO shares (tbl, key, value) VALUES (?1, ?2, ?3)",
+                        params![table, key, value],
+                    )?;
+                }
+                WriteOp::Delete { table, key } => {
+                    tx.execute(
+                        "DELETE FROM shares WHERE tbl = ?1 AND key = ?2",
+                        params![table, key],
+                    )?;
+                }
+            }
+        }
+        tx.commit().context("Failed to commit SQLite batch")
+    }
+
+    fn transaction_lock(&self) -> &Mutex<()> {
+        &self.tx_lock
+    }
+
+    fn description(&self) -> String {
+        format!("SQLite ({})", self.path)
+    }
+
+    fn size_bytes(&self) -> Result<u64> {
+        let wal = format!("{}-wal", self.path);
+        Ok(disk_usage(Path::new(&self.path))? + disk_usage(Path::new(&wal))?)
+    }
+
+    /// Copies every row while holding the connection, so no write lands halfway
+    fn snapshot(&self) -> Result<Box<dyn ShareStore>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT tbl, key, value FROM shares")?;
+        let mut batch = WriteBatch::default();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let table: String = row.get(0)?;
+            let key: Vec<u8> = row.get(1)?;
+            let value: Vec<u8> = row.get(2)?;
+            batch.put(&table, &key, &value);
+        }
+
+        let copy = MemoryStore::new();
+        copy.write_batch(batch)?;
+        Ok(Box::new(copy))
+    }
+
+    fn backup_to(&self, path: &Path) -> Result<()> {
+        anyhow::ensure!(!path.exists(), "{} already exists", path.display());
+        let target = path.to_str().context("Non-UTF-8 backup path")?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute("VACUUM INTO ?1", params![target])
+            .context(format!("Failed to back up SQLite store to {}", target))?;
+        Ok(())
+    }
+
+    /// Deleted rows are zeroed in place (`secure_delete`); truncating the WAL
+    /// drops the frames that still hold them
+    fn purge(&self, _tables: &[&str]) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .context("Failed to checkpoint SQLite WAL")
+    }
+}
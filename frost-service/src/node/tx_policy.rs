@@ -0,0 +1,981 @@
+//! Node-side transaction validation
+//!
+//! Instead of signing whatever 32-byte hash the aggregator sends, a node can be
+//! given the full unsigned transaction. It then:
+//! 1. Recomputes the sighash / signing payload itself
+//! 2. Checks it matches the FROST message being signed
+//! 3. Applies its locally configured `[node.policy]`
+//!
+//! Only when all three pass does the node release its signature share.
+
+use anyhow::{Context, Result};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::str::FromStr;
+
+use crate::config::{
+    BitcoinPolicyConfig, EvmPolicyConfig, SigningPolicyConfig, SolanaPolicyConfig,
+};
+
+/// The transaction behind a FROST signing request
+#[derive(Debug, Clone, Object, Serialize, Deserialize)]
+pub struct TransactionContext {
+    /// "psbt" / "litecoin_psbt" (base64 PSBT), "bch" (unsigned tx + prevouts JSON),
+    /// "evm" (hex unsigned RLP tx), "solana" (hex message bytes), "eip191" (hex message),
+    /// "eip712" (typed data JSON), "nostr" (unsigned event JSON),
+    /// "bip322" (script_pubkey + message JSON), "sui" (base64 BCS TransactionData),
+    /// "aptos" (hex BCS RawTransaction), "cardano" (hex CBOR transaction body),
+    /// "stellar" (network passphrase + envelope JSON) or "algorand" (base64 msgpack txn)
+    pub kind: String,
+    pub payload: String,
+    /// Input being signed (psbt / litecoin_psbt / bch only)
+    #[oai(skip_serializing_if_is_none)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_index: Option<u32>,
+}
+
+/// Check a round 2 request against the local policy
+///
+/// `curve` is the FROST curve of the endpoint, `message` the bytes FROST will
+/// sign and `group_key` the serialized verifying key of the key signing it.
+pub fn check(
+    policy: &SigningPolicyConfig,
+    curve: &str,
+    message: &[u8],
+    transaction: Option<&TransactionContext>,
+    group_key: &[u8],
+) -> Result<()> {
+    let Some(tx) = transaction else {
+        // Limits can't be checked against a bare hash, so they imply require_transaction
+        if policy.require_transaction || policy.has_limits() {
+            anyhow::bail!("Policy requires the full transaction, got a bare message hash");
+        }
+        return Ok(());
+    };
+
+    match (tx.kind.as_str(), curve) {
+        // Both kinds sign the same Taproot sighash with the same key, so the
+        // label can't choose the policy: every PSBT must pass both
+        ("psbt", "secp256k1-tr") | ("litecoin_psbt", "secp256k1-tr") => check_psbt(
+            &[
+                (&policy.bitcoin, &bitcoin_script),
+                (&policy.litecoin, &crate::chains::litecoin::script_pubkey),
+            ],
+            tx,
+            message,
+            group_key,
+        ),
+        ("evm", "secp256k1") => check_evm(&policy.evm, tx, message),
+        ("eip191", "secp256k1") | ("eip712", "secp256k1") => check_ethereum_message(tx, message),
+        ("solana", "ed25519") => check_solana(&policy.solana, tx, message),
+        ("nostr", "secp256k1-tr") => check_nostr(tx, message),
+        ("bip322", "secp256k1-tr") => check_bip322(tx, message),
+        ("sui", "ed25519") | ("aptos", "ed25519") => {
+            check_unchecked_kind(policy, tx)?;
+            check_move(tx, message)
+        }
+        ("cardano", "ed25519") => {
+            check_unchecked_kind(policy, tx)?;
+            check_cardano(tx, message)
+        }
+        ("stellar", "ed25519") => {
+            check_unchecked_kind(policy, tx)?;
+            check_stellar(tx, message)
+        }
+        ("algorand", "ed25519") => {
+            check_unchecked_kind(policy, tx)?;
+            check_algorand(tx, message)
+        }
+        ("bch", "secp256k1-bch") => check_bch(&policy.bitcoin_cash, tx, message, group_key),
+        (kind, curve) => anyhow::bail!(
+            "Transaction kind '{}' can't be signed with curve {}",
+            kind,
+            curve
+        ),
+    }
+}
+
+// ============================================================================
+// Bitcoin / Litecoin (PSBT, Taproot key-path)
+// ============================================================================
+
+/// A chain's output policy and the parser for its addresses
+type OutputPolicy<'a> = (
+    &'a BitcoinPolicyConfig,
+    &'a dyn Fn(&str) -> Result<bitcoin::ScriptBuf>,
+);
+
+fn bitcoin_script(addr: &str) -> Result<bitcoin::ScriptBuf> {
+    Ok(bitcoin::Address::from_str(addr)?
+        .assume_checked()
+        .script_pubkey())
+}
+
+/// Every policy in `policies` must accept the outputs
+fn check_psbt(
+    policies: &[OutputPolicy],
+    tx: &TransactionContext,
+    message: &[u8],
+    group_key: &[u8],
+) -> Result<()> {
+    use bitcoin::hashes::Hash;
+    use bitcoin::sighash::{Prevouts, SighashCache};
+    use bitcoin::TapSighashType;
+
+    let psbt: bitcoin::psbt::Psbt = tx.payload.parse().context("Invalid PSBT")?;
+    let input_index = tx
+        .input_index
+        .context("PSBT context requires input_index")? as usize;
+
+    if input_index >= psbt.inputs.len() {
+        anyhow::bail!(
+            "Input index {} out of range ({} inputs)",
+            input_index,
+            psbt.inputs.len()
+        );
+    }
+
+    let prevouts: Vec<bitcoin::TxOut> = psbt
+        .inputs
+        .iter()
+        .map(|input| input.witness_utxo.clone())
+        .collect::<Option<_>>()
+        .context("Missing witness_utxo in PSBT inputs")?;
+
+    let sighash = SighashCache::new(&psbt.unsigned_tx)
+        .taproot_key_spend_signature_hash(
+            input_index,
+            &Prevouts::All(&prevouts),
+            TapSighashType::Default,
+        )
+        .context("Sighash calculation failed")?;
+
+    if sighash.as_byte_array().as_slice() != message {
+        anyhow::bail!(
+            "Message does not match sighash of input {} in the PSBT",
+            input_index
+        );
+    }
+
+    // Change is only what the node knows is ours: the key's own (untweaked)
+    // P2TR output and configured change addresses. The aggregator picks the
+    // inputs, so a script it spends from proves nothing.
+    let own_script = taproot_script(group_key)?;
+    for (policy, script_of) in policies {
+        let mut change_scripts = vec![own_script.clone()];
+        for addr in &policy.change_addresses {
+            change_scripts.push(
+                script_of(addr).context(format!("Invalid change address in policy: {}", addr))?,
+            );
+        }
+        check_outputs(
+            policy,
+            &psbt.unsigned_tx.output,
+            &change_scripts,
+            *script_of,
+        )?;
+    }
+    Ok(())
+}
+
+/// Untweaked P2TR scriptPubKey of a secp256k1-tr group key (see `chains::bitcoin`)
+fn taproot_script(group_key: &[u8]) -> Result<bitcoin::ScriptBuf> {
+    let key = bitcoin::secp256k1::PublicKey::from_slice(group_key)
+        .context("Invalid secp256k1 group key")?;
+    let tweaked =
+        bitcoin::key::TweakedPublicKey::dangerous_assume_tweaked(key.x_only_public_key().0);
+    Ok(bitcoin::ScriptBuf::new_p2tr_tweaked(tweaked))
+}
+
+/// Allowlist and spend limit over every output that isn't to `change_scripts`
+fn check_outputs(
+    policy: &BitcoinPolicyConfig,
+    outputs: &[bitcoin::TxOut],
+    change_scripts: &[bitcoin::ScriptBuf],
+    script_of: &dyn Fn(&str) -> Result<bitcoin::ScriptBuf>,
+) -> Result<()> {
+    let allowed_scripts = policy
+        .allowed_addresses
+        .iter()
+        .map(|addr| script_of(addr).context(format!("Invalid address in policy: {}", addr)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut spend_sats = 0u64;
+    for output in outputs {
+        if change_scripts.contains(&output.script_pubkey) {
+            continue;
+        }
+
+        if !allowed_scripts.is_empty() && !allowed_scripts.contains(&output.script_pubkey) {
+            anyhow::bail!(
+                "Output to {} is not in the allowed address list",
+                output.script_pubkey
+            );
+        }
+        spend_sats = spend_sats.saturating_add(output.value.to_sat());
+    }
+
+    if let Some(max) = policy.max_spend_sats {
+        if spend_sats > max {
+            anyhow::bail!("Spend of {} sats exceeds policy limit {}", spend_sats, max);
+        }
+    }
+
+    Ok(())
+}
+
+/// Bitcoin Cash: the message must be the FORKID sighash of the input, and
+/// the outputs pass `[node.policy.bitcoin_cash]` (change: the key's own P2PKH)
+fn check_bch(
+    policy: &BitcoinPolicyConfig,
+    tx: &TransactionContext,
+    message: &[u8],
+    group_key: &[u8],
+) -> Result<()> {
+    use crate::chains::bitcoin_cash;
+
+    let (unsigned, transaction) = bitcoin_cash::UnsignedTransaction::parse(&tx.payload)?;
+    let input_index = tx.input_index.context("bch context requires input_index")? as usize;
+
+    if unsigned.sighash(&transaction, input_index)?.as_slice() != message {
+        anyhow::bail!(
+            "Message does not match sighash of input {} in the transaction",
+            input_index
+        );
+    }
+
+    let mut change_scripts = vec![bitcoin_cash::own_script(group_key)?];
+    for addr in &policy.change_addresses {
+        change_scripts.push(
+            bitcoin_cash::script_pubkey(addr)
+                .context(format!("Invalid change address in policy: {}", addr))?,
+        );
+    }
+    check_outputs(
+        policy,
+        &transaction.output,
+        &change_scripts,
+        &bitcoin_cash::script_pubkey,
+    )
+}
+
+// ============================================================================
+// EVM (legacy EIP-155, EIP-2930, EIP-1559)
+// ============================================================================
+
+/// Fields of an unsigned EVM transaction the policy looks at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvmTransaction {
+    pub tx_type: u8,
+    pub chain_id: Option<u64>,
+    pub to: Option<ethereum_types::H160>,
+    pub value: ethereum_types::U256,
+}
+
+/// Decode an unsigned EVM transaction (the bytes whose keccak256 gets signed)
+pub fn decode_evm_transaction(raw: &[u8]) -> Result<EvmTransaction> {
+    let first = *raw.first().context("Empty EVM transaction")?;
+
+    // Typed transactions: type byte || rlp([chain_id, ...])
+    let (tx_type, list, to_idx, value_idx) = match first {
+        0x01 => (1u8, rlp::Rlp::new(&raw[1..]), 4, 5),
+        0x02 => (2u8, rlp::Rlp::new(&raw[1..]), 5, 6),
+        b if b >= 0xc0 => (0u8, rlp::Rlp::new(raw), 3, 4),
+        b => anyhow::bail!("Unsupported EVM transaction type 0x{:02x}", b),
+    };
+
+    if !list.is_list() {
+        anyhow::bail!("EVM transaction is not an RLP list");
+    }
+    let fields = list.item_count().context("Invalid RLP")?;
+
+    let chain_id = match tx_type {
+        0 => match fields {
+            // EIP-155 signing payload: [..., chain_id, 0, 0]
+            9 => Some(list.val_at::<u64>(6).context("Invalid chain id")?),
+            6 => None,
+            n => anyhow::bail!("Legacy transaction has {} fields, expected 6 or 9", n),
+        },
+        _ => {
+            let expected = if tx_type == 1 { 8 } else { 9 };
+            if fields != expected {
+                anyhow::bail!(
+                    "Type {} transaction has {} fields, expected {}",
+                    tx_type,
+                    fields,
+                    expected
+                );
+            }
+            Some(list.val_at::<u64>(0).context("Invalid chain id")?)
+        }
+    };
+
+    let to_bytes = list
+        .at(to_idx)
+        .and_then(|r| r.data())
+        .context("Invalid to")?;
+    let to = match to_bytes.len() {
+        0 => None,
+        20 => Some(ethereum_types::H160::from_slice(to_bytes)),
+        n => anyhow::bail!("Invalid to address length {}", n),
+    };
+
+    let value = list
+        .val_at::<ethereum_types::U256>(value_idx)
+        .context("Invalid value")?;
+
+    Ok(EvmTransaction {
+        tx_type,
+        chain_id,
+        to,
+        value,
+    })
+}
+
+fn check_evm(policy: &EvmPolicyConfig, tx: &TransactionContext, message: &[u8]) -> Result<()> {
+    let raw = hex::decode(tx.payload.trim_start_matches("0x")).context("Invalid EVM tx hex")?;
+
+    if Keccak256::digest(&raw).as_slice() != message {
+        anyhow::bail!("Message does not match keccak256 of the EVM transaction");
+    }
+
+    let decoded = decode_evm_transaction(&raw)?;
+
+    let Some(chain_id) = decoded.chain_id else {
+        anyhow::bail!("Refusing pre-EIP-155 transaction without chain id (replayable)");
+    };
+    if !policy.allowed_chain_ids.is_empty() && !policy.allowed_chain_ids.contains(&chain_id) {
+        anyhow::bail!("Chain id {} is not allowed by policy", chain_id);
+    }
+
+    if !policy.allowed_recipients.is_empty() {
+        let Some(to) = decoded.to else {
+            anyhow::bail!("Contract creation is not allowed by policy");
+        };
+        let allowed = policy.allowed_recipients.iter().any(|addr| {
+            hex::decode(addr.trim_start_matches("0x"))
+                .map(|bytes| bytes == to.as_bytes())
+                .unwrap_or(false)
+        });
+        if !allowed {
+            anyhow::bail!("Recipient 0x{} is not allowed by policy", hex::encode(to));
+        }
+    }
+
+    if let Some(max) = &policy.max_value_wei {
+        let max = ethereum_types::U256::from_dec_str(max)
+            .map_err(|e| anyhow::anyhow!("Invalid max_value_wei in policy: {:?}", e))?;
+        if decoded.value > max {
+            anyhow::bail!("Value {} wei exceeds policy limit {}", decoded.value, max);
+        }
+    }
+
+    Ok(())
+}
+
+/// Off-chain messages: only check the hash was derived with the right prefix /
+/// domain separation, so a transaction hash can't pass as a message
+fn check_ethereum_message(tx: &TransactionContext, message: &[u8]) -> Result<()> {
+    use crate::signing_aggregator::eip712;
+
+    let hash = if tx.kind == "eip191" {
+        let bytes = hex::decode(&tx.payload).context("Invalid EIP-191 message hex")?;
+        eip712::eip191_hash(&bytes)
+    } else {
+        let typed_data: serde_json::Value =
+            serde_json::from_str(&tx.payload).context("Invalid EIP-712 JSON")?;
+        eip712::hash_typed_data(&typed_data)?
+    };
+
+    if hash.as_slice() != message {
+        anyhow::bail!("Message does not match the {} hash", tx.kind);
+    }
+    Ok(())
+}
+
+/// Nostr events: the message must be the id of the event in the payload
+fn check_nostr(tx: &TransactionContext, message: &[u8]) -> Result<()> {
+    let event: crate::chains::nostr::UnsignedEvent =
+        serde_json::from_str(&tx.payload).context("Invalid Nostr event JSON")?;
+
+    if event.id()?.as_slice() != message {
+        anyhow::bail!("Message does not match the Nostr event id");
+    }
+    Ok(())
+}
+
+/// BIP-322 messages: the message must be the `to_sign` sighash, which can't
+/// collide with a real spend since `to_spend` has no real inputs
+fn check_bip322(tx: &TransactionContext, message: &[u8]) -> Result<()> {
+    let context: crate::signing_aggregator::bip322::MessageContext =
+        serde_json::from_str(&tx.payload).context("Invalid BIP-322 context JSON")?;
+
+    if context.sighash()?.as_slice() != message {
+        anyhow::bail!("Message does not match the BIP-322 sighash");
+    }
+    Ok(())
+}
+
+/// Kinds below only get the payload hash checked; with limits configured for
+/// other chains they'd be an unrestricted way out, so refuse them unless the
+/// operator lists them in `unchecked_kinds`
+fn check_unchecked_kind(policy: &SigningPolicyConfig, tx: &TransactionContext) -> Result<()> {
+    if policy.has_limits() && !policy.unchecked_kinds.contains(&tx.kind) {
+        anyhow::bail!(
+            "No recipient/amount policy for '{}' transactions; refusing while limits are configured (see unchecked_kinds)",
+            tx.kind
+        );
+    }
+    Ok(())
+}
+
+/// Sui / Aptos: the message must be the chain's intent digest / prefixed
+/// signing message of the forwarded transaction bytes
+fn check_move(tx: &TransactionContext, message: &[u8]) -> Result<()> {
+    let expected = if tx.kind == "sui" {
+        use base64::prelude::*;
+        let tx_bytes = BASE64_STANDARD
+            .decode(&tx.payload)
+            .context("Invalid Sui transaction base64")?;
+        crate::chains::sui::intent_digest(&tx_bytes).to_vec()
+    } else {
+        crate::chains::aptos::signing_message(&crate::chains::aptos::decode_raw(&tx.payload)?)
+    };
+
+    if expected != message {
+        anyhow::bail!("Message does not match the {} signing payload", tx.kind);
+    }
+    Ok(())
+}
+
+/// Cardano: the message must be the hash of the forwarded transaction body
+fn check_cardano(tx: &TransactionContext, message: &[u8]) -> Result<()> {
+    let body = crate::chains::cardano::decode_body(&tx.payload)?;
+
+    if crate::chains::cardano::body_hash(&body).as_slice() != message {
+        anyhow::bail!("Message does not match the Cardano body hash");
+    }
+    Ok(())
+}
+
+/// Stellar: the message must be the signature payload hash of the envelope
+fn check_stellar(tx: &TransactionContext, message: &[u8]) -> Result<()> {
+    let payload: crate::chains::stellar::SignaturePayload =
+        serde_json::from_str(&tx.payload).context("Invalid Stellar payload JSON")?;
+
+    if payload.hash()?.as_slice() != message {
+        anyhow::bail!("Message does not match the Stellar transaction hash");
+    }
+    Ok(())
+}
+
+/// Algorand: the message must be the "TX"-prefixed transaction
+fn check_algorand(tx: &TransactionContext, message: &[u8]) -> Result<()> {
+    let txn = crate::chains::algorand::decode_txn(&tx.payload)?;
+
+    if crate::chains::algorand::signing_message(&txn) != message {
+        anyhow::bail!("Message does not match the Algorand transaction");
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Solana (legacy and v0 messages)
+// ============================================================================
+
+/// The parts of a serialized Solana message (legacy or v0) we care about
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaMessage {
+    pub versioned: bool,
+    pub num_required_signatures: usize,
+    /// Static account keys (signers first)
+    pub account_keys: Vec<[u8; 32]>,
+    /// Program id of every instruction, in order
+    pub program_ids: Vec<[u8; 32]>,
+    /// Address lookup tables referenced by a v0 message
+    pub lookup_tables: Vec<[u8; 32]>,
+}
+
+impl SolanaMessage {
+    /// Parse a serialized message, rejecting trailing bytes
+    pub fn parse(message: &[u8]) -> Result<Self> {
+        let mut reader = SolanaReader {
+            data: message,
+            pos: 0,
+        };
+
+        // Versioned messages set the top bit of the first byte
+        let versioned = reader.peek()? & 0x80 != 0;
+        if versioned {
+            let version = reader.byte()? & 0x7f;
+            if version != 0 {
+                anyhow::bail!("Unsupported Solana message version {}", version);
+            }
+        }
+
+        let num_required_signatures = reader.byte()? as usize;
+        reader.take(2)?; // readonly signed / unsigned counts
+        let num_keys = reader.compact_u16()?;
+        let mut account_keys = Vec::with_capacity(num_keys);
+        for _ in 0..num_keys {
+            account_keys.push(reader.key()?);
+        }
+        if num_required_signatures == 0 || num_required_signatures > account_keys.len() {
+            anyhow::bail!(
+                "Invalid header: {} required signatures for {} accounts",
+                num_required_signatures,
+                account_keys.len()
+            );
+        }
+        reader.take(32)?; // recent blockhash
+
+        let num_instructions = reader.compact_u16()?;
+        let mut program_ids = Vec::with_capacity(num_instructions);
+        for _ in 0..num_instructions {
+            let program_idx = reader.byte()? as usize;
+            // Program ids must be static keys (lookup tables can't supply them)
+            let program = account_keys
+                .get(program_idx)
+                .context(format!("Program index {} out of range", program_idx))?;
+            program_ids.push(*program);
+
+            let num_accounts = reader.compact_u16()?;
+            reader.take(num_accounts)?;
+            let data_len = reader.compact_u16()?;
+            reader.take(data_len)?;
+        }
+
+        let mut lookup_tables = Vec::new();
+        if versioned {
+            let num_lookups = reader.compact_u16()?;
+            for _ in 0..num_lookups {
+                lookup_tables.push(reader.key()?);
+                let writable = reader.compact_u16()?;
+                reader.take(writable)?;
+                let readonly = reader.compact_u16()?;
+                reader.take(readonly)?;
+            }
+        }
+
+        if reader.pos != message.len() {
+            anyhow::bail!(
+                "Trailing {} bytes after Solana message",
+                message.len() - reader.pos
+            );
+        }
+
+        Ok(Self {
+            versioned,
+            num_required_signatures,
+            account_keys,
+            program_ids,
+            lookup_tables,
+        })
+    }
+
+    /// Keys that must sign the message, in signature order
+    pub fn required_signers(&self) -> &[[u8; 32]] {
+        &self.account_keys[..self.num_required_signatures]
+    }
+}
+
+struct SolanaReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> SolanaReader<'a> {
+    fn peek(&self) -> Result<u8> {
+        self.data
+            .get(self.pos)
+            .copied()
+            .context("Truncated Solana message")
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn key(&mut self) -> Result<[u8; 32]> {
+        Ok(self.take(32)?.try_into().unwrap())
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|end| *end <= self.data.len())
+            .context("Truncated Solana message")?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    /// Solana's compact-u16 (1-3 byte little-endian varint)
+    fn compact_u16(&mut self) -> Result<usize> {
+        let mut value = 0usize;
+        for i in 0..3 {
+            let b = self.byte()?;
+            value |= ((b & 0x7f) as usize) << (7 * i);
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        anyhow::bail!("Invalid compact-u16 in Solana message")
+    }
+}
+
+fn check_solana(
+    policy: &SolanaPolicyConfig,
+    tx: &TransactionContext,
+    message: &[u8],
+) -> Result<()> {
+    let raw = hex::decode(&tx.payload).context("Invalid Solana message hex")?;
+
+    // Ed25519 signs the serialized message itself, not a hash
+    if raw != message {
+        anyhow::bail!("Message does not match the Solana transaction message");
+    }
+
+    let programs = SolanaMessage::parse(&raw)?.program_ids;
+
+    if !policy.allowed_program_ids.is_empty() {
+        let allowed = policy
+            .allowed_program_ids
+            .iter()
+            .map(|id| {
+                bs58::decode(id)
+                    .into_vec()
+                    .context(format!("Invalid program id in solana policy: {}", id))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for program in &programs {
+            if !allowed.iter().any(|a| a.as_slice() == program) {
+                anyhow::bail!(
+                    "Program {} is not allowed by policy",
+                    bs58::encode(program).into_string()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evm_context(raw: &[u8]) -> TransactionContext {
+        TransactionContext {
+            kind: "evm".to_string(),
+            payload: hex::encode(raw),
+            input_index: None,
+        }
+    }
+
+    fn eip1559_tx(chain_id: u64, to: [u8; 20], value: u64) -> Vec<u8> {
+        let mut stream = rlp::RlpStream::new_list(9);
+        stream.append(&chain_id);
+        stream.append(&0u64); // nonce
+        stream.append(&1_000_000_000u64);
+        stream.append(&20_000_000_000u64);
+        stream.append(&21_000u64);
+        stream.append(&to.as_slice());
+        stream.append(&value);
+        stream.append(&Vec::<u8>::new());
+        stream.begin_list(0);
+
+        let mut raw = vec![0x02];
+        raw.extend_from_slice(&stream.out());
+        raw
+    }
+
+    #[test]
+    fn test_evm_policy() {
+        let recipient = [0x11u8; 20];
+        let raw = eip1559_tx(1, recipient, 5_000);
+        let hash = Keccak256::digest(&raw);
+
+        let decoded = decode_evm_transaction(&raw).unwrap();
+        assert_eq!(decoded.tx_type, 2);
+        assert_eq!(decoded.chain_id, Some(1));
+        assert_eq!(decoded.value, 5_000u64.into());
+
+        let mut policy = SigningPolicyConfig::default();
+        policy.evm.allowed_chain_ids = vec![1];
+        policy.evm.allowed_recipients = vec![format!("0x{}", hex::encode(recipient))];
+        policy.evm.max_value_wei = Some("10000".to_string());
+        assert!(check(&policy, "secp256k1", &hash, Some(&evm_context(&raw)), &[]).is_ok());
+
+        // Aggregator swapped the hash
+        assert!(check(
+            &policy,
+            "secp256k1",
+            &[0u8; 32],
+            Some(&evm_context(&raw)),
+            &[]
+        )
+        .is_err());
+
+        // Over the limit / other chain / other recipient
+        let raw = eip1559_tx(1, recipient, 50_000);
+        let hash = Keccak256::digest(&raw);
+        assert!(check(&policy, "secp256k1", &hash, Some(&evm_context(&raw)), &[]).is_err());
+        let raw = eip1559_tx(137, recipient, 5_000);
+        let hash = Keccak256::digest(&raw);
+        assert!(check(&policy, "secp256k1", &hash, Some(&evm_context(&raw)), &[]).is_err());
+        let raw = eip1559_tx(1, [0x22u8; 20], 5_000);
+        let hash = Keccak256::digest(&raw);
+        assert!(check(&policy, "secp256k1", &hash, Some(&evm_context(&raw)), &[]).is_err());
+    }
+
+    /// PSBT spending `prevouts` to `outputs`, and the key-path sighash of input 0
+    fn psbt_context(
+        prevouts: Vec<bitcoin::TxOut>,
+        outputs: Vec<bitcoin::TxOut>,
+    ) -> (TransactionContext, Vec<u8>) {
+        use bitcoin::hashes::Hash;
+        use bitcoin::sighash::{Prevouts, SighashCache};
+
+        let unsigned_tx = bitcoin::Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input: (0..prevouts.len() as u32)
+                .map(|vout| bitcoin::TxIn {
+                    previous_output: bitcoin::OutPoint::new(bitcoin::Txid::all_zeros(), vout),
+                    ..Default::default()
+                })
+                .collect(),
+            output: outputs,
+        };
+        let sighash = SighashCache::new(&unsigned_tx)
+            .taproot_key_spend_signature_hash(
+                0,
+                &Prevouts::All(&prevouts),
+                bitcoin::TapSighashType::Default,
+            )
+            .unwrap();
+
+        let mut psbt = bitcoin::psbt::Psbt::from_unsigned_tx(unsigned_tx).unwrap();
+        for (input, prevout) in psbt.inputs.iter_mut().zip(prevouts) {
+            input.witness_utxo = Some(prevout);
+        }
+        let ctx = TransactionContext {
+            kind: "psbt".to_string(),
+            payload: psbt.to_string(),
+            input_index: Some(0),
+        };
+        (ctx, sighash.as_byte_array().to_vec())
+    }
+
+    /// Compressed public key of the secret key `[byte; 32]`
+    fn key(byte: u8) -> [u8; 33] {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        bitcoin::secp256k1::SecretKey::from_slice(&[byte; 32])
+            .unwrap()
+            .public_key(&secp)
+            .serialize()
+    }
+
+    fn txout(sats: u64, script: &bitcoin::ScriptBuf) -> bitcoin::TxOut {
+        bitcoin::TxOut {
+            value: bitcoin::Amount::from_sat(sats),
+            script_pubkey: script.clone(),
+        }
+    }
+
+    #[test]
+    fn test_psbt_change_is_only_own_script() {
+        let group_key = key(1);
+        let own = taproot_script(&group_key).unwrap();
+        let foreign = taproot_script(&key(2)).unwrap();
+
+        let mut policy = SigningPolicyConfig::default();
+        policy.bitcoin.max_spend_sats = Some(10_000);
+
+        // Change back to our own key isn't spending
+        let (ctx, sighash) = psbt_context(
+            vec![txout(100_000, &own)],
+            vec![txout(5_000, &foreign), txout(94_000, &own)],
+        );
+        assert!(check(&policy, "secp256k1-tr", &sighash, Some(&ctx), &group_key).is_ok());
+
+        // An aggregator-supplied input doesn't make its script change
+        let (ctx, sighash) = psbt_context(
+            vec![txout(100_000, &own), txout(1_000, &foreign)],
+            vec![txout(99_000, &foreign)],
+        );
+        let err = check(&policy, "secp256k1-tr", &sighash, Some(&ctx), &group_key).unwrap_err();
+        assert!(err.to_string().contains("exceeds policy limit"), "{}", err);
+
+        policy.bitcoin.max_spend_sats = None;
+        policy.bitcoin.allowed_addresses = vec![bitcoin::Address::from_script(
+            &taproot_script(&key(3)).unwrap(),
+            bitcoin::Network::Bitcoin,
+        )
+        .unwrap()
+        .to_string()];
+        let err = check(&policy, "secp256k1-tr", &sighash, Some(&ctx), &group_key).unwrap_err();
+        assert!(err.to_string().contains("allowed address list"), "{}", err);
+    }
+
+    #[test]
+    fn test_litecoin_label_doesnt_bypass_bitcoin_policy() {
+        let group_key = key(1);
+        let own = taproot_script(&group_key).unwrap();
+        let foreign = taproot_script(&key(2)).unwrap();
+
+        // Only a Bitcoin policy; the Litecoin one is left at its default
+        let mut policy = SigningPolicyConfig::default();
+        policy.bitcoin.max_spend_sats = Some(10_000);
+        let (mut ctx, sighash) =
+            psbt_context(vec![txout(100_000, &own)], vec![txout(99_000, &foreign)]);
+        assert!(check(&policy, "secp256k1-tr", &sighash, Some(&ctx), &group_key).is_err());
+        ctx.kind = "litecoin_psbt".to_string();
+        assert!(check(&policy, "secp256k1-tr", &sighash, Some(&ctx), &group_key).is_err());
+
+        // And a Litecoin limit applies to PSBTs labelled as Bitcoin
+        let mut policy = SigningPolicyConfig::default();
+        policy.litecoin.max_spend_sats = Some(10_000);
+        ctx.kind = "psbt".to_string();
+        assert!(check(&policy, "secp256k1-tr", &sighash, Some(&ctx), &group_key).is_err());
+    }
+
+    #[test]
+    fn test_bch_output_policy() {
+        use crate::chains::bitcoin_cash::{own_script, Prevout, UnsignedTransaction};
+        use bitcoin::hashes::Hash;
+
+        let group_key = key(1);
+        let own = own_script(&group_key).unwrap();
+        let foreign = own_script(&key(2)).unwrap();
+
+        let bch_context = |prevouts: &[(u64, &bitcoin::ScriptBuf)],
+                           outputs: Vec<(u64, &bitcoin::ScriptBuf)>| {
+            let tx = bitcoin::Transaction {
+                version: bitcoin::transaction::Version::TWO,
+                lock_time: bitcoin::absolute::LockTime::ZERO,
+                input: (0..prevouts.len() as u32)
+                    .map(|vout| bitcoin::TxIn {
+                        previous_output: bitcoin::OutPoint::new(bitcoin::Txid::all_zeros(), vout),
+                        ..Default::default()
+                    })
+                    .collect(),
+                output: outputs
+                    .into_iter()
+                    .map(|(sats, script)| txout(sats, script))
+                    .collect(),
+            };
+            let unsigned = UnsignedTransaction {
+                tx: hex::encode(bitcoin::consensus::serialize(&tx)),
+                prevouts: prevouts
+                    .iter()
+                    .map(|(value, script)| Prevout {
+                        value: *value,
+                        script_pubkey: hex::encode(script.as_bytes()),
+                    })
+                    .collect(),
+            };
+            let sighash = unsigned.sighash(&tx, 0).unwrap();
+            let ctx = TransactionContext {
+                kind: "bch".to_string(),
+                payload: serde_json::to_string(&unsigned).unwrap(),
+                input_index: Some(0),
+            };
+            (ctx, sighash)
+        };
+
+        let mut policy = SigningPolicyConfig::default();
+        policy.bitcoin_cash.max_spend_sats = Some(10_000);
+
+        let (ctx, sighash) =
+            bch_context(&[(100_000, &own)], vec![(5_000, &foreign), (94_000, &own)]);
+        assert!(check(&policy, "secp256k1-bch", &sighash, Some(&ctx), &group_key).is_ok());
+
+        // Paying an aggregator-supplied input's script is a spend
+        let (ctx, sighash) = bch_context(
+            &[(100_000, &own), (1_000, &foreign)],
+            vec![(99_000, &foreign)],
+        );
+        assert!(check(&policy, "secp256k1-bch", &sighash, Some(&ctx), &group_key).is_err());
+    }
+
+    #[test]
+    fn test_unchecked_kinds_refused_under_limits() {
+        // Algorand "TX"-prefixed msgpack of an empty map
+        let ctx = TransactionContext {
+            kind: "algorand".to_string(),
+            payload: "gA==".to_string(),
+            input_index: None,
+        };
+        let message = b"TX\x80";
+
+        let mut policy = SigningPolicyConfig::default();
+        assert!(check(&policy, "ed25519", message, Some(&ctx), &[]).is_ok());
+
+        policy.evm.allowed_chain_ids = vec![1];
+        assert!(check(&policy, "ed25519", message, Some(&ctx), &[]).is_err());
+
+        policy.unchecked_kinds = vec!["algorand".to_string()];
+        assert!(check(&policy, "ed25519", message, Some(&ctx), &[]).is_ok());
+    }
+
+    #[test]
+    fn test_require_transaction() {
+        let mut policy = SigningPolicyConfig::default();
+        assert!(check(&policy, "secp256k1-tr", &[0u8; 32], None, &[]).is_ok());
+        policy.require_transaction = true;
+        assert!(check(&policy, "secp256k1-tr", &[0u8; 32], None, &[]).is_err());
+
+        // Any limit refuses a bare hash, or leaving out the transaction would skip it
+        let mut policy = SigningPolicyConfig::default();
+        policy.bitcoin.max_spend_sats = Some(10_000);
+        assert!(check(&policy, "secp256k1-tr", &[0u8; 32], None, &[]).is_err());
+        let mut policy = SigningPolicyConfig::default();
+        policy.solana.allowed_program_ids = vec!["11111111111111111111111111111111".to_string()];
+        assert!(check(&policy, "ed25519", &[0u8; 32], None, &[]).is_err());
+    }
+
+    #[test]
+    fn test_solana_message_parse() {
+        let system_program = [0u8; 32];
+        let payer = [7u8; 32];
+
+        // Legacy message: header, 2 keys, blockhash, 1 transfer instruction
+        let mut msg = vec![1, 0, 1, 2];
+        msg.extend_from_slice(&payer);
+        msg.extend_from_slice(&system_program);
+        msg.extend_from_slice(&[9u8; 32]);
+        msg.extend_from_slice(&[1, 1, 1, 0, 4, 2, 0, 0, 0]);
+
+        let parsed = SolanaMessage::parse(&msg).unwrap();
+        assert_eq!(parsed.program_ids, vec![system_program]);
+        assert_eq!(parsed.required_signers(), &[payer]);
+
+        // v0: version prefix + same body + empty lookup table list
+        let mut v0 = vec![0x80];
+        v0.extend_from_slice(&msg);
+        v0.push(0);
+        assert_eq!(
+            SolanaMessage::parse(&v0).unwrap().program_ids,
+            vec![system_program]
+        );
+        assert!(SolanaMessage::parse(&[v0.as_slice(), &[0]].concat()).is_err());
+
+        let mut policy = SigningPolicyConfig::default();
+        policy.solana.allowed_program_ids = vec![bs58::encode(payer).into_string()];
+        let ctx = TransactionContext {
+            kind: "solana".to_string(),
+            payload: hex::encode(&msg),
+            input_index: None,
+        };
+        assert!(check(&policy, "ed25519", &msg, Some(&ctx), &[]).is_err());
+        policy.solana.allowed_program_ids = vec![bs58::encode(system_program).into_string()];
+        assert!(check(&policy, "ed25519", &msg, Some(&ctx), &[]).is_ok());
+    }
+}
@@ -0,0 +1,1145 @@
+//! Multi-operator approval workflow
+//!
+//! Signing requests are parked in a persisted pending state until k-of-m
+//! configured operators have approved them. Each decision is a BIP340 Schnorr
+//! signature by the operator key over `approval_digest(id, decision, payload_hash)`.
+//! Once the k-th approval arrives, the aggregator runs the FROST rounds and the
+//! approval evidence is attached to the signed result.
+
+use poem_openapi::param::{Path, Query};
+use poem_openapi::payload::Json;
+use poem_openapi::{ApiResponse, Object, OpenApi};
+use std::sync::Arc;
+
+use super::approval_store::{
+    approval_digest, find_operator, now_secs, verify_operator_signature, ApprovalRecord,
+    ApprovalStatus, ApprovalStore, Decision, OperatorDecision, PendingSignature, SignedOutput,
+};
+use super::bip322::Format as Bip322Format;
+use super::signing_api::{
+    ErrorResponse, EthereumMessage, SignBip322Request, SignBip322Response, SignBip322Result,
+    SignEthereumMessageResponse, SignEthereumMessageResult, SignEthereumRequest,
+    SignEthereumResponse, SignEthereumResult, SignMessageRequest, SignMessageResponse,
+    SignNostrRequest, SignNostrResponse, SignNostrResult, SignPersonalMessageRequest,
+    SignPsbtRequest, SignPsbtResponse, SignPsbtResult, SignResult, SignSolanaRequest,
+    SignSolanaResponse, SignSolanaResult, SignTransactionRequest, SignTransactionResponse,
+    SignTransactionResult, SigningAggregatorApi,
+};
+use crate::audit::{AuditEvent, AuditKind};
+use crate::chains::ethereum::EthereumTransaction;
+use crate::chains::nostr::UnsignedEvent;
+use crate::config::ApprovalConfig;
+use crate::node::tx_policy::{SolanaMessage, TransactionContext};
+
+pub struct ApprovalApi {
+    pub approval: Arc<ApprovalConfig>,
+    pub store: Arc<ApprovalStore>,
+    pub signer: Arc<SigningAggregatorApi>,
+}
+
+#[derive(Debug, Object)]
+pub struct SubmitApprovalRequest {
+    /// "psbt", "message", "ethereum", "personal_message", "typed_data", "solana", "nostr",
+    /// "bip322" or "transaction"
+    pub kind: String,
+    /// Registered chain name (kind = transaction, uses `transaction_payload` + `passphrases`;
+    /// kind = psbt, "bitcoin" or "litecoin")
+    pub chain: Option<String>,
+    /// Unsigned transaction in the chain's native encoding (kind = transaction)
+    pub transaction_payload: Option<String>,
+    /// base64-encoded PSBT (kind = psbt)
+    pub psbt: Option<String>,
+    /// One passphrase per PSBT input (kind = psbt) / per signer slot we hold (kind = solana)
+    pub passphrases: Option<Vec<String>>,
+    /// Passphrase (kind = message / ethereum / nostr / bip322)
+    pub passphrase: Option<String>,
+    /// hex-encoded message (kind = message), base64 Solana message (kind = solana),
+    /// UTF-8 message (kind = bip322)
+    pub message: Option<String>,
+    /// Curve for message signing, defaults to secp256k1
+    pub curve: Option<String>,
+    /// Transaction behind the message (kind = message), checked by signer node policy
+    pub transaction: Option<TransactionContext>,
+    /// Unsigned Ethereum transaction (kind = ethereum, uses `passphrase`)
+    pub ethereum: Option<EthereumTransaction>,
+    /// "utf8" (default) or "hex" (kind = personal_message, uses `passphrase` + `message`)
+    pub encoding: Option<String>,
+    /// eth_signTypedData_v4 document (kind = typed_data, uses `passphrase`)
+    pub typed_data: Option<serde_json::Value>,
+    /// Unsigned NIP-01 event (kind = nostr, uses `passphrase`)
+    pub nostr_event: Option<UnsignedEvent>,
+    /// "simple" (default) or "full" (kind = bip322)
+    pub format: Option<String>,
+    /// Free-form submitter reference (ticket, user id)
+    pub submitted_by: Option<String>,
+}
+
+#[derive(Debug, Object)]
+pub struct OperatorDecisionRequest {
+    pub operator: String,
+    /// hex BIP340 signature over sha256("frost-approval:v1:{id}:{decision}:{payload_hash}")
+    pub signature: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Object, Clone)]
+pub struct OperatorDecisionView {
+    pub operator: String,
+    pub decision: String,
+    pub signature: String,
+    pub comment: Option<String>,
+    pub decided_at: u64,
+}
+
+/// Proof that a signature was released by k-of-m operators, attached to every sign
+/// response whose request went through /api/approvals (absent on direct signing)
+#[derive(Debug, Object, Clone)]
+pub struct ApprovalEvidence {
+    pub request_id: String,
+    pub payload_hash: String,
+    pub required_approvals: usize,
+    pub approvals: Vec<OperatorDecisionView>,
+}
+
+#[derive(Debug, Object)]
+pub struct ApprovalRequestView {
+    pub id: String,
+    pub kind: String,
+    pub status: String,
+    pub payload_hash: String,
+    pub submitted_by: Option<String>,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub required_approvals: usize,
+    pub decisions: Vec<OperatorDecisionView>,
+    #[oai(skip_serializing_if_is_none)]
+    pub signed_psbt: Option<SignPsbtResponse>,
+    #[oai(skip_serializing_if_is_none)]
+    pub signed_message: Option<SignMessageResponse>,
+    #[oai(skip_serializing_if_is_none)]
+    pub signed_ethereum: Option<SignEthereumResponse>,
+    #[oai(skip_serializing_if_is_none)]
+    pub signed_ethereum_message: Option<SignEthereumMessageResponse>,
+    #[oai(skip_serializing_if_is_none)]
+    pub signed_solana: Option<SignSolanaResponse>,
+    #[oai(skip_serializing_if_is_none)]
+    pub signed_nostr: Option<SignNostrResponse>,
+    #[oai(skip_serializing_if_is_none)]
+    pub signed_bip322: Option<SignBip322Response>,
+    #[oai(skip_serializing_if_is_none)]
+    pub signed_transaction: Option<SignTransactionResponse>,
+    #[oai(skip_serializing_if_is_none)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Object)]
+pub struct ApprovalListResponse {
+    pub requests: Vec<ApprovalRequestView>,
+}
+
+#[derive(Debug, Object)]
+pub struct ExpireResponse {
+    pub expired: Vec<String>,
+}
+
+#[derive(ApiResponse)]
+pub enum ApprovalResult {
+    #[oai(status = 200)]
+    Ok(Json<Box<ApprovalRequestView>>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
+    #[oai(status = 404)]
+    NotFound(Json<ErrorResponse>),
+    #[oai(status = 409)]
+    Conflict(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+pub enum ApprovalListResult {
+    #[oai(status = 200)]
+    Ok(Json<ApprovalListResponse>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+pub enum ExpireResult {
+    #[oai(status = 200)]
+    Ok(Json<ExpireResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+fn internal(e: impl std::fmt::Display) -> ApprovalResult {
+    ApprovalResult::InternalError(Json(ErrorResponse {
+        error: format!("Approval store error: {}", e),
+    }))
+}
+
+#[OpenApi]
+impl ApprovalApi {
+    /// Submit a signing request for operator approval
+    #[oai(path = "/api/approvals", method = "post")]
+    async fn submit(&self, Json(req): Json<SubmitApprovalRequest>) -> ApprovalResult {
+        let request = match req.kind.as_str() {
+            "psbt" => match (req.psbt, req.passphrases) {
+                (Some(psbt), Some(passphrases)) => {
+                    // Reject garbage up front so operators never approve an unparseable PSBT
+                    if let Err(e) = psbt.parse::<bitcoin::psbt::Psbt>() {
+                        return ApprovalResult::BadRequest(Json(ErrorResponse {
+                            error: format!("Invalid PSBT: {}", e),
+                        }));
+                    }
+                    let chain = match req.chain.as_deref().map(|c| self.signer.chains.get(c)) {
+                        None => None,
+                        Some(Ok(adapter)) if adapter.name() == "bitcoin" => None,
+                        Some(Ok(adapter)) if adapter.name() == "litecoin" => {
+                            Some(adapter.name().to_string())
+                        }
+                        Some(Ok(adapter)) => {
+                            return ApprovalResult::BadRequest(Json(ErrorResponse {
+                                error: format!("Chain {} does not use PSBTs", adapter.name()),
+                            }))
+                        }
+                        Some(Err(e)) => {
+                            return ApprovalResult::BadRequest(Json(ErrorResponse {
+                                error: e.to_string(),
+                            }))
+                        }
+                    };
+                    PendingSignature::Psbt {
+                        psbt,
+                        passphrases,
+                        chain,
+                    }
+                }
+                _ => {
+                    return ApprovalResult::BadRequest(Json(ErrorResponse {
+                        error: "kind=psbt requires psbt and passphrases".to_string(),
+                    }))
+                }
+            },
+            "message" => match (req.passphrase, req.message) {
+                (Some(passphrase), Some(message)) => {
+                    // Nodes hex-decode it; fail now rather than after the approvals are in
+                    if let Err(e) = hex::decode(&message) {
+                        return ApprovalResult::BadRequest(Json(ErrorResponse {
+                            error: format!("Invalid hex message: {}", e),
+                        }));
+                    }
+                    PendingSignature::Message {
+                        passphrase,
+                        message,
+                        curve: req.curve.unwrap_or_else(|| "secp256k1".to_string()),
+                        transaction: req.transaction,
+                    }
+                }
+                _ => {
+                    return ApprovalResult::BadRequest(Json(ErrorResponse {
+                        error: "kind=message requires passphrase and message".to_string(),
+                    }))
+                }
+            },
+            "ethereum" => match (req.passphrase, req.ethereum) {
+                (Some(passphrase), Some(transaction)) => {
+                    // Operators approve exactly what gets signed, so bind the chain id now
+                    if let Err(e) = transaction.prepare(self.signer.ethereum_chain_id()) {
+                        return ApprovalResult::BadRequest(Json(ErrorResponse {
+                            error: format!("Invalid transaction: {}", e),
+                        }));
+                    }
+                    PendingSignature::Ethereum {
+                        passphrase,
+                        transaction,
+                    }
+                }
+                _ => {
+                    return ApprovalResult::BadRequest(Json(ErrorResponse {
+                        error: "kind=ethereum requires passphrase and ethereum".to_string(),
+                    }))
+                }
+            },
+            "personal_message" => match (req.passphrase, req.message) {
+                (Some(passphrase), Some(message)) => {
+                    let personal = SignPersonalMessageRequest {
+                        passphrase,
+                        message,
+                        encoding: req.encoding.unwrap_or_else(|| "utf8".to_string()),
+                    };
+                    match personal.to_message() {
+                        Ok(message) => PendingSignature::EthereumMessage {
+                            passphrase: personal.passphrase,
+                            message,
+                        },
+                        Err(error) => {
+                            return ApprovalResult::BadRequest(Json(ErrorResponse { error }))
+                        }
+                    }
+                }
+                _ => {
+                    return ApprovalResult::BadRequest(Json(ErrorResponse {
+                        error: "kind=personal_message requires passphrase and message".to_string(),
+                    }))
+                }
+            },
+            "typed_data" => match (req.passphrase, req.typed_data) {
+                (Some(passphrase), Some(typed_data)) => {
+                    let message = EthereumMessage::Eip712 { typed_data };
+                    // Hash now so operators never approve a document that can't be signed
+                    if let Err(e) = message.hash() {
+                        return ApprovalResult::BadRequest(Json(ErrorResponse {
+                            error: format!("Invalid typed data: {}", e),
+                        }));
+                    }
+                    PendingSignature::EthereumMessage {
+                        passphrase,
+                        message,
+                    }
+                }
+                _ => {
+                    return ApprovalResult::BadRequest(Json(ErrorResponse {
+                        error: "kind=typed_data requires passphrase and typed_data".to_string(),
+                    }))
+                }
+            },
+            "solana" => match (req.message, req.passphrases) {
+                (Some(message), Some(passphrases)) => {
+                    use base64::prelude::*;
+
+                    let parsed = BASE64_STANDARD
+                        .decode(&message)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|raw| SolanaMessage::parse(&raw));
+                    if let Err(e) = parsed {
+                        return ApprovalResult::BadRequest(Json(ErrorResponse {
+                            error: format!("Invalid Solana message: {}", e),
+                        }));
+                    }
+                    PendingSignature::Solana {
+                        message,
+                        passphrases,
+                    }
+                }
+                _ => {
+                    return ApprovalResult::BadRequest(Json(ErrorResponse {
+                        error: "kind=solana requires message and passphrases".to_string(),
+                    }))
+                }
+            },
+            "nostr" => match (req.passphrase, req.nostr_event) {
+                (Some(passphrase), Some(event)) => PendingSignature::Nostr {
+                    passphrase,
+                    // Operators approve the exact event, so its timestamp can't move
+                    event: event.stamped(),
+                },
+                _ => {
+                    return ApprovalResult::BadRequest(Json(ErrorResponse {
+                        error: "kind=nostr requires passphrase and nostr_event".to_string(),
+                    }))
+                }
+            },
+            "bip322" => match (req.passphrase, req.message) {
+                (Some(passphrase), Some(message)) => {
+                    let format = req.format.unwrap_or_else(|| "simple".to_string());
+                    if Bip322Format::from_name(&format).is_none() {
+                        return ApprovalResult::BadRequest(Json(ErrorResponse {
+                            error: format!(
+                                "Unknown BIP-322 format '{}', expected simple or full",
+                                format
+                            ),
+                        }));
+                    }
+                    PendingSignature::Bip322 {
+                        passphrase,
+                        message,
+                        format,
+                    }
+                }
+                _ => {
+                    return ApprovalResult::BadRequest(Json(ErrorResponse {
+                        error: "kind=bip322 requires passphrase and message".to_string(),
+                    }))
+                }
+            },
+            "transaction" => match (req.chain, req.transaction_payload, req.passphrases) {
+                (Some(chain), Some(transaction), Some(passphrases)) => {
+                    let adapter = match self.signer.chains.get(&chain) {
+                        Ok(adapter) => adapter,
+                        Err(e) => {
+                            return ApprovalResult::BadRequest(Json(ErrorResponse {
+                                error: e.to_string(),
+                            }))
+                        }
+                    };
+                    // Prepare it now so operators never approve what the adapter rejects
+                    let public_keys = match self
+                        .signer
+                        .chain_public_keys(adapter.as_ref(), &passphrases)
+                        .await
+                    {
+                        Ok(public_keys) => public_keys,
+                        Err(e) => return internal(format!("Failed to fetch signer pubkey: {}", e)),
+                    };
+                    if let Err(e) = adapter.prepare(&transaction, &public_keys) {
+                        return ApprovalResult::BadRequest(Json(ErrorResponse {
+                            error: format!("Invalid transaction: {:#}", e),
+                        }));
+                    }
+                    PendingSignature::Transaction {
+                        chain: adapter.name().to_string(),
+                        transaction,
+                        passphrases,
+                    }
+                }
+                _ => {
+                    return ApprovalResult::BadRequest(Json(ErrorResponse {
+                        error: "kind=transaction requires chain, transaction_payload and passphrases"
+                            .to_string(),
+                    }))
+                }
+            },
+            other => {
+                return ApprovalResult::BadRequest(Json(ErrorResponse {
+                    error: format!(
+                        "Unknown kind '{}', expected psbt, message, ethereum, personal_message, typed_data, solana, nostr, bip322 or transaction",
+                        other
+                    ),
+                }))
+            }
+        };
+
+        let payload_hash = match request.payload_hash() {
+            Ok(h) => h,
+            Err(e) => return internal(e),
+        };
+
+        let now = now_secs();
+        let record = ApprovalRecord {
+            id: hex::encode(rand::random::<[u8; 16]>()),
+            request,
+            payload_hash,
+            status: ApprovalStatus::Pending,
+            submitted_by: req.submitted_by,
+            created_at: now,
+            expires_at: now + self.approval.ttl_secs,
+            decisions: Vec::new(),
+            output: None,
+            error: None,
+        };
+
+        if let Err(e) = self.store.put(&record) {
+            return internal(e);
+        }
+
+        tracing::info!(
+            "📝 Approval request {} submitted ({}), needs {}-of-{} operators",
+            record.id,
+            record.request.kind(),
+            self.approval.required_approvals,
+            self.approval.operators.len()
+        );
+
+        ApprovalResult::Ok(Json(Box::new(self.view(&record))))
+    }
+
+    /// List approval requests (optionally filtered by status)
+    #[oai(path = "/api/approvals", method = "get")]
+    async fn list(&self, Query(status): Query<Option<String>>) -> ApprovalListResult {
+        let filter = match status.as_deref() {
+            None => None,
+            Some(s) => match ApprovalStatus::from_str(s) {
+                Some(st) => Some(st),
+                None => {
+                    return ApprovalListResult::BadRequest(Json(ErrorResponse {
+                        error: format!("Unknown status '{}'", s),
+                    }))
+                }
+            },
+        };
+
+        let now = now_secs();
+        match self.store.list() {
+            Ok(records) => ApprovalListResult::Ok(Json(ApprovalListResponse {
+                requests: records
+                    .into_iter()
+                    .map(|mut r| {
+                        // Report overdue requests as expired even before the sweep ran
+                        if r.is_overdue(now) {
+                            r.status = ApprovalStatus::Expired;
+                        }
+                        r
+                    })
+                    .filter(|r| filter.is_none_or(|f| r.status == f))
+                    .map(|r| self.view(&r))
+                    .collect(),
+            })),
+            Err(e) => ApprovalListResult::InternalError(Json(ErrorResponse {
+                error: format!("Approval store error: {}", e),
+            })),
+        }
+    }
+
+    /// Get a single approval request with its decisions and result
+    #[oai(path = "/api/approvals/:id", method = "get")]
+    async fn get(&self, Path(id): Path<String>) -> ApprovalResult {
+        match self.store.get(&id) {
+            Ok(Some(mut record)) => {
+                if record.is_overdue(now_secs()) {
+                    record.status = ApprovalStatus::Expired;
+                }
+                ApprovalResult::Ok(Json(Box::new(self.view(&record))))
+            }
+            Ok(None) => ApprovalResult::NotFound(Json(ErrorResponse {
+                error: format!("Approval request {} not found", id),
+            })),
+            Err(e) => internal(e),
+        }
+    }
+
+    /// Approve a request (signed by an operator key)
+    ///
+    /// The k-th approval triggers FROST signing; the response then carries the
+    /// signed PSBT/message together with the approval evidence.
+    #[oai(path = "/api/approvals/:id/approve", method = "post")]
+    async fn approve(
+        &self,
+        Path(id): Path<String>,
+        Json(req): Json<OperatorDecisionRequest>,
+    ) -> ApprovalResult {
+        self.decide(&id, Decision::Approve, req).await
+    }
+
+    /// Reject a request (signed by an operator key). A single rejection is final.
+    #[oai(path = "/api/approvals/:id/reject", method = "post")]
+    async fn reject(
+        &self,
+        Path(id): Path<String>,
+        Json(req): Json<OperatorDecisionRequest>,
+    ) -> ApprovalResult {
+        self.decide(&id, Decision::Reject, req).await
+    }
+
+    /// Expire all pending requests past their deadline
+    #[oai(path = "/api/approvals/expire", method = "post")]
+    async fn expire(&self) -> ExpireResult {
+        match self.store.expire_overdue().await {
+            Ok(expired) => {
+                if !expired.is_empty() {
+                    tracing::info!("⌛ Expired {} approval request(s)", expired.len());
+                }
+                for id in &expired {
+                    self.signer.audit.record(
+                        AuditEvent::new(AuditKind::PolicyDecision, "expired")
+                            .detail(format!("request={}", id)),
+                    );
+                }
+                ExpireResult::Ok(Json(ExpireResponse { expired }))
+            }
+            Err(e) => ExpireResult::InternalError(Json(ErrorResponse {
+                error: format!("Approval store error: {}", e),
+            })),
+        }
+    }
+}
+
+impl ApprovalApi {
+    async fn decide(
+        &self,
+        id: &str,
+        decision: Decision,
+        req: OperatorDecisionRequest,
+    ) -> ApprovalResult {
+        let guard = self.store.lock().await;
+
+        let mut record = match self.store.get(id) {
+            Ok(Some(r)) => r,
+            Ok(None) => {
+                return ApprovalResult::NotFound(Json(ErrorResponse {
+                    error: format!("Approval request {} not found", id),
+                }))
+            }
+            Err(e) => return internal(e),
+        };
+
+        if record.is_overdue(now_secs()) {
+            record.status = ApprovalStatus::Expired;
+            if let Err(e) = self.store.put(&record) {
+                return internal(e);
+            }
+        }
+
+        if record.status != ApprovalStatus::Pending {
+            return ApprovalResult::Conflict(Json(ErrorResponse {
+                error: format!(
+                    "Approval request {} is {}, not pending",
+                    id,
+                    record.status.as_str()
+                ),
+            }));
+        }
+
+        let Some(operator) = find_operator(&self.approval, &req.operator) else {
+            return ApprovalResult::Forbidden(Json(ErrorResponse {
+                error: format!("Unknown operator '{}'", req.operator),
+            }));
+        };
+
+        if record.decisions.iter().any(|d| d.operator == req.operator) {
+            return ApprovalResult::Conflict(Json(ErrorResponse {
+                error: format!("Operator '{}' already decided on {}", req.operator, id),
+            }));
+        }
+
+        let digest = approval_digest(&record.id, decision, &record.payload_hash);
+        if let Err(e) = verify_operator_signature(operator, &digest, &req.signature) {
+            tracing::warn!(
+                "Rejected {} from operator {} on {}: {}",
+                decision.as_str(),
+                req.operator,
+                id,
+                e
+            );
+            self.signer.audit.record(
+                AuditEvent::new(AuditKind::PolicyDecision, "invalid_signature")
+                    .participants([&req.operator])
+                    .detail(format!("request={} decision={}", id, decision.as_str())),
+            );
+            return ApprovalResult::Forbidden(Json(ErrorResponse {
+                error: format!("Invalid operator signature: {}", e),
+            }));
+        }
+
+        record.decisions.push(OperatorDecision {
+            operator: req.operator.clone(),
+            decision,
+            signature: req.signature,
+            comment: req.comment,
+            decided_at: now_secs(),
+        });
+
+        tracing::info!(
+            "🗳️  Operator {} {}d request {} ({}/{} approvals)",
+            req.operator,
+            decision.as_str(),
+            id,
+            record.approvals().count(),
+            self.approval.required_approvals
+        );
+
+        if decision == Decision::Reject {
+            record.status = ApprovalStatus::Rejected;
+        } else if record.approvals().count() >= self.approval.required_approvals {
+            record.status = ApprovalStatus::Executing;
+        }
+
+        if let Err(e) = self.store.put(&record) {
+            return internal(e);
+        }
+
+        self.signer.audit.record(
+            AuditEvent::new(AuditKind::PolicyDecision, decision.as_str())
+                .participants([&req.operator])
+                .detail(format!(
+                    "request={} payload={} status={}",
+                    record.id,
+                    record.payload_hash,
+                    record.status.as_str()
+                )),
+        );
+
+        // Release the store lock while the FROST rounds run
+        drop(guard);
+
+        if record.status == ApprovalStatus::Executing {
+            self.execute(&mut record).await;
+
+            let _guard = self.store.lock().await;
+            if let Err(e) = self.store.put(&record) {
+                return internal(e);
+            }
+        }
+
+        ApprovalResult::Ok(Json(Box::new(self.view(&record))))
+    }
+
+    /// Run the approved signing request and record its output
+    async fn execute(&self, record: &mut ApprovalRecord) {
+        tracing::info!("✅ Request {} approved, starting FROST signing", record.id);
+
+        let outcome = match record.request.clone() {
+            PendingSignature::Psbt {
+                psbt,
+                passphrases,
+                chain,
+            } => {
+                match self
+                    .signer
+                    .execute_sign_psbt(SignPsbtRequest {
+                        psbt,
+                        passphrases,
+                        chain: chain.unwrap_or_else(|| "bitcoin".to_string()),
+                    })
+                    .await
+                {
+                    SignPsbtResult::Ok(Json(resp)) => Ok(SignedOutput::Psbt {
+                        signed_psbt: resp.signed_psbt,
+                        signatures_added: resp.signatures_added,
+                    }),
+                    SignPsbtResult::BadRequest(Json(e))
+                    | SignPsbtResult::Forbidden(Json(e))
+                    | SignPsbtResult::InternalError(Json(e)) => Err(e.error),
+                }
+            }
+            PendingSignature::Message {
+                passphrase,
+                message,
+                curve,
+                transaction,
+            } => {
+                match self
+                    .signer
+                    .execute_sign_message(SignMessageRequest {
+                        passphrase,
+                        message,
+                        curve,
+                        transaction,
+                    })
+                    .await
+                {
+                    SignResult::Ok(Json(resp)) => Ok(SignedOutput::Message {
+                        signature: resp.signature,
+                        verified: resp.verified,
+                    }),
+                    SignResult::BadRequest(Json(e))
+                    | SignResult::Forbidden(Json(e))
+                    | SignResult::InternalError(Json(e)) => Err(e.error),
+                }
+            }
+            PendingSignature::Ethereum {
+                passphrase,
+                transaction,
+            } => {
+                match self
+                    .signer
+                    .execute_sign_ethereum(SignEthereumRequest {
+                        passphrase,
+                        transaction,
+                    })
+                    .await
+                {
+                    SignEthereumResult::Ok(Json(resp)) => Ok(SignedOutput::Ethereum {
+                        raw_transaction: resp.raw_transaction,
+                        tx_hash: resp.tx_hash,
+                        signing_hash: resp.signing_hash,
+                        chain_id: resp.chain_id,
+                        from: resp.from,
+                        recovered_address: resp.recovered_address,
+                        sender_verified: resp.sender_verified,
+                    }),
+                    SignEthereumResult::BadRequest(Json(e))
+                    | SignEthereumResult::Forbidden(Json(e))
+                    | SignEthereumResult::InternalError(Json(e)) => Err(e.error),
+                }
+            }
+            PendingSignature::EthereumMessage {
+                passphrase,
+                message,
+            } => {
+                match self
+                    .signer
+                    .execute_sign_ethereum_message(&passphrase, message)
+                    .await
+                {
+                    SignEthereumMessageResult::Ok(Json(resp)) => {
+                        Ok(SignedOutput::EthereumMessage {
+                            signature: resp.signature,
+                            hash: resp.hash,
+                            address: resp.address,
+                            recovered_address: resp.recovered_address,
+                            verified: resp.verified,
+                        })
+                    }
+                    SignEthereumMessageResult::BadRequest(Json(e))
+                    | SignEthereumMessageResult::Forbidden(Json(e))
+                    | SignEthereumMessageResult::InternalError(Json(e)) => Err(e.error),
+                }
+            }
+            PendingSignature::Solana {
+                message,
+                passphrases,
+            } => {
+                match self
+                    .signer
+                    .execute_sign_solana(SignSolanaRequest {
+                        message,
+                        passphrases,
+                    })
+                    .await
+                {
+                    SignSolanaResult::Ok(Json(resp)) => Ok(SignedOutput::Solana {
+                        transaction: resp.transaction,
+                        signature: resp.signature,
+                        signers: resp.signers,
+                        missing_signers: resp.missing_signers,
+                        fully_signed: resp.fully_signed,
+                    }),
+                    SignSolanaResult::BadRequest(Json(e))
+                    | SignSolanaResult::Forbidden(Json(e))
+                    | SignSolanaResult::InternalError(Json(e)) => Err(e.error),
+                }
+            }
+            PendingSignature::Nostr { passphrase, event } => {
+                match self
+                    .signer
+                    .execute_sign_nostr(SignNostrRequest { passphrase, event })
+                    .await
+                {
+                    SignNostrResult::Ok(Json(resp)) => Ok(SignedOutput::Nostr {
+                        event: resp.event,
+                        npub: resp.npub,
+                        verified: resp.verified,
+                    }),
+                    SignNostrResult::BadRequest(Json(e))
+                    | SignNostrResult::Forbidden(Json(e))
+                    | SignNostrResult::InternalError(Json(e)) => Err(e.error),
+                }
+            }
+            PendingSignature::Bip322 {
+                passphrase,
+                message,
+                format,
+            } => {
+                match self
+                    .signer
+                    .execute_sign_bip322(SignBip322Request {
+                        passphrase,
+                        message,
+                        format,
+                    })
+                    .await
+                {
+                    SignBip322Result::Ok(Json(resp)) => Ok(SignedOutput::Bip322 {
+                        address: resp.address,
+                        signature: resp.signature,
+                        format: resp.format,
+                        verified: resp.verified,
+                    }),
+                    SignBip322Result::BadRequest(Json(e))
+                    | SignBip322Result::Forbidden(Json(e))
+                    | SignBip322Result::InternalError(Json(e)) => Err(e.error),
+                }
+            }
+            PendingSignature::Transaction {
+                chain,
+                transaction,
+                passphrases,
+            } => {
+                match self
+                    .signer
+                    .execute_sign_transaction(SignTransactionRequest {
+                        chain,
+                        transaction,
+                        passphrases,
+                    })
+                    .await
+                {
+                    SignTransactionResult::Ok(Json(resp)) => Ok(SignedOutput::Transaction {
+                        chain: resp.chain,
+                        transaction: resp.transaction,
+                        tx_id: resp.tx_id,
+                        signers: resp.signers,
+                        missing_signers: resp.missing_signers,
+                        verified: resp.verified,
+                        signature: resp.signature,
+                    }),
+                    SignTransactionResult::BadRequest(Json(e))
+                    | SignTransactionResult::Forbidden(Json(e))
+                    | SignTransactionResult::InternalError(Json(e)) => Err(e.error),
+                }
+            }
+        };
+
+        match outcome {
+            Ok(output) => {
+                record.status = ApprovalStatus::Signed;
+                record.output = Some(output);
+                tracing::info!("✅ Approved request {} signed", record.id);
+            }
+            Err(e) => {
+                tracing::error!("Approved request {} failed to sign: {}", record.id, e);
+                record.status = ApprovalStatus::Failed;
+                record.error = Some(e);
+            }
+        }
+    }
+
+    fn evidence(&self, record: &ApprovalRecord) -> ApprovalEvidence {
+        ApprovalEvidence {
+            request_id: record.id.clone(),
+            payload_hash: record.payload_hash.clone(),
+            required_approvals: self.approval.required_approvals,
+            approvals: record.approvals().map(decision_view).collect(),
+        }
+    }
+
+    fn view(&self, record: &ApprovalRecord) -> ApprovalRequestView {
+        let mut signed_psbt = None;
+        let mut signed_message = None;
+        let mut signed_ethereum = None;
+        let mut signed_ethereum_message = None;
+        let mut signed_solana = None;
+        let mut signed_nostr = None;
+        let mut signed_bip322 = None;
+        let mut signed_transaction = None;
+
+        match &record.output {
+            Some(SignedOutput::Psbt {
+                signed_psbt: psbt,
+                signatures_added,
+            }) => {
+                signed_psbt = Some(SignPsbtResponse {
+                    signed_psbt: psbt.clone(),
+                    signatures_added: *signatures_added,
+                    approval: Some(self.evidence(record)),
+                })
+            }
+            Some(SignedOutput::Message {
+                signature,
+                verified,
+            }) => {
+                signed_message = Some(SignMessageResponse {
+                    signature: signature.clone(),
+                    verified: *verified,
+                    approval: Some(self.evidence(record)),
+                })
+            }
+            Some(SignedOutput::Ethereum {
+                raw_transaction,
+                tx_hash,
+                signing_hash,
+                chain_id,
+                from,
+                recovered_address,
+                sender_verified,
+            }) => {
+                signed_ethereum = Some(SignEthereumResponse {
+                    raw_transaction: raw_transaction.clone(),
+                    tx_hash: tx_hash.clone(),
+                    signing_hash: signing_hash.clone(),
+                    chain_id: *chain_id,
+                    from: from.clone(),
+                    recovered_address: recovered_address.clone(),
+                    sender_verified: *sender_verified,
+                    approval: Some(self.evidence(record)),
+                })
+            }
+            Some(SignedOutput::EthereumMessage {
+                signature,
+                hash,
+                address,
+                recovered_address,
+                verified,
+            }) => {
+                signed_ethereum_message = Some(SignEthereumMessageResponse {
+                    signature: signature.clone(),
+                    hash: hash.clone(),
+                    address: address.clone(),
+                    recovered_address: recovered_address.clone(),
+                    verified: *verified,
+                    approval: Some(self.evidence(record)),
+                })
+            }
+            Some(SignedOutput::Solana {
+                transaction,
+                signature,
+                signers,
+                missing_signers,
+                fully_signed,
+            }) => {
+                signed_solana = Some(SignSolanaResponse {
+                    transaction: transaction.clone(),
+                    signature: signature.clone(),
+                    signers: signers.clone(),
+                    missing_signers: missing_signers.clone(),
+                    fully_signed: *fully_signed,
+                    approval: Some(self.evidence(record)),
+                })
+            }
+            Some(SignedOutput::Nostr {
+                event,
+                npub,
+                verified,
+            }) => {
+                signed_nostr = Some(SignNostrResponse {
+                    event: event.clone(),
+                    npub: npub.clone(),
+                    verified: *verified,
+                    approval: Some(self.evidence(record)),
+                })
+            }
+            Some(SignedOutput::Bip322 {
+                address,
+                signature,
+                format,
+                verified,
+            }) => {
+                signed_bip322 = Some(SignBip322Response {
+                    address: address.clone(),
+                    signature: signature.clone(),
+                    format: format.clone(),
+                    verified: *verified,
+                    approval: Some(self.evidence(record)),
+                })
+            }
+            Some(SignedOutput::Transaction {
+                chain,
+                transaction,
+                tx_id,
+                signers,
+                missing_signers,
+                verified,
+                signature,
+            }) => {
+                signed_transaction = Some(SignTransactionResponse {
+                    chain: chain.clone(),
+                    transaction: transaction.clone(),
+                    tx_id: tx_id.clone(),
+                    signers: signers.clone(),
+                    missing_signers: missing_signers.clone(),
+                    verified: *verified,
+                    signature: signature.clone(),
+                    approval: Some(self.evidence(record)),
+                })
+            }
+            None => {}
+        }
+
+        ApprovalRequestView {
+            id: record.id.clone(),
+            kind: record.request.kind().to_string(),
+            status: record.status.as_str().to_string(),
+            payload_hash: record.payload_hash.clone(),
+            submitted_by: record.submitted_by.clone(),
+            created_at: record.created_at,
+            expires_at: record.expires_at,
+            required_approvals: self.approval.required_approvals,
+            decisions: record.decisions.iter().map(decision_view).collect(),
+            signed_psbt,
+            signed_message,
+            signed_ethereum,
+            signed_ethereum_message,
+            signed_solana,
+            signed_nostr,
+            signed_bip322,
+            signed_transaction,
+            error: record.error.clone(),
+        }
+    }
+}
+
+fn decision_view(d: &OperatorDecision) -> OperatorDecisionView {
+    OperatorDecisionView {
+        operator: d.operator.clone(),
+        decision: d.decision.as_str().to_string(),
+        signature: d.signature.clone(),
+        comment: d.comment.clone(),
+        decided_at: d.decided_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditLog;
+    use crate::chains::ChainRegistry;
+    use crate::config::AggregatorConfig;
+    use poem::listener::{Acceptor, Listener, TcpListener};
+    use poem_openapi::types::ParseFromJSON;
+
+    /// Stands in for signer node 0's pubkey endpoint
+    #[poem::handler]
+    fn pubkey() -> poem::web::Json<serde_json::Value> {
+        poem::web::Json(serde_json::json!({
+            "public_key": "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_transaction_is_prepared_at_submit() {
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = acceptor.local_addr()[0].as_socket_addr().copied().unwrap();
+        tokio::spawn(
+            poem::Server::new_with_acceptor(acceptor)
+                .run(poem::Route::new().at("/api/curve/:curve/pubkey", poem::get(pubkey))),
+        );
+
+        let dir = std::env::temp_dir().join(format!("frost-approval-api-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let api = ApprovalApi {
+            approval: Arc::new(ApprovalConfig {
+                required_approvals: 1,
+                operators: vec![],
+                ttl_secs: 60,
+                storage_path: dir.join("approvals").display().to_string(),
+            }),
+            store: Arc::new(ApprovalStore::open(dir.join("approvals")).unwrap()),
+            signer: Arc::new(SigningAggregatorApi {
+                config: Arc::new(AggregatorConfig {
+                    signer_nodes: vec![format!("http://{}", addr)],
+                    threshold: 2,
+                    chains: None,
+                    approval: None,
+                }),
+                network: Arc::new(None),
+                audit: Arc::new(AuditLog::open(dir.join("audit.log"), "aggregator").unwrap()),
+                chains: Arc::new(ChainRegistry::from_config(None, None).unwrap()),
+            }),
+        };
+
+        let submit = |chain_id: u64| {
+            let payload = EthereumTransaction {
+                tx_type: None,
+                chain_id: None,
+                nonce: 0,
+                to: Some("0x000000000000000000000000000000000000dead".to_string()),
+                value: Some("1".to_string()),
+                data: None,
+                gas_limit: 21000,
+                gas_price: Some("1".to_string()),
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                access_list: None,
+            }
+            .prepare(chain_id)
+            .unwrap()
+            .unsigned_payload();
+            SubmitApprovalRequest::parse_from_json(Some(serde_json::json!({
+                "kind": "transaction",
+                "chain": "ethereum",
+                "transaction_payload": hex::encode(payload),
+                "passphrases": ["pass"],
+            })))
+            .unwrap()
+        };
+
+        // Signed for another chain, the adapter would refuse it only after approval
+        let result = api.submit(Json(submit(5))).await;
+        assert!(matches!(result, ApprovalResult::BadRequest(_)));
+
+        match api.submit(Json(submit(1))).await {
+            ApprovalResult::Ok(Json(view)) => assert_eq!(view.kind, "transaction"),
+            _ => panic!("mainnet transaction should be accepted"),
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
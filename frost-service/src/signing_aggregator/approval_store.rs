@@ -0,0 +1,469 @@
+//! Persistent store for signing requests awaiting operator approval
+//!
+//! Requests are kept in RocksDB (one JSON record per request id) so a pending
+//! withdrawal survives an aggregator restart. Operator decisions are BIP340
+//! Schnorr signatures over a canonical approval digest.
+use anyhow::{Context, Result};
+use bitcoin::hashes::{sha256, Hash};
+use rocksdb::{ColumnFamilyDescriptor, IteratorMode, Options, DB};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::signing_api::EthereumMessage;
+use crate::chains::ethereum::EthereumTransaction;
+use crate::chains::nostr::{SignedEvent, UnsignedEvent};
+use crate::config::{ApprovalConfig, OperatorConfig};
+use crate::node::tx_policy::TransactionContext;
+
+const CF_APPROVAL_REQUESTS: &str = "approval_requests";
+
+/// What the operators are being asked to approve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum PendingSignature {
+    Psbt {
+        psbt: String,
+        passphrases: Vec<String>,
+        /// Litecoin PSBTs only; absent for Bitcoin so older payload hashes still match
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        chain: Option<String>,
+    },
+    Message {
+        passphrase: String,
+        message: String,
+        curve: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transaction: Option<TransactionContext>,
+    },
+    Ethereum {
+        passphrase: String,
+        transaction: EthereumTransaction,
+    },
+    #[serde(rename = "ethereum_message")]
+    EthereumMessage {
+        passphrase: String,
+        message: EthereumMessage,
+    },
+    Solana {
+        message: String, // base64 legacy / v0 message
+        passphrases: Vec<String>,
+    },
+    Nostr {
+        passphrase: String,
+        event: UnsignedEvent, // created_at fixed at submission
+    },
+    Bip322 {
+        passphrase: String,
+        message: String,
+        format: String,
+    },
+    /// Any registered chain, via its adapter
+    Transaction {
+        chain: String,
+        transaction: String,
+        passphrases: Vec<String>,
+    },
+}
+
+impl PendingSignature {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PendingSignature::Psbt { .. } => "psbt",
+            PendingSignature::Message { .. } => "message",
+            PendingSignature::Ethereum { .. } => "ethereum",
+            PendingSignature::EthereumMessage { .. } => "ethereum_message",
+            PendingSignature::Solana { .. } => "solana",
+            PendingSignature::Nostr { .. } => "nostr",
+            PendingSignature::Bip322 { .. } => "bip322",
+            PendingSignature::Transaction { .. } => "transaction",
+        }
+    }
+
+    /// Hash of the canonical JSON payload - this is what operators sign off on
+    pub fn payload_hash(&self) -> Result<String> {
+        let json = serde_json::to_vec(self).context("Failed to serialize signing payload")?;
+        Ok(hex::encode(sha256::Hash::hash(&json).as_byte_array()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalStatus {
+    Pending,
+    Executing,
+    Signed,
+    Failed,
+    Rejected,
+    Expired,
+}
+
+impl ApprovalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApprovalStatus::Pending => "pending",
+            ApprovalStatus::Executing => "executing",
+            ApprovalStatus::Signed => "signed",
+            ApprovalStatus::Failed => "failed",
+            ApprovalStatus::Rejected => "rejected",
+            ApprovalStatus::Expired => "expired",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(ApprovalStatus::Pending),
+            "executing" => Some(ApprovalStatus::Executing),
+            "signed" => Some(ApprovalStatus::Signed),
+            "failed" => Some(ApprovalStatus::Failed),
+            "rejected" => Some(ApprovalStatus::Rejected),
+            "expired" => Some(ApprovalStatus::Expired),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Decision {
+    Approve,
+    Reject,
+}
+
+impl Decision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Decision::Approve => "approve",
+            Decision::Reject => "reject",
+        }
+    }
+}
+
+/// One operator's signed decision (kept as evidence with the request)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperatorDecision {
+    pub operator: String,
+    pub decision: Decision,
+    pub signature: String, // hex BIP340 signature over approval_digest()
+    pub comment: Option<String>,
+    pub decided_at: u64,
+}
+
+/// Output of the FROST signing run once the request was approved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SignedOutput {
+    Psbt {
+        signed_psbt: String,
+        signatures_added: usize,
+    },
+    Message {
+        signature: String,
+        verified: bool,
+    },
+    Ethereum {
+        raw_transaction: String,
+        tx_hash: String,
+        signing_hash: String,
+        chain_id: u64,
+        from: String,
+        recovered_address: Option<String>,
+        sender_verified: bool,
+    },
+    #[serde(rename = "ethereum_message")]
+    EthereumMessage {
+        signature: String,
+        hash: String,
+        address: String,
+        recovered_address: Option<String>,
+        verified: bool,
+    },
+    Solana {
+        transaction: String,
+        signature: Option<String>,
+        signers: Vec<String>,
+        missing_signers: Vec<String>,
+        fully_signed: bool,
+    },
+    Nostr {
+        event: SignedEvent,
+        npub: String,
+        verified: bool,
+    },
+    Bip322 {
+        address: String,
+        signature: String,
+        format: String,
+        verified: bool,
+    },
+    Transaction {
+        chain: String,
+        transaction: String,
+        tx_id: Option<String>,
+        signers: Vec<String>,
+        missing_signers: Vec<String>,
+        verified: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalRecord {
+    pub id: String,
+    pub request: PendingSignature,
+    pub payload_hash: String,
+    pub status: ApprovalStatus,
+    pub submitted_by: Option<String>,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub decisions: Vec<OperatorDecision>,
+    pub output: Option<SignedOutput>,
+    pub error: Option<String>,
+}
+
+impl ApprovalRecord {
+    pub fn approvals(&self) -> impl Iterator<Item = &OperatorDecision> {
+        self.decisions
+            .iter()
+            .filter(|d| d.decision == Decision::Approve)
+    }
+
+    pub fn is_overdue(&self, now: u64) -> bool {
+        self.status == ApprovalStatus::Pending && now >= self.expires_at
+    }
+}
+
+/// Canonical digest an operator signs for a decision
+///
+/// Binds the decision to the request id AND the payload hash, so an approval
+/// can't be replayed onto a different request or a modified PSBT.
+pub fn approval_digest(id: &str, decision: Decision, payload_hash: &str) -> [u8; 32] {
+    let preimage = format!(
+        "frost-approval:v1:{}:{}:{}",
+        id,
+        decision.as_str(),
+        payload_hash
+    );
+    sha256::Hash::hash(preimage.as_bytes()).to_byte_array()
+}
+
+/// Verify an operator's BIP340 signature over the approval digest
+pub fn verify_operator_signature(
+    operator: &OperatorConfig,
+    digest: &[u8; 32],
+    signature_hex: &str,
+) -> Result<()> {
+    let pubkey_bytes = hex::decode(&operator.public_key)
+        .context(format!("Invalid public key for operator {}", operator.name))?;
+    let pubkey = bitcoin::key::XOnlyPublicKey::from_slice(&pubkey_bytes)
+        .context(format!("Invalid public key for operator {}", operator.name))?;
+
+    let sig_bytes = hex::decode(signature_hex).context("Invalid signature hex")?;
+    let signature = bitcoin::secp256k1::schnorr::Signature::from_slice(&sig_bytes)
+        .context("Invalid Schnorr signature")?;
+
+    let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+    let msg = bitcoin::secp256k1::Message::from_digest(*digest);
+    secp.verify_schnorr(&signature, &msg, &pubkey)
+        .context("Operator signature verification failed")
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// RocksDB-backed approval request store
+pub struct ApprovalStore {
+    db: Arc<DB>,
+    /// Serializes read-modify-write cycles on records
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl ApprovalStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+
+        let cfs = vec![ColumnFamilyDescriptor::new(
+            CF_APPROVAL_REQUESTS,
+            Options::default(),
+        )];
+
+        let db =
+            DB::open_cf_descriptors(&opts, path, cfs).context("Failed to open approval store")?;
+
+        Ok(Self {
+            db: Arc::new(db),
+            lock: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Guard for read-modify-write sequences (hold across get + put)
+    pub async fn lock(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.lock.lock().await
+    }
+
+    pub fn get(&self, id: &str) -> Result<Option<ApprovalRecord>> {
+        let cf = self
+            .db
+            .cf_handle(CF_APPROVAL_REQUESTS)
+            .ok_or_else(|| anyhow::anyhow!("Column family {} not found", CF_APPROVAL_REQUESTS))?;
+
+        match self.db.get_cf(&cf, id.as_bytes())? {
+            Some(bytes) => Ok(Some(
+                serde_json::from_slice(&bytes).context("Failed to parse approval record")?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    pub fn put(&self, record: &ApprovalRecord) -> Result<()> {
+        let cf = self
+            .db
+            .cf_handle(CF_APPROVAL_REQUESTS)
+            .ok_or_else(|| anyhow::anyhow!("Column family {} not found", CF_APPROVAL_REQUESTS))?;
+
+        let bytes = serde_json::to_vec(record).context("Failed to serialize approval record")?;
+        self.db
+            .put_cf(&cf, record.id.as_bytes(), bytes)
+            .context("Failed to store approval record")
+    }
+
+    /// All records, newest first
+    pub fn list(&self) -> Result<Vec<ApprovalRecord>> {
+        let cf = self
+            .db
+            .cf_handle(CF_APPROVAL_REQUESTS)
+            .ok_or_else(|| anyhow::anyhow!("Column family {} not found", CF_APPROVAL_REQUESTS))?;
+
+        let mut records = Vec::new();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (_, value) = item?;
+            let record: ApprovalRecord =
+                serde_json::from_slice(&value).context("Failed to parse approval record")?;
+            records.push(record);
+        }
+
+        records.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+        Ok(records)
+    }
+
+    /// Mark every overdue pending request as expired, returns the expired ids
+    pub async fn expire_overdue(&self) -> Result<Vec<String>> {
+        let _guard = self.lock().await;
+        let now = now_secs();
+
+        let mut expired = Vec::new();
+        for mut record in self.list()? {
+            if record.is_overdue(now) {
+                record.status = ApprovalStatus::Expired;
+                self.put(&record)?;
+                expired.push(record.id);
+            }
+        }
+
+        Ok(expired)
+    }
+
+    /// Fail every request left executing by a previous run, returns their ids
+    ///
+    /// Only one aggregator opens the store, so at startup nothing is still
+    /// signing them. Whether the nodes released shares is unknown; failing
+    /// (rather than re-running) leaves resubmission to the operators.
+    pub async fn fail_interrupted(&self) -> Result<Vec<String>> {
+        let _guard = self.lock().await;
+
+        let mut failed = Vec::new();
+        for mut record in self.list()? {
+            if record.status == ApprovalStatus::Executing {
+                record.status = ApprovalStatus::Failed;
+                record.error = Some("Aggregator stopped while signing; resubmit".to_string());
+                self.put(&record)?;
+                failed.push(record.id);
+            }
+        }
+
+        Ok(failed)
+    }
+}
+
+/// Look up an operator by name in the approval config
+pub fn find_operator<'a>(config: &'a ApprovalConfig, name: &str) -> Option<&'a OperatorConfig> {
+    config.operators.iter().find(|op| op.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operator_signature_roundtrip() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let keypair = bitcoin::secp256k1::Keypair::from_seckey_slice(&secp, &[7u8; 32]).unwrap();
+        let (xonly, _) = keypair.x_only_public_key();
+        let operator = OperatorConfig {
+            name: "alice".to_string(),
+            public_key: hex::encode(xonly.serialize()),
+        };
+
+        let digest = approval_digest("req-1", Decision::Approve, "abcd");
+        let msg = bitcoin::secp256k1::Message::from_digest(digest);
+        let sig = secp.sign_schnorr_no_aux_rand(&msg, &keypair);
+
+        assert!(verify_operator_signature(&operator, &digest, &hex::encode(sig.as_ref())).is_ok());
+
+        // Signature must not transfer to a different decision or payload
+        let reject = approval_digest("req-1", Decision::Reject, "abcd");
+        assert!(verify_operator_signature(&operator, &reject, &hex::encode(sig.as_ref())).is_err());
+        let other = approval_digest("req-1", Decision::Approve, "abce");
+        assert!(verify_operator_signature(&operator, &other, &hex::encode(sig.as_ref())).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fail_interrupted() {
+        let path = std::env::temp_dir().join(format!("frost-approvals-{}", std::process::id()));
+        let store = ApprovalStore::open(&path).unwrap();
+        let record = |id: &str, status| ApprovalRecord {
+            id: id.to_string(),
+            request: PendingSignature::Message {
+                passphrase: "wallet".to_string(),
+                message: "00".to_string(),
+                curve: "secp256k1".to_string(),
+                transaction: None,
+            },
+            payload_hash: String::new(),
+            status,
+            submitted_by: None,
+            created_at: now_secs(),
+            expires_at: now_secs() + 60,
+            decisions: Vec::new(),
+            output: None,
+            error: None,
+        };
+        store
+            .put(&record("stuck", ApprovalStatus::Executing))
+            .unwrap();
+        store
+            .put(&record("waiting", ApprovalStatus::Pending))
+            .unwrap();
+
+        assert_eq!(store.fail_interrupted().await.unwrap(), vec!["stuck"]);
+        let stuck = store.get("stuck").unwrap().unwrap();
+        assert_eq!(stuck.status, ApprovalStatus::Failed);
+        assert!(stuck.error.is_some());
+        assert_eq!(
+            store.get("waiting").unwrap().unwrap().status,
+            ApprovalStatus::Pending
+        );
+        assert!(store.fail_interrupted().await.unwrap().is_empty());
+
+        drop(store);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
@@ -0,0 +1,311 @@
+//! BIP-322 generic signed messages
+//!
+//! A message is signed by spending a virtual `to_spend` output locked to the
+//! address with a virtual `to_sign` transaction. The FROST key only signs the
+//! Taproot key-path sighash of `to_sign`; verification also accepts P2WPKH
+//! signatures so customer-supplied proofs can be checked.
+
+use anyhow::{Context, Result};
+use bitcoin::blockdata::opcodes::all::{OP_PUSHBYTES_0, OP_RETURN};
+use bitcoin::hashes::{sha256, Hash, HashEngine};
+use bitcoin::script::Builder;
+use bitcoin::sighash::{EcdsaSighashType, Prevouts, SighashCache, TapSighashType};
+use bitcoin::transaction::Version;
+use bitcoin::{
+    absolute, Amount, OutPoint, Script, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid,
+    Witness,
+};
+use serde::{Deserialize, Serialize};
+
+/// Signature encodings from the BIP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Witness stack of `to_sign`
+    Simple,
+    /// Whole `to_sign` transaction
+    Full,
+}
+
+impl Format {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Format::Simple => "simple",
+            Format::Full => "full",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "simple" => Some(Format::Simple),
+            "full" => Some(Format::Full),
+            _ => None,
+        }
+    }
+}
+
+/// Transaction context payload (kind "bip322") forwarded to signer nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageContext {
+    pub script_pubkey: String, // hex
+    pub message: String,
+}
+
+impl MessageContext {
+    /// Taproot sighash this context commits to
+    pub fn sighash(&self) -> Result<[u8; 32]> {
+        let script =
+            ScriptBuf::from_hex(&self.script_pubkey).context("Invalid script_pubkey hex")?;
+        if !script.is_p2tr() {
+            anyhow::bail!("BIP-322 signing is only supported for P2TR addresses");
+        }
+        taproot_sighash(&script, self.message.as_bytes())
+    }
+}
+
+/// tagged_hash("BIP0322-signed-message", message)
+pub fn message_hash(message: &[u8]) -> [u8; 32] {
+    let tag = sha256::Hash::hash(b"BIP0322-signed-message");
+    let mut engine = sha256::Hash::engine();
+    engine.input(tag.as_byte_array());
+    engine.input(tag.as_byte_array());
+    engine.input(message);
+    sha256::Hash::from_engine(engine).to_byte_array()
+}
+
+/// Virtual transaction whose only output is locked to the signing address
+pub fn to_spend(script_pubkey: &Script, message: &[u8]) -> Transaction {
+    Transaction {
+        version: Version(0),
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint {
+                txid: Txid::all_zeros(),
+                vout: 0xFFFFFFFF,
+            },
+            script_sig: Builder::new()
+                .push_opcode(OP_PUSHBYTES_0)
+                .push_slice(message_hash(message))
+                .into_script(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: script_pubkey.to_owned(),
+        }],
+    }
+}
+
+/// Unsigned virtual transaction spending `to_spend`
+pub fn to_sign(to_spend: &Transaction) -> Transaction {
+    Transaction {
+        version: Version(0),
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint {
+                txid: to_spend.compute_txid(),
+                vout: 0,
+            },
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: Builder::new().push_opcode(OP_RETURN).into_script(),
+        }],
+    }
+}
+
+/// Taproot key-path sighash (SIGHASH_DEFAULT) FROST signs for `message`
+pub fn taproot_sighash(script_pubkey: &Script, message: &[u8]) -> Result<[u8; 32]> {
+    let to_spend = to_spend(script_pubkey, message);
+    let to_sign = to_sign(&to_spend);
+    let sighash = SighashCache::new(&to_sign).taproot_key_spend_signature_hash(
+        0,
+        &Prevouts::All(&to_spend.output),
+        TapSighashType::Default,
+    )?;
+    Ok(sighash.to_byte_array())
+}
+
+/// Encode a 64-byte key-path signature for `message` in `format`
+pub fn encode(script_pubkey: &Script, message: &[u8], signature: &[u8], format: Format) -> Vec<u8> {
+    let mut witness = Witness::new();
+    witness.push(signature);
+
+    match format {
+        Format::Simple => bitcoin::consensus::serialize(&witness),
+        Format::Full => {
+            let mut tx = to_sign(&to_spend(script_pubkey, message));
+            tx.input[0].witness = witness;
+            bitcoin::consensus::serialize(&tx)
+        }
+    }
+}
+
+/// Check a simple or full signature over `message` by `script_pubkey`
+///
+/// Returns the detected format and whether the signature is valid; malformed
+/// or unsupported proofs (other script types, proof-of-funds inputs) are errors.
+pub fn verify(script_pubkey: &Script, message: &[u8], signature: &[u8]) -> Result<(Format, bool)> {
+    let to_spend = to_spend(script_pubkey, message);
+    let mut to_sign = to_sign(&to_spend);
+
+    let format = match bitcoin::consensus::deserialize::<Transaction>(signature) {
+        Ok(full) => {
+            if full.input.len() != 1 {
+                anyhow::bail!("Proof-of-funds inputs are not supported");
+            }
+            if full.input[0].previous_output != to_sign.input[0].previous_output {
+                anyhow::bail!("to_sign does not spend to_spend for this address and message");
+            }
+            if full.output != to_sign.output {
+                anyhow::bail!("to_sign must have a single OP_RETURN output");
+            }
+            // The signer picks version, lock time and sequence in the full format
+            to_sign = full;
+            Format::Full
+        }
+        Err(_) => {
+            to_sign.input[0].witness = bitcoin::consensus::deserialize::<Witness>(signature)
+                .context("Signature is neither a BIP-322 witness nor a to_sign transaction")?;
+            Format::Simple
+        }
+    };
+
+    let witness = &to_sign.input[0].witness;
+    let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+    let mut cache = SighashCache::new(&to_sign);
+
+    let valid = if script_pubkey.is_p2tr() {
+        if witness.len() != 1 {
+            anyhow::bail!("Only Taproot key-path spends are supported");
+        }
+        let sig = bitcoin::taproot::Signature::from_slice(&witness[0])
+            .context("Invalid Taproot signature")?;
+        let output_key = bitcoin::key::XOnlyPublicKey::from_slice(&script_pubkey.as_bytes()[2..])?;
+        let sighash = cache.taproot_key_spend_signature_hash(
+            0,
+            &Prevouts::All(&to_spend.output),
+            sig.sighash_type,
+        )?;
+        secp.verify_schnorr(
+            &sig.signature,
+            &bitcoin::secp256k1::Message::from_digest(sighash.to_byte_array()),
+            &output_key,
+        )
+        .is_ok()
+    } else if script_pubkey.is_p2wpkh() {
+        if witness.len() != 2 {
+            anyhow::bail!("P2WPKH witness must be [signature, pubkey]");
+        }
+        let sig = bitcoin::ecdsa::Signature::from_slice(&witness[0])
+            .context("Invalid ECDSA signature")?;
+        let public_key = bitcoin::CompressedPublicKey::from_slice(&witness[1])
+            .context("Invalid compressed pubkey")?;
+        if ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash()) != *script_pubkey {
+            return Ok((format, false));
+        }
+        let sighash = cache.p2wpkh_signature_hash(
+            0,
+            script_pubkey,
+            Amount::ZERO,
+            EcdsaSighashType::from_consensus(sig.sighash_type.to_u32()),
+        )?;
+        secp.verify_ecdsa(
+            &bitcoin::secp256k1::Message::from_digest(sighash.to_byte_array()),
+            &sig.signature,
+            &public_key.0,
+        )
+        .is_ok()
+    } else {
+        anyhow::bail!("Only P2TR and P2WPKH addresses are supported");
+    };
+
+    Ok((format, valid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::prelude::*;
+    use std::str::FromStr;
+
+    fn script_of(address: &str) -> ScriptBuf {
+        bitcoin::Address::from_str(address)
+            .unwrap()
+            .assume_checked()
+            .script_pubkey()
+    }
+
+    #[test]
+    fn test_bip322_vectors() {
+        assert_eq!(
+            hex::encode(message_hash(b"")),
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1"
+        );
+        assert_eq!(
+            hex::encode(message_hash(b"Hello World")),
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a"
+        );
+
+        let script = script_of("bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l");
+        let spend = to_spend(&script, b"Hello World");
+        assert_eq!(
+            spend.compute_txid().to_string(),
+            "b79d196740ad5217771c1098fc4a4b51e0535c32236c71f1ea4d61a2d603352b"
+        );
+        assert_eq!(
+            to_sign(&spend).compute_txid().to_string(),
+            "88737ae86f2077145f93cc4b153ae9a1cb8d56afa511988c149c5c8c9d93bddf"
+        );
+
+        let signature = BASE64_STANDARD
+            .decode("AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=")
+            .unwrap();
+        assert_eq!(
+            verify(&script, b"Hello World", &signature).unwrap(),
+            (Format::Simple, true)
+        );
+        assert!(!verify(&script, b"Hello World!", &signature).unwrap().1);
+
+        let taproot = script_of("bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3");
+        let signature = BASE64_STANDARD
+            .decode("AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==")
+            .unwrap();
+        assert_eq!(
+            verify(&taproot, b"Hello World", &signature).unwrap(),
+            (Format::Simple, true)
+        );
+    }
+
+    #[test]
+    fn test_taproot_sign_roundtrip() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let keypair = bitcoin::secp256k1::Keypair::from_seckey_slice(&secp, &[0x11; 32]).unwrap();
+        let (x_only, _) = keypair.x_only_public_key();
+        // Untweaked output key, like the FROST addresses
+        let script = ScriptBuf::new_p2tr_tweaked(
+            bitcoin::key::TweakedPublicKey::dangerous_assume_tweaked(x_only),
+        );
+
+        let sighash = taproot_sighash(&script, b"proof of reserves").unwrap();
+        let sig = secp
+            .sign_schnorr_no_aux_rand(&bitcoin::secp256k1::Message::from_digest(sighash), &keypair)
+            .serialize();
+
+        for format in [Format::Simple, Format::Full] {
+            let encoded = encode(&script, b"proof of reserves", &sig, format);
+            assert_eq!(
+                verify(&script, b"proof of reserves", &encoded).unwrap(),
+                (format, true)
+            );
+            assert!(
+                !verify(&script, b"something else", &encoded)
+                    .unwrap_or((format, false))
+                    .1
+            );
+        }
+    }
+}
@@ -0,0 +1,320 @@
+//! EIP-191 personal messages and EIP-712 typed data hashing
+//!
+//! Both are hashed server-side so a caller can't get an arbitrary 32-byte
+//! hash (e.g. a transaction) signed under the guise of an off-chain message.
+
+use anyhow::{Context, Result};
+use ethereum_types::U256;
+use serde::Deserialize;
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// keccak256("\x19Ethereum Signed Message:\n" || len(message) || message)
+pub fn eip191_hash(message: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(format!("\x19Ethereum Signed Message:\n{}", message.len()).as_bytes());
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypedData {
+    types: BTreeMap<String, Vec<TypedField>>,
+    primary_type: String,
+    domain: Value,
+    #[serde(default)]
+    message: Value,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TypedField {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+/// EIP-712 signing hash of an `eth_signTypedData_v4` JSON document
+///
+/// keccak256("\x19\x01" || hashStruct(domain) || hashStruct(message))
+pub fn hash_typed_data(document: &Value) -> Result<[u8; 32]> {
+    let typed: TypedData =
+        serde_json::from_value(document.clone()).context("Invalid EIP-712 typed data")?;
+
+    if !typed.types.contains_key("EIP712Domain") {
+        anyhow::bail!("EIP-712 types must include EIP712Domain");
+    }
+
+    let encoder = Encoder {
+        types: &typed.types,
+    };
+
+    let domain_separator = encoder.hash_struct("EIP712Domain", &typed.domain)?;
+
+    let mut hasher = Keccak256::new();
+    hasher.update([0x19, 0x01]);
+    hasher.update(domain_separator);
+    // Domain-only documents sign just the separator
+    if typed.primary_type != "EIP712Domain" {
+        hasher.update(encoder.hash_struct(&typed.primary_type, &typed.message)?);
+    }
+    Ok(hasher.finalize().into())
+}
+
+struct Encoder<'a> {
+    types: &'a BTreeMap<String, Vec<TypedField>>,
+}
+
+impl Encoder<'_> {
+    fn fields(&self, name: &str) -> Result<&[TypedField]> {
+        self.types
+            .get(name)
+            .map(Vec::as_slice)
+            .context(format!("Unknown EIP-712 type '{}'", name))
+    }
+
+    /// Strip array suffixes: "Person[][3]" -> "Person"
+    fn base_type(ty: &str) -> &str {
+        ty.split('[').next().unwrap_or(ty)
+    }
+
+    fn collect_dependencies(&self, name: &str, found: &mut BTreeSet<String>) -> Result<()> {
+        if found.contains(name) {
+            return Ok(());
+        }
+        found.insert(name.to_string());
+        for field in self.fields(name)? {
+            let base = Self::base_type(&field.ty);
+            if self.types.contains_key(base) {
+                self.collect_dependencies(base, found)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+    fn encode_type(&self, name: &str) -> Result<String> {
+        let mut deps = BTreeSet::new();
+        self.collect_dependencies(name, &mut deps)?;
+        deps.remove(name);
+
+        let mut encoded = String::new();
+        for ty in std::iter::once(name).chain(deps.iter().map(String::as_str)) {
+            let fields: Vec<String> = self
+                .fields(ty)?
+                .iter()
+                .map(|f| format!("{} {}", f.ty, f.name))
+                .collect();
+            encoded.push_str(&format!("{}({})", ty, fields.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    fn hash_struct(&self, name: &str, value: &Value) -> Result<[u8; 32]> {
+        let object = value
+            .as_object()
+            .context(format!("Value for {} must be an object", name))?;
+
+        let mut hasher = Keccak256::new();
+        hasher.update(Keccak256::digest(self.encode_type(name)?.as_bytes()));
+        for field in self.fields(name)? {
+            let field_value = object
+                .get(&field.name)
+                .context(format!("Missing field {}.{}", name, field.name))?;
+            hasher.update(
+                self.encode_value(&field.ty, field_value)
+                    .context(format!("Invalid field {}.{}", name, field.name))?,
+            );
+        }
+        Ok(hasher.finalize().into())
+    }
+
+    fn encode_value(&self, ty: &str, value: &Value) -> Result<[u8; 32]> {
+        // Arrays: keccak256 of the concatenated encoded elements
+        if let Some(open) = ty.rfind('[') {
+            let element_ty = &ty[..open];
+            let length = &ty[open + 1..ty.len() - 1];
+            let items = value.as_array().context("Expected an array")?;
+            if !length.is_empty() && length.parse::<usize>().ok() != Some(items.len()) {
+                anyhow::bail!("Expected {} elements, got {}", length, items.len());
+            }
+
+            let mut hasher = Keccak256::new();
+            for item in items {
+                hasher.update(self.encode_value(element_ty, item)?);
+            }
+            return Ok(hasher.finalize().into());
+        }
+
+        if self.types.contains_key(ty) {
+            return self.hash_struct(ty, value);
+        }
+
+        let mut word = [0u8; 32];
+        match ty {
+            "string" => {
+                let s = value.as_str().context("Expected a string")?;
+                word = Keccak256::digest(s.as_bytes()).into();
+            }
+            "bytes" => {
+                word = Keccak256::digest(decode_hex(value)?).into();
+            }
+            "bool" => {
+                word[31] = value.as_bool().context("Expected a bool")? as u8;
+            }
+            "address" => {
+                let bytes = decode_hex(value)?;
+                if bytes.len() != 20 {
+                    anyhow::bail!("Address must be 20 bytes");
+                }
+                word[12..].copy_from_slice(&bytes);
+            }
+            _ if ty.starts_with("bytes") => {
+                let size: usize = ty[5..].parse().context("Invalid bytesN type")?;
+                let bytes = decode_hex(value)?;
+                if size == 0 || size > 32 || bytes.len() != size {
+                    anyhow::bail!("{} value must be {} bytes", ty, size);
+                }
+                word[..size].copy_from_slice(&bytes);
+            }
+            _ if ty.starts_with("uint") => {
+                let bits = int_bits(&ty[4..])?;
+                let (negative, magnitude) = parse_integer(value)?;
+                if negative || (bits < 256 && magnitude >> bits != U256::zero()) {
+                    anyhow::bail!("Value out of range for {}", ty);
+                }
+                magnitude.to_big_endian(&mut word);
+            }
+            _ if ty.starts_with("int") => {
+                let bits = int_bits(&ty[3..])?;
+                let (negative, magnitude) = parse_integer(value)?;
+                let limit = U256::one() << (bits - 1);
+                if (negative && magnitude > limit) || (!negative && magnitude >= limit) {
+                    anyhow::bail!("Value out of range for {}", ty);
+                }
+                // Two's complement, sign-extended to 256 bits
+                let encoded = if negative {
+                    (!magnitude).overflowing_add(U256::one()).0
+                } else {
+                    magnitude
+                };
+                encoded.to_big_endian(&mut word);
+            }
+            other => anyhow::bail!("Unsupported EIP-712 type '{}'", other),
+        }
+        Ok(word)
+    }
+}
+
+fn int_bits(suffix: &str) -> Result<usize> {
+    let bits = if suffix.is_empty() {
+        256
+    } else {
+        suffix.parse().context("Invalid integer size")?
+    };
+    if bits == 0 || bits > 256 || bits % 8 != 0 {
+        anyhow::bail!("Invalid integer size {}", bits);
+    }
+    Ok(bits)
+}
+
+/// JSON number or decimal / 0x-hex string, returned as (negative, magnitude)
+fn parse_integer(value: &Value) -> Result<(bool, U256)> {
+    match value {
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                Ok((false, U256::from(u)))
+            } else if let Some(i) = n.as_i64() {
+                Ok((i < 0, U256::from(i.unsigned_abs())))
+            } else {
+                anyhow::bail!("Integer {} is not exact, pass it as a string", n)
+            }
+        }
+        Value::String(s) => {
+            let (negative, digits) = match s.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, s.as_str()),
+            };
+            let magnitude = crate::chains::ethereum::parse_quantity(digits)?;
+            Ok((negative && !magnitude.is_zero(), magnitude))
+        }
+        _ => anyhow::bail!("Expected an integer"),
+    }
+}
+
+fn decode_hex(value: &Value) -> Result<Vec<u8>> {
+    let s = value.as_str().context("Expected a hex string")?;
+    hex::decode(s.trim_start_matches("0x")).context("Invalid hex")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eip191_hash() {
+        assert_eq!(
+            hex::encode(eip191_hash(b"hello")),
+            "50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750"
+        );
+    }
+
+    #[test]
+    fn test_eip712_mail_example() {
+        // Example from the EIP-712 specification
+        let document = serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Person": [
+                    {"name": "name", "type": "string"},
+                    {"name": "wallet", "type": "address"}
+                ],
+                "Mail": [
+                    {"name": "from", "type": "Person"},
+                    {"name": "to", "type": "Person"},
+                    {"name": "contents", "type": "string"}
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+                "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+                "contents": "Hello, Bob!"
+            }
+        });
+
+        assert_eq!(
+            hex::encode(hash_typed_data(&document).unwrap()),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    #[test]
+    fn test_int_encoding() {
+        let types = BTreeMap::new();
+        let encoder = Encoder { types: &types };
+
+        let minus_one = encoder
+            .encode_value("int8", &serde_json::json!(-1))
+            .unwrap();
+        assert_eq!(minus_one, [0xff; 32]);
+        assert!(encoder
+            .encode_value("uint8", &serde_json::json!(256))
+            .is_err());
+        assert!(encoder
+            .encode_value("int8", &serde_json::json!("-129"))
+            .is_err());
+    }
+}
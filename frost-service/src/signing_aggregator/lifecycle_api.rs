@@ -0,0 +1,422 @@
+//! Key lifecycle orchestration across signer nodes
+//!
+//! Freeze, retire and destroy are sent to every signer node and each node's
+//! outcome is reported back; repeating a request finishes a partial one.
+//! Unfreezing and destroying take k-of-m operator signatures over
+//! `unfreeze_digest` / `destroy_digest`, naming the freeze or retirement
+//! acted on, so an approval for one can't be replayed against a later one.
+//! Every node checks them against its own `[node.lifecycle]` operators; the
+//! aggregator checks them up front too when it has `[aggregator.approval]`.
+
+use anyhow::Context;
+use poem_openapi::param::{Path, Query};
+use poem_openapi::payload::Json;
+use poem_openapi::{ApiResponse, Object, OpenApi};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use super::signing_api::ErrorResponse;
+use crate::audit::{AuditEvent, AuditKind, AuditLog};
+use crate::config::AggregatorConfig;
+use crate::curves::CurveType;
+use crate::node::lifecycle::{destroy_digest, unfreeze_digest, verify_quorum, OperatorApproval};
+
+pub struct LifecycleApi {
+    pub config: Arc<AggregatorConfig>,
+    pub audit: Arc<AuditLog>,
+}
+
+#[derive(Debug, Object)]
+pub struct KeyLifecycleRequest {
+    pub passphrase: String,
+    pub reason: Option<String>,
+}
+
+/// Unfreeze or destroy, signed by operators over
+/// sha256("frost-lifecycle:v1:{unfreeze|destroy}:{curve}:{passphrase}:{transition_id}")
+/// where `transition_id` is the freeze's or the retirement's
+#[derive(Debug, Object)]
+pub struct ApprovedLifecycleRequest {
+    pub passphrase: String,
+    pub reason: Option<String>,
+    pub approvals: Vec<OperatorApproval>,
+}
+
+/// One signer node's view of the key
+#[derive(Debug, Object, Clone, Default)]
+pub struct NodeKeyState {
+    pub node: String,
+    pub state: Option<String>,
+    pub changed_at: Option<u64>,
+    pub transition_id: Option<String>,
+    #[oai(skip_serializing_if_is_none)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Object)]
+pub struct KeyLifecycleResponse {
+    pub curve: String,
+    /// State every node reports, or "mixed"
+    pub state: String,
+    /// Id of the change just applied (sign this one to unfreeze after a freeze)
+    pub transition_id: Option<String>,
+    /// Every node answered and agrees on `state`
+    pub complete: bool,
+    pub nodes: Vec<NodeKeyState>,
+}
+
+#[derive(ApiResponse)]
+pub enum KeyLifecycleResult {
+    #[oai(status = 200)]
+    Ok(Json<KeyLifecycleResponse>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
+    #[oai(status = 409)]
+    Conflict(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+/// `KeyStateRequest` / `KeyStateResponse` of the signer node API
+#[derive(Serialize)]
+struct NodeStateRequest<'a> {
+    passphrase: &'a str,
+    state: &'a str,
+    reason: Option<&'a str>,
+    transition_id: &'a str,
+    approvals: &'a [OperatorApproval],
+}
+
+#[derive(Deserialize)]
+struct NodeStateResponse {
+    state: String,
+    changed_at: u64,
+    transition_id: Option<String>,
+}
+
+#[OpenApi]
+impl LifecycleApi {
+    /// Lifecycle state of a key on every signer node
+    #[oai(path = "/api/keys/:curve/state", method = "get")]
+    async fn key_state(
+        &self,
+        Path(curve): Path<String>,
+        Query(passphrase): Query<String>,
+    ) -> KeyLifecycleResult {
+        let curve = match parse_curve(&curve) {
+            Ok(curve) => curve,
+            Err(e) => return KeyLifecycleResult::BadRequest(e),
+        };
+        let nodes = self.query_nodes(curve, &passphrase).await;
+        KeyLifecycleResult::Ok(Json(summarize(curve, None, nodes)))
+    }
+
+    /// Freeze a key: nodes refuse to sign with it until operators unfreeze it
+    #[oai(path = "/api/keys/:curve/freeze", method = "post")]
+    async fn freeze(
+        &self,
+        Path(curve): Path<String>,
+        Json(req): Json<KeyLifecycleRequest>,
+    ) -> KeyLifecycleResult {
+        self.transition(
+            &curve,
+            &req.passphrase,
+            "frozen",
+            req.reason.as_deref(),
+            &[],
+            &[],
+        )
+        .await
+    }
+
+    /// Unfreeze a key with k-of-m operator approval
+    #[oai(path = "/api/keys/:curve/unfreeze", method = "post")]
+    async fn unfreeze(
+        &self,
+        Path(curve): Path<String>,
+        Json(req): Json<ApprovedLifecycleRequest>,
+    ) -> KeyLifecycleResult {
+        self.approved_transition(&curve, req, "frozen", "active")
+            .await
+    }
+
+    /// Retire a key for good: it can no longer sign, only be destroyed
+    #[oai(path = "/api/keys/:curve/retire", method = "post")]
+    async fn retire(
+        &self,
+        Path(curve): Path<String>,
+        Json(req): Json<KeyLifecycleRequest>,
+    ) -> KeyLifecycleResult {
+        self.transition(
+            &curve,
+            &req.passphrase,
+            "retired",
+            req.reason.as_deref(),
+            &[],
+            &[],
+        )
+        .await
+    }
+
+    /// Erase a retired key's shares on every node with k-of-m operator approval
+    #[oai(path = "/api/keys/:curve/destroy", method = "post")]
+    async fn destroy(
+        &self,
+        Path(curve): Path<String>,
+        Json(req): Json<ApprovedLifecycleRequest>,
+    ) -> KeyLifecycleResult {
+        self.approved_transition(&curve, req, "retired", "destroyed")
+            .await
+    }
+}
+
+impl LifecycleApi {
+    /// Unfreeze (`from` frozen) or destroy (`from` retired) with operator approvals
+    async fn approved_transition(
+        &self,
+        curve: &str,
+        req: ApprovedLifecycleRequest,
+        from: &str,
+        state: &str,
+    ) -> KeyLifecycleResult {
+        let curve_type = match parse_curve(curve) {
+            Ok(curve) => curve,
+            Err(e) => return KeyLifecycleResult::BadRequest(e),
+        };
+        let action = if state == "destroyed" {
+            "destroy"
+        } else {
+            "unfreeze"
+        };
+
+        // Every node has to have reached `from` through the same change, or
+        // nobody knows which freeze / retirement the operators signed for
+        let nodes = self.query_nodes(curve_type, &req.passphrase).await;
+        if let Some(node) = nodes.iter().find(|node| node.error.is_some()) {
+            return KeyLifecycleResult::InternalError(Json(ErrorResponse {
+                error: format!(
+                    "Signer node {} unavailable: {}",
+                    node.node,
+                    node.error.as_deref().unwrap_or_default()
+                ),
+            }));
+        }
+        let transition_ids: BTreeSet<_> = nodes
+            .iter()
+            .filter(|node| node.state.as_deref() == Some(from))
+            .map(|node| node.transition_id.clone())
+            .collect();
+        let transition_id = match transition_ids.into_iter().collect::<Vec<_>>().as_slice() {
+            [] => {
+                return KeyLifecycleResult::BadRequest(Json(ErrorResponse {
+                    error: format!("{} key is not {}", curve_type.as_str(), from),
+                }))
+            }
+            [Some(id)] => id.clone(),
+            _ => {
+                return KeyLifecycleResult::Conflict(Json(ErrorResponse {
+                    error: format!(
+                        "Signer nodes were {} separately; repeat that through the aggregator first",
+                        from
+                    ),
+                }))
+            }
+        };
+
+        // The nodes verify the approvals themselves; this only fails early
+        let digest = if action == "destroy" {
+            destroy_digest(curve_type.as_str(), &req.passphrase, &transition_id)
+        } else {
+            unfreeze_digest(curve_type.as_str(), &req.passphrase, &transition_id)
+        };
+        let operators = match &self.config.approval {
+            Some(approval) => verify_quorum(
+                approval.required_approvals,
+                &approval.operators,
+                &digest,
+                &req.approvals,
+            ),
+            None => Ok(req.approvals.iter().map(|a| a.operator.clone()).collect()),
+        };
+        let operators = match operators {
+            Ok(operators) => operators,
+            Err(e) => {
+                tracing::warn!("Refused {} {}: {}", curve_type.as_str(), action, e);
+                self.audit.record(
+                    AuditEvent::new(AuditKind::KeyLifecycle, "denied")
+                        .curve(curve_type.as_str())
+                        .passphrase(&req.passphrase)
+                        .participants(req.approvals.iter().map(|a| &a.operator))
+                        .detail(format!("{} of transition {}: {}", action, transition_id, e)),
+                );
+                return KeyLifecycleResult::Forbidden(Json(ErrorResponse { error: e }));
+            }
+        };
+
+        self.transition(
+            curve,
+            &req.passphrase,
+            state,
+            req.reason.as_deref(),
+            &req.approvals,
+            &operators,
+        )
+        .await
+    }
+
+    /// Send one state change to every signer node under a fresh transition id
+    async fn transition(
+        &self,
+        curve: &str,
+        passphrase: &str,
+        state: &str,
+        reason: Option<&str>,
+        approvals: &[OperatorApproval],
+        approved_by: &[String],
+    ) -> KeyLifecycleResult {
+        let curve = match parse_curve(curve) {
+            Ok(curve) => curve,
+            Err(e) => return KeyLifecycleResult::BadRequest(e),
+        };
+        let transition_id = hex::encode(rand::random::<[u8; 16]>());
+        let request = NodeStateRequest {
+            passphrase,
+            state,
+            reason,
+            transition_id: &transition_id,
+            approvals,
+        };
+
+        let client = reqwest::Client::new();
+        let mut nodes = Vec::new();
+        for url in &self.config.signer_nodes {
+            let result = async {
+                let resp = client
+                    .post(format!("{}/api/keys/{}/state", url, curve.as_str()))
+                    .json(&request)
+                    .send()
+                    .await?;
+                node_response(resp).await
+            }
+            .await;
+            nodes.push(node_state(url, result));
+        }
+
+        let response = summarize(curve, Some(transition_id), nodes);
+        let failed: Vec<_> = response
+            .nodes
+            .iter()
+            .filter_map(|node| node.error.as_ref().map(|e| format!("{}: {}", node.node, e)))
+            .collect();
+        let mut detail = format!(
+            "-> {} (transition {})",
+            state,
+            response.transition_id.as_deref().unwrap_or_default()
+        );
+        if let Some(reason) = reason {
+            detail.push_str(&format!(": {}", reason));
+        }
+        if !failed.is_empty() {
+            detail.push_str(&format!("; failed on {}", failed.join(", ")));
+        }
+        self.audit.record(
+            AuditEvent::new(
+                AuditKind::KeyLifecycle,
+                if response.complete {
+                    "success"
+                } else {
+                    "partial"
+                },
+            )
+            .curve(curve.as_str())
+            .passphrase(passphrase)
+            .participants(approved_by)
+            .detail(detail),
+        );
+
+        if !response.complete {
+            tracing::warn!(
+                "{} key -> {} incomplete, {} of {} nodes failed",
+                curve.as_str(),
+                state,
+                failed.len(),
+                response.nodes.len()
+            );
+        }
+        KeyLifecycleResult::Ok(Json(response))
+    }
+
+    async fn query_nodes(&self, curve: CurveType, passphrase: &str) -> Vec<NodeKeyState> {
+        let client = reqwest::Client::new();
+        let mut nodes = Vec::new();
+        for url in &self.config.signer_nodes {
+            let result = async {
+                let resp = client
+                    .get(format!("{}/api/keys/{}/state", url, curve.as_str()))
+                    .query(&[("passphrase", passphrase)])
+                    .send()
+                    .await?;
+                node_response(resp).await
+            }
+            .await;
+            nodes.push(node_state(url, result));
+        }
+        nodes
+    }
+}
+
+fn parse_curve(name: &str) -> Result<CurveType, Json<ErrorResponse>> {
+    CurveType::from_name(name).ok_or_else(|| {
+        Json(ErrorResponse {
+            error: format!("Unsupported curve: {}", name),
+        })
+    })
+}
+
+async fn node_response(resp: reqwest::Response) -> anyhow::Result<NodeStateResponse> {
+    if !resp.status().is_success() {
+        let error = resp.text().await.unwrap_or_else(|_| "Unknown".to_string());
+        anyhow::bail!("{}", error);
+    }
+    resp.json().await.context("Invalid key state response")
+}
+
+fn node_state(url: &str, result: anyhow::Result<NodeStateResponse>) -> NodeKeyState {
+    match result {
+        Ok(resp) => NodeKeyState {
+            node: url.to_string(),
+            state: Some(resp.state),
+            changed_at: Some(resp.changed_at),
+            transition_id: resp.transition_id,
+            error: None,
+        },
+        Err(e) => NodeKeyState {
+            node: url.to_string(),
+            error: Some(e.to_string()),
+            ..Default::default()
+        },
+    }
+}
+
+fn summarize(
+    curve: CurveType,
+    transition_id: Option<String>,
+    nodes: Vec<NodeKeyState>,
+) -> KeyLifecycleResponse {
+    let states: BTreeSet<_> = nodes.iter().map(|node| node.state.clone()).collect();
+    let (state, complete) = match states.into_iter().collect::<Vec<_>>().as_slice() {
+        [Some(state)] => (state.clone(), true),
+        _ => ("mixed".to_string(), false),
+    };
+
+    KeyLifecycleResponse {
+        curve: curve.as_str().to_string(),
+        state,
+        transition_id,
+        complete,
+        nodes,
+    }
+}
@@ -9,17 +9,26 @@
 //! - Address aggregator: DKG orchestration (low risk, generates addresses)
 //! - Signing aggregator: FROST signing (high risk, signs transactions)
 
+pub mod approval_api;
+pub mod approval_store;
+pub mod bip322;
+pub mod eip712;
+pub mod lifecycle_api;
 pub mod signing_api;
 
 use anyhow::Result;
 use poem::{listener::TcpListener, Route, Server};
-use poem_openapi::OpenApiService;
+use poem_openapi::{OpenApi, OpenApiService};
 use std::sync::Arc;
 use tokio::signal;
 
+use crate::audit::{AuditEvent, AuditKind};
+
 pub async fn run(
     server_config: crate::config::ServerConfig,
     aggregator_config: crate::config::AggregatorConfig,
+    network_config: Option<crate::config::NetworkConfig>,
+    audit_log: Arc<crate::audit::AuditLog>,
 ) -> Result<()> {
     tracing::info!("Signer nodes: {:?}", aggregator_config.signer_nodes);
     tracing::info!(
@@ -28,11 +37,70 @@ pub async fn run(
         aggregator_config.signer_nodes.len()
     );
 
+    let chains = Arc::new(crate::chains::ChainRegistry::from_config(
+        network_config.as_ref(),
+        aggregator_config.chains.as_deref(),
+    )?);
+    tracing::info!("Chains: {}", chains.names().join(", "));
+
+    let config = Arc::new(aggregator_config);
+    let network = Arc::new(network_config);
+
     // Create signing API
     let api = signing_api::SigningAggregatorApi {
-        config: Arc::new(aggregator_config),
+        config: config.clone(),
+        network: network.clone(),
+        audit: audit_log.clone(),
+        chains: chains.clone(),
     };
 
+    // Freeze / unfreeze / retire / destroy across every signer node
+    let lifecycle_api = lifecycle_api::LifecycleApi {
+        config: config.clone(),
+        audit: audit_log.clone(),
+    };
+
+    // With [aggregator.approval], /api/sign/* is closed and requests go through /api/approvals
+    match config.approval.clone() {
+        Some(approval) => {
+            tracing::info!(
+                "Operator approval required: {}-of-{} operators",
+                approval.required_approvals,
+                approval.operators.len()
+            );
+
+            let store = Arc::new(approval_store::ApprovalStore::open(&approval.storage_path)?);
+            tracing::info!("✅ Approval store opened at {}", approval.storage_path);
+            for id in store.fail_interrupted().await? {
+                tracing::warn!("⚠️  Approval request {} was interrupted while signing", id);
+                audit_log.record(
+                    AuditEvent::new(AuditKind::PolicyDecision, "failed")
+                        .detail(format!("request={} interrupted by restart", id)),
+                );
+            }
+            tracing::info!("   🗳️  POST /api/approvals, /api/approvals/:id/approve|reject");
+
+            let approval_api = approval_api::ApprovalApi {
+                approval: Arc::new(approval),
+                store,
+                signer: Arc::new(signing_api::SigningAggregatorApi {
+                    config: config.clone(),
+                    network,
+                    audit: audit_log,
+                    chains,
+                }),
+            };
+
+            serve((api, approval_api, lifecycle_api), &server_config).await
+        }
+        None => serve((api, lifecycle_api), &server_config).await,
+    }
+}
+
+async fn serve<T: OpenApi + 'static>(
+    api: T,
+    server_config: &crate::config::ServerConfig,
+) -> Result<()> {
     let api_service = OpenApiService::new(api, "FROST Signing Aggregator", "2.0");
 
     let ui = api_service.scalar();
@@ -50,6 +118,14 @@ pub async fn run(
     );
     tracing::info!("   ✍️  POST /api/sign/message {{passphrase, message}}");
     tracing::info!("   ✍️  POST /api/sign/psbt {{psbt, passphrases}}");
+    tracing::info!("   ✍️  POST /api/sign/ethereum {{passphrase, transaction}}");
+    tracing::info!("   ✍️  POST /api/sign/ethereum/personal, /api/sign/ethereum/typed-data");
+    tracing::info!("   ✍️  POST /api/sign/solana {{message, passphrases}}");
+    tracing::info!("   ✍️  POST /api/sign/nostr {{passphrase, event}}");
+    tracing::info!("   ✍️  POST /api/sign/bip322 {{passphrase, message, format}}");
+    tracing::info!("   🔍 POST /api/verify/bip322 {{address, message, signature}}");
+    tracing::info!("   ✍️  POST /api/sign/transaction {{chain, transaction, passphrases}}");
+    tracing::info!("   🔒 POST /api/keys/{{curve}}/freeze|unfreeze|retire|destroy");
     tracing::info!(
         "   📖 Documentation: http://{}:{}/docs",
         server_config.host,
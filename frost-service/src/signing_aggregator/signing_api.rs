@@ -5,12 +5,24 @@
 
 use poem_openapi::payload::Json;
 use poem_openapi::{ApiResponse, Object, OpenApi};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::config::AggregatorConfig;
+use super::approval_api::ApprovalEvidence;
+use super::bip322;
+use crate::audit::{AuditEvent, AuditKind, AuditLog};
+use crate::chains::ethereum::EthereumTransaction;
+use crate::chains::nostr::{SignedEvent, UnsignedEvent};
+use crate::chains::{ChainAdapter, ChainRegistry};
+use crate::config::{AggregatorConfig, NetworkConfig};
+use crate::curves::CurveType;
+use crate::node::tx_policy::TransactionContext;
 
 pub struct SigningAggregatorApi {
     pub config: Arc<AggregatorConfig>,
+    pub network: Arc<Option<NetworkConfig>>,
+    pub audit: Arc<AuditLog>,
+    pub chains: Arc<ChainRegistry>,
 }
 
 #[derive(Debug, Object)]
@@ -19,6 +31,9 @@ pub struct SignMessageRequest {
     pub message: String, // hex-encoded
     #[oai(default = "default_curve")]
     pub curve: String, // "secp256k1" or "ed25519", defaults to secp256k1
+    /// Full EVM transaction / Solana message behind `message`, forwarded so
+    /// signer nodes can validate it against their local policy
+    pub transaction: Option<TransactionContext>,
 }
 
 fn default_curve() -> String {
@@ -29,18 +44,240 @@ fn default_curve() -> String {
 pub struct SignMessageResponse {
     pub signature: String, // hex-encoded Schnorr signature
     pub verified: bool,
+    #[oai(skip_serializing_if_is_none)]
+    pub approval: Option<ApprovalEvidence>,
 }
 
 #[derive(Debug, Object)]
 pub struct SignPsbtRequest {
     pub psbt: String,             // base64-encoded PSBT
     pub passphrases: Vec<String>, // one per input
+    #[oai(default = "default_psbt_chain")]
+    pub chain: String, // "bitcoin" or "litecoin", defaults to bitcoin
+}
+
+fn default_psbt_chain() -> String {
+    "bitcoin".to_string()
 }
 
 #[derive(Debug, Object)]
 pub struct SignPsbtResponse {
     pub signed_psbt: String, // base64-encoded signed PSBT
     pub signatures_added: usize,
+    #[oai(skip_serializing_if_is_none)]
+    pub approval: Option<ApprovalEvidence>,
+}
+
+#[derive(Debug, Object)]
+pub struct SignEthereumRequest {
+    pub passphrase: String,
+    pub transaction: EthereumTransaction,
+}
+
+#[derive(Debug, Object)]
+pub struct SignEthereumResponse {
+    pub raw_transaction: String, // 0x-hex, ready for eth_sendRawTransaction
+    pub tx_hash: String,
+    pub signing_hash: String,
+    pub chain_id: u64,
+    pub from: String, // address of the passphrase's secp256k1 key
+    pub recovered_address: Option<String>,
+    pub sender_verified: bool, // ecrecover(signing_hash, signature) == from
+    #[oai(skip_serializing_if_is_none)]
+    pub approval: Option<ApprovalEvidence>,
+}
+
+#[derive(Debug, Object)]
+pub struct SignSolanaRequest {
+    pub message: String, // base64-encoded legacy or v0 message
+    /// One per required signer slot we hold, in any order
+    pub passphrases: Vec<String>,
+}
+
+#[derive(Debug, Object)]
+pub struct SignSolanaResponse {
+    pub transaction: String, // base64 wire transaction, ready for sendTransaction
+    /// base58 fee-payer signature (the transaction id), if we hold the fee payer
+    pub signature: Option<String>,
+    pub signers: Vec<String>,         // base58 pubkeys signed for
+    pub missing_signers: Vec<String>, // required signers still zeroed
+    pub fully_signed: bool,
+    #[oai(skip_serializing_if_is_none)]
+    pub approval: Option<ApprovalEvidence>,
+}
+
+#[derive(Debug, Object)]
+pub struct SignBip322Request {
+    pub passphrase: String,
+    pub message: String, // UTF-8 message to sign
+    #[oai(default = "default_bip322_format")]
+    pub format: String, // "simple" (witness) or "full" (to_sign transaction)
+}
+
+fn default_bip322_format() -> String {
+    "simple".to_string()
+}
+
+#[derive(Debug, Object)]
+pub struct SignBip322Response {
+    pub address: String,   // P2TR address of the passphrase's Taproot key
+    pub signature: String, // base64 BIP-322 signature
+    pub format: String,
+    pub verified: bool, // signature checks out against the address
+    #[oai(skip_serializing_if_is_none)]
+    pub approval: Option<ApprovalEvidence>,
+}
+
+#[derive(Debug, Object)]
+pub struct VerifyBip322Request {
+    pub address: String,
+    pub message: String,
+    pub signature: String, // base64, simple or full
+}
+
+#[derive(Debug, Object)]
+pub struct VerifyBip322Response {
+    pub valid: bool,
+    #[oai(skip_serializing_if_is_none)]
+    pub format: Option<String>,
+    #[oai(skip_serializing_if_is_none)]
+    pub error: Option<String>, // why the signature couldn't be checked
+}
+
+#[derive(Debug, Object)]
+pub struct SignNostrRequest {
+    pub passphrase: String,
+    pub event: UnsignedEvent,
+}
+
+#[derive(Debug, Object)]
+pub struct SignNostrResponse {
+    pub event: SignedEvent, // ready to publish to relays
+    pub npub: String,
+    pub verified: bool, // BIP340 signature checks out against the event pubkey
+    #[oai(skip_serializing_if_is_none)]
+    pub approval: Option<ApprovalEvidence>,
+}
+
+#[derive(Debug, Object)]
+pub struct SignTransactionRequest {
+    /// Any registered chain (see GET /api/chains on the address aggregator)
+    pub chain: String,
+    /// Unsigned transaction in the chain's native encoding
+    pub transaction: String,
+    /// Passphrases whose keys sign, as the chain adapter expects them
+    pub passphrases: Vec<String>,
+}
+
+#[derive(Debug, Object)]
+pub struct SignTransactionResponse {
+    pub chain: String,
+    pub transaction: String, // signed, in the chain's native encoding
+    pub tx_id: Option<String>,
+    pub signers: Vec<String>,
+    pub missing_signers: Vec<String>,
+    pub verified: bool, // every signature checks out under the chain's rules
+    /// Detached signature for chains that submit it next to the transaction (sui, aptos)
+    #[oai(skip_serializing_if_is_none)]
+    pub signature: Option<String>,
+    #[oai(skip_serializing_if_is_none)]
+    pub approval: Option<ApprovalEvidence>,
+}
+
+/// Off-chain Ethereum message, hashed server-side before signing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "standard", rename_all = "lowercase")]
+pub enum EthereumMessage {
+    /// EIP-191 personal_sign, `message` is the raw message bytes (hex)
+    Eip191 { message: String },
+    /// EIP-712 eth_signTypedData_v4 document
+    Eip712 { typed_data: serde_json::Value },
+}
+
+impl EthereumMessage {
+    /// Signing hash plus the context signer nodes use to recompute it
+    pub fn hash(&self) -> anyhow::Result<([u8; 32], TransactionContext)> {
+        match self {
+            EthereumMessage::Eip191 { message } => {
+                let bytes = hex::decode(message.trim_start_matches("0x"))
+                    .map_err(|e| anyhow::anyhow!("Invalid message hex: {}", e))?;
+                Ok((
+                    super::eip712::eip191_hash(&bytes),
+                    TransactionContext {
+                        kind: "eip191".to_string(),
+                        payload: hex::encode(&bytes),
+                        input_index: None,
+                    },
+                ))
+            }
+            EthereumMessage::Eip712 { typed_data } => Ok((
+                super::eip712::hash_typed_data(typed_data)?,
+                TransactionContext {
+                    kind: "eip712".to_string(),
+                    payload: typed_data.to_string(),
+                    input_index: None,
+                },
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Object)]
+pub struct SignPersonalMessageRequest {
+    pub passphrase: String,
+    pub message: String,
+    /// "utf8" (default) or "hex"
+    #[oai(default = "default_message_encoding")]
+    pub encoding: String,
+}
+
+fn default_message_encoding() -> String {
+    "utf8".to_string()
+}
+
+impl SignPersonalMessageRequest {
+    pub fn to_message(&self) -> Result<EthereumMessage, String> {
+        let bytes = match self.encoding.as_str() {
+            "utf8" => self.message.as_bytes().to_vec(),
+            "hex" => hex::decode(self.message.trim_start_matches("0x"))
+                .map_err(|e| format!("Invalid message hex: {}", e))?,
+            other => {
+                return Err(format!(
+                    "Unknown encoding '{}', expected utf8 or hex",
+                    other
+                ))
+            }
+        };
+        Ok(EthereumMessage::Eip191 {
+            message: hex::encode(bytes),
+        })
+    }
+}
+
+#[derive(Debug, Object)]
+pub struct SignTypedDataRequest {
+    pub passphrase: String,
+    /// eth_signTypedData_v4 document: {types, primaryType, domain, message}
+    pub typed_data: serde_json::Value,
+}
+
+#[derive(Debug, Object)]
+pub struct SignEthereumMessageResponse {
+    pub signature: String, // 0x-hex [r || s || v], v = 27/28
+    pub hash: String,      // EIP-191 / EIP-712 signing hash
+    pub address: String,   // address of the passphrase's secp256k1 key
+    pub recovered_address: Option<String>,
+    pub verified: bool, // ecrecover(hash, signature) == address
+    #[oai(skip_serializing_if_is_none)]
+    pub approval: Option<ApprovalEvidence>,
+}
+
+/// Ethereum signature that recovers to `from`
+pub(crate) struct EthereumSignature {
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+    pub recovery_id: u8,
+    pub from: String,
 }
 
 #[derive(Debug, Object)]
@@ -52,7 +289,7 @@ pub struct HealthResponse {
 
 #[derive(Debug, Object)]
 pub struct ErrorResponse {
-    error: String,
+    pub error: String,
 }
 
 #[derive(ApiResponse)]
@@ -61,6 +298,8 @@ pub enum SignResult {
     Ok(Json<SignMessageResponse>),
     #[oai(status = 400)]
     BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
     #[oai(status = 500)]
     InternalError(Json<ErrorResponse>),
 }
@@ -71,10 +310,95 @@ pub enum SignPsbtResult {
     Ok(Json<SignPsbtResponse>),
     #[oai(status = 400)]
     BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+pub enum SignEthereumResult {
+    #[oai(status = 200)]
+    Ok(Json<SignEthereumResponse>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+pub enum SignTransactionResult {
+    #[oai(status = 200)]
+    Ok(Json<Box<SignTransactionResponse>>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+pub enum SignSolanaResult {
+    #[oai(status = 200)]
+    Ok(Json<SignSolanaResponse>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+pub enum SignBip322Result {
+    #[oai(status = 200)]
+    Ok(Json<SignBip322Response>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+pub enum VerifyBip322Result {
+    #[oai(status = 200)]
+    Ok(Json<VerifyBip322Response>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+pub enum SignNostrResult {
+    #[oai(status = 200)]
+    Ok(Json<Box<SignNostrResponse>>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
     #[oai(status = 500)]
     InternalError(Json<ErrorResponse>),
 }
 
+#[derive(ApiResponse)]
+pub enum SignEthereumMessageResult {
+    #[oai(status = 200)]
+    Ok(Json<SignEthereumMessageResponse>),
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+    #[oai(status = 403)]
+    Forbidden(Json<ErrorResponse>),
+    #[oai(status = 500)]
+    InternalError(Json<ErrorResponse>),
+}
+
+const APPROVAL_REQUIRED: &str =
+    "Operator approval is required: submit this request via POST /api/approvals";
+
 #[OpenApi]
 impl SigningAggregatorApi {
     /// Sign message with FROST threshold signatures
@@ -87,29 +411,311 @@ impl SigningAggregatorApi {
     /// 5. Call /api/frost/aggregate to get final signature
     #[oai(path = "/api/sign/message", method = "post")]
     async fn sign_message(&self, Json(req): Json<SignMessageRequest>) -> SignResult {
-        tracing::info!("Signing message with FROST (curve: {})", req.curve);
+        if self.config.approval.is_some() {
+            self.audit.record(
+                AuditEvent::new(AuditKind::PolicyDecision, "denied")
+                    .curve(req.curve.clone())
+                    .passphrase(&req.passphrase)
+                    .message(&message_bytes(&req.message))
+                    .detail("direct /api/sign/message while approval is required"),
+            );
+            return SignResult::Forbidden(Json(ErrorResponse {
+                error: APPROVAL_REQUIRED.to_string(),
+            }));
+        }
 
-        // Determine FROST endpoint based on curve
-        let curve_suffix = match req.curve.as_str() {
-            "ed25519" => "ed25519",
-            "secp256k1" => "secp256k1",       // ECDSA for Ethereum
-            "secp256k1-tr" => "secp256k1-tr", // Taproot for Bitcoin
-            _ => "secp256k1-tr",              // Default to Taproot for backward compat
+        self.execute_sign_message(req).await
+    }
+
+    /// Sign PSBT with FROST threshold signatures
+    ///
+    /// For each input in PSBT:
+    /// 1. Extract sighash
+    /// 2. Orchestrate FROST signing for that input's passphrase
+    /// 3. Add Schnorr signature to PSBT witness
+    #[oai(path = "/api/sign/psbt", method = "post")]
+    async fn sign_psbt(&self, Json(req): Json<SignPsbtRequest>) -> SignPsbtResult {
+        if self.config.approval.is_some() {
+            self.audit.record(
+                AuditEvent::new(AuditKind::PolicyDecision, "denied")
+                    .message(req.psbt.as_bytes())
+                    .detail("direct /api/sign/psbt while approval is required"),
+            );
+            return SignPsbtResult::Forbidden(Json(ErrorResponse {
+                error: APPROVAL_REQUIRED.to_string(),
+            }));
+        }
+
+        self.execute_sign_psbt(req).await
+    }
+
+    /// Sign an Ethereum transaction (legacy, EIP-2930 or EIP-1559)
+    ///
+    /// 1. RLP-encode the unsigned transaction with the configured chain id
+    /// 2. FROST-sign keccak256 of it with the passphrase's secp256k1 key
+    ///    (signer nodes get the full transaction for policy checks)
+    /// 3. Return the signed raw transaction and its hash
+    #[oai(path = "/api/sign/ethereum", method = "post")]
+    async fn sign_ethereum(&self, Json(req): Json<SignEthereumRequest>) -> SignEthereumResult {
+        if self.config.approval.is_some() {
+            self.audit.record(
+                AuditEvent::new(AuditKind::PolicyDecision, "denied")
+                    .curve("secp256k1")
+                    .passphrase(&req.passphrase)
+                    .detail("direct /api/sign/ethereum while approval is required"),
+            );
+            return SignEthereumResult::Forbidden(Json(ErrorResponse {
+                error: APPROVAL_REQUIRED.to_string(),
+            }));
+        }
+
+        self.execute_sign_ethereum(req).await
+    }
+
+    /// Sign an EIP-191 personal message (personal_sign)
+    #[oai(path = "/api/sign/ethereum/personal", method = "post")]
+    async fn sign_personal_message(
+        &self,
+        Json(req): Json<SignPersonalMessageRequest>,
+    ) -> SignEthereumMessageResult {
+        let message = match req.to_message() {
+            Ok(message) => message,
+            Err(error) => {
+                return SignEthereumMessageResult::BadRequest(Json(ErrorResponse { error }))
+            }
+        };
+        self.sign_ethereum_message(&req.passphrase, message).await
+    }
+
+    /// Sign EIP-712 typed data (eth_signTypedData_v4)
+    #[oai(path = "/api/sign/ethereum/typed-data", method = "post")]
+    async fn sign_typed_data(
+        &self,
+        Json(req): Json<SignTypedDataRequest>,
+    ) -> SignEthereumMessageResult {
+        let message = EthereumMessage::Eip712 {
+            typed_data: req.typed_data,
+        };
+        self.sign_ethereum_message(&req.passphrase, message).await
+    }
+
+    /// Sign a Solana transaction message (legacy or v0)
+    ///
+    /// 1. Parse the message and match each passphrase's Ed25519 key to a
+    ///    required signer slot
+    /// 2. FROST-sign the message once per passphrase (signer nodes get the
+    ///    message for program-id policy checks)
+    /// 3. Return the wire transaction; slots we don't hold stay zeroed
+    #[oai(path = "/api/sign/solana", method = "post")]
+    async fn sign_solana(&self, Json(req): Json<SignSolanaRequest>) -> SignSolanaResult {
+        if self.config.approval.is_some() {
+            self.audit.record(
+                AuditEvent::new(AuditKind::PolicyDecision, "denied")
+                    .curve("ed25519")
+                    .message(req.message.as_bytes())
+                    .detail("direct /api/sign/solana while approval is required"),
+            );
+            return SignSolanaResult::Forbidden(Json(ErrorResponse {
+                error: APPROVAL_REQUIRED.to_string(),
+            }));
+        }
+
+        self.execute_sign_solana(req).await
+    }
+
+    /// Sign a message with BIP-322 for the passphrase's Taproot address
+    ///
+    /// Builds the virtual `to_spend` / `to_sign` transactions, FROST-signs the
+    /// key-path sighash and returns the simple (witness) or full encoding.
+    #[oai(path = "/api/sign/bip322", method = "post")]
+    async fn sign_bip322(&self, Json(req): Json<SignBip322Request>) -> SignBip322Result {
+        if self.config.approval.is_some() {
+            self.audit.record(
+                AuditEvent::new(AuditKind::PolicyDecision, "denied")
+                    .curve("secp256k1-tr")
+                    .passphrase(&req.passphrase)
+                    .message(req.message.as_bytes())
+                    .detail("direct /api/sign/bip322 while approval is required"),
+            );
+            return SignBip322Result::Forbidden(Json(ErrorResponse {
+                error: APPROVAL_REQUIRED.to_string(),
+            }));
+        }
+
+        self.execute_sign_bip322(req).await
+    }
+
+    /// Verify a BIP-322 signature (simple or full) against a P2TR or P2WPKH address
+    #[oai(path = "/api/verify/bip322", method = "post")]
+    async fn verify_bip322(&self, Json(req): Json<VerifyBip322Request>) -> VerifyBip322Result {
+        use anyhow::Context;
+        use base64::prelude::*;
+
+        let address = match req
+            .address
+            .parse::<bitcoin::Address<bitcoin::address::NetworkUnchecked>>()
+            .map_err(anyhow::Error::from)
+            .and_then(|a| Ok(a.require_network(self.bitcoin_network())?))
+        {
+            Ok(address) => address,
+            Err(e) => {
+                return VerifyBip322Result::BadRequest(Json(ErrorResponse {
+                    error: format!("Invalid address: {}", e),
+                }))
+            }
         };
 
+        let result = BASE64_STANDARD
+            .decode(&req.signature)
+            .context("Invalid base64 signature")
+            .and_then(|signature| {
+                bip322::verify(&address.script_pubkey(), req.message.as_bytes(), &signature)
+            });
+
+        VerifyBip322Result::Ok(Json(match result {
+            Ok((format, valid)) => VerifyBip322Response {
+                valid,
+                format: Some(format.as_str().to_string()),
+                error: None,
+            },
+            Err(e) => VerifyBip322Response {
+                valid: false,
+                format: None,
+                error: Some(format!("{:#}", e)),
+            },
+        }))
+    }
+
+    /// Sign a Nostr event (NIP-01) with the passphrase's Taproot key
+    ///
+    /// Fills in `pubkey` and `created_at` when omitted, computes the event id
+    /// and returns the event with its BIP340 `sig`.
+    #[oai(path = "/api/sign/nostr", method = "post")]
+    async fn sign_nostr(&self, Json(req): Json<SignNostrRequest>) -> SignNostrResult {
+        if self.config.approval.is_some() {
+            self.audit.record(
+                AuditEvent::new(AuditKind::PolicyDecision, "denied")
+                    .curve("secp256k1-tr")
+                    .message(req.event.content.as_bytes())
+                    .detail("direct /api/sign/nostr while approval is required"),
+            );
+            return SignNostrResult::Forbidden(Json(ErrorResponse {
+                error: APPROVAL_REQUIRED.to_string(),
+            }));
+        }
+
+        self.execute_sign_nostr(req).await
+    }
+
+    /// Sign a transaction for any registered chain
+    ///
+    /// The chain's adapter decodes `transaction`, says which bytes each
+    /// passphrase signs, and applies the FROST signatures afterwards.
+    #[oai(path = "/api/sign/transaction", method = "post")]
+    async fn sign_transaction(
+        &self,
+        Json(req): Json<SignTransactionRequest>,
+    ) -> SignTransactionResult {
+        if self.config.approval.is_some() {
+            self.audit.record(
+                AuditEvent::new(AuditKind::PolicyDecision, "denied")
+                    .message(req.transaction.as_bytes())
+                    .detail(format!(
+                        "direct /api/sign/transaction ({}) while approval is required",
+                        req.chain
+                    )),
+            );
+            return SignTransactionResult::Forbidden(Json(ErrorResponse {
+                error: APPROVAL_REQUIRED.to_string(),
+            }));
+        }
+
+        self.execute_sign_transaction(req).await
+    }
+
+    /// Health check
+    #[oai(path = "/health", method = "get")]
+    async fn health(&self) -> Json<HealthResponse> {
+        Json(HealthResponse {
+            status: "ok".to_string(),
+            signer_nodes: self.config.signer_nodes.len(),
+            threshold: self.config.threshold,
+        })
+    }
+}
+
+impl SigningAggregatorApi {
+    async fn sign_ethereum_message(
+        &self,
+        passphrase: &str,
+        message: EthereumMessage,
+    ) -> SignEthereumMessageResult {
+        if self.config.approval.is_some() {
+            self.audit.record(
+                AuditEvent::new(AuditKind::PolicyDecision, "denied")
+                    .curve("secp256k1")
+                    .passphrase(passphrase)
+                    .detail("direct Ethereum message signing while approval is required"),
+            );
+            return SignEthereumMessageResult::Forbidden(Json(ErrorResponse {
+                error: APPROVAL_REQUIRED.to_string(),
+            }));
+        }
+
+        self.execute_sign_ethereum_message(passphrase, message)
+            .await
+    }
+
+    /// Append the outcome of one FROST signing round to the audit log
+    fn record_signing_round(
+        &self,
+        passphrase: &str,
+        message_hex: &str,
+        curve: &str,
+        result: &anyhow::Result<(String, usize)>,
+    ) {
+        let signers = self.config.signer_nodes.iter().take(self.config.threshold);
+        let event = AuditEvent::new(
+            AuditKind::SigningRound,
+            if result.is_ok() { "success" } else { "failure" },
+        )
+        .curve(curve)
+        .passphrase(passphrase)
+        .message(&message_bytes(message_hex))
+        .participants(signers);
+
+        self.audit.record(match result {
+            Ok((signature, _)) => event.detail(format!("signature={}", signature)),
+            Err(e) => event.detail(e.to_string()),
+        });
+    }
+
+    /// Run FROST message signing (no approval gate - callers enforce policy)
+    pub(crate) async fn execute_sign_message(&self, req: SignMessageRequest) -> SignResult {
+        tracing::info!("Signing message with FROST (curve: {})", req.curve);
+
+        // Unknown curves fall back to Taproot for backward compat
+        let curve_suffix = CurveType::from_name(&req.curve)
+            .unwrap_or(CurveType::Secp256k1Taproot)
+            .as_str();
+
         // Orchestrate FROST signing with curve-specific endpoints
-        match sign_message_for_curve(
+        let result = sign_message_for_curve(
             &req.passphrase,
             &req.message,
             &self.config.signer_nodes,
             self.config.threshold,
             curve_suffix,
+            req.transaction.as_ref(),
         )
-        .await
-        {
+        .await;
+        self.record_signing_round(&req.passphrase, &req.message, curve_suffix, &result);
+
+        match result {
             Ok((signature, _)) => SignResult::Ok(Json(SignMessageResponse {
                 signature,
                 verified: true,
+                approval: None,
             })),
             Err(e) => SignResult::InternalError(Json(ErrorResponse {
                 error: format!("FROST signing failed: {}", e),
@@ -117,199 +723,494 @@ impl SigningAggregatorApi {
         }
     }
 
-    /// Sign PSBT with FROST threshold signatures
+    /// EIP-155 chain id of the configured network (mainnet without [network])
+    pub(crate) fn ethereum_chain_id(&self) -> u64 {
+        self.network
+            .as_ref()
+            .as_ref()
+            .map(|n| n.ethereum_chain_id())
+            .unwrap_or(1)
+    }
+
+    /// Bitcoin network of the configured network (mainnet without [network])
+    pub(crate) fn bitcoin_network(&self) -> bitcoin::Network {
+        self.network
+            .as_ref()
+            .as_ref()
+            .map(|n| n.bitcoin_network())
+            .unwrap_or(bitcoin::Network::Bitcoin)
+    }
+
+    /// FROST-sign a 32-byte Ethereum hash with the passphrase's secp256k1 key
     ///
-    /// For each input in PSBT:
-    /// 1. Extract sighash
-    /// 2. Orchestrate FROST signing for that input's passphrase
-    /// 3. Add Schnorr signature to PSBT witness
-    #[oai(path = "/api/sign/psbt", method = "post")]
-    async fn sign_psbt(&self, Json(req): Json<SignPsbtRequest>) -> SignPsbtResult {
-        tracing::info!("Signing PSBT with FROST");
+    /// `context` is what the hash was computed from, so signer nodes can redo it.
+    async fn sign_ethereum_hash(
+        &self,
+        passphrase: &str,
+        hash: &[u8; 32],
+        context: TransactionContext,
+    ) -> anyhow::Result<EthereumSignature> {
+        let pubkey_hex = fetch_public_key(&self.config.signer_nodes[0], "secp256k1", passphrase)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch signer address: {}", e))?;
+        let from = crate::chains::ethereum::address_of_public_key(&hex::decode(pubkey_hex)?)?;
 
-        // Parse PSBT
-        let mut psbt: bitcoin::psbt::Psbt = match req.psbt.parse() {
-            Ok(p) => p,
+        let hash_hex = hex::encode(hash);
+        let result = sign_message_for_curve(
+            passphrase,
+            &hash_hex,
+            &self.config.signer_nodes,
+            self.config.threshold,
+            "secp256k1",
+            Some(&context),
+        )
+        .await;
+        self.record_signing_round(passphrase, &hash_hex, "secp256k1", &result);
+
+        let (signature_hex, _) =
+            result.map_err(|e| anyhow::anyhow!("FROST signing failed: {}", e))?;
+
+        // Node returns [r (32), s (32), recovery_id (1)]
+        let bytes = hex::decode(&signature_hex)?;
+        if bytes.len() < 64 {
+            anyhow::bail!("Unexpected signature format: {}", signature_hex);
+        }
+        let r: [u8; 32] = bytes[..32].try_into()?;
+        let s: [u8; 32] = bytes[32..64].try_into()?;
+
+        let (recovery_id, recovered_address) =
+            crate::chains::ethereum::find_recovery_id(hash, &r, &s, &from);
+        let Some(recovery_id) = recovery_id else {
+            anyhow::bail!(
+                "Signature does not recover to {} (got {:?})",
+                from,
+                recovered_address
+            );
+        };
+
+        Ok(EthereumSignature {
+            r,
+            s,
+            recovery_id,
+            from,
+        })
+    }
+
+    /// Run FROST Ethereum transaction signing (no approval gate - callers enforce policy)
+    pub(crate) async fn execute_sign_ethereum(
+        &self,
+        req: SignEthereumRequest,
+    ) -> SignEthereumResult {
+        let chain_id = self.ethereum_chain_id();
+        tracing::info!("Signing Ethereum transaction (chain id {})", chain_id);
+
+        let tx = match req.transaction.prepare(chain_id) {
+            Ok(tx) => tx,
             Err(e) => {
-                return SignPsbtResult::BadRequest(Json(ErrorResponse {
-                    error: format!("Invalid PSBT: {}", e),
+                return SignEthereumResult::BadRequest(Json(ErrorResponse {
+                    error: format!("Invalid transaction: {}", e),
                 }))
             }
         };
 
-        if psbt.inputs.len() != req.passphrases.len() {
-            return SignPsbtResult::BadRequest(Json(ErrorResponse {
-                error: format!(
-                    "Passphrase count mismatch: {} inputs, {} passphrases",
-                    psbt.inputs.len(),
-                    req.passphrases.len()
-                ),
-            }));
-        }
+        let signing_hash = tx.signing_hash();
 
-        // Sign each input
-        let mut signatures_added = 0;
+        match self
+            .execute_sign_transaction(SignTransactionRequest {
+                chain: "ethereum".to_string(),
+                transaction: format!("0x{}", hex::encode(tx.unsigned_payload())),
+                passphrases: vec![req.passphrase],
+            })
+            .await
+        {
+            SignTransactionResult::Ok(Json(signed)) => {
+                let from = signed.signers.into_iter().next().unwrap_or_default();
+                SignEthereumResult::Ok(Json(SignEthereumResponse {
+                    raw_transaction: signed.transaction,
+                    tx_hash: signed.tx_id.unwrap_or_default(),
+                    signing_hash: format!("0x{}", hex::encode(signing_hash)),
+                    chain_id,
+                    recovered_address: signed.verified.then(|| from.clone()),
+                    sender_verified: signed.verified,
+                    from,
+                    approval: None,
+                }))
+            }
+            SignTransactionResult::BadRequest(e) => SignEthereumResult::BadRequest(e),
+            SignTransactionResult::Forbidden(e) => SignEthereumResult::Forbidden(e),
+            SignTransactionResult::InternalError(e) => SignEthereumResult::InternalError(e),
+        }
+    }
 
-        for (input_idx, passphrase) in req.passphrases.iter().enumerate() {
-            tracing::debug!("Signing input {} with passphrase", input_idx);
+    /// Run EIP-191 / EIP-712 signing (no approval gate - callers enforce policy)
+    pub(crate) async fn execute_sign_ethereum_message(
+        &self,
+        passphrase: &str,
+        message: EthereumMessage,
+    ) -> SignEthereumMessageResult {
+        let (hash, context) = match message.hash() {
+            Ok(hashed) => hashed,
+            Err(e) => {
+                return SignEthereumMessageResult::BadRequest(Json(ErrorResponse {
+                    error: e.to_string(),
+                }))
+            }
+        };
+        tracing::info!("Signing Ethereum {} message", context.kind);
 
-            // Calculate Taproot sighash
-            use bitcoin::hashes::Hash;
-            use bitcoin::sighash::{Prevouts, SighashCache};
-            use bitcoin::TapSighashType;
+        let signature = match self.sign_ethereum_hash(passphrase, &hash, context).await {
+            Ok(signature) => signature,
+            Err(e) => {
+                return SignEthereumMessageResult::InternalError(Json(ErrorResponse {
+                    error: e.to_string(),
+                }))
+            }
+        };
 
-            let prevouts: Vec<bitcoin::TxOut> = psbt
-                .inputs
-                .iter()
-                .filter_map(|input| input.witness_utxo.clone())
-                .collect();
+        // 65-byte [r || s || v] with v = 27 + recovery id, as personal_sign returns it
+        let mut bytes = signature.r.to_vec();
+        bytes.extend_from_slice(&signature.s);
+        bytes.push(27 + signature.recovery_id);
+
+        SignEthereumMessageResult::Ok(Json(SignEthereumMessageResponse {
+            signature: format!("0x{}", hex::encode(&bytes)),
+            hash: format!("0x{}", hex::encode(hash)),
+            verified: true,
+            recovered_address: Some(signature.from.clone()),
+            address: signature.from,
+            approval: None,
+        }))
+    }
 
-            if prevouts.len() != psbt.inputs.len() {
-                return SignPsbtResult::BadRequest(Json(ErrorResponse {
-                    error: "Missing witness_utxo in PSBT inputs".to_string(),
-                }));
+    /// Run Solana transaction signing (no approval gate - callers enforce policy)
+    pub(crate) async fn execute_sign_solana(&self, req: SignSolanaRequest) -> SignSolanaResult {
+        match self
+            .execute_sign_transaction(SignTransactionRequest {
+                chain: "solana".to_string(),
+                transaction: req.message,
+                passphrases: req.passphrases,
+            })
+            .await
+        {
+            SignTransactionResult::Ok(Json(signed)) => {
+                SignSolanaResult::Ok(Json(SignSolanaResponse {
+                    transaction: signed.transaction,
+                    signature: signed.tx_id,
+                    fully_signed: signed.missing_signers.is_empty(),
+                    signers: signed.signers,
+                    missing_signers: signed.missing_signers,
+                    approval: None,
+                }))
             }
+            SignTransactionResult::BadRequest(e) => SignSolanaResult::BadRequest(e),
+            SignTransactionResult::Forbidden(e) => SignSolanaResult::Forbidden(e),
+            SignTransactionResult::InternalError(e) => SignSolanaResult::InternalError(e),
+        }
+    }
 
-            let prevouts = Prevouts::All(&prevouts);
-            let mut cache = SighashCache::new(&psbt.unsigned_tx);
-
-            let sighash = match cache.taproot_key_spend_signature_hash(
-                input_idx,
-                &prevouts,
-                TapSighashType::Default,
-            ) {
-                Ok(hash) => hash,
-                Err(e) => {
-                    return SignPsbtResult::InternalError(Json(ErrorResponse {
-                        error: format!("Sighash calculation failed: {}", e),
-                    }))
-                }
-            };
+    /// Run BIP-322 message signing (no approval gate - callers enforce policy)
+    pub(crate) async fn execute_sign_bip322(&self, req: SignBip322Request) -> SignBip322Result {
+        use base64::prelude::*;
 
-            let sighash_hex = hex::encode(sighash.as_byte_array());
+        let Some(format) = bip322::Format::from_name(&req.format) else {
+            return SignBip322Result::BadRequest(Json(ErrorResponse {
+                error: format!(
+                    "Unknown BIP-322 format '{}', expected simple or full",
+                    req.format
+                ),
+            }));
+        };
+        let internal_error =
+            |error: String| SignBip322Result::InternalError(Json(ErrorResponse { error }));
 
-            tracing::debug!("Input {}: sighash={}", input_idx, &sighash_hex[..16]);
+        let public_key = match fetch_public_key(
+            &self.config.signer_nodes[0],
+            "secp256k1-tr",
+            &req.passphrase,
+        )
+        .await
+        .and_then(|pubkey_hex| Ok(hex::decode(pubkey_hex)?))
+        {
+            Ok(public_key) => public_key,
+            Err(e) => return internal_error(format!("Failed to fetch signer pubkey: {}", e)),
+        };
 
-            // Sign with FROST (Bitcoin uses Taproot/Schnorr)
-            let (signature_hex, _) = match sign_message_for_curve(
-                passphrase,
-                &sighash_hex,
-                &self.config.signer_nodes,
-                self.config.threshold,
-                "secp256k1-tr", // Bitcoin Taproot
-            )
-            .await
-            {
-                Ok(result) => result,
-                Err(e) => {
-                    tracing::error!("FROST signing failed for input {}: {}", input_idx, e);
-                    continue; // Skip this input
-                }
+        let (address, script_pubkey) =
+            match crate::chains::bitcoin::taproot_address(&public_key, self.bitcoin_network())
+                .and_then(|address| {
+                    let script_pubkey = address
+                        .parse::<bitcoin::Address<bitcoin::address::NetworkUnchecked>>()?
+                        .assume_checked()
+                        .script_pubkey();
+                    Ok((address, script_pubkey))
+                }) {
+                Ok(derived) => derived,
+                Err(e) => return internal_error(format!("Address derivation failed: {}", e)),
             };
 
-            // Parse and add signature
-            let sig_bytes = match hex::decode(&signature_hex) {
-                Ok(bytes) => bytes,
-                Err(e) => {
-                    tracing::error!("Invalid signature hex: {}", e);
-                    continue;
-                }
-            };
+        // Nodes recompute the sighash from the address script and message
+        let context = bip322::MessageContext {
+            script_pubkey: script_pubkey.to_hex_string(),
+            message: req.message.clone(),
+        };
+        let sighash = match context.sighash() {
+            Ok(sighash) => sighash,
+            Err(e) => return internal_error(format!("{:#}", e)),
+        };
+        let context = TransactionContext {
+            kind: "bip322".to_string(),
+            payload: serde_json::to_string(&context).unwrap_or_default(),
+            input_index: None,
+        };
 
-            let schnorr_sig = match bitcoin::secp256k1::schnorr::Signature::from_slice(&sig_bytes) {
-                Ok(sig) => sig,
-                Err(e) => {
-                    tracing::error!("Invalid Schnorr signature: {}", e);
-                    continue;
-                }
-            };
+        let sighash_hex = hex::encode(sighash);
+        let result = sign_message_for_curve(
+            &req.passphrase,
+            &sighash_hex,
+            &self.config.signer_nodes,
+            self.config.threshold,
+            "secp256k1-tr",
+            Some(&context),
+        )
+        .await;
+        self.record_signing_round(&req.passphrase, &sighash_hex, "secp256k1-tr", &result);
+
+        let signature = match result.and_then(|(signature_hex, _)| Ok(hex::decode(signature_hex)?))
+        {
+            Ok(signature) => signature,
+            Err(e) => return internal_error(format!("FROST signing failed: {}", e)),
+        };
+
+        let encoded = bip322::encode(&script_pubkey, req.message.as_bytes(), &signature, format);
+        let verified = matches!(
+            bip322::verify(&script_pubkey, req.message.as_bytes(), &encoded),
+            Ok((_, true))
+        );
+        if !verified {
+            tracing::error!("❌ BIP-322 signature does not verify for {}", address);
+        }
 
-            // Verify signature locally before adding to PSBT
-            if let Some(witness_utxo) = &psbt.inputs[input_idx].witness_utxo {
-                if witness_utxo.script_pubkey.is_p2tr() {
-                    let script_bytes = witness_utxo.script_pubkey.as_bytes();
-                    if script_bytes.len() == 34 {
-                        let xonly_bytes = &script_bytes[2..34];
-                        if let Ok(xonly_pubkey) =
-                            bitcoin::key::XOnlyPublicKey::from_slice(xonly_bytes)
-                        {
-                            let secp = bitcoin::secp256k1::Secp256k1::verification_only();
-                            let msg =
-                                bitcoin::secp256k1::Message::from_digest(*sighash.as_byte_array());
-
-                            match secp.verify_schnorr(&schnorr_sig, &msg, &xonly_pubkey) {
-                                Ok(_) => tracing::info!(
-                                    "✅ Input {} signature VERIFIED locally",
-                                    input_idx
-                                ),
-                                Err(e) => tracing::error!(
-                                    "❌ Input {} signature VERIFICATION FAILED: {:?}",
-                                    input_idx,
-                                    e
-                                ),
-                            }
-                        }
-                    }
+        SignBip322Result::Ok(Json(SignBip322Response {
+            address,
+            signature: BASE64_STANDARD.encode(encoded),
+            format: format.as_str().to_string(),
+            verified,
+            approval: None,
+        }))
+    }
+
+    /// Run Nostr event signing (no approval gate - callers enforce policy)
+    pub(crate) async fn execute_sign_nostr(&self, req: SignNostrRequest) -> SignNostrResult {
+        let event = match serde_json::to_string(&req.event.stamped()) {
+            Ok(event) => event,
+            Err(e) => {
+                return SignNostrResult::BadRequest(Json(ErrorResponse {
+                    error: e.to_string(),
+                }))
+            }
+        };
+
+        match self
+            .execute_sign_transaction(SignTransactionRequest {
+                chain: "nostr".to_string(),
+                transaction: event,
+                passphrases: vec![req.passphrase],
+            })
+            .await
+        {
+            SignTransactionResult::Ok(Json(signed)) => {
+                match serde_json::from_str::<SignedEvent>(&signed.transaction) {
+                    Ok(event) => SignNostrResult::Ok(Json(Box::new(SignNostrResponse {
+                        event,
+                        npub: signed.signers.into_iter().next().unwrap_or_default(),
+                        verified: signed.verified,
+                        approval: None,
+                    }))),
+                    Err(e) => SignNostrResult::InternalError(Json(ErrorResponse {
+                        error: format!("Invalid signed event: {}", e),
+                    })),
                 }
             }
+            SignTransactionResult::BadRequest(e) => SignNostrResult::BadRequest(e),
+            SignTransactionResult::Forbidden(e) => SignNostrResult::Forbidden(e),
+            SignTransactionResult::InternalError(e) => SignNostrResult::InternalError(e),
+        }
+    }
 
-            let signature = bitcoin::taproot::Signature {
-                signature: schnorr_sig,
-                sighash_type: TapSighashType::Default,
-            };
+    /// Run FROST PSBT signing (no approval gate - callers enforce policy)
+    pub(crate) async fn execute_sign_psbt(&self, req: SignPsbtRequest) -> SignPsbtResult {
+        tracing::info!("Signing {} PSBT with FROST", req.chain);
 
-            psbt.inputs[input_idx].tap_key_sig = Some(signature);
-            signatures_added += 1;
+        // Both chains share the Taproot key, so any PSBT adapter works here
+        let chain = match self.chains.get(&req.chain) {
+            Ok(adapter) if matches!(adapter.name(), "bitcoin" | "litecoin") => adapter.name(),
+            Ok(adapter) => {
+                return SignPsbtResult::BadRequest(Json(ErrorResponse {
+                    error: format!("Chain {} does not use PSBTs", adapter.name()),
+                }))
+            }
+            Err(e) => {
+                return SignPsbtResult::BadRequest(Json(ErrorResponse {
+                    error: e.to_string(),
+                }))
+            }
+        };
 
-            tracing::info!("✅ Input {} signed and added to PSBT", input_idx);
+        match self
+            .execute_sign_transaction(SignTransactionRequest {
+                chain: chain.to_string(),
+                transaction: req.psbt,
+                passphrases: req.passphrases,
+            })
+            .await
+        {
+            SignTransactionResult::Ok(Json(signed)) => {
+                tracing::info!("✅ PSBT finalized, ready for broadcast");
+                SignPsbtResult::Ok(Json(SignPsbtResponse {
+                    signed_psbt: signed.transaction,
+                    signatures_added: signed.signers.len(),
+                    approval: None,
+                }))
+            }
+            SignTransactionResult::BadRequest(e) => SignPsbtResult::BadRequest(e),
+            SignTransactionResult::Forbidden(e) => SignPsbtResult::Forbidden(e),
+            SignTransactionResult::InternalError(e) => SignPsbtResult::InternalError(e),
         }
+    }
 
-        tracing::info!(
-            "PSBT signing complete: {}/{} inputs signed",
-            signatures_added,
-            psbt.inputs.len()
-        );
+    /// Run FROST signing for any registered chain (no approval gate - callers enforce policy)
+    ///
+    /// The chain adapter decides what gets signed and how the signatures are
+    /// applied; this only fetches keys and runs the FROST rounds.
+    /// Group key of each passphrase on `adapter`'s curve, in order (what `prepare` takes)
+    pub(crate) async fn chain_public_keys(
+        &self,
+        adapter: &dyn ChainAdapter,
+        passphrases: &[String],
+    ) -> anyhow::Result<Vec<Vec<u8>>> {
+        let mut public_keys = Vec::with_capacity(passphrases.len());
+        for passphrase in passphrases {
+            let pubkey_hex = fetch_public_key(
+                &self.config.signer_nodes[0],
+                adapter.curve().as_str(),
+                passphrase,
+            )
+            .await?;
+            public_keys.push(hex::decode(pubkey_hex)?);
+        }
+        Ok(public_keys)
+    }
 
-        // Finalize PSBT - convert tap_key_sig to final_script_witness
-        // Note: We don't use miniscript::finalize_mut() because it requires valid
-        // public keys in witness_utxo, which clients may not have during PSBT building
-        tracing::info!("Finalizing PSBT...");
+    pub(crate) async fn execute_sign_transaction(
+        &self,
+        req: SignTransactionRequest,
+    ) -> SignTransactionResult {
+        let bad_request =
+            |error: String| SignTransactionResult::BadRequest(Json(ErrorResponse { error }));
+        let internal_error =
+            |error: String| SignTransactionResult::InternalError(Json(ErrorResponse { error }));
+
+        let adapter = match self.chains.get(&req.chain) {
+            Ok(adapter) => adapter,
+            Err(e) => return bad_request(e.to_string()),
+        };
+        let curve = adapter.curve().as_str();
+        tracing::info!("Signing {} transaction (curve: {})", adapter.name(), curve);
 
-        for input in &mut psbt.inputs {
-            if let Some(tap_sig) = input.tap_key_sig {
-                // Build witness for Taproot key-path spend
-                // For default sighash (0x00), witness is just the 64-byte signature
-                let mut witness = bitcoin::Witness::new();
+        let public_keys = match self
+            .chain_public_keys(adapter.as_ref(), &req.passphrases)
+            .await
+        {
+            Ok(public_keys) => public_keys,
+            Err(e) => return internal_error(format!("Failed to fetch signer pubkey: {}", e)),
+        };
 
-                // Serialize the Schnorr signature (64 bytes)
-                // tap_sig.to_vec() includes sighash type, but for SIGHASH_DEFAULT,
-                // we only push the signature itself
-                witness.push(tap_sig.signature.serialize());
+        let inputs = match adapter.prepare(&req.transaction, &public_keys) {
+            Ok(inputs) => inputs,
+            Err(e) => return bad_request(format!("{:#}", e)),
+        };
 
-                input.final_script_witness = Some(witness);
+        let mut signatures = Vec::with_capacity(inputs.len());
+        for (index, input) in inputs.iter().enumerate() {
+            let passphrase = &req.passphrases[input.signer];
+            let message_hex = hex::encode(&input.message);
+            tracing::debug!("Input {}: message={}", index, message_hex);
 
-                // Clear signing fields after finalization
-                input.tap_key_sig = None;
+            let result = sign_message_for_curve(
+                passphrase,
+                &message_hex,
+                &self.config.signer_nodes,
+                self.config.threshold,
+                curve,
+                input.context.as_ref(),
+            )
+            .await;
+            self.record_signing_round(passphrase, &message_hex, curve, &result);
+
+            match result.and_then(|(signature_hex, _)| Ok(hex::decode(signature_hex)?)) {
+                Ok(signature) => signatures.push(Some(signature)),
+                Err(e) if input.optional => {
+                    tracing::error!("FROST signing failed for input {}: {}", index, e);
+                    signatures.push(None);
+                }
+                Err(e) => return internal_error(format!("FROST signing failed: {}", e)),
             }
         }
 
-        tracing::info!("✅ PSBT finalized, ready for broadcast");
+        let signed = match adapter.assemble(&req.transaction, &public_keys, &inputs, &signatures) {
+            Ok(signed) => signed,
+            Err(e) => return internal_error(format!("{:#}", e)),
+        };
 
-        SignPsbtResult::Ok(Json(SignPsbtResponse {
-            signed_psbt: psbt.to_string(),
-            signatures_added,
-        }))
+        tracing::info!(
+            "✅ {} transaction signed: {} signers, {} missing",
+            adapter.name(),
+            signed.signers.len(),
+            signed.missing_signers.len()
+        );
+
+        SignTransactionResult::Ok(Json(Box::new(SignTransactionResponse {
+            chain: adapter.name().to_string(),
+            transaction: signed.transaction,
+            tx_id: signed.tx_id,
+            signers: signed.signers,
+            missing_signers: signed.missing_signers,
+            verified: signed.verified,
+            signature: signed.signature,
+            approval: None,
+        })))
     }
+}
 
-    /// Health check
-    #[oai(path = "/health", method = "get")]
-    async fn health(&self) -> Json<HealthResponse> {
-        Json(HealthResponse {
-            status: "ok".to_string(),
-            signer_nodes: self.config.signer_nodes.len(),
-            threshold: self.config.threshold,
-        })
+/// Group public key (hex) of a passphrase on a curve (from the first signer node)
+async fn fetch_public_key(
+    signer_url: &str,
+    curve: &str,
+    passphrase: &str,
+) -> anyhow::Result<String> {
+    let resp = reqwest::Client::new()
+        .get(format!("{}/api/curve/{}/pubkey", signer_url, curve))
+        .query(&[("passphrase", passphrase)])
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        let error = resp.text().await.unwrap_or_else(|_| "Unknown".to_string());
+        anyhow::bail!("pubkey query failed: {}", error);
     }
+
+    let json: serde_json::Value = resp.json().await?;
+    json["public_key"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("pubkey response missing public_key"))
+}
+
+/// Message bytes as the signer nodes see them (hex-decoded, raw on bad hex)
+///
+/// Keeps aggregator and node audit entries for one round on the same message hash.
+fn message_bytes(message_hex: &str) -> Vec<u8> {
+    hex::decode(message_hex).unwrap_or_else(|_| message_hex.as_bytes().to_vec())
 }
 
 /// Sign message with FROST for any curve (secp256k1 or Ed25519)
@@ -319,6 +1220,7 @@ async fn sign_message_for_curve(
     signer_urls: &[String],
     threshold: usize,
     curve_suffix: &str,
+    transaction: Option<&TransactionContext>,
 ) -> anyhow::Result<(String, usize)> {
     use serde::{Deserialize, Serialize};
 
@@ -341,6 +1243,8 @@ async fn sign_message_for_curve(
         message: String,
         encrypted_nonces: String,
         all_commitments: Vec<CommitmentEntry>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        transaction: Option<TransactionContext>,
     }
 
     #[derive(Serialize, Clone)]
@@ -442,6 +1346,7 @@ async fn sign_message_for_curve(
                 message: message.to_string(),
                 encrypted_nonces: r1.encrypted_nonces.clone(),
                 all_commitments: all_commitments.clone(),
+                transaction: transaction.cloned(),
             })
             .send()
             .await?;
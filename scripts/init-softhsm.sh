@@ -19,6 +19,7 @@ PIN="${2:-123456}"
 SO_PIN="${3:-12345678}"
 TOKEN_LABEL="frost-${NODE_NAME}"
 KEY_LABEL="frost-master-key-${NODE_NAME}"
+KDF_KEY_LABEL="frost-kdf-key-${NODE_NAME}"
 
 # Detect OS and set library path
 if [[ "$OSTYPE" == "linux-gnu"* ]]; then
@@ -100,6 +101,17 @@ pkcs11-tool --module "$PKCS11_LIB" \
     --label "$KEY_LABEL" \
     --id 01
 
+# HMAC key for derivation = "hmac-sha256" / "sp800-108" (new keys)
+pkcs11-tool --module "$PKCS11_LIB" \
+    --slot "$SLOT" \
+    --login --pin "$PIN" \
+    --keygen \
+    --key-type GENERIC:32 \
+    --usage-sign \
+    --usage-derive \
+    --label "$KDF_KEY_LABEL" \
+    --id 02
+
 # Step 5: Verify key generation
 echo ""
 echo "✅ Step 5: Verifying key..."
@@ -121,6 +133,8 @@ echo "pkcs11_library = \"$PKCS11_LIB\""
 echo "slot = $SLOT"
 echo "pin = \"$PIN\"  # Use environment variable in production!"
 echo "key_label = \"$KEY_LABEL\""
+echo "kdf_key_label = \"$KDF_KEY_LABEL\""
+echo "derivation = \"hmac-sha256\"  # SoftHSM has no SP 800-108 KDF"
 echo "---"
 echo ""
 echo "📝 Next steps:"